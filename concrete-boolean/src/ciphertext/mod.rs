@@ -3,10 +3,60 @@
 //! This module implements the ciphertext structure containing an encryption of a Boolean message.
 
 use concrete_core::crypto::lwe::LweCiphertext;
+use concrete_core::math::tensor::AsRefTensor;
 use serde::{Deserialize, Serialize};
 
 /// A structure containing a ciphertext, meant to encrypt a Boolean message.
 ///
 /// It is used to evaluate a Boolean circuits homomorphically.
+///
+/// Besides the LWE ciphertext, it records the fingerprint of the client key it is encrypted
+/// under, so that using it with the keys of another client is detected instead of silently
+/// producing a wrong result. The unknown fingerprint (0) is compatible with any key.
+///
+/// The fingerprint is part of the serialized ciphertext: the ciphertexts serialized by the
+/// versions without fingerprints are deserialized as a
+/// [`LegacyCiphertext`](crate::legacy::LegacyCiphertext) instead.
+///
+/// A trivial ciphertext, encrypting a public constant, has a zero mask and no noise: it is
+/// compatible with any key, and the gates taking it as input skip the bootstrap.
 #[derive(Serialize, Clone, Deserialize)]
-pub struct Ciphertext(pub(crate) LweCiphertext<Vec<u32>>);
+pub struct Ciphertext(pub(crate) LweCiphertext<Vec<u32>>, pub(crate) u64);
+
+impl Ciphertext {
+    /// Wraps an LWE ciphertext encrypted under the LWE secret key of a client key.
+    ///
+    /// The gates are only correct if the phase of the LWE ciphertext is close to $1/8$ (true) or
+    /// $-1/8$ (false): any other phase has to be bootstrapped with [`ServerKey::refresh`] first.
+    ///
+    /// # Arguments
+    ///
+    /// * `ct` - the LWE ciphertext
+    /// * `fingerprint` - the fingerprint of the client key (0 if it is unknown)
+    ///
+    /// [`ServerKey::refresh`]: crate::server_key::ServerKey::refresh
+    pub fn from_lwe_ciphertext(ct: LweCiphertext<Vec<u32>>, fingerprint: u64) -> Ciphertext {
+        Ciphertext(ct, fingerprint)
+    }
+
+    /// Returns the LWE ciphertext, whose phase is close to $1/8$ for true and $-1/8$ for false.
+    pub fn as_lwe_ciphertext(&self) -> &LweCiphertext<Vec<u32>> {
+        &self.0
+    }
+
+    /// Returns the fingerprint of the client key the ciphertext is encrypted under (0 if it is
+    /// unknown).
+    pub fn fingerprint(&self) -> u64 {
+        self.1
+    }
+
+    /// Returns the message of a trivial ciphertext, whose mask is zero, and None for the other
+    /// ciphertexts.
+    pub(crate) fn as_constant(&self) -> Option<bool> {
+        if self.0.get_mask().as_tensor().iter().all(|a| *a == 0) {
+            Some(self.0.get_body().0 < (1 << 31))
+        } else {
+            None
+        }
+    }
+}
@@ -6,12 +6,15 @@
 use crate::ciphertext::Ciphertext;
 use crate::parameters::BooleanParameters;
 use crate::{PLAINTEXT_FALSE, PLAINTEXT_TRUE};
+use concrete::CryptoAPIError;
 use concrete_commons::key_kinds::BinaryKeyKind;
 use concrete_core::crypto::encoding::Plaintext;
 use concrete_core::crypto::lwe::LweCiphertext;
 use concrete_core::crypto::secret::generators::{EncryptionRandomGenerator, SecretRandomGenerator};
 use concrete_core::crypto::secret::{GlweSecretKey, LweSecretKey};
+use concrete_core::math::tensor::{AsMutSlice, AsMutTensor};
 use serde::{Deserialize, Serialize};
+use std::fmt;
 
 /// A structure containing the client key, which must be kept secret.
 ///
@@ -21,11 +24,18 @@ use serde::{Deserialize, Serialize};
 /// * `glwe_secret_key` - a GLWE secret key, used to generate the bootstrapping keys and key
 /// switching keys.
 /// * `parameters` - the cryptographic parameter set.
-#[derive(Serialize, Clone, Deserialize, PartialEq, Debug)]
+/// * `fingerprint` - the public identifier of the client key, recorded in the server key and in
+///   the ciphertexts. The client keys serialized by the versions without fingerprints are
+///   deserialized as a [`LegacyClientKey`](crate::legacy::LegacyClientKey) instead.
+///
+/// The secret keys are wiped when the client key is dropped and are never printed. The client
+/// key does not implement `Clone`, so that no copy of the secret keys can be made by accident.
+#[derive(Serialize, Deserialize, PartialEq)]
 pub struct ClientKey {
     pub(crate) lwe_secret_key: LweSecretKey<BinaryKeyKind, Vec<u32>>,
     pub(crate) glwe_secret_key: GlweSecretKey<BinaryKeyKind, Vec<u32>>,
     pub(crate) parameters: BooleanParameters,
+    pub(crate) fingerprint: u64,
 }
 
 impl ClientKey {
@@ -68,11 +78,15 @@ impl ClientKey {
             &mut encryption_generator,
         );
 
-        Ciphertext(ct)
+        Ciphertext(ct, self.fingerprint)
     }
 
     /// Decrypts a ciphertext encrypting a Boolean message using the client key.
     ///
+    /// # Panics
+    ///
+    /// If the ciphertext is encrypted under another client key.
+    ///
     /// # Example
     ///
     /// ```rust
@@ -89,6 +103,34 @@ impl ClientKey {
     /// assert_eq!(true, dec);
     /// ```
     pub fn decrypt(&self, ct: &Ciphertext) -> bool {
+        self.try_decrypt(ct).unwrap()
+    }
+
+    /// Decrypts a ciphertext encrypting a Boolean message using the client key, like
+    /// [`ClientKey::decrypt`], returning an error instead of panicking.
+    ///
+    /// # Errors
+    ///
+    /// `KeyMismatch` if the ciphertext is encrypted under another client key.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concrete_boolean::gen_keys;
+    ///
+    /// // Generate two client keys:
+    /// let (cks_1, _) = gen_keys();
+    /// let (cks_2, _) = gen_keys();
+    ///
+    /// // Decrypt with the right key and with the wrong one:
+    /// let ct = cks_1.encrypt(true);
+    /// assert_eq!(true, cks_1.try_decrypt(&ct).unwrap());
+    /// assert!(cks_2.try_decrypt(&ct).is_err());
+    /// ```
+    pub fn try_decrypt(&self, ct: &Ciphertext) -> Result<bool, CryptoAPIError> {
+        // check the keys
+        crate::try_check_fingerprint(self.fingerprint, ct.1)?;
+
         // allocation for the decryption
         let mut decrypted = Plaintext(0_u32);
 
@@ -96,7 +138,7 @@ impl ClientKey {
         self.lwe_secret_key.decrypt_lwe(&mut decrypted, &ct.0);
 
         // return
-        decrypted.0 < (1 << 31)
+        Ok(decrypted.0 < (1 << 31))
     }
 
     /// Allocates and generates a client key.
@@ -131,7 +173,35 @@ impl ClientKey {
             lwe_secret_key,
             glwe_secret_key,
             parameters: (*parameter_set).clone(),
+            fingerprint: crate::new_fingerprint(),
         };
         cks
     }
+
+    /// Returns the cryptographic parameter set of the client key.
+    pub fn parameters(&self) -> &BooleanParameters {
+        &self.parameters
+    }
+
+    /// Returns the fingerprint of the client key.
+    pub fn fingerprint(&self) -> u64 {
+        self.fingerprint
+    }
+}
+
+impl Drop for ClientKey {
+    fn drop(&mut self) {
+        concrete::zeroize(self.lwe_secret_key.as_mut_tensor().as_mut_slice());
+        concrete::zeroize(self.glwe_secret_key.as_mut_tensor().as_mut_slice());
+    }
+}
+
+/// Prints the parameters of the client key, but not its secret keys.
+impl fmt::Debug for ClientKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ClientKey")
+            .field("parameters", &self.parameters)
+            .field("fingerprint", &format_args!("{:016x}", self.fingerprint))
+            .finish_non_exhaustive()
+    }
 }
@@ -0,0 +1,102 @@
+//! The errors of the Boolean gates.
+//!
+//! The gates return the errors of `concrete`, e.g. `KeyMismatch` if one of their inputs is
+//! encrypted under another client key, and the errors specific to the threshold gates.
+
+use concrete::CryptoAPIError;
+use std::error::Error;
+use std::fmt;
+
+/// An error of a Boolean gate.
+pub enum BooleanError {
+    CryptoAPIError(CryptoAPIError),
+    ThresholdRangeError {
+        min_sum: i64,
+        max_sum: i64,
+        threshold: i64,
+        description: String,
+    },
+    ErrorProbabilityError {
+        probability: f64,
+        max_probability: f64,
+        description: String,
+    },
+}
+
+impl From<CryptoAPIError> for BooleanError {
+    fn from(error: CryptoAPIError) -> BooleanError {
+        BooleanError::CryptoAPIError(error)
+    }
+}
+
+impl fmt::Display for BooleanError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BooleanError::CryptoAPIError(error) => write!(f, "{}", error),
+            BooleanError::ThresholdRangeError { description, .. } => {
+                writeln!(f, "\n{}", description)
+            }
+            BooleanError::ErrorProbabilityError { description, .. } => {
+                writeln!(f, "\n{}", description)
+            }
+        }
+    }
+}
+
+impl fmt::Debug for BooleanError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BooleanError::CryptoAPIError(error) => write!(f, "{:?}", error),
+            BooleanError::ThresholdRangeError { description, .. } => {
+                writeln!(f, "\n{}", description)
+            }
+            BooleanError::ErrorProbabilityError { description, .. } => {
+                writeln!(f, "\n{}", description)
+            }
+        }
+    }
+}
+
+impl Error for BooleanError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            BooleanError::CryptoAPIError(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+macro_rules! ThresholdRangeError {
+    ($min_sum: expr, $max_sum: expr, $threshold: expr) => {
+        $crate::error::BooleanError::ThresholdRangeError {
+            min_sum: $min_sum,
+            max_sum: $max_sum,
+            threshold: $threshold,
+            description: format!(
+                "{}: the weighted sums are in [{}, {}] but have to be in [{}, {}]\n{:#?}\n",
+                "Invalid threshold".red().bold(),
+                $min_sum,
+                $max_sum,
+                $threshold - 2,
+                $threshold + 1,
+                Backtrace::new()
+            ),
+        }
+    };
+}
+
+macro_rules! ErrorProbabilityError {
+    ($probability: expr, $max_probability: expr) => {
+        $crate::error::BooleanError::ErrorProbabilityError {
+            probability: $probability,
+            max_probability: $max_probability,
+            description: format!(
+                "{}: the probability of error is 2^{:.1} but has to be at most 2^{:.1}\n{:#?}\n",
+                "Too much noise".red().bold(),
+                f64::log2($probability),
+                f64::log2($max_probability),
+                Backtrace::new()
+            ),
+        }
+    };
+}
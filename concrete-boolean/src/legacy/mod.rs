@@ -0,0 +1,153 @@
+//! The serialized layouts of the versions without fingerprints.
+//!
+//! The ciphertexts, the client keys and the server keys record the fingerprint of the client key
+//! (and the server keys its parameters) since they are checked by the gates. With a binary format
+//! like the one of `bincode`, the structures serialized by the versions without those fields
+//! cannot be deserialized as the current ones: they are deserialized as the legacy structures of
+//! this module instead, and then converted.
+//!
+//! The converted ciphertexts and keys have the unknown fingerprint (0), which is compatible with
+//! any key: the mismatches with their keys are not detected.
+//!
+//! # Example
+//!
+//! ```rust
+//! use concrete_boolean::ciphertext::Ciphertext;
+//! use concrete_boolean::gen_keys;
+//! use concrete_boolean::legacy::LegacyCiphertext;
+//!
+//! // A trivial encryption of true serialized by a version without fingerprints, which is only
+//! // the LWE ciphertext (0, 0, 1/8):
+//! let serialized = bincode::serialize(&vec![0_u32, 0, 1 << 29]).unwrap();
+//!
+//! // Deserialize it as a legacy ciphertext and convert it:
+//! let legacy: LegacyCiphertext = bincode::deserialize(&serialized).unwrap();
+//! let ct: Ciphertext = legacy.into();
+//!
+//! // It can be used with the keys of any client:
+//! let (cks, sks) = gen_keys();
+//! assert_eq!(true, cks.decrypt(&sks.not(&sks.not(&ct))));
+//! ```
+
+#[cfg(test)]
+mod tests;
+
+use crate::ciphertext::Ciphertext;
+use crate::client_key::ClientKey;
+use crate::parameters::BooleanParameters;
+use crate::server_key::ServerKey;
+use backtrace::Backtrace;
+use colored::Colorize;
+use concrete::CryptoAPIError;
+use concrete_commons::key_kinds::BinaryKeyKind;
+use concrete_core::crypto::bootstrap::FourierBootstrapKey;
+use concrete_core::crypto::lwe::{LweCiphertext, LweKeyswitchKey};
+use concrete_core::crypto::secret::{GlweSecretKey, LweSecretKey};
+use concrete_core::math::fft::{AlignedVec, Complex64};
+use concrete_core::math::tensor::{AsMutSlice, AsMutTensor};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// A ciphertext serialized by a version without fingerprints.
+#[derive(Serialize, Clone, Deserialize)]
+pub struct LegacyCiphertext(pub(crate) LweCiphertext<Vec<u32>>);
+
+/// Converts a legacy ciphertext into a ciphertext with the unknown fingerprint.
+impl From<LegacyCiphertext> for Ciphertext {
+    fn from(legacy: LegacyCiphertext) -> Ciphertext {
+        Ciphertext(legacy.0, 0)
+    }
+}
+
+/// A client key serialized by a version without fingerprints.
+///
+/// As the client key, its secret keys are wiped when it is dropped and are never printed.
+#[derive(Serialize, Deserialize, PartialEq)]
+pub struct LegacyClientKey {
+    pub(crate) lwe_secret_key: LweSecretKey<BinaryKeyKind, Vec<u32>>,
+    pub(crate) glwe_secret_key: GlweSecretKey<BinaryKeyKind, Vec<u32>>,
+    pub(crate) parameters: BooleanParameters,
+}
+
+/// Converts a legacy client key into a client key with the unknown fingerprint, the secret keys
+/// of the legacy client key being wiped.
+impl From<LegacyClientKey> for ClientKey {
+    fn from(legacy: LegacyClientKey) -> ClientKey {
+        ClientKey {
+            lwe_secret_key: legacy.lwe_secret_key.clone(),
+            glwe_secret_key: legacy.glwe_secret_key.clone(),
+            parameters: legacy.parameters.clone(),
+            fingerprint: 0,
+        }
+    }
+}
+
+impl Drop for LegacyClientKey {
+    fn drop(&mut self) {
+        concrete::zeroize(self.lwe_secret_key.as_mut_tensor().as_mut_slice());
+        concrete::zeroize(self.glwe_secret_key.as_mut_tensor().as_mut_slice());
+    }
+}
+
+/// Prints the parameters of the legacy client key, but not its secret keys.
+impl fmt::Debug for LegacyClientKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LegacyClientKey")
+            .field("parameters", &self.parameters)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A server key serialized by a version without fingerprints nor parameters.
+#[derive(Serialize, Clone, Deserialize, PartialEq, Debug)]
+pub struct LegacyServerKey {
+    pub(crate) key_switching_key: LweKeyswitchKey<Vec<u32>>,
+    pub(crate) bootstrapping_key: FourierBootstrapKey<AlignedVec<Complex64>, u32>,
+}
+
+impl LegacyServerKey {
+    /// Converts a legacy server key into a server key with the unknown fingerprint.
+    ///
+    /// The parameters of the client key the legacy server key is generated from are not
+    /// serialized with it, and have to be given: they are used to check the noise of the
+    /// threshold gates.
+    ///
+    /// # Errors
+    ///
+    /// * `DimensionError` if the dimension of the LWE secret key or of the GLWE secret key of the
+    ///   parameters is not the one of the legacy server key.
+    /// * `PolynomialSizeError` if the polynomial size of the parameters is not the one of the
+    ///   legacy server key.
+    pub fn into_server_key(
+        self,
+        parameters: &BooleanParameters,
+    ) -> Result<ServerKey, CryptoAPIError> {
+        let lwe_dimension = self.key_switching_key.after_key_size().0;
+        if lwe_dimension != parameters.lwe_dimension.0 {
+            return Err(concrete::DimensionError!(
+                lwe_dimension,
+                parameters.lwe_dimension.0
+            ));
+        }
+        let glwe_dimension = self.bootstrapping_key.glwe_size().to_glwe_dimension().0;
+        if glwe_dimension != parameters.glwe_dimension.0 {
+            return Err(concrete::DimensionError!(
+                glwe_dimension,
+                parameters.glwe_dimension.0
+            ));
+        }
+        let polynomial_size = self.bootstrapping_key.polynomial_size().0;
+        if polynomial_size != parameters.polynomial_size.0 {
+            return Err(concrete::PolynomialSizeError!(
+                polynomial_size,
+                parameters.polynomial_size.0
+            ));
+        }
+        Ok(ServerKey {
+            key_switching_key: self.key_switching_key,
+            bootstrapping_key: self.bootstrapping_key,
+            fingerprint: 0,
+            parameters: parameters.clone(),
+        })
+    }
+}
@@ -0,0 +1,57 @@
+use crate::ciphertext::Ciphertext;
+use crate::client_key::ClientKey;
+use crate::legacy::{LegacyCiphertext, LegacyClientKey, LegacyServerKey};
+use crate::parameters::{DEFAULT_PARAMETERS, TFHE_LIB_PARAMETERS};
+use crate::random_boolean;
+use crate::server_key::ServerKey;
+use concrete::CryptoAPIError;
+
+/// Number of assert in randomized tests
+const NB_TEST: usize = 32;
+
+#[test]
+fn test_legacy_layouts() {
+    // generate the keys
+    let cks = ClientKey::new(&DEFAULT_PARAMETERS);
+    let sks = ServerKey::new(&cks);
+
+    // serialize the keys with the layouts of the versions without fingerprints
+    let legacy_cks =
+        bincode::serialize(&(&cks.lwe_secret_key, &cks.glwe_secret_key, &cks.parameters)).unwrap();
+    let legacy_sks = bincode::serialize(&(&sks.key_switching_key, &sks.bootstrapping_key)).unwrap();
+
+    // deserialize and convert them
+    let legacy_cks: LegacyClientKey = bincode::deserialize(&legacy_cks).unwrap();
+    let legacy_cks: ClientKey = legacy_cks.into();
+    let legacy_sks: LegacyServerKey = bincode::deserialize(&legacy_sks).unwrap();
+    assert!(matches!(
+        legacy_sks.clone().into_server_key(&TFHE_LIB_PARAMETERS),
+        Err(CryptoAPIError::DimensionError { .. })
+    ));
+    let legacy_sks = legacy_sks.into_server_key(&DEFAULT_PARAMETERS).unwrap();
+    assert_eq!(legacy_cks.fingerprint, 0);
+    assert_eq!(legacy_sks.fingerprint, 0);
+
+    for _ in 0..NB_TEST {
+        // generation of two random booleans
+        let b1 = random_boolean();
+        let b2 = random_boolean();
+
+        // serialize their encryptions with the layout of the versions without fingerprints
+        let legacy_ct1 = bincode::serialize(&cks.encrypt(b1).0).unwrap();
+        let legacy_ct2 = bincode::serialize(&legacy_cks.encrypt(b2).0).unwrap();
+
+        // deserialize and convert them
+        let ct1: Ciphertext = bincode::deserialize::<LegacyCiphertext>(&legacy_ct1)
+            .unwrap()
+            .into();
+        let ct2: Ciphertext = bincode::deserialize::<LegacyCiphertext>(&legacy_ct2)
+            .unwrap()
+            .into();
+        assert_eq!(ct1.1, 0);
+
+        // the converted ciphertexts and keys can be used together and with the current ones
+        assert_eq!(legacy_cks.decrypt(&legacy_sks.and(&ct1, &ct2)), b1 && b2);
+        assert_eq!(cks.decrypt(&sks.xor(&ct1, &ct2)), b1 ^ b2);
+    }
+}
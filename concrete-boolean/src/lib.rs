@@ -39,13 +39,25 @@
 //! let output = client_key.decrypt(&ct_6);
 //! assert_eq!(output, true);
 //! ```
+//!
+//! # Features
+//!
+//! The `lwe` feature adds the conversions between the ciphertexts and the [`concrete::LWE`]
+//! ciphertexts: `ClientKey::to_lwe_secret_key`, `Ciphertext::to_lwe` and
+//! `ServerKey::bootstrap_lwe`.
 
 use crate::client_key::ClientKey;
 use crate::parameters::DEFAULT_PARAMETERS;
 use crate::server_key::ServerKey;
+use concrete::{CryptoAPIError, KeyFingerprint};
 
 pub mod ciphertext;
 pub mod client_key;
+#[macro_use]
+pub mod error;
+pub mod legacy;
+#[cfg(feature = "lwe")]
+mod lwe;
 pub mod parameters;
 pub mod server_key;
 
@@ -58,6 +70,21 @@ pub(crate) const PLAINTEXT_TRUE: u32 = 1 << (32 - PLAINTEXT_LOG_SCALING_FACTOR);
 /// The plaintext associated with false: -1/8
 pub(crate) const PLAINTEXT_FALSE: u32 = 7 << (32 - PLAINTEXT_LOG_SCALING_FACTOR);
 
+/// Draw the fingerprint of a new client key, which is never the unknown fingerprint 0
+pub(crate) fn new_fingerprint() -> u64 {
+    let mut generator = concrete_core::math::random::RandomGenerator::new(None);
+    u64::max(generator.random_uniform(), 1)
+}
+
+/// Check that a ciphertext can be used with a key, the unknown fingerprint 0 being compatible
+/// with any key
+///
+/// # Errors
+/// KeyMismatch if both fingerprints are known and different
+pub(crate) fn try_check_fingerprint(key: u64, ciphertext: u64) -> Result<(), CryptoAPIError> {
+    KeyFingerprint(ciphertext).check(KeyFingerprint(key))
+}
+
 /// tool to generate random booleans
 #[cfg(test)]
 pub(crate) fn random_boolean() -> bool {
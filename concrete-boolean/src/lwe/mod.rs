@@ -0,0 +1,195 @@
+//! The bridge with the LWE ciphertexts of `concrete`.
+//!
+//! A ciphertext is an LWE ciphertext of `u32` coefficients whose phase is $1/8$ for true and
+//! $-1/8$ for false. It is converted into a [`concrete::LWE`] encrypting 0 or 1 with a 1-bit
+//! encoder and one bit of padding, so that it can be combined with the other LWE ciphertexts
+//! of `concrete` under the LWE secret key of the client key, and an LWE is converted back by a
+//! bootstrap of the server key comparing its message to a threshold.
+//!
+//! The bridge is only compiled with the `lwe` feature, so that the Boolean gates can be used
+//! without the LWE arithmetic of `concrete`.
+
+#[cfg(test)]
+mod tests;
+
+use crate::ciphertext::Ciphertext;
+use crate::client_key::ClientKey;
+use crate::parameters::BooleanParameters;
+use crate::server_key::ServerKey;
+use backtrace::Backtrace;
+use colored::Colorize;
+use concrete::{CryptoAPIError, Encoder, KeyFingerprint, LWESecretKey, SecretKeyKind, Torus, LWE};
+use concrete_core::crypto::lwe::LweCiphertext;
+use concrete_core::math::tensor::AsRefTensor;
+
+/// The plaintext of true on the 64-bit torus of `concrete`: $1/8$.
+const LWE_PLAINTEXT_TRUE: Torus = 1 << 61;
+
+/// The number of bits the 32-bit torus of the ciphertexts is shifted by on the 64-bit torus.
+const TORUS_SHIFT: usize = 32;
+
+impl ClientKey {
+    /// Returns a copy of the LWE secret key of the client key as a [`LWESecretKey`] of
+    /// `concrete`, which decrypts the LWE converted from the ciphertexts and can be used to
+    /// build key switching and bootstrapping keys.
+    ///
+    /// The returned key is as secret as the client key itself, and has its fingerprint.
+    ///
+    /// Requires the `lwe` feature.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concrete_boolean::gen_keys;
+    ///
+    /// let (cks, _) = gen_keys();
+    /// let secret_key = cks.to_lwe_secret_key();
+    /// assert_eq!(secret_key.dimension, cks.parameters().lwe_dimension().0);
+    /// ```
+    pub fn to_lwe_secret_key(&self) -> LWESecretKey {
+        LWESecretKey::from_coefficients(
+            self.lwe_secret_key
+                .as_tensor()
+                .iter()
+                .map(|s| *s as Torus)
+                .collect(),
+            self.parameters.lwe_modular_std_dev().0,
+            SecretKeyKind::Binary,
+            KeyFingerprint(self.fingerprint),
+        )
+    }
+}
+
+impl Ciphertext {
+    /// Converts the ciphertext into an [`LWE`] of `concrete` encrypting 0 (false) or 1 (true)
+    /// with a 1-bit encoder in the rounding context and one bit of padding.
+    ///
+    /// The variance of the LWE is the one of the output of a gate, and it is encrypted under
+    /// the key returned by [`ClientKey::to_lwe_secret_key`].
+    ///
+    /// Requires the `lwe` feature.
+    ///
+    /// # Errors
+    ///
+    /// `DimensionError` if the ciphertext does not match the parameters.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concrete_boolean::gen_keys;
+    ///
+    /// let (cks, _) = gen_keys();
+    /// let secret_key = cks.to_lwe_secret_key();
+    ///
+    /// // Convert two encrypted bits:
+    /// let a = cks.encrypt(true).to_lwe(cks.parameters()).unwrap();
+    /// let b = cks.encrypt(true).to_lwe(cks.parameters()).unwrap();
+    ///
+    /// // Count them:
+    /// let sum = a.add_with_padding(&b).unwrap();
+    /// assert_eq!(sum.decrypt_decode_round(&secret_key).unwrap(), 2.);
+    /// ```
+    pub fn to_lwe(&self, parameters: &BooleanParameters) -> Result<LWE, CryptoAPIError> {
+        let dimension = self.0.lwe_size().to_lwe_dimension().0;
+        if dimension != parameters.lwe_dimension().0 {
+            return Err(concrete::DimensionError!(
+                parameters.lwe_dimension().0,
+                dimension
+            ));
+        }
+
+        // map 1/8 to 1/4 and -1/8 to 0
+        let mut ciphertext: Vec<Torus> = self
+            .0
+            .as_tensor()
+            .iter()
+            .map(|c| (*c as Torus) << TORUS_SHIFT)
+            .collect();
+        let body = ciphertext.last_mut().unwrap();
+        *body = body.wrapping_add(LWE_PLAINTEXT_TRUE);
+
+        let variance = parameters.gate_output_variance();
+        let mut encoder = Encoder::new_rounding_context(0., 1., 1, 1)?;
+        encoder.update_precision_from_variance(variance)?;
+
+        Ok(LWE {
+            ciphertext: LweCiphertext::from_container(ciphertext),
+            variance,
+            dimension,
+            encoder,
+            key_fingerprint: KeyFingerprint(self.1),
+        })
+    }
+}
+
+impl ServerKey {
+    /// Converts an [`LWE`] of `concrete` into a fresh ciphertext, encrypting true if its
+    /// message is greater than a threshold, with a bootstrap.
+    ///
+    /// The comparison is only correct for the messages at a distance less than half the width
+    /// of the interval of the encoder from the threshold, which is any message of the interval
+    /// if there is some padding. The messages closer to the threshold than the noise can be
+    /// converted either way.
+    ///
+    /// Requires the `lwe` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `lwe` - an LWE encrypted under the key returned by [`ClientKey::to_lwe_secret_key`]
+    /// * `threshold` - the threshold, in the interval of the encoder of the LWE
+    ///
+    /// # Errors
+    ///
+    /// * `DimensionError` if the LWE is not encrypted under the LWE secret key of a client key.
+    /// * `KeyMismatch` if the LWE is encrypted under another key than the one of the server key.
+    /// * `MessageOutsideIntervalError` if the threshold is outside the interval of the encoder.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concrete::{Encoder, LWE};
+    /// use concrete_boolean::gen_keys;
+    ///
+    /// let (cks, sks) = gen_keys();
+    /// let secret_key = cks.to_lwe_secret_key();
+    ///
+    /// // Encrypt a real message under the key of the client key:
+    /// let encoder = Encoder::new(0., 10., 4, 1).unwrap();
+    /// let lwe = LWE::encode_encrypt(&secret_key, 7.2, &encoder).unwrap();
+    ///
+    /// // Compare it to 5:
+    /// let ct = sks.bootstrap_lwe(&lwe, 5.).unwrap();
+    /// assert_eq!(true, cks.decrypt(&ct));
+    /// ```
+    pub fn bootstrap_lwe(&self, lwe: &LWE, threshold: f64) -> Result<Ciphertext, CryptoAPIError> {
+        if lwe.dimension != self.lwe_dimension().0 {
+            return Err(concrete::DimensionError!(
+                self.lwe_dimension().0,
+                lwe.dimension
+            ));
+        }
+        lwe.key_fingerprint
+            .check(KeyFingerprint(self.fingerprint))?;
+
+        // the threshold is not rounded to the closest message
+        let mut threshold_encoder = lwe.encoder.clone();
+        threshold_encoder.round = false;
+        let shift: Torus = threshold_encoder.encode_core(threshold)?;
+
+        // move the threshold to the phase 0, the messages above it having a phase in [0, 1/2[
+        // (without padding, only the ones at a distance less than delta / 2), and round the
+        // coefficients to the closest elements of the 32-bit torus
+        let mut ciphertext: Vec<Torus> = lwe.ciphertext.as_tensor().iter().copied().collect();
+        let body = ciphertext.last_mut().unwrap();
+        *body = body.wrapping_sub(shift);
+        let ciphertext: Vec<u32> = ciphertext
+            .iter()
+            .map(|c| ((c >> (TORUS_SHIFT - 1)).wrapping_add(1) >> 1) as u32)
+            .collect();
+
+        Ok(self.refresh(&Ciphertext(
+            LweCiphertext::from_container(ciphertext),
+            self.fingerprint,
+        )))
+    }
+}
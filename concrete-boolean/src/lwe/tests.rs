@@ -0,0 +1,77 @@
+use crate::client_key::ClientKey;
+use crate::parameters::DEFAULT_PARAMETERS;
+use crate::random_boolean;
+use crate::server_key::ServerKey;
+use concrete::{CryptoAPIError, Encoder, LWESecretKey, LWE, LWE128_630};
+
+#[test]
+fn test_to_lwe_x_add_with_padding_x_bootstrap_lwe() {
+    // generate the client key, the server key and the LWE secret key of the client key
+    let cks = ClientKey::new(&DEFAULT_PARAMETERS);
+    let sks = ServerKey::new(&cks);
+    let secret_key = cks.to_lwe_secret_key();
+
+    for _ in 0..10 {
+        // three random bits
+        let bits: Vec<bool> = (0..3).map(|_| random_boolean()).collect();
+
+        // convert their encryptions
+        let lwes: Vec<LWE> = bits
+            .iter()
+            .map(|b| cks.encrypt(*b).to_lwe(&DEFAULT_PARAMETERS).unwrap())
+            .collect();
+        for (b, lwe) in bits.iter().zip(lwes.iter()) {
+            assert_eq!(
+                lwe.decrypt_decode_round(&secret_key).unwrap(),
+                *b as usize as f64
+            );
+        }
+
+        // the sum of the first two bits, without padding but in the half [0, 2] of its interval
+        let sum = lwes[0].add_with_padding(&lwes[1]).unwrap();
+        assert_eq!(sum.encoder.nb_bit_padding, 0);
+
+        // the sum is at least 1: OR
+        let or = sks.bootstrap_lwe(&sum, 0.5).unwrap();
+        assert_eq!(cks.decrypt(&or), bits[0] || bits[1]);
+
+        // the sum is 2: AND
+        let and = sks.bootstrap_lwe(&sum, 1.5).unwrap();
+        assert_eq!(cks.decrypt(&and), bits[0] && bits[1]);
+
+        // the bits go back to the gates
+        let ct = sks.bootstrap_lwe(&lwes[2], 0.5).unwrap();
+        assert_eq!(cks.decrypt(&ct), bits[2]);
+        assert_eq!(
+            cks.decrypt(&sks.xor(&ct, &or)),
+            bits[2] ^ (bits[0] || bits[1])
+        );
+    }
+}
+
+#[test]
+fn test_bootstrap_lwe_errors() {
+    let cks = ClientKey::new(&DEFAULT_PARAMETERS);
+    let sks = ServerKey::new(&cks);
+    let secret_key = cks.to_lwe_secret_key();
+
+    // threshold outside the interval
+    let encoder = Encoder::new(0., 10., 4, 1).unwrap();
+    let lwe = LWE::encode_encrypt(&secret_key, 7., &encoder).unwrap();
+    assert!(sks.bootstrap_lwe(&lwe, 12.).is_err());
+
+    // another key
+    let other_key = ClientKey::new(&DEFAULT_PARAMETERS).to_lwe_secret_key();
+    let lwe = LWE::encode_encrypt(&other_key, 7., &encoder).unwrap();
+    assert!(matches!(
+        sks.bootstrap_lwe(&lwe, 5.),
+        Err(CryptoAPIError::KeyMismatch { .. })
+    ));
+
+    // another dimension
+    let lwe = LWE::encode_encrypt(&LWESecretKey::new(&LWE128_630), 7., &encoder).unwrap();
+    assert!(matches!(
+        sks.bootstrap_lwe(&lwe, 5.),
+        Err(CryptoAPIError::DimensionError { .. })
+    ));
+}
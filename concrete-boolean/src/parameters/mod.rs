@@ -14,15 +14,22 @@
 //! The two parameter sets offer a trade-off in terms of execution time versus error probability.
 //! The `DEFAULT_PARAMETERS` set offers better performances on homomorphic circuit evaluation
 //! with an higher probability error in comparison with the `TFHE_LIB_PARAMETERS`.
-//! Note that if you desire, you can also create your own set of parameters.
-//! This is an unsafe operation as failing to properly fix the parameters will potentially result
-//! with an incorrect and/or insecure computation.
+//! Note that if you desire, you can also create your own set of parameters with
+//! [`BooleanParameters::builder`], which checks their estimated security level and probability
+//! of error, or with the unsafe [`BooleanParameters::new_insecure`], as failing to properly fix
+//! the parameters will potentially result with an incorrect and/or insecure computation.
 
+use concrete::params::{erfc, estimate_security_bits};
+use concrete::SecretKeyKind;
 use concrete_commons::dispersion::StandardDev;
 use concrete_commons::parameters::{
     DecompositionBaseLog, DecompositionLevelCount, GlweDimension, LweDimension, PolynomialSize,
 };
 use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[cfg(test)]
+mod tests;
 
 /// A set of cryptographic parameters for homomorphic Boolean circuit evaluation.
 #[derive(Serialize, Clone, Deserialize, Debug, PartialEq)]
@@ -46,7 +53,8 @@ impl BooleanParameters {
     /// This function is unsafe, as failing to fix the parameters properly would yield incorrect
     /// and insecure computation. Unless you are a cryptographer who really knows the impact of each
     /// of those parameters, you __must__ stick with the provided parameters [`DEFAULT_PARAMETERS`]
-    /// and [`TFHE_LIB_PARAMETERS`], which both offer correct results with 128 bits of security.
+    /// and [`TFHE_LIB_PARAMETERS`], which both offer correct results with 128 bits of security,
+    /// or use [`BooleanParameters::builder`].
     #[allow(clippy::too_many_arguments)]
     pub unsafe fn new_insecure(
         lwe_dimension: LweDimension,
@@ -71,12 +79,173 @@ impl BooleanParameters {
             ks_base_log,
         }
     }
+
+    /// Returns the dimension of the LWE secret key.
+    pub fn lwe_dimension(&self) -> LweDimension {
+        self.lwe_dimension
+    }
+
+    /// Returns the dimension of the GLWE secret key.
+    pub fn glwe_dimension(&self) -> GlweDimension {
+        self.glwe_dimension
+    }
+
+    /// Returns the number of coefficients of the polynomials of the GLWE secret key.
+    pub fn polynomial_size(&self) -> PolynomialSize {
+        self.polynomial_size
+    }
+
+    /// Returns the standard deviation of the noise of the LWE encryptions.
+    pub fn lwe_modular_std_dev(&self) -> StandardDev {
+        self.lwe_modular_std_dev
+    }
+
+    /// Returns the standard deviation of the noise of the GLWE encryptions.
+    pub fn glwe_modular_std_dev(&self) -> StandardDev {
+        self.glwe_modular_std_dev
+    }
+
+    /// Returns the log2 of the decomposition base of the bootstrapping key.
+    pub fn pbs_base_log(&self) -> DecompositionBaseLog {
+        self.pbs_base_log
+    }
+
+    /// Returns the number of levels of the bootstrapping key.
+    pub fn pbs_level(&self) -> DecompositionLevelCount {
+        self.pbs_level
+    }
+
+    /// Returns the log2 of the decomposition base of the key switching key.
+    pub fn ks_base_log(&self) -> DecompositionBaseLog {
+        self.ks_base_log
+    }
+
+    /// Returns the number of levels of the key switching key.
+    pub fn ks_level(&self) -> DecompositionLevelCount {
+        self.ks_level
+    }
+
+    /// Returns a builder of a custom set of parameters, starting from the
+    /// [`DEFAULT_PARAMETERS`], which checks the security level and the probability of error of
+    /// the set it builds.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concrete_boolean::parameters::BooleanParameters;
+    /// use concrete_commons::parameters::DecompositionLevelCount;
+    ///
+    /// // Use one more level in the key switching key:
+    /// let parameters = BooleanParameters::builder()
+    ///     .ks_level(DecompositionLevelCount(6))
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(parameters.ks_level(), DecompositionLevelCount(6));
+    /// ```
+    pub fn builder() -> BooleanParametersBuilder {
+        BooleanParametersBuilder {
+            parameters: DEFAULT_PARAMETERS,
+            min_security_level: DEFAULT_SECURITY_LEVEL,
+            max_error_probability: DEFAULT_ERROR_PROBABILITY,
+        }
+    }
+
+    /// Returns an estimation of the probability that a two-input gate outputs the wrong value.
+    ///
+    /// It is computed from the variance of the noise of the linear combination of two outputs
+    /// of gates bootstrapped by the gate, at a distance 1/8 of the boundaries of the test
+    /// polynomial. The probabilities given in the documentation of [`DEFAULT_PARAMETERS`] and
+    /// [`TFHE_LIB_PARAMETERS`] are upper bounds of this estimation.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concrete_boolean::parameters::DEFAULT_PARAMETERS;
+    ///
+    /// assert!(DEFAULT_PARAMETERS.error_probability() <= f64::powi(2., -25));
+    /// ```
+    pub fn error_probability(&self) -> f64 {
+        self.bootstrap_error_probability(2)
+    }
+
+    /// Returns an estimation of the security level in bits, the minimum of the ones of the LWE
+    /// secret key and of the GLWE secret key, rounded to the closest integer.
+    ///
+    /// The security level of each uniform binary key is estimated with
+    /// [`concrete::params::estimate_security_bits`], which interpolates the parameter sets of
+    /// `concrete` given by an older version of the lattice estimator. It is more conservative
+    /// than the version the provided parameter sets were chosen with, and only meaningful between
+    /// 80 and 128 bits.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concrete_boolean::parameters::{DEFAULT_PARAMETERS, TFHE_LIB_PARAMETERS};
+    ///
+    /// assert_eq!(DEFAULT_PARAMETERS.security_level(), 123);
+    /// assert_eq!(TFHE_LIB_PARAMETERS.security_level(), 120);
+    /// ```
+    pub fn security_level(&self) -> usize {
+        let lwe_security_level =
+            key_security_level(self.lwe_dimension.0, self.lwe_modular_std_dev.0);
+        let glwe_security_level = key_security_level(
+            self.glwe_dimension.0 * self.polynomial_size.0,
+            self.glwe_modular_std_dev.0,
+        );
+        f64::min(lwe_security_level, glwe_security_level).round() as usize
+    }
+
+    /// Returns the variance of the noise of the output of a gate, which is the key switch of the
+    /// output of a bootstrap, or of a fresh encryption if it is bigger.
+    pub(crate) fn gate_output_variance(&self) -> f64 {
+        let var_bootstrap = <u32 as concrete_npe::Cross>::bootstrap(
+            self.lwe_dimension.0,
+            self.glwe_dimension.0,
+            self.pbs_level.0,
+            self.pbs_base_log.0,
+            self.polynomial_size.0,
+            f64::powi(self.glwe_modular_std_dev.0, 2),
+        );
+        let var_keyswitch = <u32 as concrete_npe::LWE>::key_switch(
+            self.glwe_dimension.0 * self.polynomial_size.0,
+            self.ks_level.0,
+            self.ks_base_log.0,
+            f64::powi(self.lwe_modular_std_dev.0, 2),
+            var_bootstrap,
+        );
+        f64::max(var_keyswitch, f64::powi(self.lwe_modular_std_dev.0, 2))
+    }
+
+    /// Returns the variance of the rounding of the modulus switching at the beginning of a
+    /// bootstrap, for a uniform binary LWE secret key.
+    pub(crate) fn modulus_switching_variance(&self) -> f64 {
+        let polynomial_size = self.polynomial_size.0 as f64;
+        (self.lwe_dimension.0 as f64 / 2. + 1.) / (48. * polynomial_size * polynomial_size)
+    }
+
+    /// Returns the probability that a bootstrap outputs the wrong value, when its input is a
+    /// linear combination of outputs of gates whose phase is at a distance 1/8 of the boundaries
+    /// of the test polynomial, with `squared_norm` the sum of the squares of the weights.
+    pub(crate) fn bootstrap_error_probability(&self, squared_norm: u64) -> f64 {
+        let variance =
+            squared_norm as f64 * self.gate_output_variance() + self.modulus_switching_variance();
+        erfc(1. / 8. / f64::sqrt(2. * variance))
+    }
 }
 
+/// The upper bound of the probability of error of a gate with the [`DEFAULT_PARAMETERS`]:
+/// $2^{-25}$.
+pub(crate) const DEFAULT_ERROR_PROBABILITY: f64 = 2.980_232_238_769_531_3e-8;
+
+/// The security level of the [`DEFAULT_PARAMETERS`] estimated by
+/// [`BooleanParameters::security_level`], in bits.
+pub(crate) const DEFAULT_SECURITY_LEVEL: usize = 123;
+
 /// Default parameter set.
 ///
 /// This parameter set ensures 128-bits of security, and a probability of error is upper-bounded by
 /// $2^{-25}$. The secret keys generated with this parameter set are uniform binary.
+/// Its security level estimated by [`BooleanParameters::security_level`] is 123 bits.
 /// This parameter set allows to evaluate faster Boolean circuits than the `TFHE_LIB_PARAMETERS`
 /// one.
 pub const DEFAULT_PARAMETERS: BooleanParameters = BooleanParameters {
@@ -97,6 +266,7 @@ pub const DEFAULT_PARAMETERS: BooleanParameters = BooleanParameters {
 /// [here](https://github.com/tfhe/tfhe/blob/master/src/libtfhe/tfhe_gate_bootstrapping.cpp).
 /// The secret keys generated with this parameter set are uniform binary.
 /// This parameter set ensures a probability of error is upper-bounded by $2^{-165}$.
+/// Its security level estimated by [`BooleanParameters::security_level`] is 120 bits.
 pub const TFHE_LIB_PARAMETERS: BooleanParameters = BooleanParameters {
     lwe_dimension: LweDimension(630),
     glwe_dimension: GlweDimension(1),
@@ -108,3 +278,192 @@ pub const TFHE_LIB_PARAMETERS: BooleanParameters = BooleanParameters {
     ks_base_log: DecompositionBaseLog(2),
     ks_level: DecompositionLevelCount(8),
 };
+
+/// A builder of a custom set of parameters, returned by [`BooleanParameters::builder`].
+///
+/// By default, the set is required to have at least the estimated security level and at most
+/// the probability of error $2^{-25}$ of the [`DEFAULT_PARAMETERS`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct BooleanParametersBuilder {
+    parameters: BooleanParameters,
+    min_security_level: usize,
+    max_error_probability: f64,
+}
+
+impl BooleanParametersBuilder {
+    /// Sets the dimension of the LWE secret key.
+    pub fn lwe_dimension(mut self, lwe_dimension: LweDimension) -> Self {
+        self.parameters.lwe_dimension = lwe_dimension;
+        self
+    }
+
+    /// Sets the dimension of the GLWE secret key.
+    pub fn glwe_dimension(mut self, glwe_dimension: GlweDimension) -> Self {
+        self.parameters.glwe_dimension = glwe_dimension;
+        self
+    }
+
+    /// Sets the number of coefficients of the polynomials of the GLWE secret key.
+    pub fn polynomial_size(mut self, polynomial_size: PolynomialSize) -> Self {
+        self.parameters.polynomial_size = polynomial_size;
+        self
+    }
+
+    /// Sets the standard deviation of the noise of the LWE encryptions.
+    pub fn lwe_modular_std_dev(mut self, lwe_modular_std_dev: StandardDev) -> Self {
+        self.parameters.lwe_modular_std_dev = lwe_modular_std_dev;
+        self
+    }
+
+    /// Sets the standard deviation of the noise of the GLWE encryptions.
+    pub fn glwe_modular_std_dev(mut self, glwe_modular_std_dev: StandardDev) -> Self {
+        self.parameters.glwe_modular_std_dev = glwe_modular_std_dev;
+        self
+    }
+
+    /// Sets the log2 of the decomposition base of the bootstrapping key.
+    pub fn pbs_base_log(mut self, pbs_base_log: DecompositionBaseLog) -> Self {
+        self.parameters.pbs_base_log = pbs_base_log;
+        self
+    }
+
+    /// Sets the number of levels of the bootstrapping key.
+    pub fn pbs_level(mut self, pbs_level: DecompositionLevelCount) -> Self {
+        self.parameters.pbs_level = pbs_level;
+        self
+    }
+
+    /// Sets the log2 of the decomposition base of the key switching key.
+    pub fn ks_base_log(mut self, ks_base_log: DecompositionBaseLog) -> Self {
+        self.parameters.ks_base_log = ks_base_log;
+        self
+    }
+
+    /// Sets the number of levels of the key switching key.
+    pub fn ks_level(mut self, ks_level: DecompositionLevelCount) -> Self {
+        self.parameters.ks_level = ks_level;
+        self
+    }
+
+    /// Sets the minimal security level of the set, in bits (123 by default, the estimated
+    /// security level of the [`DEFAULT_PARAMETERS`]).
+    pub fn min_security_level(mut self, min_security_level: usize) -> Self {
+        self.min_security_level = min_security_level;
+        self
+    }
+
+    /// Sets the maximal probability of error of a gate ($2^{-25}$ by default).
+    pub fn max_error_probability(mut self, max_error_probability: f64) -> Self {
+        self.max_error_probability = max_error_probability;
+        self
+    }
+
+    /// Checks the set of parameters and returns it.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concrete_boolean::parameters::{BooleanParameters, ParametersError};
+    /// use concrete_commons::parameters::LweDimension;
+    ///
+    /// // A smaller LWE dimension with the same noise is not secure:
+    /// let res = BooleanParameters::builder()
+    ///     .lwe_dimension(LweDimension(500))
+    ///     .build();
+    /// assert!(matches!(res, Err(ParametersError::InsufficientSecurity { .. })));
+    /// ```
+    pub fn build(self) -> Result<BooleanParameters, ParametersError> {
+        let parameters = self.parameters;
+        if !parameters.polynomial_size.0.is_power_of_two() {
+            return Err(ParametersError::InvalidPolynomialSize(
+                parameters.polynomial_size,
+            ));
+        }
+        for (base_log, level) in [
+            (parameters.pbs_base_log, parameters.pbs_level),
+            (parameters.ks_base_log, parameters.ks_level),
+        ]
+        .iter()
+        {
+            if base_log.0 == 0 || level.0 == 0 || base_log.0 * level.0 > 32 {
+                return Err(ParametersError::InvalidDecomposition(*base_log, *level));
+            }
+        }
+        let security_level = parameters.security_level();
+        if security_level < self.min_security_level {
+            return Err(ParametersError::InsufficientSecurity {
+                security_level,
+                min_security_level: self.min_security_level,
+            });
+        }
+        let error_probability = parameters.error_probability();
+        if error_probability > self.max_error_probability {
+            return Err(ParametersError::ErrorProbabilityTooHigh {
+                error_probability,
+                max_error_probability: self.max_error_probability,
+            });
+        }
+        Ok(parameters)
+    }
+}
+
+/// The errors of [`BooleanParametersBuilder::build`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum ParametersError {
+    /// The number of coefficients of the polynomials is not a power of two.
+    InvalidPolynomialSize(PolynomialSize),
+    /// A decomposition has no level, a base of 1, or more than 32 bits.
+    InvalidDecomposition(DecompositionBaseLog, DecompositionLevelCount),
+    /// The estimated security level is below the minimal one.
+    InsufficientSecurity {
+        security_level: usize,
+        min_security_level: usize,
+    },
+    /// The estimated probability of error is above the maximal one.
+    ErrorProbabilityTooHigh {
+        error_probability: f64,
+        max_error_probability: f64,
+    },
+}
+
+impl fmt::Display for ParametersError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParametersError::InvalidPolynomialSize(polynomial_size) => write!(
+                f,
+                "the polynomial size {} is not a power of two",
+                polynomial_size.0
+            ),
+            ParametersError::InvalidDecomposition(base_log, level) => write!(
+                f,
+                "the decomposition with a base log of {} and {} levels is invalid",
+                base_log.0, level.0
+            ),
+            ParametersError::InsufficientSecurity {
+                security_level,
+                min_security_level,
+            } => write!(
+                f,
+                "the security level is {} bits, below {} bits",
+                security_level, min_security_level
+            ),
+            ParametersError::ErrorProbabilityTooHigh {
+                error_probability,
+                max_error_probability,
+            } => write!(
+                f,
+                "the probability of error is 2^{:.1}, above 2^{:.1}",
+                f64::log2(*error_probability),
+                f64::log2(*max_error_probability)
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ParametersError {}
+
+/// Returns the security level of a uniform binary key of dimension `dimension`, with a noise of
+/// standard deviation `std_dev`.
+fn key_security_level(dimension: usize, std_dev: f64) -> f64 {
+    estimate_security_bits(dimension, f64::log2(std_dev), SecretKeyKind::Binary)
+}
@@ -0,0 +1,104 @@
+use crate::parameters::{
+    BooleanParameters, ParametersError, DEFAULT_ERROR_PROBABILITY, DEFAULT_PARAMETERS,
+    DEFAULT_SECURITY_LEVEL, TFHE_LIB_PARAMETERS,
+};
+use concrete_commons::dispersion::StandardDev;
+use concrete_commons::parameters::{
+    DecompositionBaseLog, DecompositionLevelCount, LweDimension, PolynomialSize,
+};
+
+#[test]
+/// test the metadata of the provided parameter sets against their documentation
+fn test_provided_parameters() {
+    assert_eq!(DEFAULT_PARAMETERS.security_level(), DEFAULT_SECURITY_LEVEL);
+    assert_eq!(TFHE_LIB_PARAMETERS.security_level(), 120);
+    assert!(DEFAULT_PARAMETERS.error_probability() <= DEFAULT_ERROR_PROBABILITY);
+    assert!(TFHE_LIB_PARAMETERS.error_probability() <= f64::powi(2., -165));
+    assert!(TFHE_LIB_PARAMETERS.error_probability() < DEFAULT_PARAMETERS.error_probability());
+
+    // the default parameters are built without error
+    assert_eq!(BooleanParameters::builder().build(), Ok(DEFAULT_PARAMETERS));
+}
+
+#[test]
+fn test_builder_security() {
+    // the security level is the one of the least secure key
+    let parameters = BooleanParameters::builder()
+        .lwe_dimension(LweDimension(600))
+        .build();
+    assert_eq!(parameters.unwrap().security_level(), 126);
+    let parameters = BooleanParameters::builder()
+        .lwe_dimension(LweDimension(700))
+        .build();
+    assert_eq!(parameters.unwrap().security_level(), 128);
+
+    // more noise is more secure, but it increases the probability of error
+    let res = BooleanParameters::builder()
+        .lwe_modular_std_dev(StandardDev(f64::powi(2., -11)))
+        .build();
+    assert!(matches!(
+        res,
+        Err(ParametersError::ErrorProbabilityTooHigh { .. })
+    ));
+
+    // a smaller dimension with the same noise is not
+    let res = BooleanParameters::builder()
+        .lwe_dimension(LweDimension(500))
+        .build();
+    assert!(matches!(
+        res,
+        Err(ParametersError::InsufficientSecurity {
+            min_security_level: DEFAULT_SECURITY_LEVEL,
+            ..
+        })
+    ));
+
+    // unless the minimal security level is lowered
+    let res = BooleanParameters::builder()
+        .lwe_dimension(LweDimension(500))
+        .min_security_level(100)
+        .build();
+    assert!(res.is_ok());
+}
+
+#[test]
+fn test_builder_error_probability() {
+    // a coarser key switch is too noisy
+    let res = BooleanParameters::builder()
+        .ks_level(DecompositionLevelCount(4))
+        .build();
+    assert!(matches!(
+        res,
+        Err(ParametersError::ErrorProbabilityTooHigh { .. })
+    ));
+
+    // unless the maximal probability of error is raised
+    let res = BooleanParameters::builder()
+        .ks_level(DecompositionLevelCount(4))
+        .max_error_probability(0.01)
+        .build();
+    assert!(res.is_ok());
+}
+
+#[test]
+fn test_builder_invalid() {
+    let res = BooleanParameters::builder()
+        .polynomial_size(PolynomialSize(500))
+        .build();
+    assert_eq!(
+        res,
+        Err(ParametersError::InvalidPolynomialSize(PolynomialSize(500)))
+    );
+
+    let res = BooleanParameters::builder()
+        .pbs_base_log(DecompositionBaseLog(12))
+        .pbs_level(DecompositionLevelCount(3))
+        .build();
+    assert_eq!(
+        res,
+        Err(ParametersError::InvalidDecomposition(
+            DecompositionBaseLog(12),
+            DecompositionLevelCount(3)
+        ))
+    );
+}
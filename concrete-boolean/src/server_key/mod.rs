@@ -4,13 +4,24 @@
 //! available homomorphic Boolean gates ($\mathrm{AND}$, $\mathrm{MUX}$, $\mathrm{NAND}$,
 //! $\mathrm{NOR}$,
 //! $\mathrm{NOT}$, $\mathrm{OR}$, $\mathrm{XNOR}$, $\mathrm{XOR}$).
+//! It also provides the three-input majority gate $\mathrm{MAJ}$ and the threshold gates, which
+//! bootstrap a weighted sum of ciphertexts.
+//!
+//! Every two-input gate also has a variant with a clear operand, and the trivial encryptions of
+//! public constants are propagated: a gate whose output only depends on its encrypted inputs
+//! through a constant or a negation does not compute any bootstrap.
 
 #[cfg(test)]
 mod tests;
 
 use crate::ciphertext::Ciphertext;
 use crate::client_key::ClientKey;
-use crate::{PLAINTEXT_LOG_SCALING_FACTOR, PLAINTEXT_TRUE};
+use crate::error::BooleanError;
+use crate::parameters::{BooleanParameters, DEFAULT_ERROR_PROBABILITY};
+use crate::{PLAINTEXT_FALSE, PLAINTEXT_LOG_SCALING_FACTOR, PLAINTEXT_TRUE};
+use backtrace::Backtrace;
+use colored::Colorize;
+use concrete::CryptoAPIError;
 use concrete_commons::parameters::LweDimension;
 use concrete_core::crypto::bootstrap::{Bootstrap, FourierBootstrapKey, StandardBootstrapKey};
 use concrete_core::crypto::encoding::Cleartext;
@@ -18,7 +29,7 @@ use concrete_core::crypto::glwe::GlweCiphertext;
 use concrete_core::crypto::lwe::{LweCiphertext, LweKeyswitchKey};
 use concrete_core::crypto::secret::generators::EncryptionRandomGenerator;
 use concrete_core::math::fft::{AlignedVec, Complex64};
-use concrete_core::math::tensor::AsMutTensor;
+use concrete_core::math::tensor::{AsMutSlice, AsMutTensor};
 use serde::{Deserialize, Serialize};
 
 /// A structure containing the server public key.
@@ -29,10 +40,20 @@ use serde::{Deserialize, Serialize};
 /// In more details, it contains:
 /// * `key_switching_key` - a public key, used to perform the key-switching operation.
 /// * `bootstrapping_key` - a public key, used to perform the bootstrapping operation.
+/// * `fingerprint` - the fingerprint of the client key it is generated from.
+/// * `parameters` - the parameters of the client key it is generated from, used to check the
+///   noise of the threshold gates.
+///
+/// The server keys serialized by the versions without fingerprints nor parameters are
+/// deserialized as a [`LegacyServerKey`](crate::legacy::LegacyServerKey) instead.
+///
+/// The gates panic if one of their inputs is encrypted under another client key.
 #[derive(Serialize, Clone, Deserialize, PartialEq, Debug)]
 pub struct ServerKey {
     pub(crate) key_switching_key: LweKeyswitchKey<Vec<u32>>,
     pub(crate) bootstrapping_key: FourierBootstrapKey<AlignedVec<Complex64>, u32>,
+    pub(crate) fingerprint: u64,
+    pub(crate) parameters: BooleanParameters,
 }
 
 impl ServerKey {
@@ -90,7 +111,7 @@ impl ServerKey {
         );
 
         // Convert the GLWE secret key into an LWE secret key:
-        let big_lwe_secret_key = cks.glwe_secret_key.clone().into_lwe_secret_key();
+        let mut big_lwe_secret_key = cks.glwe_secret_key.clone().into_lwe_secret_key();
 
         // Fill the key switching key:
         ksk.fill_with_keyswitch_key(
@@ -100,37 +121,714 @@ impl ServerKey {
             &mut encryption_generator,
         );
 
+        // Wipe the copy of the GLWE secret key:
+        concrete::zeroize(big_lwe_secret_key.as_mut_tensor().as_mut_slice());
+
         // Pack the keys in the server key set:
         let sks: ServerKey = ServerKey {
             key_switching_key: ksk,
             bootstrapping_key: fourier_bsk,
+            fingerprint: cks.fingerprint,
+            parameters: cks.parameters.clone(),
         };
         sks
     }
 
-    /// Computes homomorphically an AND gate between two ciphertexts encrypting Boolean values:
-    /// $$ ct_{out} = ct_{left}~\mathrm{AND}~ct_{right} $$
+    /// Encrypts a public Boolean constant trivially, with a zero mask and without noise.
+    ///
+    /// The trivial ciphertext can be used with the ciphertexts of any client key, and the gates
+    /// taking it as input do not compute any bootstrap.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concrete_boolean::gen_keys;
+    ///
+    /// // Generate the client key and the server key:
+    /// let (cks, sks) = gen_keys();
+    ///
+    /// // Trivial encryption of a constant:
+    /// let ct = sks.trivial_encrypt(true);
+    ///
+    /// // Decryption:
+    /// let dec = cks.decrypt(&ct);
+    /// assert_eq!(true, dec);
+    /// ```
+    pub fn trivial_encrypt(&self, message: bool) -> Ciphertext {
+        // allocate a ciphertext with a zero mask
+        let mut ct = LweCiphertext::allocate(0_u32, self.lwe_dimension().to_lwe_size());
+
+        // set its body to the encoded boolean message
+        ct.get_mut_body().0 = if message {
+            PLAINTEXT_TRUE
+        } else {
+            PLAINTEXT_FALSE
+        };
+
+        Ciphertext(ct, 0)
+    }
+
+    /// Computes homomorphically an AND gate between two ciphertexts encrypting Boolean values:
+    /// $$ ct_{out} = ct_{left}~\mathrm{AND}~ct_{right} $$
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concrete_boolean::gen_keys;
+    ///
+    /// // Generate the client key and the server key:
+    /// let (cks, sks) = gen_keys();
+    ///
+    /// // Encrypt two messages:
+    /// let ct1 = cks.encrypt(true);
+    /// let ct2 = cks.encrypt(false);
+    ///
+    /// // Compute homomorphically an AND gate:
+    /// let ct_res = sks.and(&ct1, &ct2);
+    ///
+    /// // Decrypt:
+    /// let dec_and = cks.decrypt(&ct_res);
+    /// assert_eq!(false, dec_and);
+    /// ```
+    pub fn and(&self, ct_left: &Ciphertext, ct_right: &Ciphertext) -> Ciphertext {
+        self.try_and(ct_left, ct_right).unwrap()
+    }
+
+    /// Computes an homomorphic MUX gate between three ciphertexts encrypting Boolean values:
+    /// $$ct_{out} = (ct_{condition}?~ct_{then}:~ct_{else}) $$
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concrete_boolean::gen_keys;
+    ///
+    /// // Generate the client key and the server key:
+    /// let (cks, sks) = gen_keys();
+    ///
+    /// // Encrypt three messages:
+    /// let ct1 = cks.encrypt(true);
+    /// let ct2 = cks.encrypt(false);
+    /// let ct3 = cks.encrypt(true);
+    ///
+    /// // Compute homomorphically a MUX gate:
+    /// let ct_res = sks.mux(&ct1, &ct2, &ct3);
+    ///
+    /// // Decrypt:
+    /// let dec_mux = cks.decrypt(&ct_res);
+    /// assert_eq!(false, dec_mux);
+    /// ```
+    pub fn mux(
+        &self,
+        ct_condition: &Ciphertext,
+        ct_then: &Ciphertext,
+        ct_else: &Ciphertext,
+    ) -> Ciphertext {
+        self.try_mux(ct_condition, ct_then, ct_else).unwrap()
+    }
+
+    /// Computes homomorphically a NAND gate between two ciphertexts encrypting Boolean values:
+    /// $$ct_{out} = \mathrm{NOT} (ct_{left}~\mathrm{AND}~ct_{right})$$
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concrete_boolean::gen_keys;
+    ///
+    /// // Generate the client key and the server key:
+    /// let (cks, sks) = gen_keys();
+    ///
+    /// // Encrypt two messages:
+    /// let ct1 = cks.encrypt(true);
+    /// let ct2 = cks.encrypt(false);
+    ///
+    /// // Compute homomorphically a NAND gate:
+    /// let ct_res = sks.nand(&ct1, &ct2);
+    ///
+    /// // Decrypt:
+    /// let dec_nand = cks.decrypt(&ct_res);
+    /// assert_eq!(true, dec_nand);
+    /// ```
+    pub fn nand(&self, ct_left: &Ciphertext, ct_right: &Ciphertext) -> Ciphertext {
+        self.try_nand(ct_left, ct_right).unwrap()
+    }
+
+    /// Computes homomorphically a NOR gate between two ciphertexts encrypting Boolean values:
+    /// $$ ct_{out} = \mathrm{NOT}(ct_{left}~\mathrm{OR}~ct_{right}) $$
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concrete_boolean::gen_keys;
+    ///
+    /// // Generate the client key and the server key:
+    /// let (cks, sks) = gen_keys();
+    ///
+    /// // Encrypt two messages:
+    /// let ct1 = cks.encrypt(true);
+    /// let ct2 = cks.encrypt(false);
+    ///
+    /// // Compute homomorphically the NOR gate:
+    /// let ct_res = sks.nor(&ct1, &ct2);
+    ///
+    /// // Decrypt:
+    /// let dec_nor = cks.decrypt(&ct_res);
+    /// assert_eq!(false, dec_nor);
+    /// ```
+    pub fn nor(&self, ct_left: &Ciphertext, ct_right: &Ciphertext) -> Ciphertext {
+        self.try_nor(ct_left, ct_right).unwrap()
+    }
+
+    /// Computes homomorphically a NOT gate of a ciphertexts encrypting a Boolean value:
+    /// $$ct_{out} = \mathrm{NOT}(ct_{in})$$
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concrete_boolean::gen_keys;
+    ///
+    /// // Generate the client key and the server key:
+    /// let (cks, sks) = gen_keys();
+    ///
+    /// // Encrypt a message:
+    /// let ct = cks.encrypt(true);
+    ///
+    /// // Compute homomorphically a NOT gate:
+    /// let ct_res = sks.not(&ct);
+    ///
+    /// // Decrypt:
+    /// let dec_not = cks.decrypt(&ct_res);
+    /// assert_eq!(false, dec_not);
+    /// ```
+    pub fn not(&self, ct: &Ciphertext) -> Ciphertext {
+        self.try_not(ct).unwrap()
+    }
+
+    /// Computes homomorphically an OR gate between two ciphertexts encrypting Boolean values:
+    /// $$ct_{out} = ct_{left}~\mathrm{OR}~ct_{right}$$
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concrete_boolean::gen_keys;
+    ///
+    /// // Generate the client key and the server key:
+    /// let (cks, sks) = gen_keys();
+    ///
+    /// // Encrypt two messages:
+    /// let ct1 = cks.encrypt(true);
+    /// let ct2 = cks.encrypt(false);
+    ///
+    /// // Compute homomorphically the OR gate:
+    /// let ct_res = sks.or(&ct1, &ct2);
+    ///
+    /// // Decrypt:
+    /// let dec_or = cks.decrypt(&ct_res);
+    /// assert_eq!(true, dec_or);
+    /// ```
+    pub fn or(&self, ct_left: &Ciphertext, ct_right: &Ciphertext) -> Ciphertext {
+        self.try_or(ct_left, ct_right).unwrap()
+    }
+
+    /// Computes homomorphically an XNOR gate (or equality test) between two ciphertexts encrypting
+    /// Boolean values:
+    /// $$ct_{out} = (ct_{left}~==~ct_{right}) $$
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concrete_boolean::gen_keys;
+    ///
+    /// // Generate the client key and the server key:
+    /// let (cks, sks) = gen_keys();
+    ///
+    /// // Encrypt two messages:
+    /// let ct1 = cks.encrypt(true);
+    /// let ct2 = cks.encrypt(false);
+    ///
+    /// // Compute the XNOR gate:
+    /// let ct_res = sks.xnor(&ct1, &ct2);
+    ///
+    /// // Decrypt:
+    /// let dec_xnor = cks.decrypt(&ct_res);
+    /// assert_eq!(false, dec_xnor);
+    /// ```
+    pub fn xnor(&self, ct_left: &Ciphertext, ct_right: &Ciphertext) -> Ciphertext {
+        self.try_xnor(ct_left, ct_right).unwrap()
+    }
+
+    /// Computes homomorphically an XOR gate between two ciphertexts encrypting Boolean values:
+    /// $$ct_{out}= ct_{left}~\mathrm{XOR}~ct_{right}$$
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concrete_boolean::gen_keys;
+    ///
+    /// // Generate the client key and the server key:
+    /// let (cks, sks) = gen_keys();
+    ///
+    /// // Encryption of two messages:
+    /// let ct1 = cks.encrypt(true);
+    /// let ct2 = cks.encrypt(false);
+    ///
+    /// // Compute the XOR gate:
+    /// let ct_res = sks.xor(&ct1, &ct2);
+    ///
+    /// // Decryption:
+    /// let dec_xor = cks.decrypt(&ct_res);
+    /// assert_eq!(true, dec_xor);
+    /// ```
+    pub fn xor(&self, ct_left: &Ciphertext, ct_right: &Ciphertext) -> Ciphertext {
+        self.try_xor(ct_left, ct_right).unwrap()
+    }
+
+    /// Computes homomorphically an AND gate between a ciphertext encrypting a Boolean value and a
+    /// clear Boolean value, without any bootstrap:
+    /// $$ct_{out} = ct~\mathrm{AND}~b$$
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concrete_boolean::gen_keys;
+    ///
+    /// // Generate the client key and the server key:
+    /// let (cks, sks) = gen_keys();
+    ///
+    /// // Encrypt a message:
+    /// let ct = cks.encrypt(true);
+    ///
+    /// // Compute homomorphically an AND gate with a clear value:
+    /// let ct_res = sks.and_clear(&ct, false);
+    ///
+    /// // Decrypt:
+    /// let dec_and = cks.decrypt(&ct_res);
+    /// assert_eq!(false, dec_and);
+    /// ```
+    pub fn and_clear(&self, ct: &Ciphertext, b: bool) -> Ciphertext {
+        self.try_and_clear(ct, b).unwrap()
+    }
+
+    /// Computes homomorphically a NAND gate between a ciphertext encrypting a Boolean value and a
+    /// clear Boolean value, without any bootstrap:
+    /// $$ct_{out} = \mathrm{NOT}(ct~\mathrm{AND}~b)$$
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concrete_boolean::gen_keys;
+    ///
+    /// // Generate the client key and the server key:
+    /// let (cks, sks) = gen_keys();
+    ///
+    /// // Encrypt a message:
+    /// let ct = cks.encrypt(true);
+    ///
+    /// // Compute homomorphically a NAND gate with a clear value:
+    /// let ct_res = sks.nand_clear(&ct, false);
+    ///
+    /// // Decrypt:
+    /// let dec_nand = cks.decrypt(&ct_res);
+    /// assert_eq!(true, dec_nand);
+    /// ```
+    pub fn nand_clear(&self, ct: &Ciphertext, b: bool) -> Ciphertext {
+        self.try_nand_clear(ct, b).unwrap()
+    }
+
+    /// Computes homomorphically a NOR gate between a ciphertext encrypting a Boolean value and a
+    /// clear Boolean value, without any bootstrap:
+    /// $$ct_{out} = \mathrm{NOT}(ct~\mathrm{OR}~b)$$
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concrete_boolean::gen_keys;
+    ///
+    /// // Generate the client key and the server key:
+    /// let (cks, sks) = gen_keys();
+    ///
+    /// // Encrypt a message:
+    /// let ct = cks.encrypt(true);
+    ///
+    /// // Compute homomorphically a NOR gate with a clear value:
+    /// let ct_res = sks.nor_clear(&ct, false);
+    ///
+    /// // Decrypt:
+    /// let dec_nor = cks.decrypt(&ct_res);
+    /// assert_eq!(false, dec_nor);
+    /// ```
+    pub fn nor_clear(&self, ct: &Ciphertext, b: bool) -> Ciphertext {
+        self.try_nor_clear(ct, b).unwrap()
+    }
+
+    /// Computes homomorphically an OR gate between a ciphertext encrypting a Boolean value and a
+    /// clear Boolean value, without any bootstrap:
+    /// $$ct_{out} = ct~\mathrm{OR}~b$$
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concrete_boolean::gen_keys;
+    ///
+    /// // Generate the client key and the server key:
+    /// let (cks, sks) = gen_keys();
+    ///
+    /// // Encrypt a message:
+    /// let ct = cks.encrypt(true);
+    ///
+    /// // Compute homomorphically an OR gate with a clear value:
+    /// let ct_res = sks.or_clear(&ct, false);
+    ///
+    /// // Decrypt:
+    /// let dec_or = cks.decrypt(&ct_res);
+    /// assert_eq!(true, dec_or);
+    /// ```
+    pub fn or_clear(&self, ct: &Ciphertext, b: bool) -> Ciphertext {
+        self.try_or_clear(ct, b).unwrap()
+    }
+
+    /// Computes homomorphically an XNOR gate between a ciphertext encrypting a Boolean value and a
+    /// clear Boolean value, without any bootstrap:
+    /// $$ct_{out} = (ct~==~b)$$
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concrete_boolean::gen_keys;
+    ///
+    /// // Generate the client key and the server key:
+    /// let (cks, sks) = gen_keys();
+    ///
+    /// // Encrypt a message:
+    /// let ct = cks.encrypt(true);
+    ///
+    /// // Compute homomorphically an XNOR gate with a clear value:
+    /// let ct_res = sks.xnor_clear(&ct, false);
+    ///
+    /// // Decrypt:
+    /// let dec_xnor = cks.decrypt(&ct_res);
+    /// assert_eq!(false, dec_xnor);
+    /// ```
+    pub fn xnor_clear(&self, ct: &Ciphertext, b: bool) -> Ciphertext {
+        self.try_xnor_clear(ct, b).unwrap()
+    }
+
+    /// Computes homomorphically an XOR gate between a ciphertext encrypting a Boolean value and a
+    /// clear Boolean value, without any bootstrap:
+    /// $$ct_{out} = ct~\mathrm{XOR}~b$$
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concrete_boolean::gen_keys;
+    ///
+    /// // Generate the client key and the server key:
+    /// let (cks, sks) = gen_keys();
+    ///
+    /// // Encrypt a message:
+    /// let ct = cks.encrypt(true);
+    ///
+    /// // Compute homomorphically an XOR gate with a clear value:
+    /// let ct_res = sks.xor_clear(&ct, false);
+    ///
+    /// // Decrypt:
+    /// let dec_xor = cks.decrypt(&ct_res);
+    /// assert_eq!(true, dec_xor);
+    /// ```
+    pub fn xor_clear(&self, ct: &Ciphertext, b: bool) -> Ciphertext {
+        self.try_xor_clear(ct, b).unwrap()
+    }
+
+    /// Computes homomorphically a majority gate between three ciphertexts encrypting Boolean
+    /// values, with a single bootstrap:
+    /// $$ct_{out} = \mathrm{MAJ}(ct_1, ct_2, ct_3)$$
+    ///
+    /// It is true if at least two of its inputs are true, which is also the carry of a full
+    /// adder.
+    ///
+    /// # Errors
+    ///
+    /// The errors of [`ServerKey::threshold_gate`]: `KeyMismatch` if one of the ciphertexts is
+    /// encrypted under another client key, or `ErrorProbabilityError` if the parameters of the
+    /// server key are too noisy for the sum of three ciphertexts.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concrete_boolean::gen_keys;
+    ///
+    /// // Generate the client key and the server key:
+    /// let (cks, sks) = gen_keys();
+    ///
+    /// // Encryption of three messages:
+    /// let ct1 = cks.encrypt(true);
+    /// let ct2 = cks.encrypt(false);
+    /// let ct3 = cks.encrypt(true);
+    ///
+    /// // Compute the majority gate:
+    /// let ct_res = sks.maj(&ct1, &ct2, &ct3).unwrap();
+    ///
+    /// // Decryption:
+    /// let dec_maj = cks.decrypt(&ct_res);
+    /// assert_eq!(true, dec_maj);
+    /// ```
+    pub fn maj(
+        &self,
+        ct_1: &Ciphertext,
+        ct_2: &Ciphertext,
+        ct_3: &Ciphertext,
+    ) -> Result<Ciphertext, BooleanError> {
+        self.threshold_gate(&[ct_1, ct_2, ct_3], &[1, 1, 1], 2)
+    }
+
+    /// Computes homomorphically a threshold gate between ciphertexts encrypting Boolean values,
+    /// with a single bootstrap of their linear combination:
+    /// $$ct_{out} = \left(\sum_i w_i \cdot ct_i \geq t\right)$$
+    ///
+    /// The trivial encryptions are folded into the threshold, and the gate outputs a trivial
+    /// encryption when its value does not depend on the other inputs.
+    ///
+    /// # Errors
+    ///
+    /// * `KeyMismatch` if one of the ciphertexts is encrypted under another client key.
+    /// * `NbCTError` if there are not as many weights as ciphertexts.
+    /// * `ThresholdRangeError` if the weighted sums of the non-trivial inputs are not all in
+    ///   $[t - 2, t + 1]$: a single bootstrap only separates two values of the sum on each side
+    ///   of the threshold.
+    /// * `ErrorProbabilityError` if the probability of error of the bootstrap of the linear
+    ///   combination, computed from the parameters of the server key, is above the one of a gate
+    ///   with the [`DEFAULT_PARAMETERS`](crate::parameters::DEFAULT_PARAMETERS), $2^{-25}$. With
+    ///   those parameters, the sum of the squares of the weights must be at most 4.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concrete_boolean::gen_keys;
+    ///
+    /// // Generate the client key and the server key:
+    /// let (cks, sks) = gen_keys();
+    ///
+    /// // Encryption of three messages:
+    /// let ct1 = cks.encrypt(true);
+    /// let ct2 = cks.encrypt(true);
+    /// let ct3 = cks.encrypt(false);
+    ///
+    /// // Compute ct1 - ct2 + ct3 >= 1:
+    /// let ct_res = sks.threshold_gate(&[&ct1, &ct2, &ct3], &[1, -1, 1], 1).unwrap();
+    ///
+    /// // Decryption:
+    /// let dec = cks.decrypt(&ct_res);
+    /// assert_eq!(false, dec);
+    ///
+    /// // The sums of ct1 + ct2 + ct3 are not all in [t - 2, t + 1] for a three-input AND:
+    /// assert!(sks.threshold_gate(&[&ct1, &ct2, &ct3], &[1, 1, 1], 3).is_err());
+    /// ```
+    pub fn threshold_gate(
+        &self,
+        cts: &[&Ciphertext],
+        weights: &[i32],
+        threshold: i32,
+    ) -> Result<Ciphertext, BooleanError> {
+        // Check the inputs:
+        self.try_check_keys(cts)?;
+        if cts.len() != weights.len() {
+            return Err(concrete::NbCTError!(weights.len(), cts.len()).into());
+        }
+
+        // Propagate the constants:
+        let mut threshold = threshold as i64;
+        let mut encrypted_cts: Vec<&Ciphertext> = Vec::new();
+        let mut encrypted_weights: Vec<i32> = Vec::new();
+        for (ct, weight) in cts.iter().zip(weights.iter()) {
+            match ct.as_constant() {
+                Some(true) => threshold -= *weight as i64,
+                Some(false) => (),
+                None if *weight == 0 => (),
+                None => {
+                    encrypted_cts.push(ct);
+                    encrypted_weights.push(*weight);
+                }
+            }
+        }
+
+        // Compute the range of the weighted sums of the encrypted inputs:
+        let min_sum: i64 = encrypted_weights
+            .iter()
+            .map(|w| i64::min(*w as i64, 0))
+            .sum();
+        let max_sum: i64 = encrypted_weights
+            .iter()
+            .map(|w| i64::max(*w as i64, 0))
+            .sum();
+        if min_sum >= threshold {
+            return Ok(self.trivial_encrypt(true));
+        }
+        if max_sum < threshold {
+            return Ok(self.trivial_encrypt(false));
+        }
+
+        // Check that the phases of the sums are in ]-1/2, 1/2[, at a distance 1/8 of 0:
+        if min_sum < threshold - 2 || max_sum > threshold + 1 {
+            return Err(ThresholdRangeError!(min_sum, max_sum, threshold));
+        }
+
+        // Check the noise:
+        let squared_norm: u64 = encrypted_weights
+            .iter()
+            .map(|w| (*w as i64 * *w as i64) as u64)
+            .sum();
+        let error_probability = self.parameters.bootstrap_error_probability(squared_norm);
+        if error_probability > DEFAULT_ERROR_PROBABILITY {
+            return Err(ErrorProbabilityError!(
+                error_probability,
+                DEFAULT_ERROR_PROBABILITY
+            ));
+        }
+
+        // With b_i in {-1, 1} the sign of the input i, the phase of the linear combination is
+        // sum_i w_i * b_i / 8 = (2 * sum - sum_i w_i) / 8, shifted to (2 * (sum - t) + 1) / 8:
+        let weight_sum: i64 = encrypted_weights.iter().map(|w| *w as i64).sum();
+        let shift = weight_sum - 2 * threshold + 1;
+        Ok(self.bootstrap_linear_combination(&encrypted_cts, &encrypted_weights, shift as i32))
+    }
+
+    /// Bootstraps a ciphertext into a fresh encryption of true if its phase is in $[0, 1/2[$, and
+    /// of false otherwise.
+    ///
+    /// The gates already output fresh ciphertexts: this is meant for the ciphertexts built with
+    /// [`Ciphertext::from_lwe_ciphertext`], whose phase can be anywhere on the torus.
+    ///
+    /// # Panics
+    ///
+    /// If the ciphertext is encrypted under another client key.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concrete_boolean::ciphertext::Ciphertext;
+    /// use concrete_boolean::gen_keys;
+    ///
+    /// // Generate the client key and the server key:
+    /// let (cks, sks) = gen_keys();
+    ///
+    /// // Add the phases of two encryptions of true, 1/8 + 1/8 = 1/4:
+    /// let mut lwe = cks.encrypt(true).as_lwe_ciphertext().clone();
+    /// lwe.update_with_add(cks.encrypt(true).as_lwe_ciphertext());
+    /// let ct = Ciphertext::from_lwe_ciphertext(lwe, cks.fingerprint());
+    ///
+    /// // Refresh:
+    /// let ct_res = sks.refresh(&ct);
+    /// assert_eq!(true, cks.decrypt(&ct_res));
+    /// ```
+    pub fn refresh(&self, ct: &Ciphertext) -> Ciphertext {
+        self.try_refresh(ct).unwrap()
+    }
+
+    /// Returns the fingerprint of the client key the server key is generated from.
+    pub fn fingerprint(&self) -> u64 {
+        self.fingerprint
+    }
+
+    /// Returns the dimension of the LWE secret key the ciphertexts are encrypted under.
+    pub fn lwe_dimension(&self) -> LweDimension {
+        self.key_switching_key.after_key_size()
+    }
+
+    /// Computes the linear combination of ciphertexts with weights, shifted by a multiple of 1/8,
+    /// and bootstraps it into a fresh ciphertext, which is true if the phase of the linear
+    /// combination is in $[0, 1/2[$.
+    fn bootstrap_linear_combination(
+        &self,
+        cts: &[&Ciphertext],
+        weights: &[i32],
+        shift: i32,
+    ) -> Ciphertext {
+        // Compute the linear combination:
+        let mut ct_temp = LweCiphertext::allocate(0_u32, cts[0].0.lwe_size());
+        for (ct, weight) in cts.iter().zip(weights.iter()) {
+            let mut ct_weighted = ct.0.clone();
+            ct_weighted.update_with_scalar_mul(Cleartext(*weight as u32));
+            ct_temp.update_with_add(&ct_weighted);
+        }
+        ct_temp.get_mut_body().0 = ct_temp
+            .get_mut_body()
+            .0
+            .wrapping_add((shift as u32).wrapping_mul(PLAINTEXT_TRUE)); // +shift/8
+
+        // Create the accumulator:
+        let mut accumulator = GlweCiphertext::allocate(
+            0_u32,
+            self.bootstrapping_key.polynomial_size(),
+            self.bootstrapping_key.glwe_size(),
+        );
+
+        // Fill the body of accumulator with the Test Polynomial:
+        accumulator
+            .get_mut_body()
+            .as_mut_tensor()
+            .fill_with_element(PLAINTEXT_TRUE); // 1/8
+
+        // Allocate the output of the PBS:
+        let mut ct_pbs = LweCiphertext::allocate(
+            0_u32,
+            self.bootstrapping_key.output_lwe_dimension().to_lwe_size(),
+        );
+
+        // Compute the programmable bootstrapping with fixed test polynomial:
+        self.bootstrapping_key
+            .bootstrap(&mut ct_pbs, &ct_temp, &accumulator);
+
+        // Compute the key switch to get back to input key:
+        let mut ct_ks = LweCiphertext::allocate(0_u32, cts[0].0.lwe_size());
+        self.key_switching_key
+            .keyswitch_ciphertext(&mut ct_ks, &ct_pbs);
+
+        // Output the result:
+        Ciphertext(ct_ks, self.fingerprint)
+    }
+}
+
+/// The gates returning an error instead of panicking when one of their inputs is encrypted under
+/// another client key, which the gates of the previous block unwrap.
+impl ServerKey {
+    /// Computes homomorphically an AND gate like [`ServerKey::and`], returning an error instead of
+    /// panicking.
+    ///
+    /// # Errors
+    ///
+    /// `KeyMismatch` if one of the ciphertexts is encrypted under another client key.
     ///
     /// # Example
     ///
     /// ```rust
     /// use concrete_boolean::gen_keys;
     ///
-    /// // Generate the client key and the server key:
-    /// let (cks, sks) = gen_keys();
-    ///
-    /// // Encrypt two messages:
-    /// let ct1 = cks.encrypt(true);
-    /// let ct2 = cks.encrypt(false);
+    /// // Generate the keys of two clients:
+    /// let (cks_1, sks_1) = gen_keys();
+    /// let (cks_2, _) = gen_keys();
     ///
-    /// // Compute homomorphically an AND gate:
-    /// let ct_res = sks.and(&ct1, &ct2);
+    /// // Compute an AND gate between ciphertexts of the first client:
+    /// let ct_res = sks_1.try_and(&cks_1.encrypt(true), &cks_1.encrypt(true)).unwrap();
+    /// assert_eq!(true, cks_1.decrypt(&ct_res));
     ///
-    /// // Decrypt:
-    /// let dec_and = cks.decrypt(&ct_res);
-    /// assert_eq!(false, dec_and);
+    /// // An input of the second client is detected:
+    /// assert!(sks_1.try_and(&cks_1.encrypt(true), &cks_2.encrypt(true)).is_err());
     /// ```
-    pub fn and(&self, ct_left: &Ciphertext, ct_right: &Ciphertext) -> Ciphertext {
+    pub fn try_and(
+        &self,
+        ct_left: &Ciphertext,
+        ct_right: &Ciphertext,
+    ) -> Result<Ciphertext, CryptoAPIError> {
+        // Check the keys:
+        self.try_check_keys(&[ct_left, ct_right])?;
+
+        // Propagate the constants:
+        if let Some(right) = ct_right.as_constant() {
+            return self.try_and_clear(ct_left, right);
+        }
+        if let Some(left) = ct_left.as_constant() {
+            return self.try_and_clear(ct_right, left);
+        }
+
         // Compute the linear combination for AND: ct_left + ct_right + (0,...,0,-1/8)
         let mut ct_temp = ct_left.0.clone();
         ct_temp.update_with_add(&ct_right.0);
@@ -168,38 +866,44 @@ impl ServerKey {
             .keyswitch_ciphertext(&mut ct_ks, &ct_pbs);
 
         // Result
-        Ciphertext(ct_ks)
+        Ok(Ciphertext(ct_ks, self.fingerprint))
     }
 
-    /// Computes an homomorphic MUX gate between three ciphertexts encrypting Boolean values:
-    /// $$ct_{out} = (ct_{condition}?~ct_{then}:~ct_{else}) $$
-    ///
-    /// # Example
-    ///
-    /// ```rust
-    /// use concrete_boolean::gen_keys;
-    ///
-    /// // Generate the client key and the server key:
-    /// let (cks, sks) = gen_keys();
+    /// Computes homomorphically a MUX gate like [`ServerKey::mux`], returning an error instead of
+    /// panicking.
     ///
-    /// // Encrypt three messages:
-    /// let ct1 = cks.encrypt(true);
-    /// let ct2 = cks.encrypt(false);
-    /// let ct3 = cks.encrypt(true);
+    /// # Errors
     ///
-    /// // Compute homomorphically a MUX gate:
-    /// let ct_res = sks.mux(&ct1, &ct2, &ct3);
-    ///
-    /// // Decrypt:
-    /// let dec_mux = cks.decrypt(&ct_res);
-    /// assert_eq!(false, dec_mux);
-    /// ```
-    pub fn mux(
+    /// `KeyMismatch` if one of the ciphertexts is encrypted under another client key.
+    pub fn try_mux(
         &self,
         ct_condition: &Ciphertext,
         ct_then: &Ciphertext,
         ct_else: &Ciphertext,
-    ) -> Ciphertext {
+    ) -> Result<Ciphertext, CryptoAPIError> {
+        // Check the keys:
+        self.try_check_keys(&[ct_condition, ct_then, ct_else])?;
+
+        // Propagate the constants:
+        if let Some(condition) = ct_condition.as_constant() {
+            return Ok(if condition {
+                ct_then.clone()
+            } else {
+                ct_else.clone()
+            });
+        }
+        match (ct_then.as_constant(), ct_else.as_constant()) {
+            (Some(then_value), Some(else_value)) if then_value == else_value => {
+                return Ok(self.trivial_encrypt(then_value))
+            }
+            (Some(then_value), Some(_)) => return self.try_xnor_clear(ct_condition, then_value),
+            (Some(true), None) => return self.try_or(ct_condition, ct_else),
+            (Some(false), None) => return self.try_nor(ct_condition, &self.try_not(ct_else)?),
+            (None, Some(true)) => return self.try_nand(ct_condition, &self.try_not(ct_then)?),
+            (None, Some(false)) => return self.try_and(ct_condition, ct_then),
+            (None, None) => (),
+        }
+
         // In theory MUX gate = (ct_condition AND ct_then) + (!ct_condition AND ct_else)
 
         // Compute the linear combination for first AND: ct_condition + ct_then + (0,...,0,-1/8)
@@ -267,32 +971,31 @@ impl ServerKey {
             .keyswitch_ciphertext(&mut ct_ks, &ct_temp);
 
         // Output the result:
-        Ciphertext(ct_ks)
+        Ok(Ciphertext(ct_ks, self.fingerprint))
     }
 
-    /// Computes homomorphically a NAND gate between two ciphertexts encrypting Boolean values:
-    /// $$ct_{out} = \mathrm{NOT} (ct_{left}~\mathrm{AND}~ct_{right})$$
+    /// Computes homomorphically a NAND gate like [`ServerKey::nand`], returning an error instead of
+    /// panicking.
     ///
-    /// # Example
-    ///
-    /// ```rust
-    /// use concrete_boolean::gen_keys;
-    ///
-    /// // Generate the client key and the server key:
-    /// let (cks, sks) = gen_keys();
-    ///
-    /// // Encrypt two messages:
-    /// let ct1 = cks.encrypt(true);
-    /// let ct2 = cks.encrypt(false);
-    ///
-    /// // Compute homomorphically a NAND gate:
-    /// let ct_res = sks.nand(&ct1, &ct2);
+    /// # Errors
     ///
-    /// // Decrypt:
-    /// let dec_nand = cks.decrypt(&ct_res);
-    /// assert_eq!(true, dec_nand);
-    /// ```
-    pub fn nand(&self, ct_left: &Ciphertext, ct_right: &Ciphertext) -> Ciphertext {
+    /// `KeyMismatch` if one of the ciphertexts is encrypted under another client key.
+    pub fn try_nand(
+        &self,
+        ct_left: &Ciphertext,
+        ct_right: &Ciphertext,
+    ) -> Result<Ciphertext, CryptoAPIError> {
+        // Check the keys:
+        self.try_check_keys(&[ct_left, ct_right])?;
+
+        // Propagate the constants:
+        if let Some(right) = ct_right.as_constant() {
+            return self.try_nand_clear(ct_left, right);
+        }
+        if let Some(left) = ct_left.as_constant() {
+            return self.try_nand_clear(ct_right, left);
+        }
+
         // Compute the linear combination for NAND: - ct_left - ct_right + (0,...,0,1/8)
         let mut ct_temp = ct_left.0.clone();
         ct_temp.update_with_neg();
@@ -331,32 +1034,31 @@ impl ServerKey {
             .keyswitch_ciphertext(&mut ct_ks, &ct_pbs);
 
         // Output the result
-        Ciphertext(ct_ks)
+        Ok(Ciphertext(ct_ks, self.fingerprint))
     }
 
-    /// Computes homomorphically a NOR gate between two ciphertexts encrypting Boolean values:
-    /// $$ ct_{out} = \mathrm{NOT}(ct_{left}~\mathrm{OR}~ct_{right}) $$
-    ///
-    /// # Example
-    ///
-    /// ```rust
-    /// use concrete_boolean::gen_keys;
-    ///
-    /// // Generate the client key and the server key:
-    /// let (cks, sks) = gen_keys();
-    ///
-    /// // Encrypt two messages:
-    /// let ct1 = cks.encrypt(true);
-    /// let ct2 = cks.encrypt(false);
+    /// Computes homomorphically a NOR gate like [`ServerKey::nor`], returning an error instead of
+    /// panicking.
     ///
-    /// // Compute homomorphically the NOR gate:
-    /// let ct_res = sks.nor(&ct1, &ct2);
+    /// # Errors
     ///
-    /// // Decrypt:
-    /// let dec_nor = cks.decrypt(&ct_res);
-    /// assert_eq!(false, dec_nor);
-    /// ```
-    pub fn nor(&self, ct_left: &Ciphertext, ct_right: &Ciphertext) -> Ciphertext {
+    /// `KeyMismatch` if one of the ciphertexts is encrypted under another client key.
+    pub fn try_nor(
+        &self,
+        ct_left: &Ciphertext,
+        ct_right: &Ciphertext,
+    ) -> Result<Ciphertext, CryptoAPIError> {
+        // Check the keys:
+        self.try_check_keys(&[ct_left, ct_right])?;
+
+        // Propagate the constants:
+        if let Some(right) = ct_right.as_constant() {
+            return self.try_nor_clear(ct_left, right);
+        }
+        if let Some(left) = ct_left.as_constant() {
+            return self.try_nor_clear(ct_right, left);
+        }
+
         // Compute the linear combination for NOR: - ct_left - ct_right + (0,...,0,-1/8)
         let mut ct_temp = ct_left.0.clone();
         ct_temp.update_with_neg();
@@ -395,62 +1097,31 @@ impl ServerKey {
             .keyswitch_ciphertext(&mut ct_ks, &ct_pbs);
 
         // Output the result:
-        Ciphertext(ct_ks)
+        Ok(Ciphertext(ct_ks, self.fingerprint))
     }
 
-    /// Computes homomorphically a NOT gate of a ciphertexts encrypting a Boolean value:
-    /// $$ct_{out} = \mathrm{NOT}(ct_{in})$$
-    ///
-    /// # Example
+    /// Computes homomorphically an OR gate like [`ServerKey::or`], returning an error instead of
+    /// panicking.
     ///
-    /// ```rust
-    /// use concrete_boolean::gen_keys;
-    ///
-    /// // Generate the client key and the server key:
-    /// let (cks, sks) = gen_keys();
-    ///
-    /// // Encrypt a message:
-    /// let ct = cks.encrypt(true);
+    /// # Errors
     ///
-    /// // Compute homomorphically a NOT gate:
-    /// let ct_res = sks.not(&ct);
-    ///
-    /// // Decrypt:
-    /// let dec_not = cks.decrypt(&ct_res);
-    /// assert_eq!(false, dec_not);
-    /// ```
-    pub fn not(&self, ct: &Ciphertext) -> Ciphertext {
-        // Compute the linear combination for NOT: -ct
-        let mut ct = ct.0.clone();
-        ct.update_with_neg();
-
-        // Output the result:
-        Ciphertext(ct)
-    }
+    /// `KeyMismatch` if one of the ciphertexts is encrypted under another client key.
+    pub fn try_or(
+        &self,
+        ct_left: &Ciphertext,
+        ct_right: &Ciphertext,
+    ) -> Result<Ciphertext, CryptoAPIError> {
+        // Check the keys:
+        self.try_check_keys(&[ct_left, ct_right])?;
+
+        // Propagate the constants:
+        if let Some(right) = ct_right.as_constant() {
+            return self.try_or_clear(ct_left, right);
+        }
+        if let Some(left) = ct_left.as_constant() {
+            return self.try_or_clear(ct_right, left);
+        }
 
-    /// Computes homomorphically an OR gate between two ciphertexts encrypting Boolean values:
-    /// $$ct_{out} = ct_{left}~\mathrm{OR}~ct_{right}$$
-    ///
-    /// # Example
-    ///
-    /// ```rust
-    /// use concrete_boolean::gen_keys;
-    ///
-    /// // Generate the client key and the server key:
-    /// let (cks, sks) = gen_keys();
-    ///
-    /// // Encrypt two messages:
-    /// let ct1 = cks.encrypt(true);
-    /// let ct2 = cks.encrypt(false);
-    ///
-    /// // Compute homomorphically the OR gate:
-    /// let ct_res = sks.or(&ct1, &ct2);
-    ///
-    /// // Decrypt:
-    /// let dec_or = cks.decrypt(&ct_res);
-    /// assert_eq!(true, dec_or);
-    /// ```
-    pub fn or(&self, ct_left: &Ciphertext, ct_right: &Ciphertext) -> Ciphertext {
         // Compute the linear combination for OR: ct_left + ct_right + (0,...,0,+1/8)
         let mut ct_temp = ct_left.0.clone();
         ct_temp.update_with_add(&ct_right.0);
@@ -488,33 +1159,31 @@ impl ServerKey {
             .keyswitch_ciphertext(&mut ct_ks, &ct_pbs);
 
         // Output the result:
-        Ciphertext(ct_ks)
+        Ok(Ciphertext(ct_ks, self.fingerprint))
     }
 
-    /// Computes homomorphically an XNOR gate (or equality test) between two ciphertexts encrypting
-    /// Boolean values:
-    /// $$ct_{out} = (ct_{left}~==~ct_{right}) $$
-    ///
-    /// # Example
-    ///
-    /// ```rust
-    /// use concrete_boolean::gen_keys;
-    ///
-    /// // Generate the client key and the server key:
-    /// let (cks, sks) = gen_keys();
-    ///
-    /// // Encrypt two messages:
-    /// let ct1 = cks.encrypt(true);
-    /// let ct2 = cks.encrypt(false);
+    /// Computes homomorphically an XNOR gate like [`ServerKey::xnor`], returning an error instead
+    /// of panicking.
     ///
-    /// // Compute the XNOR gate:
-    /// let ct_res = sks.xnor(&ct1, &ct2);
+    /// # Errors
     ///
-    /// // Decrypt:
-    /// let dec_xnor = cks.decrypt(&ct_res);
-    /// assert_eq!(false, dec_xnor);
-    /// ```
-    pub fn xnor(&self, ct_left: &Ciphertext, ct_right: &Ciphertext) -> Ciphertext {
+    /// `KeyMismatch` if one of the ciphertexts is encrypted under another client key.
+    pub fn try_xnor(
+        &self,
+        ct_left: &Ciphertext,
+        ct_right: &Ciphertext,
+    ) -> Result<Ciphertext, CryptoAPIError> {
+        // Check the keys:
+        self.try_check_keys(&[ct_left, ct_right])?;
+
+        // Propagate the constants:
+        if let Some(right) = ct_right.as_constant() {
+            return self.try_xnor_clear(ct_left, right);
+        }
+        if let Some(left) = ct_left.as_constant() {
+            return self.try_xnor_clear(ct_right, left);
+        }
+
         // Compute the linear combination for XNOR: 2*(-ct_left - ct_right) + (0,...,0,-1/4)
         let mut ct_temp = ct_left.0.clone();
         ct_temp.update_with_neg();
@@ -554,32 +1223,31 @@ impl ServerKey {
             .keyswitch_ciphertext(&mut ct_ks, &ct_pbs);
 
         // Output the result:
-        Ciphertext(ct_ks)
+        Ok(Ciphertext(ct_ks, self.fingerprint))
     }
 
-    /// Computes homomorphically an XOR gate between two ciphertexts encrypting Boolean values:
-    /// $$ct_{out}= ct_{left}~\mathrm{XOR}~ct_{right}$$
-    ///
-    /// # Example
-    ///
-    /// ```rust
-    /// use concrete_boolean::gen_keys;
-    ///
-    /// // Generate the client key and the server key:
-    /// let (cks, sks) = gen_keys();
-    ///
-    /// // Encryption of two messages:
-    /// let ct1 = cks.encrypt(true);
-    /// let ct2 = cks.encrypt(false);
+    /// Computes homomorphically an XOR gate like [`ServerKey::xor`], returning an error instead of
+    /// panicking.
     ///
-    /// // Compute the XOR gate:
-    /// let ct_res = sks.xor(&ct1, &ct2);
+    /// # Errors
     ///
-    /// // Decryption:
-    /// let dec_xor = cks.decrypt(&ct_res);
-    /// assert_eq!(true, dec_xor);
-    /// ```
-    pub fn xor(&self, ct_left: &Ciphertext, ct_right: &Ciphertext) -> Ciphertext {
+    /// `KeyMismatch` if one of the ciphertexts is encrypted under another client key.
+    pub fn try_xor(
+        &self,
+        ct_left: &Ciphertext,
+        ct_right: &Ciphertext,
+    ) -> Result<Ciphertext, CryptoAPIError> {
+        // Check the keys:
+        self.try_check_keys(&[ct_left, ct_right])?;
+
+        // Propagate the constants:
+        if let Some(right) = ct_right.as_constant() {
+            return self.try_xor_clear(ct_left, right);
+        }
+        if let Some(left) = ct_left.as_constant() {
+            return self.try_xor_clear(ct_right, left);
+        }
+
         // Compute the linear combination for XOR: 2*(ct_left + ct_right) + (0,...,0,1/4)
         let mut ct_temp = ct_left.0.clone();
         ct_temp.update_with_add(&ct_right.0);
@@ -618,6 +1286,184 @@ impl ServerKey {
             .keyswitch_ciphertext(&mut ct_ks, &ct_pbs);
 
         // Output the result:
-        Ciphertext(ct_ks)
+        Ok(Ciphertext(ct_ks, self.fingerprint))
+    }
+
+    /// Computes homomorphically a NOT gate like [`ServerKey::not`], returning an error instead of
+    /// panicking.
+    ///
+    /// # Errors
+    ///
+    /// `KeyMismatch` if the ciphertext is encrypted under another client key.
+    pub fn try_not(&self, ct: &Ciphertext) -> Result<Ciphertext, CryptoAPIError> {
+        // Check the keys:
+        self.try_check_keys(&[ct])?;
+
+        // Compute the linear combination for NOT: -ct
+        let mut ct_not = ct.0.clone();
+        ct_not.update_with_neg();
+
+        // Output the result:
+        Ok(Ciphertext(ct_not, ct.1))
+    }
+
+    /// Computes homomorphically an AND gate with a clear operand like [`ServerKey::and_clear`],
+    /// returning an error instead of panicking.
+    ///
+    /// # Errors
+    ///
+    /// `KeyMismatch` if the ciphertext is encrypted under another client key.
+    pub fn try_and_clear(&self, ct: &Ciphertext, b: bool) -> Result<Ciphertext, CryptoAPIError> {
+        // Check the keys:
+        self.try_check_keys(&[ct])?;
+
+        if b {
+            Ok(ct.clone())
+        } else {
+            Ok(self.trivial_encrypt(false))
+        }
+    }
+
+    /// Computes homomorphically a NAND gate with a clear operand like [`ServerKey::nand_clear`],
+    /// returning an error instead of panicking.
+    ///
+    /// # Errors
+    ///
+    /// `KeyMismatch` if the ciphertext is encrypted under another client key.
+    pub fn try_nand_clear(&self, ct: &Ciphertext, b: bool) -> Result<Ciphertext, CryptoAPIError> {
+        // Check the keys:
+        self.try_check_keys(&[ct])?;
+
+        if b {
+            self.try_not(ct)
+        } else {
+            Ok(self.trivial_encrypt(true))
+        }
+    }
+
+    /// Computes homomorphically a NOR gate with a clear operand like [`ServerKey::nor_clear`],
+    /// returning an error instead of panicking.
+    ///
+    /// # Errors
+    ///
+    /// `KeyMismatch` if the ciphertext is encrypted under another client key.
+    pub fn try_nor_clear(&self, ct: &Ciphertext, b: bool) -> Result<Ciphertext, CryptoAPIError> {
+        // Check the keys:
+        self.try_check_keys(&[ct])?;
+
+        if b {
+            Ok(self.trivial_encrypt(false))
+        } else {
+            self.try_not(ct)
+        }
+    }
+
+    /// Computes homomorphically an OR gate with a clear operand like [`ServerKey::or_clear`],
+    /// returning an error instead of panicking.
+    ///
+    /// # Errors
+    ///
+    /// `KeyMismatch` if the ciphertext is encrypted under another client key.
+    pub fn try_or_clear(&self, ct: &Ciphertext, b: bool) -> Result<Ciphertext, CryptoAPIError> {
+        // Check the keys:
+        self.try_check_keys(&[ct])?;
+
+        if b {
+            Ok(self.trivial_encrypt(true))
+        } else {
+            Ok(ct.clone())
+        }
+    }
+
+    /// Computes homomorphically an XNOR gate with a clear operand like [`ServerKey::xnor_clear`],
+    /// returning an error instead of panicking.
+    ///
+    /// # Errors
+    ///
+    /// `KeyMismatch` if the ciphertext is encrypted under another client key.
+    pub fn try_xnor_clear(&self, ct: &Ciphertext, b: bool) -> Result<Ciphertext, CryptoAPIError> {
+        // Check the keys:
+        self.try_check_keys(&[ct])?;
+
+        if b {
+            Ok(ct.clone())
+        } else {
+            self.try_not(ct)
+        }
+    }
+
+    /// Computes homomorphically an XOR gate with a clear operand like [`ServerKey::xor_clear`],
+    /// returning an error instead of panicking.
+    ///
+    /// # Errors
+    ///
+    /// `KeyMismatch` if the ciphertext is encrypted under another client key.
+    pub fn try_xor_clear(&self, ct: &Ciphertext, b: bool) -> Result<Ciphertext, CryptoAPIError> {
+        // Check the keys:
+        self.try_check_keys(&[ct])?;
+
+        if b {
+            self.try_not(ct)
+        } else {
+            Ok(ct.clone())
+        }
+    }
+
+    /// Refreshes a ciphertext like [`ServerKey::refresh`], returning an error instead of panicking.
+    ///
+    /// # Errors
+    ///
+    /// `KeyMismatch` if the ciphertext is encrypted under another client key.
+    pub fn try_refresh(&self, ct: &Ciphertext) -> Result<Ciphertext, CryptoAPIError> {
+        // Check the keys:
+        self.try_check_keys(&[ct])?;
+
+        // A trivial ciphertext does not need any bootstrap:
+        if let Some(message) = ct.as_constant() {
+            return Ok(self.trivial_encrypt(message));
+        }
+
+        // Create the accumulator:
+        let mut accumulator = GlweCiphertext::allocate(
+            0_u32,
+            self.bootstrapping_key.polynomial_size(),
+            self.bootstrapping_key.glwe_size(),
+        );
+
+        // Fill the body of accumulator with the Test Polynomial:
+        accumulator
+            .get_mut_body()
+            .as_mut_tensor()
+            .fill_with_element(PLAINTEXT_TRUE); // 1/8
+
+        // Allocate the output of the PBS:
+        let mut ct_pbs = LweCiphertext::allocate(
+            0_u32,
+            self.bootstrapping_key.output_lwe_dimension().to_lwe_size(),
+        );
+
+        // Compute the programmable bootstrapping with fixed test polynomial:
+        self.bootstrapping_key
+            .bootstrap(&mut ct_pbs, &ct.0, &accumulator);
+
+        // Compute the key switch to get back to input key:
+        let mut ct_ks = LweCiphertext::allocate(0_u32, ct.0.lwe_size());
+        self.key_switching_key
+            .keyswitch_ciphertext(&mut ct_ks, &ct_pbs);
+
+        // Output the result:
+        Ok(Ciphertext(ct_ks, self.fingerprint))
+    }
+
+    /// Checks that the inputs of a gate are encrypted under the client key of the server key.
+    ///
+    /// # Errors
+    ///
+    /// `KeyMismatch` if one of the ciphertexts is encrypted under another client key.
+    fn try_check_keys(&self, cts: &[&Ciphertext]) -> Result<(), CryptoAPIError> {
+        for ct in cts.iter() {
+            crate::try_check_fingerprint(self.fingerprint, ct.1)?;
+        }
+        Ok(())
     }
 }
@@ -1,10 +1,12 @@
 use crate::ciphertext::Ciphertext;
 use crate::client_key::ClientKey;
-use crate::parameters::DEFAULT_PARAMETERS;
+use crate::error::BooleanError;
+use crate::parameters::{DEFAULT_ERROR_PROBABILITY, DEFAULT_PARAMETERS, TFHE_LIB_PARAMETERS};
 use crate::server_key::ServerKey;
 use crate::{
     random_boolean, random_integer, PLAINTEXT_FALSE, PLAINTEXT_LOG_SCALING_FACTOR, PLAINTEXT_TRUE,
 };
+use concrete::CryptoAPIError;
 use concrete_commons::parameters::{DecompositionBaseLog, DecompositionLevelCount, LweSize};
 use concrete_core::crypto::bootstrap::Bootstrap;
 use concrete_core::crypto::encoding::Plaintext;
@@ -24,6 +26,72 @@ const NB_CT: usize = 8;
 /// Number of gates computed in the deep circuit test
 const NB_GATE: usize = 1 << 11;
 
+#[test]
+/// test that the secret keys do not appear in the debug output of the client key
+fn test_client_key_debug_redacted() {
+    // generate the client key set
+    let cks = ClientKey::new(&DEFAULT_PARAMETERS);
+
+    let debug = format!("{:?}", cks);
+    assert!(debug.contains("parameters"));
+    assert!(!debug.contains("secret_key"));
+    assert!(!debug.contains("tensor"));
+}
+
+#[test]
+#[should_panic(expected = "Key mismatch")]
+/// test that decrypting with another client key panics
+fn test_decrypt_key_mismatch() {
+    // generate two client keys
+    let cks_1 = ClientKey::new(&DEFAULT_PARAMETERS);
+    let cks_2 = ClientKey::new(&DEFAULT_PARAMETERS);
+
+    // a ciphertext without fingerprint can be decrypted with any key
+    let ct = cks_1.encrypt(true);
+    cks_2.decrypt(&Ciphertext(ct.0.clone(), 0));
+
+    cks_2.decrypt(&ct);
+}
+
+#[test]
+#[should_panic(expected = "Key mismatch")]
+/// test that a gate with an input encrypted under another client key panics
+fn test_gate_key_mismatch() {
+    // generate two client keys and the server key of the first one
+    let cks_1 = ClientKey::new(&DEFAULT_PARAMETERS);
+    let cks_2 = ClientKey::new(&DEFAULT_PARAMETERS);
+    let sks = ServerKey::new(&cks_1);
+
+    sks.and(&cks_1.encrypt(true), &cks_2.encrypt(true));
+}
+
+#[test]
+/// test that the try gates return an error instead of panicking on an input of another client
+fn test_try_gates_key_mismatch() {
+    // generate two client keys and the server key of the first one
+    let cks_1 = ClientKey::new(&DEFAULT_PARAMETERS);
+    let cks_2 = ClientKey::new(&DEFAULT_PARAMETERS);
+    let sks = ServerKey::new(&cks_1);
+    let ct_1 = cks_1.encrypt(true);
+    let ct_2 = cks_2.encrypt(true);
+
+    // inputs of the other client
+    assert!(matches!(
+        sks.try_and(&ct_1, &ct_2),
+        Err(CryptoAPIError::KeyMismatch { .. })
+    ));
+    assert!(sks.try_mux(&ct_2, &ct_1, &ct_1).is_err());
+    assert!(sks.try_not(&ct_2).is_err());
+    assert!(sks.try_or_clear(&ct_2, false).is_err());
+    assert!(sks.maj(&ct_1, &ct_1, &ct_2).is_err());
+    assert!(sks.try_refresh(&ct_2).is_err());
+    assert!(cks_1.try_decrypt(&ct_2).is_err());
+
+    // inputs of the client of the server key
+    let ct_res = sks.try_xor(&ct_1, &ct_1).unwrap();
+    assert!(!cks_1.try_decrypt(&ct_res).unwrap());
+}
+
 #[test]
 /// test encryption and decryption with the LWE secret key
 fn test_encrypt_decrypt_lwe_secret_key() {
@@ -190,10 +258,10 @@ fn test_encrypt_ks_decrypt() {
             .keyswitch_ciphertext(&mut ct_ks_true, &ct_true);
 
         // decryption of false
-        let dec_false = cks.decrypt(&Ciphertext(ct_ks_false));
+        let dec_false = cks.decrypt(&Ciphertext(ct_ks_false, sks.fingerprint));
 
         // decryption of true
-        let dec_true = cks.decrypt(&Ciphertext(ct_ks_true));
+        let dec_true = cks.decrypt(&Ciphertext(ct_ks_true, sks.fingerprint));
 
         // assert
         assert!(!dec_false);
@@ -441,6 +509,253 @@ fn test_xor_gate() {
     }
 }
 
+#[test]
+fn test_refresh() {
+    // generate the client key set
+    let cks = ClientKey::new(&DEFAULT_PARAMETERS);
+
+    // generate the server key set
+    let sks = ServerKey::new(&cks);
+
+    for _ in 0..NB_TEST {
+        // generation of a random boolean
+        let b = random_boolean();
+
+        // encryption of b, shifted by 1/16
+        let mut ct = cks.encrypt(b);
+        ct.0.get_mut_body().0 =
+            ct.0.get_mut_body()
+                .0
+                .wrapping_add(1_u32 << (32 - PLAINTEXT_LOG_SCALING_FACTOR - 1));
+
+        // refresh
+        let ct_res = sks.refresh(&Ciphertext::from_lwe_ciphertext(
+            ct.as_lwe_ciphertext().clone(),
+            ct.fingerprint(),
+        ));
+
+        // decryption
+        let dec = cks.decrypt(&ct_res);
+
+        // assert
+        assert_eq!(b, dec);
+    }
+}
+
+#[test]
+/// test that the trivial encryptions decrypt under any client key and are detected
+fn test_trivial_encrypt() {
+    // generate two client keys and the server key of the first one
+    let cks_1 = ClientKey::new(&DEFAULT_PARAMETERS);
+    let cks_2 = ClientKey::new(&DEFAULT_PARAMETERS);
+    let sks = ServerKey::new(&cks_1);
+
+    for b in [false, true].iter() {
+        let ct = sks.trivial_encrypt(*b);
+        assert_eq!(ct.as_constant(), Some(*b));
+        assert_eq!(cks_1.decrypt(&ct), *b);
+        assert_eq!(cks_2.decrypt(&ct), *b);
+        assert_eq!(sks.not(&ct).as_constant(), Some(!*b));
+        assert_eq!(cks_1.encrypt(*b).as_constant(), None);
+    }
+}
+
+#[test]
+fn test_clear_gates() {
+    // generate the client key set
+    let cks = ClientKey::new(&DEFAULT_PARAMETERS);
+
+    // generate the server key set
+    let sks = ServerKey::new(&cks);
+
+    for _ in 0..NB_TEST {
+        // generation of two random booleans
+        let b1 = random_boolean();
+        let b2 = random_boolean();
+
+        // encryption of b1
+        let ct = cks.encrypt(b1);
+
+        // gates with a clear operand
+        assert_eq!(cks.decrypt(&sks.and_clear(&ct, b2)), b1 && b2);
+        assert_eq!(cks.decrypt(&sks.nand_clear(&ct, b2)), !(b1 && b2));
+        assert_eq!(cks.decrypt(&sks.nor_clear(&ct, b2)), !(b1 || b2));
+        assert_eq!(cks.decrypt(&sks.or_clear(&ct, b2)), b1 || b2);
+        assert_eq!(cks.decrypt(&sks.xnor_clear(&ct, b2)), b1 == b2);
+        assert_eq!(cks.decrypt(&sks.xor_clear(&ct, b2)), b1 ^ b2);
+    }
+}
+
+#[test]
+/// test the gates with trivial inputs, whose constant outputs are trivial
+fn test_constant_propagation() {
+    // generate the client key set
+    let cks = ClientKey::new(&DEFAULT_PARAMETERS);
+
+    // generate the server key set
+    let sks = ServerKey::new(&cks);
+
+    for _ in 0..NB_TEST {
+        // generation of three random booleans, encrypted or trivially encrypted
+        let b1 = random_boolean();
+        let b2 = random_boolean();
+        let b3 = random_boolean();
+        let ct1 = cks.encrypt(b1);
+        let ct2 = cks.encrypt(b2);
+        let triv1 = sks.trivial_encrypt(b1);
+        let triv2 = sks.trivial_encrypt(b2);
+        let triv3 = sks.trivial_encrypt(b3);
+
+        // two-input gates
+        for (ct_left, ct_right) in [(&ct1, &triv2), (&triv1, &ct2), (&triv1, &triv2)].iter() {
+            assert_eq!(cks.decrypt(&sks.and(ct_left, ct_right)), b1 && b2);
+            assert_eq!(cks.decrypt(&sks.nand(ct_left, ct_right)), !(b1 && b2));
+            assert_eq!(cks.decrypt(&sks.nor(ct_left, ct_right)), !(b1 || b2));
+            assert_eq!(cks.decrypt(&sks.or(ct_left, ct_right)), b1 || b2);
+            assert_eq!(cks.decrypt(&sks.xnor(ct_left, ct_right)), b1 == b2);
+            assert_eq!(cks.decrypt(&sks.xor(ct_left, ct_right)), b1 ^ b2);
+        }
+        assert_eq!(sks.and(&triv1, &triv2).as_constant(), Some(b1 && b2));
+        if !b2 {
+            assert_eq!(sks.and(&ct1, &triv2).as_constant(), Some(false));
+        }
+
+        // mux with at least one trivial input
+        let ct3 = cks.encrypt(b3);
+        let expected = if b1 { b2 } else { b3 };
+        for (ct_condition, ct_then, ct_else) in [
+            (&triv1, &ct2, &ct3),
+            (&ct1, &triv2, &ct3),
+            (&ct1, &ct2, &triv3),
+            (&ct1, &triv2, &triv3),
+            (&triv1, &triv2, &triv3),
+        ]
+        .iter()
+        {
+            let ct_res = sks.mux(ct_condition, ct_then, ct_else);
+            assert_eq!(cks.decrypt(&ct_res), expected);
+        }
+        if b2 == b3 {
+            assert_eq!(sks.mux(&ct1, &triv2, &triv3).as_constant(), Some(b2));
+        }
+
+        // refresh of a trivial ciphertext
+        assert_eq!(sks.refresh(&triv1).as_constant(), Some(b1));
+    }
+}
+
+#[test]
+fn test_three_input_gates() {
+    // generate the client key set
+    let cks = ClientKey::new(&DEFAULT_PARAMETERS);
+
+    // generate the server key set
+    let sks = ServerKey::new(&cks);
+
+    for _ in 0..NB_TEST {
+        // generation of three random booleans
+        let b1 = random_boolean();
+        let b2 = random_boolean();
+        let b3 = random_boolean();
+
+        // encryption of the booleans
+        let ct1 = cks.encrypt(b1);
+        let ct2 = cks.encrypt(b2);
+        let ct3 = cks.encrypt(b3);
+        let triv3 = sks.trivial_encrypt(b3);
+
+        // MAJ gate
+        let maj = b1 as u8 + b2 as u8 + b3 as u8 >= 2;
+        let ct_res = sks.maj(&ct1, &ct2, &ct3).unwrap();
+        assert_eq!(cks.decrypt(&ct_res), maj);
+
+        // with a trivial input
+        let ct_res = sks.maj(&ct1, &ct2, &triv3).unwrap();
+        assert_eq!(cks.decrypt(&ct_res), maj);
+    }
+}
+
+#[test]
+fn test_threshold_gate() {
+    // generate the client key set
+    let cks = ClientKey::new(&DEFAULT_PARAMETERS);
+
+    // generate the server key set
+    let sks = ServerKey::new(&cks);
+
+    for _ in 0..NB_TEST {
+        // generation of three random booleans
+        let b1 = random_boolean();
+        let b2 = random_boolean();
+        let b3 = random_boolean();
+        let (x1, x2, x3) = (b1 as i32, b2 as i32, b3 as i32);
+
+        // encryption of the booleans
+        let ct1 = cks.encrypt(b1);
+        let ct2 = cks.encrypt(b2);
+        let ct3 = cks.encrypt(b3);
+
+        // threshold gates
+        let ct_res = sks
+            .threshold_gate(&[&ct1, &ct2, &ct3], &[1, -1, 1], 1)
+            .unwrap();
+        assert_eq!(cks.decrypt(&ct_res), x1 - x2 + x3 >= 1);
+        let ct_res = sks.threshold_gate(&[&ct1, &ct2], &[-1, 1], 0).unwrap();
+        assert_eq!(cks.decrypt(&ct_res), x2 >= x1);
+        let ct_res = sks
+            .threshold_gate(&[&ct1, &ct2, &ct3], &[1, 1, 0], 2)
+            .unwrap();
+        assert_eq!(cks.decrypt(&ct_res), b1 && b2);
+
+        // a constant threshold gate outputs a trivial ciphertext
+        let ct_res = sks.threshold_gate(&[&ct1, &ct2], &[1, 1], 0).unwrap();
+        assert_eq!(ct_res.as_constant(), Some(true));
+        let ct_res = sks
+            .threshold_gate(&[&ct1, &sks.trivial_encrypt(false)], &[1, 1], 2)
+            .unwrap();
+        assert_eq!(ct_res.as_constant(), Some(false));
+    }
+}
+
+#[test]
+fn test_threshold_gate_errors() {
+    let (cks, sks) = crate::gen_keys();
+    let ct = cks.encrypt(true);
+
+    // not as many weights as ciphertexts
+    assert!(matches!(
+        sks.threshold_gate(&[&ct, &ct], &[1], 1),
+        Err(BooleanError::CryptoAPIError(
+            CryptoAPIError::NbCTError { .. }
+        ))
+    ));
+
+    // AND3 does not fit in a single bootstrap
+    assert!(matches!(
+        sks.threshold_gate(&[&ct, &ct, &ct], &[1, 1, 1], 3),
+        Err(BooleanError::ThresholdRangeError { .. })
+    ));
+
+    // the sum of the squares of the weights is 5
+    assert!(matches!(
+        sks.threshold_gate(&[&ct, &ct], &[2, 1], 2),
+        Err(BooleanError::ErrorProbabilityError { .. })
+    ));
+}
+
+#[test]
+fn test_bootstrap_error_probability() {
+    // the linear combinations of the two-input gates are within the error probability
+    for parameters in [DEFAULT_PARAMETERS, TFHE_LIB_PARAMETERS].iter() {
+        assert!(parameters.bootstrap_error_probability(2) <= DEFAULT_ERROR_PROBABILITY);
+    }
+
+    // the sum of the squares of the weights is at most 4 with the default parameters
+    assert!(DEFAULT_PARAMETERS.bootstrap_error_probability(4) <= DEFAULT_ERROR_PROBABILITY);
+    assert!(DEFAULT_PARAMETERS.bootstrap_error_probability(5) > DEFAULT_ERROR_PROBABILITY);
+    assert!(TFHE_LIB_PARAMETERS.bootstrap_error_probability(5) <= DEFAULT_ERROR_PROBABILITY);
+}
+
 /// generate a random index for the table in the long run tests
 fn random_index() -> usize {
     (random_integer() % (NB_CT as u32)) as usize
@@ -0,0 +1,164 @@
+//! compressed_vector_lwe module containing LWE ciphertexts switched to a smaller modulus,
+//! to send encrypted results with fewer bits per coefficient
+
+#[cfg(test)]
+mod tests;
+
+use crate::error::CryptoAPIError;
+use crate::{read_from_binary_file, write_to_binary_file, KeyFingerprint, Torus};
+use concrete_commons::numeric::Numeric;
+use concrete_commons::parameters::LweSize;
+use concrete_core::crypto::lwe::LweList;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt;
+
+/// Structure containing a list of LWE ciphertexts whose coefficients are modulo 2^log_q
+/// instead of modulo 2^64, packed next to each other in 64-bit words
+///
+/// # Attributes
+/// * `coefficients` - the packed coefficients of all the LWE ciphertexts of the list
+/// * `log_q` - the log2 of the modulus of the coefficients
+/// * `variances` - the variances of the noise of each LWE ciphertext, rounding included
+/// * `dimension` - the length the LWE mask
+/// * `nb_ciphertexts` - the number of LWE ciphertexts present in the list
+/// * `encoders` - the encoders of each LWE ciphertext of the list
+/// * `key_fingerprint` - the fingerprint of the secret key of the LWE ciphertexts
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CompressedVectorLWE {
+    pub coefficients: Vec<u64>,
+    pub log_q: usize,
+    pub variances: Vec<f64>,
+    pub dimension: usize,
+    pub nb_ciphertexts: usize,
+    pub encoders: Vec<crate::Encoder>,
+    pub key_fingerprint: KeyFingerprint,
+}
+
+impl CompressedVectorLWE {
+    /// Switch back the coefficients to the Torus, the rounding noise is not removed
+    ///
+    /// # Output
+    /// * a VectorLWE struct, with the same variances and encoders
+    pub fn decompress(&self) -> crate::VectorLWE {
+        let shift = <Torus as Numeric>::BITS - self.log_q;
+        let coefficients: Vec<Torus> = unpack(
+            &self.coefficients,
+            self.log_q,
+            self.nb_ciphertexts * (self.dimension + 1),
+        )
+        .iter()
+        .map(|c| if shift == 0 { *c } else { *c << shift })
+        .collect();
+        crate::VectorLWE {
+            ciphertexts: LweList::from_container(coefficients, LweSize(self.dimension + 1)),
+            variances: self.variances.clone(),
+            dimension: self.dimension,
+            nb_ciphertexts: self.nb_ciphertexts,
+            encoders: self.encoders.clone(),
+            key_fingerprint: self.key_fingerprint,
+        }
+    }
+
+    /// Decrypt the ciphertexts and directly decode the outputs
+    ///
+    /// # Argument
+    /// * `sk` - the LWE secret key the ciphertexts were encrypted with
+    ///
+    /// # Output
+    /// * a list of f64
+    /// * DimensionError - if the ciphertexts and the key have incompatible dimensions
+    /// * KeyMismatch - if the ciphertexts were not encrypted under the key
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// // encode and encrypt
+    /// let encoder = Encoder::new(-5., 5., 4, 1).unwrap();
+    /// let sk = LWESecretKey::new(&LWE128_630);
+    /// let ciphertexts = VectorLWE::encode_encrypt(&sk, &[-3.2, 4.3, 0.12], &encoder).unwrap();
+    ///
+    /// // 20 bits per coefficient instead of 64
+    /// let compressed = ciphertexts.modulus_switch(20, sk.kind).unwrap();
+    /// let messages = compressed.decrypt_decode(&sk).unwrap();
+    /// ```
+    pub fn decrypt_decode(&self, sk: &crate::LWESecretKey) -> Result<Vec<f64>, CryptoAPIError> {
+        self.decompress().decrypt_decode(sk)
+    }
+
+    /// Return the number of bytes of the packed coefficients
+    pub fn get_size_in_bytes(&self) -> usize {
+        self.coefficients.len() * std::mem::size_of::<u64>()
+    }
+
+    /// Save the ciphertexts in a binary file
+    pub fn save(&self, path: &str) -> Result<(), Box<dyn Error>> {
+        write_to_binary_file(path, self)
+    }
+
+    /// Load ciphertexts saved with save
+    pub fn load(path: &str) -> Result<CompressedVectorLWE, Box<dyn Error>> {
+        read_from_binary_file(path)
+    }
+}
+
+/// Pack values of log_q bits next to each other in 64-bit words
+///
+/// # Argument
+/// * `values` - the values, each one smaller than 2^log_q
+/// * `log_q` - the number of bits of each value
+///
+/// # Output
+/// * the packed words, the last one being padded with zeros
+pub(crate) fn pack(values: &[Torus], log_q: usize) -> Vec<u64> {
+    let mut words: Vec<u64> = vec![0; (values.len() * log_q + 63) / 64];
+    for (i, value) in values.iter().enumerate() {
+        let (word, offset) = ((i * log_q) / 64, (i * log_q) % 64);
+        words[word] |= value << offset;
+        if offset + log_q > 64 {
+            words[word + 1] |= value >> (64 - offset);
+        }
+    }
+    words
+}
+
+/// Unpack values packed with pack
+///
+/// # Argument
+/// * `words` - the packed words
+/// * `log_q` - the number of bits of each value
+/// * `nb_values` - the number of packed values
+///
+/// # Output
+/// * the values
+pub(crate) fn unpack(words: &[u64], log_q: usize, nb_values: usize) -> Vec<Torus> {
+    let mask: u64 = if log_q == 64 {
+        u64::MAX
+    } else {
+        (1 << log_q) - 1
+    };
+    (0..nb_values)
+        .map(|i| {
+            let (word, offset) = ((i * log_q) / 64, (i * log_q) % 64);
+            let mut value = words[word] >> offset;
+            if offset + log_q > 64 {
+                value |= words[word + 1] << (64 - offset);
+            }
+            value & mask
+        })
+        .collect()
+}
+
+impl fmt::Display for CompressedVectorLWE {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            " CompressedVectorLWE {{\n         -> log_q = {}\n         -> dimension = {}\n         -> nb of ciphertexts = {}\n         -> size = {} bytes\n       }}",
+            self.log_q,
+            self.dimension,
+            self.nb_ciphertexts,
+            self.get_size_in_bytes()
+        )
+    }
+}
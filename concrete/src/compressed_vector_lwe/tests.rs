@@ -0,0 +1,119 @@
+use itertools::izip;
+
+#[test]
+fn test_pack_x_unpack() {
+    for log_q in [1, 7, 20, 32, 63, 64].iter() {
+        let nb_values: usize = 100;
+        let mask: u64 = if *log_q == 64 {
+            u64::MAX
+        } else {
+            (1 << log_q) - 1
+        };
+        let values: Vec<u64> = concrete_core::math::random::RandomGenerator::new(None)
+            .random_uniform_tensor::<u64>(nb_values)
+            .into_container()
+            .iter()
+            .map(|v| v & mask)
+            .collect();
+
+        let words = super::pack(&values, *log_q);
+        assert_eq!(words.len(), (nb_values * log_q + 63) / 64);
+        assert_eq!(super::unpack(&words, *log_q, nb_values), values);
+    }
+}
+
+#[test]
+fn test_encode_encrypt_x_modulus_switch_x_decrypt() {
+    // random settings
+    let (min, max) = generate_random_interval!();
+    let (precision, padding) = generate_precision_padding!(6, 2);
+
+    // encoder
+    let encoder = crate::Encoder::new(min, max, precision, padding).unwrap();
+
+    // generate a secret key
+    let secret_key = crate::LWESecretKey::new(&crate::LWE128_630);
+
+    // a few messages
+    let nb_messages: usize = 10;
+    let messages: Vec<f64> = random_messages!(min, max, nb_messages);
+
+    // encode and encrypt
+    let ciphertexts = crate::VectorLWE::encode_encrypt(&secret_key, &messages, &encoder).unwrap();
+
+    // modulus switch
+    let compressed = ciphertexts.modulus_switch(24, secret_key.kind).unwrap();
+    assert!(2 * compressed.get_size_in_bytes() < nb_messages * 631 * 8);
+
+    // decrypt
+    let decryptions = compressed.decrypt_decode(&secret_key).unwrap();
+
+    // test
+    for (m, d, e) in izip!(
+        messages.iter(),
+        decryptions.iter(),
+        compressed.encoders.iter()
+    ) {
+        assert_eq_granularity!(m, d, e);
+    }
+}
+
+#[test]
+fn test_encode_encrypt_x_modulus_switch_non_binary_x_decrypt() {
+    // random settings
+    let (min, max) = generate_random_interval!();
+    let (precision, padding) = generate_precision_padding!(6, 2);
+
+    // encoder
+    let encoder = crate::Encoder::new(min, max, precision, padding).unwrap();
+
+    for kind in [
+        crate::SecretKeyKind::Ternary,
+        crate::SecretKeyKind::Gaussian,
+    ]
+    .iter()
+    {
+        // generate a secret key
+        let secret_key = crate::LWESecretKey::new_with_kind(&crate::LWE128_630, *kind);
+
+        // a few messages
+        let nb_messages: usize = 10;
+        let messages: Vec<f64> = random_messages!(min, max, nb_messages);
+
+        // encode and encrypt
+        let ciphertexts =
+            crate::VectorLWE::encode_encrypt(&secret_key, &messages, &encoder).unwrap();
+
+        // the rounding noise grows with the second moment of the key
+        let binary = ciphertexts
+            .modulus_switch(24, crate::SecretKeyKind::Binary)
+            .unwrap();
+        let compressed = ciphertexts.modulus_switch(24, *kind).unwrap();
+        for (var_binary, var) in izip!(binary.variances.iter(), compressed.variances.iter()) {
+            assert!(var > var_binary);
+        }
+
+        // decrypt
+        let decryptions = compressed.decrypt_decode(&secret_key).unwrap();
+
+        // test
+        for (m, d, e) in izip!(
+            messages.iter(),
+            decryptions.iter(),
+            compressed.encoders.iter()
+        ) {
+            assert_eq_granularity!(m, d, e);
+        }
+    }
+}
+
+#[test]
+fn test_modulus_switch_wrong_modulus() {
+    let encoder = crate::Encoder::new(0., 1., 4, 1).unwrap();
+    let secret_key = crate::LWESecretKey::new(&crate::LWEParams::new(64, -20));
+    let ciphertexts = crate::VectorLWE::encode_encrypt(&secret_key, &[0.5], &encoder).unwrap();
+
+    assert!(ciphertexts.modulus_switch(0, secret_key.kind).is_err());
+    assert!(ciphertexts.modulus_switch(65, secret_key.kind).is_err());
+    assert!(ciphertexts.modulus_switch(64, secret_key.kind).is_ok());
+}
@@ -128,6 +128,79 @@ impl Encoder {
         })
     }
 
+    /// Instantiate a new Encoder for integers written with `nb_bit_precision` bits,
+    /// i.e. the interval [0, 2^nb_bit_precision[ or [-2^(nb_bit_precision-1), 2^(nb_bit_precision-1)[ if signed.
+    /// The granularity is exactly 1 so this encoder is in a rounding context and there is no margin:
+    /// without padding, homomorphic additions and multiplications by integer constants wrap modulo 2^nb_bit_precision exactly,
+    /// and decrypt_decode_round outputs exact integers.
+    ///
+    /// # Arguments
+    /// * `nb_bit_precision` - number of bits of the integers
+    /// * `signed` - true for two's complement integers
+    /// * `nb_bit_padding` - number of bits for left padding with zeros
+    /// # Output
+    /// * a new instantiation of an Encoder
+    /// * PrecisionError - if `nb_bit_precision` is zero
+    /// # Example
+    /// ```rust
+    /// use concrete::Encoder;
+    ///
+    /// // signed integers in [-8, 8[ with 2 bits of padding
+    /// let encoder = Encoder::new_integer(4, true, 2).unwrap();
+    /// ```
+    pub fn new_integer(
+        nb_bit_precision: usize,
+        signed: bool,
+        nb_bit_padding: usize,
+    ) -> Result<Encoder, CryptoAPIError> {
+        if nb_bit_precision == 0 {
+            return Err(PrecisionError!());
+        }
+
+        let delta = f64::powi(2., nb_bit_precision as i32);
+        Ok(Encoder {
+            o: if signed { -delta / 2. } else { 0. },
+            delta,
+            nb_bit_precision,
+            nb_bit_padding,
+            round: true,
+        })
+    }
+
+    /// Instantiate a new Encoder for integers modulo `modulus`, i.e. the interval [0, modulus[.
+    /// The torus is exactly the interval so, without padding, homomorphic additions and
+    /// multiplications by integer constants wrap modulo `modulus`.
+    /// The modulus has to be a power of two, so that decrypt_decode_round outputs exact integers:
+    /// this is Encoder::new_integer(log2(modulus), false, nb_bit_padding).
+    ///
+    /// # Arguments
+    /// * `modulus` - the modulus of the integers, a power of two
+    /// * `nb_bit_padding` - number of bits for left padding with zeros
+    /// # Output
+    /// * a new instantiation of an Encoder
+    /// * PrecisionError - if `modulus` is smaller than 2
+    /// * ModulusNotPowerOfTwoError - if `modulus` is not a power of two
+    /// # Example
+    /// ```rust
+    /// use concrete::Encoder;
+    ///
+    /// // integers modulo 16 without padding
+    /// let encoder = Encoder::new_modular(16, 0).unwrap();
+    ///
+    /// // integers modulo 10 cannot be decoded exactly
+    /// assert!(Encoder::new_modular(10, 0).is_err());
+    /// ```
+    pub fn new_modular(modulus: u64, nb_bit_padding: usize) -> Result<Encoder, CryptoAPIError> {
+        if modulus < 2 {
+            return Err(PrecisionError!());
+        }
+        if !modulus.is_power_of_two() {
+            return Err(ModulusNotPowerOfTwoError!(modulus));
+        }
+
+        Encoder::new_integer(modulus.trailing_zeros() as usize, false, nb_bit_padding)
+    }
+
     /// After an homomorphic operation, update an encoder using the variance
     /// # Arguments
     /// * `variance` - variance
@@ -443,6 +516,251 @@ impl Encoder {
         }
     }
 
+    /// Create the encoder of the sum of two messages encoded with self and other,
+    /// i.e. the output encoder of add_with_padding
+    /// # Argument
+    /// * `other` - an Encoder with the same delta and the same number of bits of padding
+    /// # Output
+    /// * a new Encoder
+    /// * PaddingError - if the encoders do not have the same number of bits of padding
+    /// * NotEnoughPaddingError - if there is no bit of padding
+    /// * DeltaError - if the encoders do not have the same delta
+    /// # Example
+    /// ```rust
+    /// use concrete::Encoder;
+    ///
+    /// // instantiation
+    /// let encoder_1 = Encoder::new(0., 10., 6, 2).unwrap();
+    /// let encoder_2 = Encoder::new(-5., 5., 6, 2).unwrap();
+    ///
+    /// // encoder of the sum
+    /// let encoder_sum = encoder_1.add(&encoder_2).unwrap();
+    /// ```
+    pub fn add(&self, other: &Encoder) -> Result<Encoder, CryptoAPIError> {
+        if self.nb_bit_padding != other.nb_bit_padding {
+            return Err(PaddingError!(self.nb_bit_padding, other.nb_bit_padding));
+        } else if self.nb_bit_padding == 0 {
+            return Err(NotEnoughPaddingError!(self.nb_bit_padding, 1));
+        } else if !deltas_eq!(self.delta, other.delta) {
+            return Err(DeltaError!(self.delta, other.delta));
+        }
+
+        Ok(Encoder {
+            o: self.o + other.o,
+            delta: self.delta * 2.,
+            nb_bit_precision: usize::min(self.nb_bit_precision, other.nb_bit_precision),
+            nb_bit_padding: self.nb_bit_padding - 1,
+            round: self.round,
+        })
+    }
+
+    /// Create the encoder of the messages of self multiplied by a real constant,
+    /// i.e. the output encoder of mul_constant_with_padding: the interval is scaled by
+    /// `max_constant` and `nb_bit_padding` bits of padding are consumed
+    /// The precision does not take into account the noise of the ciphertext, which can lower it
+    /// # Argument
+    /// * `constant` - a real constant
+    /// * `max_constant` - the maximum absolute value of the constant
+    /// * `nb_bit_padding` - the number of bits of padding to be consumed
+    /// # Output
+    /// * a new Encoder
+    /// * ConstantMaximumError - if the constant is bigger than `max_constant`
+    /// * ZeroInIntervalError - if zero is not in the interval of self
+    /// * NotEnoughPaddingError - if there is not enough padding
+    /// # Example
+    /// ```rust
+    /// use concrete::Encoder;
+    ///
+    /// // instantiation
+    /// let encoder = Encoder::new(-10., 10., 6, 8).unwrap();
+    ///
+    /// // encoder of the messages multiplied by -2.5, consuming 6 bits of padding
+    /// let encoder_scaled = encoder.scale(-2.5, 4., 6).unwrap();
+    /// ```
+    pub fn scale(
+        &self,
+        constant: f64,
+        max_constant: f64,
+        nb_bit_padding: usize,
+    ) -> Result<Encoder, CryptoAPIError> {
+        let mut res = self.scale_abs(constant, max_constant, nb_bit_padding)?;
+        if constant < 0. {
+            res.opposite_inplace()?;
+        }
+        Ok(res)
+    }
+
+    /// Create the encoder of the messages of self multiplied by the absolute value of a real
+    /// constant with mul_constant_with_padding, before the opposite of a negative product is taken
+    pub(crate) fn scale_abs(
+        &self,
+        constant: f64,
+        max_constant: f64,
+        nb_bit_padding: usize,
+    ) -> Result<Encoder, CryptoAPIError> {
+        // check that the constant if below the maximum
+        if constant > max_constant || constant < -max_constant {
+            return Err(ConstantMaximumError!(constant, max_constant));
+        }
+        // check that zero is in the interval
+        else if self.o > 0. || self.o + self.delta < 0. {
+            return Err(ZeroInIntervalError!(self.o, self.delta));
+        }
+        // check bits of paddings
+        else if self.nb_bit_padding < nb_bit_padding {
+            return Err(NotEnoughPaddingError!(self.nb_bit_padding, nb_bit_padding));
+        }
+
+        // discretize the absolute value of the constant with regard to the number of bits of
+        // padding to use
+        let c_abs = constant.abs();
+        let scal: Torus =
+            (c_abs / max_constant * f64::powi(2., nb_bit_padding as i32)).round() as Torus;
+        let discret_c_abs = (scal as f64) * f64::powi(2., -(nb_bit_padding as i32)) * max_constant;
+
+        // compute  the rounding error on c_abs
+        let rounding_error = (discret_c_abs - c_abs).abs();
+
+        // new interval
+        let new_o = self.o * max_constant;
+        let new_max = (self.o + self.delta - self.get_granularity()) * max_constant;
+        let new_delta = new_max - new_o;
+
+        // compute the max of the messages (based on the metadata of the encoder)
+        let granularity = self.get_granularity();
+        let max = f64::max(
+            (self.o + self.delta - self.get_granularity()).abs(),
+            self.o.abs(),
+        );
+
+        // compute the new granularity
+        let new_granularity = 2.
+            * (granularity * rounding_error / 2.
+                + granularity / 2. * discret_c_abs
+                + rounding_error * max)
+                .abs();
+
+        // compute the new precision
+        let new_precision = usize::min(
+            f64::log2(new_delta / new_granularity).floor() as usize,
+            self.nb_bit_precision,
+        );
+
+        // create the new encoder
+        let mut res = Encoder::new(
+            new_o,
+            new_max,
+            usize::min(nb_bit_padding, self.nb_bit_precision),
+            self.nb_bit_padding - nb_bit_padding,
+        )?;
+        res.nb_bit_precision = usize::min(res.nb_bit_precision, new_precision);
+        res.round = self.round;
+        Ok(res)
+    }
+
+    /// Create the encoder of the messages of self mapped with x -> a * x + b, i.e. the output
+    /// encoder of mul_constant_with_padding followed by add_constant_dynamic_encoder
+    /// # Argument
+    /// * `a` - a real constant
+    /// * `b` - a real constant
+    /// * `max_constant` - the maximum absolute value of `a`
+    /// * `nb_bit_padding` - the number of bits of padding to be consumed by the multiplication
+    /// # Output
+    /// * a new Encoder
+    /// * ConstantMaximumError - if `a` is bigger than `max_constant`
+    /// * ZeroInIntervalError - if zero is not in the interval of self
+    /// * NotEnoughPaddingError - if there is not enough padding
+    /// # Example
+    /// ```rust
+    /// use concrete::Encoder;
+    ///
+    /// // instantiation
+    /// let encoder = Encoder::new(-10., 10., 6, 8).unwrap();
+    ///
+    /// // encoder of the messages mapped with x -> 3x - 4
+    /// let encoder_affine = encoder.affine(3., -4., 4., 6).unwrap();
+    /// ```
+    pub fn affine(
+        &self,
+        a: f64,
+        b: f64,
+        max_constant: f64,
+        nb_bit_padding: usize,
+    ) -> Result<Encoder, CryptoAPIError> {
+        let mut res = self.scale(a, max_constant, nb_bit_padding)?;
+        res.o += b;
+        Ok(res)
+    }
+
+    /// Create the encoder of the image of the messages of self by a function,
+    /// with the same precision and the same number of bits of padding,
+    /// typically to be used as the output encoder of bootstrap_with_function.
+    /// The function is evaluated on every input of the look up table of a bootstrap,
+    /// so the interval is exactly the one of the values such a bootstrap can output.
+    /// # Argument
+    /// * `f` - a function
+    /// * `polynomial_size` - the polynomial size of the bootstrapping key
+    /// # Output
+    /// * a new Encoder
+    /// * InvalidEncoderError - if self is not valid
+    /// * NotPowerOfTwoError - if `polynomial_size` is not a power of two
+    /// * MinMaxError - if f is constant on the messages of self
+    /// # Example
+    /// ```rust
+    /// use concrete::Encoder;
+    ///
+    /// // instantiation
+    /// let encoder = Encoder::new(-3., 3., 6, 2).unwrap();
+    ///
+    /// // encoder of the image of the messages by the sine function with a bootstrap in dimension 1024
+    /// let encoder_sin = encoder.image_of(|x| x.sin(), 1024).unwrap();
+    /// ```
+    pub fn image_of<F: Fn(f64) -> f64>(
+        &self,
+        f: F,
+        polynomial_size: usize,
+    ) -> Result<Encoder, CryptoAPIError> {
+        let mut new_min = f64::INFINITY;
+        let mut new_max = f64::NEG_INFINITY;
+        for x in self.get_look_up_table_inputs(polynomial_size)?.iter() {
+            let y = f(*x);
+            new_min = f64::min(new_min, y);
+            new_max = f64::max(new_max, y);
+        }
+
+        Encoder::new(new_min, new_max, self.nb_bit_precision, self.nb_bit_padding)
+    }
+
+    /// Decode the inputs of the look up table of a bootstrap of messages encoded with self
+    ///
+    /// # Argument
+    /// * `polynomial_size` - the polynomial size of the bootstrapping key
+    ///
+    /// # Output
+    /// * the messages the function of the bootstrap is evaluated on, one for each entry of the
+    ///   look up table
+    /// * InvalidEncoderError - if self is not valid
+    /// * NotPowerOfTwoError - if `polynomial_size` is not a power of two
+    pub(crate) fn get_look_up_table_inputs(
+        &self,
+        polynomial_size: usize,
+    ) -> Result<Vec<f64>, CryptoAPIError> {
+        if !self.is_valid() {
+            return Err(InvalidEncoderError!(self.nb_bit_precision, self.delta));
+        } else if !polynomial_size.is_power_of_two() {
+            return Err(NotPowerOfTwoError!(polynomial_size));
+        }
+
+        // the look up table is built with one bit of padding
+        let mut encoder = self.clone();
+        encoder.nb_bit_padding = 1;
+
+        let shift: usize = <Torus as Numeric>::BITS - polynomial_size.trailing_zeros() as usize - 1;
+        (0..polynomial_size)
+            .map(|i| encoder.decode_core((i as Torus) << shift))
+            .collect()
+    }
+
     /// Wrap the core_api encode function with the padding
     /// # Argument
     /// * `m` - the message to encode
@@ -264,3 +264,114 @@ fn margins_with_reals() {
         );
     }
 }
+
+#[test]
+fn test_new_integer_x_encode_single_x_decode_single() {
+    let (precision, padding) = generate_precision_padding!(8, 8);
+    for signed in [false, true].iter() {
+        let encoder = crate::Encoder::new_integer(precision, *signed, padding).unwrap();
+        let min: i64 = if *signed { -(1 << (precision - 1)) } else { 0 };
+
+        // every integer of the interval is exactly on the grid of the encoder
+        assert_eq!(encoder.get_granularity(), 1.);
+        for i in 0..(1 << precision) {
+            let m = (min + i) as f64;
+            let plaintext = encoder.encode_single(m).unwrap();
+            let decoding = encoder.decode_single(plaintext.plaintexts[0]).unwrap();
+            assert_eq!(m, decoding);
+        }
+    }
+    assert!(crate::Encoder::new_integer(0, false, padding).is_err());
+}
+
+#[test]
+fn test_new_modular_x_encode_single_x_decode_single() {
+    let padding: usize = random_index!(8);
+
+    // a power of two modulus is an unsigned integer encoder
+    let encoder = crate::Encoder::new_modular(16, padding).unwrap();
+    assert_eq!(
+        encoder,
+        crate::Encoder::new_integer(4, false, padding).unwrap()
+    );
+
+    // any other modulus
+    let modulus: u64 = random_index!(200) as u64 + 3;
+    let result = crate::Encoder::new_modular(modulus, padding);
+    assert_eq!(result.is_ok(), modulus.is_power_of_two());
+    if let Ok(encoder) = result {
+        for m in 0..modulus {
+            let plaintext = encoder.encode_single(m as f64).unwrap();
+            let decoding = encoder.decode_single(plaintext.plaintexts[0]).unwrap();
+            assert_eq!(m, decoding as u64);
+        }
+    }
+    assert!(crate::Encoder::new_modular(1, padding).is_err());
+}
+
+#[test]
+fn test_encoder_arithmetic() {
+    // random settings
+    let (min, max) = generate_random_interval!();
+    let (precision, padding) = generate_precision_padding!(8, 8);
+    let encoder = crate::Encoder::new(min, max, precision, padding + 1).unwrap();
+    let m: f64 = random_message!(min, max);
+
+    // sum: same output encoder as add_with_padding
+    let (min2, _) = generate_random_interval!();
+    let mut encoder_2 = encoder.clone();
+    encoder_2.o = min2;
+    let m2: f64 = random_message!(encoder_2.get_min(), encoder_2.get_max());
+    let encoder_sum = encoder.add(&encoder_2).unwrap();
+    let secret_key = crate::LWESecretKey::new(&crate::LWE128_630);
+    let ct_1 = crate::LWE::encode_encrypt(&secret_key, m, &encoder).unwrap();
+    let ct_2 = crate::LWE::encode_encrypt(&secret_key, m2, &encoder_2).unwrap();
+    let ct_sum = ct_1.add_with_padding(&ct_2).unwrap();
+    assert_eq!(encoder_sum.o, ct_sum.encoder.o);
+    assert_eq!(encoder_sum.delta, ct_sum.encoder.delta);
+    assert_eq!(encoder_sum.nb_bit_padding, ct_sum.encoder.nb_bit_padding);
+    assert!(encoder
+        .add(&crate::Encoder::new(min, max + 1., precision, padding + 1).unwrap())
+        .is_err());
+
+    // multiplication by a positive or a negative constant: same output encoder as
+    // mul_constant_with_padding
+    let encoder_zero = crate::Encoder::new(-10., 10., 6, 8).unwrap();
+    let secret_key_zero = crate::LWESecretKey::new(&crate::LWE128_1024);
+    let m_zero: f64 = random_message!(-10., 10.);
+    let ct_zero = crate::LWE::encode_encrypt(&secret_key_zero, m_zero, &encoder_zero).unwrap();
+    for a in [random_message!(0.1, 4.), random_message!(-4., -0.1)].iter() {
+        let encoder_scaled = encoder_zero.scale(*a, 4., 6).unwrap();
+        let ct_scaled = ct_zero.mul_constant_with_padding(*a, 4., 6).unwrap();
+        assert_eq!(encoder_scaled, ct_scaled.encoder);
+
+        // affine map
+        let b: f64 = random_message!(-100., 100.);
+        let encoder_affine = encoder_zero.affine(*a, b, 4., 6).unwrap();
+        let y = a * m_zero + b;
+        assert!(
+            encoder_affine.get_min() - encoder_affine.get_granularity() <= y
+                && y <= encoder_affine.get_max() + encoder_affine.get_granularity()
+        );
+        assert_eq!(encoder_affine.nb_bit_padding, 2);
+    }
+    assert!(encoder_zero.scale(5., 4., 6).is_err());
+    assert!(encoder_zero.scale(1., 4., 9).is_err());
+
+    // image of a function: the bounds of the interval are the extreme values of the look up table
+    let f = |x: f64| (x - min) * (x - max);
+    let encoder_image = encoder.image_of(f, 1024).unwrap();
+    let values: Vec<f64> = encoder
+        .get_look_up_table_inputs(1024)
+        .unwrap()
+        .iter()
+        .map(|x| f(*x))
+        .collect();
+    let value_min = values.iter().cloned().fold(f64::INFINITY, f64::min);
+    let value_max = values.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    let tolerance = 1e-9 * f64::max(value_min.abs(), value_max.abs());
+    assert!((encoder_image.get_min() - value_min).abs() <= tolerance);
+    assert!((encoder_image.get_max() - value_max).abs() <= tolerance);
+    assert!(encoder.image_of(|_| 1., 1024).is_err());
+    assert!(encoder.image_of(f, 1000).is_err());
+}
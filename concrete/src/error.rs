@@ -106,6 +106,54 @@ pub enum CryptoAPIError {
         polynomial_size: usize,
         description: String,
     },
+    AutomorphismError {
+        automorphism: usize,
+        polynomial_size: usize,
+        description: String,
+    },
+    AutomorphismKeyError {
+        automorphism: usize,
+        key_automorphism: usize,
+        description: String,
+    },
+    OffsetError {
+        o_1: f64,
+        o_2: f64,
+        description: String,
+    },
+    SecurityError {
+        security: f64,
+        min_security: usize,
+        description: String,
+    },
+    ParametersNotFoundError {
+        precision: usize,
+        padding: usize,
+        security: usize,
+        description: String,
+    },
+    CancelledError {
+        progress: f64,
+        description: String,
+    },
+    KeyMismatch {
+        expected: crate::KeyFingerprint,
+        found: crate::KeyFingerprint,
+        description: String,
+    },
+    ModulusError {
+        log_q: usize,
+        description: String,
+    },
+    DecompositionError {
+        base_log: usize,
+        level: usize,
+        description: String,
+    },
+    ModulusNotPowerOfTwoError {
+        modulus: u64,
+        description: String,
+    },
 }
 impl fmt::Display for CryptoAPIError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -154,6 +202,26 @@ impl fmt::Display for CryptoAPIError {
                 writeln!(f, "\n{}", description)
             }
             CryptoAPIError::LweToRlweError { description, .. } => writeln!(f, "\n{}", description),
+            CryptoAPIError::AutomorphismError { description, .. } => {
+                writeln!(f, "\n{}", description)
+            }
+            CryptoAPIError::AutomorphismKeyError { description, .. } => {
+                writeln!(f, "\n{}", description)
+            }
+            CryptoAPIError::OffsetError { description, .. } => writeln!(f, "\n{}", description),
+            CryptoAPIError::SecurityError { description, .. } => writeln!(f, "\n{}", description),
+            CryptoAPIError::ParametersNotFoundError { description, .. } => {
+                writeln!(f, "\n{}", description)
+            }
+            CryptoAPIError::CancelledError { description, .. } => writeln!(f, "\n{}", description),
+            CryptoAPIError::KeyMismatch { description, .. } => writeln!(f, "\n{}", description),
+            CryptoAPIError::ModulusError { description, .. } => writeln!(f, "\n{}", description),
+            CryptoAPIError::DecompositionError { description, .. } => {
+                writeln!(f, "\n{}", description)
+            }
+            CryptoAPIError::ModulusNotPowerOfTwoError { description, .. } => {
+                writeln!(f, "\n{}", description)
+            }
         }
     }
 }
@@ -205,6 +273,26 @@ impl fmt::Debug for CryptoAPIError {
                 writeln!(f, "\n{}", description)
             }
             CryptoAPIError::LweToRlweError { description, .. } => writeln!(f, "\n{}", description),
+            CryptoAPIError::AutomorphismError { description, .. } => {
+                writeln!(f, "\n{}", description)
+            }
+            CryptoAPIError::AutomorphismKeyError { description, .. } => {
+                writeln!(f, "\n{}", description)
+            }
+            CryptoAPIError::OffsetError { description, .. } => writeln!(f, "\n{}", description),
+            CryptoAPIError::SecurityError { description, .. } => writeln!(f, "\n{}", description),
+            CryptoAPIError::ParametersNotFoundError { description, .. } => {
+                writeln!(f, "\n{}", description)
+            }
+            CryptoAPIError::CancelledError { description, .. } => writeln!(f, "\n{}", description),
+            CryptoAPIError::KeyMismatch { description, .. } => writeln!(f, "\n{}", description),
+            CryptoAPIError::ModulusError { description, .. } => writeln!(f, "\n{}", description),
+            CryptoAPIError::DecompositionError { description, .. } => {
+                writeln!(f, "\n{}", description)
+            }
+            CryptoAPIError::ModulusNotPowerOfTwoError { description, .. } => {
+                writeln!(f, "\n{}", description)
+            }
         }
     }
 }
@@ -234,6 +322,16 @@ impl Error for CryptoAPIError {
             CryptoAPIError::WrongSizeError { description, .. } => description,
             CryptoAPIError::NotEnoughValidEncoderError { description, .. } => description,
             CryptoAPIError::LweToRlweError { description, .. } => description,
+            CryptoAPIError::AutomorphismError { description, .. } => description,
+            CryptoAPIError::AutomorphismKeyError { description, .. } => description,
+            CryptoAPIError::OffsetError { description, .. } => description,
+            CryptoAPIError::SecurityError { description, .. } => description,
+            CryptoAPIError::ParametersNotFoundError { description, .. } => description,
+            CryptoAPIError::CancelledError { description, .. } => description,
+            CryptoAPIError::KeyMismatch { description, .. } => description,
+            CryptoAPIError::ModulusError { description, .. } => description,
+            CryptoAPIError::DecompositionError { description, .. } => description,
+            CryptoAPIError::ModulusNotPowerOfTwoError { description, .. } => description,
         }
     }
 }
@@ -606,3 +704,169 @@ macro_rules! LweToRlweError {
         }
     };
 }
+
+#[macro_export]
+macro_rules! AutomorphismError {
+    ($automorphism: expr, $polynomial_size: expr) => {
+        CryptoAPIError::AutomorphismError {
+            automorphism: $automorphism,
+            polynomial_size: $polynomial_size,
+            description: format!(
+                "{}: X -> X^{} with polynomial_size = {}, the exponent must be odd and smaller than 2 * polynomial_size\n{:#?}\n",
+                "Invalid automorphism".red().bold(),
+                $automorphism,
+                $polynomial_size,
+                Backtrace::new()
+            ),
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! AutomorphismKeyError {
+    ($automorphism: expr, $key_automorphism: expr) => {
+        CryptoAPIError::AutomorphismKeyError {
+            automorphism: $automorphism,
+            key_automorphism: $key_automorphism,
+            description: format!(
+                "{}: X -> X^{} was asked but the key is for X -> X^{}\n{:#?}\n",
+                "Wrong automorphism key".red().bold(),
+                $automorphism,
+                $key_automorphism,
+                Backtrace::new()
+            ),
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! OffsetError {
+    ($o_1: expr, $o_2: expr) => {
+        CryptoAPIError::OffsetError {
+            o_1: $o_1,
+            o_2: $o_2,
+            description: format!(
+                "{} : {} != {}\n{:#?}\n",
+                "Offsets should be the same".red().bold(),
+                $o_1,
+                $o_2,
+                Backtrace::new()
+            ),
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! SecurityError {
+    ($security: expr, $min_security: expr) => {
+        CryptoAPIError::SecurityError {
+            security: $security,
+            min_security: $min_security,
+            description: format!(
+                "{}: about {:.1} bits of security are estimated but at least {} are required\n{:#?}\n",
+                "Insecure parameters".red().bold(),
+                $security,
+                $min_security,
+                Backtrace::new()
+            ),
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! ParametersNotFoundError {
+    ($precision: expr, $padding: expr, $security: expr) => {
+        CryptoAPIError::ParametersNotFoundError {
+            precision: $precision,
+            padding: $padding,
+            security: $security,
+            description: format!(
+                "{}: no parameter set found for {} bit(s) of precision, {} bit(s) of padding and {} bits of security\n{:#?}\n",
+                "Parameters not found".red().bold(),
+                $precision,
+                $padding,
+                $security,
+                Backtrace::new()
+            ),
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! CancelledError {
+    ($progress: expr) => {
+        CryptoAPIError::CancelledError {
+            progress: $progress,
+            description: format!(
+                "{}: the key generation was cancelled by the progress callback at {:.1}%\n{:#?}\n",
+                "Cancelled".red().bold(),
+                100. * $progress,
+                Backtrace::new()
+            ),
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! KeyMismatch {
+    ($expected: expr, $found: expr) => {
+        CryptoAPIError::KeyMismatch {
+            expected: $expected,
+            found: $found,
+            description: format!(
+                "{}: expected a key with fingerprint {} but found {}\n{:#?}\n",
+                "Key mismatch".red().bold(),
+                $expected,
+                $found,
+                Backtrace::new()
+            ),
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! ModulusError {
+    ($log_q: expr) => {
+        CryptoAPIError::ModulusError {
+            log_q: $log_q,
+            description: format!(
+                "{}: the log2 of the modulus is {} but has to be between 1 and 64\n{:#?}\n",
+                "Wrong modulus".red().bold(),
+                $log_q,
+                Backtrace::new()
+            ),
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! DecompositionError {
+    ($base_log: expr, $level: expr) => {
+        CryptoAPIError::DecompositionError {
+            base_log: $base_log,
+            level: $level,
+            description: format!(
+                "{}: base_log (= {}) and level (= {}) must be non-zero with base_log * level <= 64\n{:#?}\n",
+                "Invalid decomposition".red().bold(),
+                $base_log,
+                $level,
+                Backtrace::new()
+            ),
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! ModulusNotPowerOfTwoError {
+    ($modulus: expr) => {
+        CryptoAPIError::ModulusNotPowerOfTwoError {
+            modulus: $modulus,
+            description: format!(
+                "{}: the modulus is {} but has to be a power of two\n{:#?}\n",
+                "Wrong modulus".red().bold(),
+                $modulus,
+                Backtrace::new()
+            ),
+        }
+    };
+}
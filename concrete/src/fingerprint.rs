@@ -0,0 +1,99 @@
+//! fingerprint module identifying the secret keys, so that a ciphertext used with a key
+//! it is not encrypted under is detected instead of silently producing garbage
+
+use crate::error::CryptoAPIError;
+use backtrace::Backtrace;
+use colored::Colorize;
+use concrete_core::math::random::RandomGenerator;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Public identifier of a secret key, recorded in the keys derived from it and in the
+/// ciphertexts encrypted under it
+///
+/// It is derived from the public parameters of the key and a random salt drawn when the key
+/// is generated, and never from the secret coefficients. The unknown fingerprint (0) is the one
+/// of trivial ciphertexts, and of the keys and ciphertexts saved before fingerprints existed:
+/// it is compatible with any key.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub struct KeyFingerprint(pub u64);
+
+impl KeyFingerprint {
+    /// The fingerprint compatible with any key
+    pub const UNKNOWN: KeyFingerprint = KeyFingerprint(0);
+
+    /// Draw a new fingerprint for a secret key
+    ///
+    /// # Argument
+    /// * `dimension` - the total number of coefficients of the key
+    /// * `std_dev` - the standard deviation of the encryptions with the key
+    /// * `kind` - the distribution of the coefficients of the key
+    ///
+    /// # Output
+    /// * a fingerprint that is not the unknown one
+    pub(crate) fn new(
+        dimension: usize,
+        std_dev: f64,
+        kind: crate::SecretKeyKind,
+    ) -> KeyFingerprint {
+        let salt: u64 = RandomGenerator::new(None)
+            .random_uniform_tensor(1)
+            .into_container()[0];
+        let mut hash = salt;
+        for public in [dimension as u64, std_dev.to_bits(), kind.to_torus()].iter() {
+            hash = mix(hash ^ public);
+        }
+        KeyFingerprint(u64::max(hash, 1))
+    }
+
+    /// Return true if this is the unknown fingerprint
+    pub fn is_unknown(&self) -> bool {
+        *self == KeyFingerprint::UNKNOWN
+    }
+
+    /// Check that a ciphertext with this fingerprint can be used with a key
+    ///
+    /// # Argument
+    /// * `expected` - the fingerprint of the key
+    ///
+    /// # Output
+    /// * KeyMismatch - if both fingerprints are known and different
+    pub fn check(self, expected: KeyFingerprint) -> Result<(), CryptoAPIError> {
+        if self.is_unknown() || expected.is_unknown() || self == expected {
+            Ok(())
+        } else {
+            Err(KeyMismatch!(expected, self))
+        }
+    }
+
+    /// Return the fingerprint of the result of an operation between two ciphertexts
+    ///
+    /// # Argument
+    /// * `other` - the fingerprint of the other ciphertext
+    ///
+    /// # Output
+    /// * the known fingerprint among the two, if any
+    /// * KeyMismatch - if both fingerprints are known and different
+    pub(crate) fn combine(self, other: KeyFingerprint) -> Result<KeyFingerprint, CryptoAPIError> {
+        other.check(self)?;
+        if self.is_unknown() {
+            Ok(other)
+        } else {
+            Ok(self)
+        }
+    }
+}
+
+/// The finalizer of splitmix64, spreading every bit of the input over the output
+fn mix(x: u64) -> u64 {
+    let mut z = x.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+impl fmt::Display for KeyFingerprint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:016x}", self.0)
+    }
+}
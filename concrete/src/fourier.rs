@@ -0,0 +1,127 @@
+//! fourier module computing products of polynomials in the ring Z_q[X] / (X^N + 1) with the FFT
+//! of concrete-core, used by the RLWE key switch and the GGSW external product
+
+use crate::Torus;
+use concrete_commons::parameters::PolynomialSize;
+use concrete_core::math::fft::{AlignedVec, Complex64, Fft, FourierPolynomial};
+use concrete_core::math::polynomial::Polynomial;
+
+/// The polynomial sizes supported by the FFT of concrete-core
+const FFT_POLYNOMIAL_SIZES: [usize; 5] = [256, 512, 1024, 2048, 4096];
+
+/// A list of torus polynomials, e.g. the rows of a key switching key or of a GGSW ciphertext,
+/// ready to be multiplied by polynomials with small integer coefficients
+///
+/// The polynomials are converted once to the Fourier domain, the other polynomial sizes fall back
+/// on the schoolbook product with the raw coefficients
+pub(crate) enum FourierPolynomials<'a> {
+    Fourier {
+        fft: Fft,
+        polynomials: Vec<FourierPolynomial<AlignedVec<Complex64>>>,
+    },
+    Standard {
+        polynomial_size: usize,
+        coefficients: &'a [Torus],
+    },
+}
+
+impl<'a> FourierPolynomials<'a> {
+    /// Convert a list of torus polynomials
+    ///
+    /// # Argument
+    /// * `coefficients` - the coefficients of the polynomials, polynomial after polynomial
+    /// * `polynomial_size` - the number of coefficients in a polynomial
+    pub(crate) fn new(coefficients: &'a [Torus], polynomial_size: usize) -> FourierPolynomials<'a> {
+        if !FFT_POLYNOMIAL_SIZES.contains(&polynomial_size) {
+            return FourierPolynomials::Standard {
+                polynomial_size,
+                coefficients,
+            };
+        }
+
+        let fft = Fft::new(PolynomialSize(polynomial_size));
+        let polynomials = coefficients
+            .chunks(polynomial_size)
+            .map(|coeffs| {
+                let mut fourier = FourierPolynomial::allocate(
+                    Complex64::new(0., 0.),
+                    PolynomialSize(polynomial_size),
+                );
+                fft.forward_as_torus(&mut fourier, &Polynomial::from_container(coeffs));
+                fourier
+            })
+            .collect();
+        FourierPolynomials::Fourier { fft, polynomials }
+    }
+
+    /// Compute res = res + sum_t terms[t].1 * P_(terms[t].0 + k) for every polynomial k of res,
+    /// where P_i is the i-th converted polynomial
+    ///
+    /// # Argument
+    /// * `res` - the output polynomials, updated in place
+    /// * `terms` - pairs of an index in the list and of a polynomial with small signed integer
+    ///   coefficients, stored in the torus
+    pub(crate) fn update_with_add_products(&self, res: &mut [Torus], terms: &[(usize, &[Torus])]) {
+        match self {
+            FourierPolynomials::Fourier { fft, polynomials } => {
+                let polynomial_size = fft.polynomial_size();
+
+                // convert the integer polynomials once
+                let fourier_terms: Vec<(usize, FourierPolynomial<AlignedVec<Complex64>>)> = terms
+                    .iter()
+                    .map(|(index, coeffs)| {
+                        let mut fourier =
+                            FourierPolynomial::allocate(Complex64::new(0., 0.), polynomial_size);
+                        fft.forward_as_integer(&mut fourier, &Polynomial::from_container(*coeffs));
+                        (*index, fourier)
+                    })
+                    .collect();
+
+                // accumulate in the Fourier domain and convert back every output polynomial
+                let mut acc = FourierPolynomial::allocate(Complex64::new(0., 0.), polynomial_size);
+                for (k, out) in res.chunks_mut(polynomial_size.0).enumerate() {
+                    for coeff in acc.coefficient_iter_mut() {
+                        *coeff = Complex64::new(0., 0.);
+                    }
+                    for (index, fourier) in fourier_terms.iter() {
+                        acc.update_with_multiply_accumulate(&polynomials[index + k], fourier);
+                    }
+                    fft.add_backward_as_torus(&mut Polynomial::from_container(out), &mut acc);
+                }
+            }
+            FourierPolynomials::Standard {
+                polynomial_size,
+                coefficients,
+            } => {
+                for (k, out) in res.chunks_mut(*polynomial_size).enumerate() {
+                    for (index, coeffs) in terms.iter() {
+                        let start = (index + k) * polynomial_size;
+                        update_with_wrapping_add_mul(
+                            out,
+                            coeffs,
+                            &coefficients[start..(start + polynomial_size)],
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Compute res = res + lhs * rhs in the ring Z_q[X] / (X^N + 1) with the schoolbook product
+pub(crate) fn update_with_wrapping_add_mul(res: &mut [Torus], lhs: &[Torus], rhs: &[Torus]) {
+    let n = res.len();
+    for (i, l) in lhs.iter().enumerate() {
+        if *l == 0 {
+            continue;
+        }
+        for (j, r) in rhs.iter().enumerate() {
+            let prod = l.wrapping_mul(*r);
+            if i + j < n {
+                res[i + j] = res[i + j].wrapping_add(prod);
+            } else {
+                res[i + j - n] = res[i + j - n].wrapping_sub(prod);
+            }
+        }
+    }
+}
@@ -0,0 +1,199 @@
+//! generic_torus module describing the unsigned integer types the LWE ciphertexts and their keys
+//! can be made of
+
+use crate::error::CryptoAPIError;
+use crate::Torus;
+use concrete_commons::numeric::{CastFrom, CastInto, Numeric};
+use concrete_core::crypto::bootstrap::{Bootstrap, FourierBootstrapKey};
+use concrete_core::crypto::glwe::GlweCiphertext;
+use concrete_core::crypto::lwe::{LweCiphertext, LweKeyswitchKey, LweList};
+use concrete_core::math::fft::{AlignedVec, Complex64};
+use concrete_core::math::torus::UnsignedTorus;
+use concrete_npe as npe;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// The unsigned integer types the coefficients of the LWE ciphertexts, of the key switching keys
+/// and of the bootstrapping keys can be stored in
+///
+/// * `u64` - the Torus type, used by LWE, VectorLWE, LWEKSK and LWEBSK
+/// * `u32` - used by LWE32, VectorLWE32, LWEKSK32 and LWEBSK32, which halves the memory and the
+///   bandwidth when the noise and the messages fit in 32 bits
+///
+/// The encoders always work on the 64-bit torus, the plaintexts being rounded to the closest
+/// element of the torus of the ciphertexts. The trait is sealed, so that the heavy computations
+/// are compiled once for each width with the library.
+///
+/// # Example
+/// ```rust
+/// use concrete::*;
+///
+/// // encoder
+/// let encoder = Encoder::new(-2., 6., 4, 2).unwrap();
+///
+/// // generate a secret key
+/// let secret_key = LWESecretKey::new(&LWE128_1024);
+///
+/// // encrypt a message in a ciphertext made of u32
+/// let ciphertext = LWE32::encode_encrypt(&secret_key, 3.2, &encoder).unwrap();
+/// let decryption: f64 = ciphertext.decrypt_decode(&secret_key).unwrap();
+/// assert!((decryption - 3.2).abs() < encoder.get_granularity());
+/// ```
+pub trait GenericTorus:
+    UnsignedTorus
+    + CastFrom<u64>
+    + CastInto<u64>
+    + npe::LWE
+    + npe::Cross
+    + Serialize
+    + DeserializeOwned
+    + Send
+    + Sync
+    + sealed::Sealed
+{
+}
+
+pub(crate) mod sealed {
+    use super::*;
+
+    /// The computations that are not generic, so that they are not compiled again by every
+    /// crate using the library
+    pub trait Sealed: UnsignedTorus {
+        fn fourier_bootstrap(
+            bsk: &FourierBootstrapKey<AlignedVec<Complex64>, Self>,
+            result: &mut LweCiphertext<Vec<Self>>,
+            input: &[Self],
+            accumulator: &GlweCiphertext<Vec<Self>>,
+        );
+
+        fn fourier_bootstrap_borrowed(
+            bsk: &FourierBootstrapKey<&[Complex64], Self>,
+            result: &mut LweCiphertext<Vec<Self>>,
+            input: &[Self],
+            accumulator: &GlweCiphertext<Vec<Self>>,
+        );
+
+        fn keyswitch_ciphertext(
+            ksk: &LweKeyswitchKey<Vec<Self>>,
+            result: &mut LweCiphertext<Vec<Self>>,
+            input: &LweCiphertext<Vec<Self>>,
+        );
+
+        fn keyswitch_list(
+            ksk: &LweKeyswitchKey<Vec<Self>>,
+            result: &mut LweList<Vec<Self>>,
+            input: &LweList<Vec<Self>>,
+        );
+
+        fn generate_bsk(
+            sk_input: &crate::LWESecretKey,
+            sk_output: &crate::RLWESecretKey,
+            base_log: usize,
+            level: usize,
+            callback: &mut (dyn FnMut(f64) -> bool + Send),
+        ) -> Result<crate::GenericLWEBSK<Self>, CryptoAPIError>;
+
+        fn generate_ksk(
+            sk_before: &crate::LWESecretKey,
+            sk_after: &crate::LWESecretKey,
+            base_log: usize,
+            level: usize,
+            callback: &mut (dyn FnMut(f64) -> bool + Send),
+        ) -> Result<crate::GenericLWEKSK<Self>, CryptoAPIError>;
+    }
+}
+
+macro_rules! impl_generic_torus {
+    ($T:ty) => {
+        impl sealed::Sealed for $T {
+            fn fourier_bootstrap(
+                bsk: &FourierBootstrapKey<AlignedVec<Complex64>, $T>,
+                result: &mut LweCiphertext<Vec<$T>>,
+                input: &[$T],
+                accumulator: &GlweCiphertext<Vec<$T>>,
+            ) {
+                bsk.bootstrap(result, &LweCiphertext::from_container(input), accumulator);
+            }
+
+            fn fourier_bootstrap_borrowed(
+                bsk: &FourierBootstrapKey<&[Complex64], $T>,
+                result: &mut LweCiphertext<Vec<$T>>,
+                input: &[$T],
+                accumulator: &GlweCiphertext<Vec<$T>>,
+            ) {
+                bsk.bootstrap(result, &LweCiphertext::from_container(input), accumulator);
+            }
+
+            fn keyswitch_ciphertext(
+                ksk: &LweKeyswitchKey<Vec<$T>>,
+                result: &mut LweCiphertext<Vec<$T>>,
+                input: &LweCiphertext<Vec<$T>>,
+            ) {
+                ksk.keyswitch_ciphertext(result, input);
+            }
+
+            fn keyswitch_list(
+                ksk: &LweKeyswitchKey<Vec<$T>>,
+                result: &mut LweList<Vec<$T>>,
+                input: &LweList<Vec<$T>>,
+            ) {
+                ksk.keyswitch_list(result, input);
+            }
+
+            fn generate_bsk(
+                sk_input: &crate::LWESecretKey,
+                sk_output: &crate::RLWESecretKey,
+                base_log: usize,
+                level: usize,
+                callback: &mut (dyn FnMut(f64) -> bool + Send),
+            ) -> Result<crate::GenericLWEBSK<$T>, CryptoAPIError> {
+                crate::GenericLWEBSK::generate(sk_input, sk_output, base_log, level, callback)
+            }
+
+            fn generate_ksk(
+                sk_before: &crate::LWESecretKey,
+                sk_after: &crate::LWESecretKey,
+                base_log: usize,
+                level: usize,
+                callback: &mut (dyn FnMut(f64) -> bool + Send),
+            ) -> Result<crate::GenericLWEKSK<$T>, CryptoAPIError> {
+                crate::GenericLWEKSK::generate(sk_before, sk_after, base_log, level, callback)
+            }
+        }
+
+        impl GenericTorus for $T {}
+    };
+}
+
+impl_generic_torus!(u32);
+impl_generic_torus!(u64);
+
+/// Round an element of the 64-bit torus to the closest element of the torus of T
+pub(crate) fn from_torus<T: GenericTorus>(value: Torus) -> T {
+    let shift = <Torus as Numeric>::BITS - T::BITS;
+    if shift == 0 {
+        T::cast_from(value)
+    } else {
+        T::cast_from((value >> (shift - 1)).wrapping_add(1) >> 1)
+    }
+}
+
+/// Map an element of the torus of T to the 64-bit torus
+pub(crate) fn to_torus<T: GenericTorus>(value: T) -> Torus {
+    let value: Torus = value.cast_into();
+    value << (<Torus as Numeric>::BITS - T::BITS)
+}
+
+/// Check that a standard deviation is big enough for the noise to exist in the torus of T
+///
+/// # Output
+/// * NoNoiseInCiphertext - if the noise would be rounded away
+pub(crate) fn check_std_dev<T: GenericTorus>(std_dev: f64) -> Result<(), CryptoAPIError> {
+    use backtrace::Backtrace;
+    use colored::Colorize;
+
+    if std_dev < f64::powi(2., -(T::BITS as i32) + 2) {
+        return Err(NoNoiseInCiphertext!(f64::powi(std_dev, 2)));
+    }
+    Ok(())
+}
@@ -0,0 +1,481 @@
+//! ggsw ciphertext module
+
+#[cfg(test)]
+mod tests;
+
+use crate::error::CryptoAPIError;
+use crate::fourier::FourierPolynomials;
+use crate::{read_from_file, write_to_file, KeyFingerprint, SecretKeyKind, Torus};
+use backtrace::Backtrace;
+use colored::Colorize;
+use concrete_commons::dispersion::StandardDev;
+use concrete_commons::parameters::{
+    CiphertextCount, DecompositionBaseLog, DecompositionLevelCount, GlweDimension, GlweSize,
+    PolynomialSize,
+};
+use concrete_core::crypto::encoding::Plaintext;
+use concrete_core::crypto::ggsw::GgswCiphertext;
+use concrete_core::crypto::glwe::GlweList;
+use concrete_core::crypto::secret::generators::EncryptionRandomGenerator;
+use concrete_core::math::tensor::{AsMutSlice, AsMutTensor, AsRefSlice, AsRefTensor, IntoTensor};
+use itertools::izip;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt;
+
+/// Structure containing a single GGSW ciphertext of a small integer m (usually a bit)
+/// It is made of (dimension + 1) * level RLWE ciphertexts, ordered by level and then by row:
+/// the row i of the level j encrypts -m * S_i * q / B^(j+1) for i < dimension and m * q / B^(j+1) for i = dimension
+///
+/// # Attributes
+/// * `ciphertexts` - the RLWE ciphertexts of the GGSW
+/// * `variance` - the variance of the noise of the RLWE ciphertexts
+/// * `dimension` - the length the RLWE mask
+/// * `polynomial_size` - the number of coefficients in a polynomial
+/// * `base_log` - the log2 of the decomposition base
+/// * `level` - the number of levels of the decomposition
+/// * `kind` - the kind of the RLWE secret key (binary for older ciphertexts)
+/// * `fingerprint` - the fingerprint of the RLWE secret key (unknown for older ciphertexts)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GGSW {
+    pub ciphertexts: GlweList<Vec<Torus>>,
+    pub variance: f64,
+    pub dimension: usize,
+    pub polynomial_size: usize,
+    pub base_log: usize,
+    pub level: usize,
+    #[serde(default)]
+    pub kind: SecretKeyKind,
+    #[serde(default)]
+    pub fingerprint: KeyFingerprint,
+}
+
+impl GGSW {
+    /// Instantiate a new GGSW filled with zeros
+    ///
+    /// # Arguments
+    /// * `polynomial_size` - the number of coefficients in polynomials
+    /// * `dimension` - the length the RLWE mask
+    /// * `base_log` - the log2 of the decomposition base
+    /// * `level` - the number of levels of the decomposition
+    ///
+    /// # Output
+    /// * a new instantiation of a GGSW
+    /// * NotPowerOfTwoError if `polynomial_size` is not a power of 2
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// // creates an empty GGSW ciphertext with a polynomial size of 1024 and a dimension of 1
+    /// let empty_ciphertext = GGSW::zero(1024, 1, 6, 4).unwrap();
+    /// ```
+    pub fn zero(
+        polynomial_size: usize,
+        dimension: usize,
+        base_log: usize,
+        level: usize,
+    ) -> Result<GGSW, CryptoAPIError> {
+        if !polynomial_size.is_power_of_two() {
+            return Err(NotPowerOfTwoError!(polynomial_size));
+        }
+
+        Ok(GGSW {
+            ciphertexts: GlweList::allocate(
+                0,
+                PolynomialSize(polynomial_size),
+                GlweDimension(dimension),
+                CiphertextCount((dimension + 1) * level),
+            ),
+            variance: 0.,
+            dimension,
+            polynomial_size,
+            base_log,
+            level,
+            kind: SecretKeyKind::Binary,
+            fingerprint: KeyFingerprint::UNKNOWN,
+        })
+    }
+
+    /// Encrypt a small integer (usually a bit) into a GGSW ciphertext
+    ///
+    /// # Arguments
+    /// * `sk` - an RLWE secret key
+    /// * `message` - the small integer to encrypt
+    /// * `base_log` - the log2 of the decomposition base
+    /// * `level` - the number of levels of the decomposition
+    ///
+    /// # Output
+    /// * a new GGSW encrypting `message`
+    /// * NotPowerOfTwoError if the polynomial size of `sk` is not a power of 2
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// // generate a secret key
+    /// let sk = RLWESecretKey::new(&RLWE128_1024_1);
+    ///
+    /// // encrypt the bit 1
+    /// let ct = GGSW::encrypt(&sk, 1, 6, 4).unwrap();
+    /// ```
+    pub fn encrypt(
+        sk: &crate::RLWESecretKey,
+        message: Torus,
+        base_log: usize,
+        level: usize,
+    ) -> Result<GGSW, CryptoAPIError> {
+        if !sk.polynomial_size.is_power_of_two() {
+            return Err(NotPowerOfTwoError!(sk.polynomial_size));
+        }
+
+        let mut ggsw = GgswCiphertext::allocate(
+            0 as Torus,
+            PolynomialSize(sk.polynomial_size),
+            GlweSize(sk.dimension + 1),
+            DecompositionLevelCount(level),
+            DecompositionBaseLog(base_log),
+        );
+        with_glwe_secret_key!(sk.kind, sk.val.as_slice(), sk.polynomial_size, |key| {
+            key.encrypt_constant_ggsw(
+                &mut ggsw,
+                &Plaintext(message),
+                StandardDev::from_standard_dev(sk.std_dev),
+                &mut EncryptionRandomGenerator::new(None),
+            )
+        });
+
+        Ok(GGSW {
+            ciphertexts: GlweList::from_container(
+                ggsw.into_tensor().into_container(),
+                GlweDimension(sk.dimension),
+                PolynomialSize(sk.polynomial_size),
+            ),
+            variance: sk.get_variance(),
+            dimension: sk.dimension,
+            polynomial_size: sk.polynomial_size,
+            base_log,
+            level,
+            kind: sk.kind,
+            fingerprint: sk.fingerprint,
+        })
+    }
+
+    /// Compute the external product between this GGSW of m and every RLWE ciphertext of a VectorRLWE
+    /// The output encrypts m times the input messages, the encoders are kept
+    ///
+    /// # Argument
+    /// * `ct` - a VectorRLWE struct
+    ///
+    /// # Output
+    /// * a new VectorRLWE
+    /// * DimensionError - if the ciphertexts have incompatible dimensions
+    /// * PolynomialSizeError - if the ciphertexts have incompatible polynomial sizes
+    /// * KeyMismatch - if the ciphertexts are encrypted under different keys
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// // generate a secret key
+    /// let sk = RLWESecretKey::new(&RLWE128_1024_1);
+    ///
+    /// // encode and encrypt some messages
+    /// let encoder = Encoder::new(-43., -10., 5, 2).unwrap();
+    /// let messages: Vec<f64> = vec![-39.69, -19.37, -40.74, -41.26, -35.77];
+    /// let ct = VectorRLWE::encode_encrypt_packed(&sk, &messages, &encoder).unwrap();
+    ///
+    /// // external product with an encryption of 1
+    /// let ggsw = GGSW::encrypt(&sk, 1, 6, 4).unwrap();
+    /// let res = ggsw.external_product(&ct).unwrap();
+    /// ```
+    pub fn external_product(
+        &self,
+        ct: &crate::VectorRLWE,
+    ) -> Result<crate::VectorRLWE, CryptoAPIError> {
+        self.check_compatibility(ct)?;
+
+        // compute the external products
+        let mut res =
+            crate::VectorRLWE::zero(self.polynomial_size, self.dimension, ct.nb_ciphertexts)?;
+        res.key_fingerprint = self.fingerprint.combine(ct.key_fingerprint)?;
+        let fourier_rows = self.fourier_rows();
+        let ct_size = ct.get_ciphertext_size();
+        for (mut ct_out, ct_in) in izip!(
+            res.ciphertexts.as_mut_tensor().subtensor_iter_mut(ct_size),
+            ct.ciphertexts.as_tensor().subtensor_iter(ct_size)
+        ) {
+            self.external_product_raw(&fourier_rows, ct_out.as_mut_slice(), ct_in.as_slice());
+        }
+
+        // deal with encoders, noise and new precision
+        for (output_enc, input_enc, vout, vin) in izip!(
+            res.encoders.iter_mut(),
+            ct.encoders.iter(),
+            res.variances.iter_mut(),
+            ct.variances.iter()
+        ) {
+            output_enc.copy(input_enc);
+
+            if input_enc.is_valid() {
+                *vout = self.kind.get_external_product_variance(
+                    self.dimension,
+                    self.level,
+                    self.base_log,
+                    self.polynomial_size,
+                    self.variance,
+                    *vin,
+                );
+                output_enc.update_precision_from_variance(*vout)?;
+            }
+        }
+
+        Ok(res)
+    }
+
+    /// Compute a CMux between two VectorRLWE with this GGSW of a bit b as selector
+    /// The output encrypts the messages of `ct0` if b = 0 and the messages of `ct1` if b = 1
+    ///
+    /// # Argument
+    /// * `ct0` - the VectorRLWE selected when the bit is 0
+    /// * `ct1` - the VectorRLWE selected when the bit is 1
+    ///
+    /// # Output
+    /// * a new VectorRLWE
+    /// * DimensionError - if the ciphertexts have incompatible dimensions
+    /// * PolynomialSizeError - if the ciphertexts have incompatible polynomial sizes
+    /// * KeyMismatch - if the ciphertexts are encrypted under different keys
+    /// * WrongSizeError - if the two VectorRLWE do not contain the same number of ciphertexts
+    /// * DeltaError - if the ciphertexts have incompatible deltas
+    /// * OffsetError - if the ciphertexts have incompatible offsets
+    /// * PaddingError - if the ciphertexts have incompatible paddings
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// // generate a secret key
+    /// let sk = RLWESecretKey::new(&RLWE128_1024_1);
+    ///
+    /// // encode and encrypt some messages
+    /// let encoder = Encoder::new(-43., -10., 5, 2).unwrap();
+    /// let ct0 = VectorRLWE::encode_encrypt_packed(&sk, &[-39.69, -19.37], &encoder).unwrap();
+    /// let ct1 = VectorRLWE::encode_encrypt_packed(&sk, &[-40.74, -41.26], &encoder).unwrap();
+    ///
+    /// // select ct1
+    /// let ggsw = GGSW::encrypt(&sk, 1, 6, 4).unwrap();
+    /// let res = ggsw.cmux(&ct0, &ct1).unwrap();
+    /// ```
+    pub fn cmux(
+        &self,
+        ct0: &crate::VectorRLWE,
+        ct1: &crate::VectorRLWE,
+    ) -> Result<crate::VectorRLWE, CryptoAPIError> {
+        self.check_compatibility(ct0)?;
+        self.check_compatibility(ct1)?;
+        if ct0.nb_ciphertexts != ct1.nb_ciphertexts {
+            return Err(WrongSizeError!(ct1.nb_ciphertexts));
+        }
+
+        // check the encoders
+        for (enc0, enc1) in ct0.encoders.iter().zip(ct1.encoders.iter()) {
+            if enc0.is_valid() && enc1.is_valid() {
+                if !deltas_eq!(enc0.delta, enc1.delta) {
+                    return Err(DeltaError!(enc0.delta, enc1.delta));
+                } else if !deltas_eq!(enc0.o, enc1.o) {
+                    return Err(OffsetError!(enc0.o, enc1.o));
+                } else if enc0.nb_bit_padding != enc1.nb_bit_padding {
+                    return Err(PaddingError!(enc0.nb_bit_padding, enc1.nb_bit_padding));
+                }
+            }
+        }
+
+        // compute ct0 + GGSW * (ct1 - ct0)
+        let mut res = ct0.clone();
+        res.key_fingerprint = self.fingerprint.combine(ct0.key_fingerprint)?;
+        res.key_fingerprint = res.key_fingerprint.combine(ct1.key_fingerprint)?;
+        let fourier_rows = self.fourier_rows();
+        let ct_size = ct0.get_ciphertext_size();
+        for (mut ct_out, ct_in0, ct_in1) in izip!(
+            res.ciphertexts.as_mut_tensor().subtensor_iter_mut(ct_size),
+            ct0.ciphertexts.as_tensor().subtensor_iter(ct_size),
+            ct1.ciphertexts.as_tensor().subtensor_iter(ct_size)
+        ) {
+            self.cmux_raw(
+                &fourier_rows,
+                ct_out.as_mut_slice(),
+                ct_in0.as_slice(),
+                ct_in1.as_slice(),
+            );
+        }
+
+        // deal with encoders, noise and new precision
+        for (output_enc, enc1, vout, v0, v1) in izip!(
+            res.encoders.iter_mut(),
+            ct1.encoders.iter(),
+            res.variances.iter_mut(),
+            ct0.variances.iter(),
+            ct1.variances.iter()
+        ) {
+            if !output_enc.is_valid() {
+                output_enc.copy(enc1);
+            }
+            *vout = self.get_cmux_variance(*v0, *v1);
+            if output_enc.is_valid() {
+                output_enc.update_precision_from_variance(*vout)?;
+            }
+        }
+
+        Ok(res)
+    }
+
+    /// Return the variance of the output of a CMux with this GGSW
+    ///
+    /// # Argument
+    /// * `var_rlwe_0` - the variance of the RLWE ciphertext selected if the GGSW encrypts 0
+    /// * `var_rlwe_1` - the variance of the RLWE ciphertext selected if the GGSW encrypts 1
+    pub(crate) fn get_cmux_variance(&self, var_rlwe_0: f64, var_rlwe_1: f64) -> f64 {
+        self.kind.get_cmux_variance(
+            var_rlwe_0,
+            var_rlwe_1,
+            self.variance,
+            self.dimension,
+            self.polynomial_size,
+            self.base_log,
+            self.level,
+        )
+    }
+
+    /// Convert the rows of the GGSW to the Fourier domain, once for all the external products
+    pub(crate) fn fourier_rows(&self) -> FourierPolynomials<'_> {
+        FourierPolynomials::new(
+            self.ciphertexts.as_tensor().as_slice(),
+            self.polynomial_size,
+        )
+    }
+
+    /// CMux between two raw RLWE ciphertexts: ct_out = ct0 + GGSW * (ct1 - ct0)
+    /// `ct_out` is overwritten
+    ///
+    /// # Argument
+    /// * `fourier_rows` - the rows of self, converted by fourier_rows
+    /// * `ct_out` - the output RLWE ciphertext
+    /// * `ct0` - the RLWE ciphertext selected if the GGSW encrypts 0
+    /// * `ct1` - the RLWE ciphertext selected if the GGSW encrypts 1
+    pub(crate) fn cmux_raw(
+        &self,
+        fourier_rows: &FourierPolynomials,
+        ct_out: &mut [Torus],
+        ct0: &[Torus],
+        ct1: &[Torus],
+    ) {
+        ct_out.copy_from_slice(ct0);
+        let diff: Vec<Torus> = izip!(ct1.iter(), ct0.iter())
+            .map(|(c1, c0)| c1.wrapping_sub(*c0))
+            .collect();
+        self.external_product_raw(fourier_rows, ct_out, &diff);
+    }
+
+    /// Add the external product between this GGSW and one raw RLWE ciphertext to `ct_out`
+    ///
+    /// # Argument
+    /// * `fourier_rows` - the rows of self, converted by fourier_rows
+    /// * `ct_out` - the output RLWE ciphertext, of size (dimension + 1) * polynomial_size
+    /// * `ct_in` - the input RLWE ciphertext, of size (dimension + 1) * polynomial_size
+    pub(crate) fn external_product_raw(
+        &self,
+        fourier_rows: &FourierPolynomials,
+        ct_out: &mut [Torus],
+        ct_in: &[Torus],
+    ) {
+        let n = self.polynomial_size;
+
+        // decompose every coefficient of every polynomial
+        let mut digits: Vec<Torus> = vec![0; (self.dimension + 1) * self.level * n];
+        for (digit_levels, polynomial) in izip!(digits.chunks_mut(self.level * n), ct_in.chunks(n))
+        {
+            for (c, coeff) in polynomial.iter().enumerate() {
+                for (j, d) in crate::rlwe_ksk::signed_decompose(*coeff, self.base_log, self.level)
+                    .iter()
+                    .enumerate()
+                {
+                    digit_levels[j * n + c] = *d;
+                }
+            }
+        }
+
+        // the digit polynomial of the input polynomial i and of the level j multiplies the row
+        // i of the level j of the GGSW
+        let terms: Vec<(usize, &[Torus])> = digits
+            .chunks(n)
+            .enumerate()
+            .map(|(t, digit_polynomial)| {
+                let (i, j) = (t / self.level, t % self.level);
+                (
+                    (j * (self.dimension + 1) + i) * (self.dimension + 1),
+                    digit_polynomial,
+                )
+            })
+            .collect();
+        fourier_rows.update_with_add_products(ct_out, &terms);
+    }
+
+    /// Check that a VectorRLWE can be used with this GGSW
+    fn check_compatibility(&self, ct: &crate::VectorRLWE) -> Result<(), CryptoAPIError> {
+        if self.dimension != ct.dimension {
+            return Err(DimensionError!(self.dimension, ct.dimension));
+        } else if self.polynomial_size != ct.polynomial_size {
+            return Err(PolynomialSizeError!(
+                self.polynomial_size,
+                ct.polynomial_size
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), Box<dyn Error>> {
+        write_to_file(path, self)
+    }
+
+    pub fn load(path: &str) -> Result<GGSW, Box<dyn Error>> {
+        read_from_file(path)
+    }
+}
+
+/// Print needed pieces of information about a GGSW
+impl fmt::Display for GGSW {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let n = 2;
+
+        write!(f, "GGSW {{\n         -> samples = [")?;
+
+        if self.ciphertexts.as_tensor().len() <= 2 * n {
+            for elt in self.ciphertexts.as_tensor().iter() {
+                write!(f, "{}, ", *elt)?;
+            }
+        } else {
+            for elt in self.ciphertexts.as_tensor().get_sub(0..n).iter() {
+                write!(f, "{}, ", *elt)?;
+            }
+            write!(f, "...")?;
+
+            for elt in self
+                .ciphertexts
+                .as_tensor()
+                .get_sub(self.ciphertexts.as_tensor().len() - n..)
+                .iter()
+            {
+                write!(f, "{}, ", *elt)?;
+            }
+        }
+        writeln!(f, "]")?;
+        writeln!(f, "         -> variance = {}", self.variance)?;
+        writeln!(f, "         -> dimension = {}", self.dimension)?;
+        writeln!(f, "         -> polynomial_size = {}", self.polynomial_size)?;
+        writeln!(f, "         -> base_log = {}", self.base_log)?;
+        writeln!(f, "         -> level = {}", self.level)?;
+        writeln!(f, "         -> kind = {:?}", self.kind)?;
+        writeln!(f, "         -> fingerprint = {}", self.fingerprint)?;
+        writeln!(f, "}}")
+    }
+}
@@ -0,0 +1,107 @@
+use itertools::izip;
+
+#[test]
+fn test_encrypt_x_external_product_x_decrypt() {
+    // generate a secret key
+    let polynomial_size: usize = 512;
+    let params = crate::RLWEParams::new(polynomial_size, random_index!(2) + 1, -40).unwrap();
+    let sk = crate::RLWESecretKey::new(&params);
+
+    // random number of messages
+    let nb_messages: usize = random_index!(polynomial_size - 1) + 1;
+
+    // random settings for the encoder and some random messages
+    let (min, max) = generate_random_interval!();
+    let (precision, padding) = generate_precision_padding!(8, 8);
+    let encoder = crate::Encoder::new(min, max, precision, padding).unwrap();
+    let messages: Vec<f64> = random_messages!(min, max, nb_messages);
+
+    // encode and encrypt
+    let ct = crate::VectorRLWE::encode_encrypt_packed(&sk, &messages, &encoder).unwrap();
+
+    // external product with an encryption of 1 and decrypt
+    let ggsw = crate::GGSW::encrypt(&sk, 1, 6, 4).unwrap();
+    let res = ggsw.external_product(&ct).unwrap();
+    let decryptions = res.decrypt_decode_round(&sk).unwrap();
+
+    // test
+    let mut cpt: usize = 0;
+    for (m, d, enc) in izip!(messages.iter(), decryptions.iter(), res.encoders.iter()) {
+        assert_eq_granularity!(m, d, enc);
+        cpt += 1;
+    }
+    assert_eq!(cpt, nb_messages);
+}
+
+#[test]
+fn test_encrypt_x_cmux_x_decrypt() {
+    // generate a secret key
+    let polynomial_size: usize = 512;
+    let params = crate::RLWEParams::new(polynomial_size, random_index!(2) + 1, -40).unwrap();
+    let sk = crate::RLWESecretKey::new(&params);
+
+    // random number of messages
+    let nb_messages: usize = random_index!(polynomial_size - 1) + 1;
+
+    // random settings for the encoder and some random messages
+    let (min, max) = generate_random_interval!();
+    let (precision, padding) = generate_precision_padding!(8, 8);
+    let encoder = crate::Encoder::new(min, max, precision, padding).unwrap();
+    let messages0: Vec<f64> = random_messages!(min, max, nb_messages);
+    let messages1: Vec<f64> = random_messages!(min, max, nb_messages);
+
+    // encode and encrypt
+    let ct0 = crate::VectorRLWE::encode_encrypt_packed(&sk, &messages0, &encoder).unwrap();
+    let ct1 = crate::VectorRLWE::encode_encrypt_packed(&sk, &messages1, &encoder).unwrap();
+
+    for bit in 0..2 {
+        // cmux and decrypt
+        let ggsw = crate::GGSW::encrypt(&sk, bit, 6, 4).unwrap();
+        let res = ggsw.cmux(&ct0, &ct1).unwrap();
+        let decryptions = res.decrypt_decode_round(&sk).unwrap();
+
+        // test
+        let expected = if bit == 0 { &messages0 } else { &messages1 };
+        let mut cpt: usize = 0;
+        for (m, d, enc) in izip!(expected.iter(), decryptions.iter(), res.encoders.iter()) {
+            assert_eq_granularity!(m, d, enc);
+            cpt += 1;
+        }
+        assert_eq!(cpt, nb_messages);
+    }
+}
+
+#[test]
+fn test_encrypt_x_external_product_x_decrypt_secret_key_kinds() {
+    let params = crate::RLWEParams::new(512, 1, -40).unwrap();
+    let encoder = crate::Encoder::new(0., 1., 4, 1).unwrap();
+    let messages: Vec<f64> = vec![0.25, 0.5, 0.75];
+
+    let mut variances: Vec<f64> = Vec::new();
+    for kind in [
+        crate::SecretKeyKind::Binary,
+        crate::SecretKeyKind::Ternary,
+        crate::SecretKeyKind::Gaussian,
+    ]
+    .iter()
+    {
+        let sk = crate::RLWESecretKey::new_with_kind(&params, *kind);
+        let ct = crate::VectorRLWE::encode_encrypt_packed(&sk, &messages, &encoder).unwrap();
+
+        // the GGSW keeps the kind of its key
+        let ggsw = crate::GGSW::encrypt(&sk, 1, 6, 4).unwrap();
+        assert_eq!(ggsw.kind, *kind);
+
+        // external product with an encryption of 1 and decrypt
+        let res = ggsw.external_product(&ct).unwrap();
+        let decryptions = res.decrypt_decode_round(&sk).unwrap();
+        for (m, d) in izip!(messages.iter(), decryptions.iter()) {
+            assert!(f64::abs(m - d) < 0.1);
+        }
+        variances.push(res.variances[0]);
+    }
+
+    // the rounding noise grows with the second moment of the key
+    assert!(variances[0] < variances[1]);
+    assert!(variances[1] < variances[2]);
+}
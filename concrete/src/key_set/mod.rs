@@ -0,0 +1,184 @@
+//! key_set module gathering all the keys generated from a parameter bundle,
+//! on the client side (secret keys) and on the server side (evaluation keys)
+
+#[cfg(test)]
+mod tests;
+
+use crate::error::CryptoAPIError;
+use crate::{read_from_binary_file, write_to_binary_file};
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+
+/// Structure containing the secret keys generated from a parameter bundle
+/// # Attributes
+/// * `params` - the parameters the keys were generated from
+/// * `lwe_secret_key` - the LWE secret key encrypting the inputs and the outputs of the server
+/// * `rlwe_secret_key` - the RLWE secret key the bootstrap outputs are encrypted with
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct ClientKeySet {
+    pub params: crate::ParameterBundle,
+    pub lwe_secret_key: crate::LWESecretKey,
+    pub rlwe_secret_key: crate::RLWESecretKey,
+}
+
+/// Structure containing the evaluation keys generated from a client key set
+/// # Attributes
+/// * `params` - the parameters the keys were generated from
+/// * `bsk` - the bootstrapping key from the LWE secret key to the RLWE secret key
+/// * `ksk` - the key switching key from the RLWE secret key back to the LWE secret key
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct ServerKeySet {
+    pub params: crate::ParameterBundle,
+    pub bsk: crate::LWEBSK,
+    pub ksk: crate::LWEKSK,
+}
+
+impl ClientKeySet {
+    /// Generate the secret keys of a parameter bundle
+    ///
+    /// # Argument
+    /// * `params` - a parameter bundle
+    ///
+    /// # Output
+    /// * a new ClientKeySet
+    pub fn new(params: &crate::ParameterBundle) -> ClientKeySet {
+        ClientKeySet {
+            params: params.clone(),
+            lwe_secret_key: crate::LWESecretKey::new(&params.lwe_params),
+            rlwe_secret_key: crate::RLWESecretKey::new(&params.rlwe_params),
+        }
+    }
+
+    /// Encode a message and encrypt it with the LWE secret key
+    ///
+    /// # Argument
+    /// * `message` - a message
+    /// * `encoder` - an encoder
+    ///
+    /// # Output
+    /// * an LWE ciphertext the server can work on
+    pub fn encode_encrypt(
+        &self,
+        message: f64,
+        encoder: &crate::Encoder,
+    ) -> Result<crate::LWE, CryptoAPIError> {
+        crate::LWE::encode_encrypt(&self.lwe_secret_key, message, encoder)
+    }
+
+    /// Decrypt and decode an LWE ciphertext encrypted with the LWE secret key
+    ///
+    /// # Argument
+    /// * `ciphertext` - an LWE ciphertext
+    ///
+    /// # Output
+    /// * the decrypted message
+    pub fn decrypt_decode(&self, ciphertext: &crate::LWE) -> Result<f64, CryptoAPIError> {
+        ciphertext.decrypt_decode(&self.lwe_secret_key)
+    }
+
+    /// Save the key set in a single binary file
+    pub fn save(&self, path: &str) -> Result<(), Box<dyn Error>> {
+        write_to_binary_file(path, self)
+    }
+
+    /// Load a key set saved with save
+    pub fn load(path: &str) -> Result<ClientKeySet, Box<dyn Error>> {
+        read_from_binary_file(path)
+    }
+}
+
+impl ServerKeySet {
+    /// Generate the evaluation keys of a client key set
+    ///
+    /// # Argument
+    /// * `client_key_set` - a client key set
+    ///
+    /// # Output
+    /// * a new ServerKeySet
+    pub fn new(client_key_set: &ClientKeySet) -> ServerKeySet {
+        let params = &client_key_set.params;
+        ServerKeySet {
+            params: params.clone(),
+            bsk: crate::LWEBSK::new(
+                &client_key_set.lwe_secret_key,
+                &client_key_set.rlwe_secret_key,
+                params.bsk_base_log,
+                params.bsk_level,
+            ),
+            ksk: crate::LWEKSK::new(
+                &client_key_set.rlwe_secret_key.to_lwe_secret_key(),
+                &client_key_set.lwe_secret_key,
+                params.ksk_base_log,
+                params.ksk_level,
+            ),
+        }
+    }
+
+    /// Bootstrap an LWE ciphertext while evaluating a function, then key switch the result
+    /// so that it is encrypted with the LWE secret key again
+    ///
+    /// # Argument
+    /// * `ciphertext` - an LWE ciphertext encrypted with the LWE secret key of the client key set
+    /// * `f` - a function to evaluate during the bootstrap
+    /// * `encoder_output` - the encoder of the output
+    ///
+    /// # Output
+    /// * an LWE ciphertext encrypted with the LWE secret key of the client key set
+    /// * DimensionError - if the ciphertext is not encrypted with an LWE key of the key set dimension
+    pub fn bootstrap_then_keyswitch<F: Fn(f64) -> f64>(
+        &self,
+        ciphertext: &crate::LWE,
+        f: F,
+        encoder_output: &crate::Encoder,
+    ) -> Result<crate::LWE, CryptoAPIError> {
+        ciphertext.pbs_keyswitch(&self.bsk, &self.ksk, f, encoder_output)
+    }
+
+    /// Save the key set in a single binary file
+    pub fn save(&self, path: &str) -> Result<(), Box<dyn Error>> {
+        write_to_binary_file(path, self)
+    }
+
+    /// Load a key set saved with save
+    pub fn load(path: &str) -> Result<ServerKeySet, Box<dyn Error>> {
+        read_from_binary_file(path)
+    }
+}
+
+/// Generate a client key set and its server key set from a parameter bundle
+///
+/// # Argument
+/// * `params` - a parameter bundle
+///
+/// # Output
+/// * a new ClientKeySet, to keep secret
+/// * a new ServerKeySet, to send to the server
+///
+/// # Example
+/// ```rust
+/// use concrete::*;
+///
+/// // keys for 3 bits of precision and one addition between two bootstraps
+/// let params = params::optimize(3, 1, 128).unwrap();
+/// let (client_key_set, server_key_set) = gen_key_sets(&params);
+///
+/// // client side
+/// let encoder = params.new_encoder(0., 7.).unwrap();
+/// let c1 = client_key_set.encode_encrypt(2., &encoder).unwrap();
+/// let c2 = client_key_set.encode_encrypt(3., &encoder).unwrap();
+///
+/// // server side
+/// let sum = c1.add_with_padding(&c2).unwrap();
+/// let res = server_key_set
+///     .bootstrap_then_keyswitch(&sum, |x| x - 1., &encoder)
+///     .unwrap();
+///
+/// // client side
+/// let m: f64 = client_key_set.decrypt_decode(&res).unwrap();
+/// assert_eq!(m.round(), 4.);
+/// ```
+pub fn gen_key_sets(params: &crate::ParameterBundle) -> (ClientKeySet, ServerKeySet) {
+    let client_key_set = ClientKeySet::new(params);
+    let server_key_set = ServerKeySet::new(&client_key_set);
+    (client_key_set, server_key_set)
+}
@@ -0,0 +1,92 @@
+use std::fs::remove_file;
+
+/// Small and insecure parameters, so that the key files stay small
+fn toy_params() -> crate::ParameterBundle {
+    crate::ParameterBundle {
+        lwe_params: crate::LWEParams::new(64, -20),
+        rlwe_params: crate::RLWEParams {
+            polynomial_size: 256,
+            dimension: 1,
+            log2_std_dev: -40,
+        },
+        bsk_base_log: 8,
+        bsk_level: 3,
+        ksk_base_log: 3,
+        ksk_level: 6,
+        precision: 2,
+        padding: 1,
+        error_probability: 0.,
+        cost: 0.,
+        security_bits: 0.,
+    }
+}
+
+#[test]
+fn test_gen_key_sets_x_encode_encrypt_x_bootstrap_then_keyswitch_x_decrypt() {
+    // keys
+    let params = crate::params::optimize(3, 1, 128).unwrap();
+    let (client_key_set, server_key_set) = crate::gen_key_sets(&params);
+
+    // encoder
+    let encoder = params.new_encoder(0., 7.).unwrap();
+
+    for _ in 0..10 {
+        // two messages whose sum stays in the interval
+        let m1: f64 = random_index!(4) as f64;
+        let m2: f64 = random_index!(4) as f64;
+
+        // client side
+        let c1 = client_key_set.encode_encrypt(m1, &encoder).unwrap();
+        let c2 = client_key_set.encode_encrypt(m2, &encoder).unwrap();
+
+        // server side
+        let sum = c1.add_with_padding(&c2).unwrap();
+        let res = server_key_set
+            .bootstrap_then_keyswitch(&sum, |x| x, &encoder)
+            .unwrap();
+
+        // the output is back under the input key
+        assert_eq!(res.dimension, params.lwe_params.dimension);
+        let decryption: f64 = client_key_set.decrypt_decode(&res).unwrap();
+        assert_eq_granularity!(m1 + m2, decryption, res.encoder);
+    }
+}
+
+#[test]
+fn test_key_sets_save_load() {
+    let client_filename: &str = "client_key_set.bin";
+    let server_filename: &str = "server_key_set.bin";
+
+    // keys
+    let (client_key_set, server_key_set) = crate::gen_key_sets(&toy_params());
+
+    // save and load
+    client_key_set.save(client_filename).unwrap();
+    server_key_set.save(server_filename).unwrap();
+    let client_key_set_2 = crate::ClientKeySet::load(client_filename).unwrap();
+    let server_key_set_2 = crate::ServerKeySet::load(server_filename).unwrap();
+    remove_file(client_filename).unwrap();
+    remove_file(server_filename).unwrap();
+    assert!(client_key_set == client_key_set_2);
+    assert!(server_key_set == server_key_set_2);
+
+    // the loaded keys still work together
+    let encoder = client_key_set_2.params.new_encoder(0., 3.).unwrap();
+    let c = client_key_set_2.encode_encrypt(2., &encoder).unwrap();
+    let res = server_key_set_2
+        .bootstrap_then_keyswitch(&c, |x| 3. - x, &encoder)
+        .unwrap();
+    let decryption: f64 = client_key_set_2.decrypt_decode(&res).unwrap();
+    assert_eq_granularity!(1., decryption, res.encoder);
+}
+
+#[test]
+fn test_key_sets_load_corrupted() {
+    let filename: &str = "corrupted_key_set.bin";
+
+    // a file of garbage is rejected instead of being decoded as a huge key
+    std::fs::write(filename, vec![0xff; 64]).unwrap();
+    let res = crate::ClientKeySet::load(filename);
+    remove_file(filename).unwrap();
+    assert!(res.is_err());
+}
@@ -10,6 +10,7 @@ use std::fs::File;
 use std::io::{BufReader, BufWriter};
 use std::path::Path;
 
+use bincode::Options;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 
@@ -134,6 +135,61 @@ macro_rules! deltas_eq {
     }};
 }
 
+/// Evaluate `$body` with `$key` bound to an LWE secret key of concrete-core, of the key kind
+/// matching `$kind`, wrapping the container `$coefficients`
+macro_rules! with_lwe_secret_key {
+    ($kind: expr, $coefficients: expr, |$key: ident| $body: expr) => {{
+        use concrete_core::crypto::secret::LweSecretKey;
+        match $kind {
+            crate::SecretKeyKind::Binary => {
+                let $key = LweSecretKey::binary_from_container($coefficients);
+                $body
+            }
+            crate::SecretKeyKind::Ternary => {
+                let $key = LweSecretKey::ternary_from_container($coefficients);
+                $body
+            }
+            crate::SecretKeyKind::Gaussian => {
+                let $key = LweSecretKey::gaussian_from_container($coefficients);
+                $body
+            }
+        }
+    }};
+}
+
+/// Evaluate `$body` with `$key` bound to a GLWE secret key of concrete-core, of the key kind
+/// matching `$kind`, wrapping the container `$coefficients` of polynomials of size
+/// `$polynomial_size`
+macro_rules! with_glwe_secret_key {
+    ($kind: expr, $coefficients: expr, $polynomial_size: expr, |$key: ident| $body: expr) => {{
+        use concrete_commons::parameters::PolynomialSize;
+        use concrete_core::crypto::secret::GlweSecretKey;
+        match $kind {
+            crate::SecretKeyKind::Binary => {
+                let $key = GlweSecretKey::binary_from_container(
+                    $coefficients,
+                    PolynomialSize($polynomial_size),
+                );
+                $body
+            }
+            crate::SecretKeyKind::Ternary => {
+                let $key = GlweSecretKey::ternary_from_container(
+                    $coefficients,
+                    PolynomialSize($polynomial_size),
+                );
+                $body
+            }
+            crate::SecretKeyKind::Gaussian => {
+                let $key = GlweSecretKey::gaussian_from_container(
+                    $coefficients,
+                    PolynomialSize($polynomial_size),
+                );
+                $body
+            }
+        }
+    }};
+}
+
 pub fn write_to_file<P: AsRef<Path>, U: Serialize>(path: P, u: &U) -> Result<(), Box<dyn Error>> {
     // Create the file
     let file = File::create(path)?;
@@ -151,6 +207,41 @@ fn read_from_file<P: AsRef<Path>, U: DeserializeOwned>(path: P) -> Result<U, Box
     Ok(u)
 }
 
+/// The options of the bincode serializer of serialize_into, i.e. fixed size integers and
+/// trailing bytes allowed, so that the files keep the same layout, and with a limit on the
+/// number of bytes read
+fn binary_options(limit: u64) -> impl bincode::Options {
+    bincode::options()
+        .with_fixint_encoding()
+        .allow_trailing_bytes()
+        .with_limit(limit)
+}
+
+pub(crate) fn write_to_binary_file<P: AsRef<Path>, U: Serialize>(
+    path: P,
+    u: &U,
+) -> Result<(), Box<dyn Error>> {
+    // Create the file
+    let file = File::create(path)?;
+    let writer = BufWriter::new(file);
+    bincode::serialize_into(writer, u)?;
+    Ok(())
+}
+
+fn read_from_binary_file<P: AsRef<Path>, U: DeserializeOwned>(
+    path: P,
+) -> Result<U, Box<dyn Error>> {
+    // Open the file in read-only mode with buffer.
+    let file = File::open(path)?;
+    // a valid file holds all the bytes it is decoded from, so that a corrupted length cannot
+    // make the decoder allocate more memory than the size of the file
+    let limit = file.metadata()?.len();
+    let reader = BufReader::new(file);
+    // Read the binary contents of the file
+    let u = binary_options(limit).deserialize_from(reader)?;
+    Ok(u)
+}
+
 // #[derive(Serialize, Deserialize)]
 // #[serde(remote = "Complex64")]
 // struct SerdeCtorus {
@@ -201,17 +292,33 @@ macro_rules! pub_mod_use {
 }
 #[macro_use]
 pub mod error;
+mod fourier;
+mod utils;
+#[doc(hidden)]
+pub use utils::zeroize;
 pub_mod_use!(lwe_params);
 pub_mod_use!(encoder);
+pub_mod_use!(generic_torus);
 pub_mod_use!(lwe);
 pub_mod_use!(plaintext);
 pub_mod_use!(vector_rlwe);
+pub_mod_use!(ggsw);
 pub_mod_use!(vector_lwe);
+pub_mod_use!(compressed_vector_lwe);
+pub_mod_use!(matrix);
 pub_mod_use!(lwe_ksk);
+pub_mod_use!(lwe_pfksk);
+pub_mod_use!(rlwe_ksk);
+pub_mod_use!(rlwe_automorphism_key);
 pub_mod_use!(lwe_bsk);
 pub_mod_use!(lwe_secret_key);
 pub_mod_use!(rlwe_params);
 pub_mod_use!(rlwe_secret_key);
+pub_mod_use!(secret_key_kind);
+pub_mod_use!(fingerprint);
+pub mod params;
+pub use params::ParameterBundle;
+pub_mod_use!(key_set);
 
 #[cfg(test)]
 mod tests_serde;
@@ -5,13 +5,12 @@ mod tests;
 
 use crate::error::CryptoAPIError;
 use crate::traits::GenericAdd;
-use crate::{read_from_file, write_to_file, Torus};
+use crate::{read_from_file, write_to_file, GenericTorus, KeyFingerprint, Torus};
 use backtrace::Backtrace;
 use colored::Colorize;
 use concrete_commons::dispersion::StandardDev;
 use concrete_commons::numeric::Numeric;
 use concrete_commons::parameters::{GlweSize, LweSize, PolynomialSize};
-use concrete_core::crypto::bootstrap::Bootstrap;
 use concrete_core::crypto::secret::generators::EncryptionRandomGenerator;
 use concrete_core::{
     crypto::{
@@ -23,28 +22,38 @@ use concrete_core::{
     math::tensor::{AsMutSlice, AsMutTensor, AsRefSlice, AsRefTensor},
 };
 use concrete_npe as npe;
-use concrete_npe::{LWE as NPELWE};
 use serde::{Deserialize, Serialize};
 use std::error::Error;
 use std::fmt;
 
-/// Structure containing a single LWE ciphertext.
+/// Structure containing a single LWE ciphertext, whose coefficients are elements of the torus
+/// of T (see GenericTorus), LWE being the usual one with Torus coefficients.
 ///
 /// # Attributes
 /// * `ciphertext` - the LWE ciphertexts
 /// * `variances` - the variance of the noise of the LWE ciphertext
 /// * `dimension` - the length the LWE mask
 /// * `encoder` - the encoder of the LWE ciphertext
+/// * `key_fingerprint` - the fingerprint of the secret key of the LWE ciphertext (unknown for
+///   trivial and older ciphertexts)
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-pub struct LWE {
-    pub ciphertext: crypto::lwe::LweCiphertext<Vec<Torus>>,
+pub struct GenericLWE<T> {
+    pub ciphertext: crypto::lwe::LweCiphertext<Vec<T>>,
     pub variance: f64,
     pub dimension: usize,
     pub encoder: crate::Encoder,
+    #[serde(default)]
+    pub key_fingerprint: KeyFingerprint,
 }
 
-impl GenericAdd<f64, CryptoAPIError> for LWE {
-    fn add(&self, right: f64) -> Result<LWE, CryptoAPIError> {
+/// An LWE ciphertext whose coefficients are Torus elements
+pub type LWE = GenericLWE<Torus>;
+
+/// An LWE ciphertext whose coefficients are u32, see GenericTorus
+pub type LWE32 = GenericLWE<u32>;
+
+impl<T: GenericTorus> GenericAdd<f64, CryptoAPIError> for GenericLWE<T> {
+    fn add(&self, right: f64) -> Result<GenericLWE<T>, CryptoAPIError> {
         self.add_constant_dynamic_encoder(right)
     }
     fn add_inplace(&mut self, right: f64) -> Result<(), CryptoAPIError> {
@@ -52,16 +61,16 @@ impl GenericAdd<f64, CryptoAPIError> for LWE {
     }
 }
 
-impl GenericAdd<&LWE, CryptoAPIError> for LWE {
-    fn add(&self, right: &LWE) -> Result<LWE, CryptoAPIError> {
+impl<T: GenericTorus> GenericAdd<&GenericLWE<T>, CryptoAPIError> for GenericLWE<T> {
+    fn add(&self, right: &GenericLWE<T>) -> Result<GenericLWE<T>, CryptoAPIError> {
         self.add_with_padding(right)
     }
-    fn add_inplace(&mut self, right: &LWE) -> Result<(), CryptoAPIError> {
+    fn add_inplace(&mut self, right: &GenericLWE<T>) -> Result<(), CryptoAPIError> {
         self.add_with_padding_inplace(right)
     }
 }
 
-impl LWE {
+impl<T: GenericTorus> GenericLWE<T> {
     /// Instantiate a new LWE filled with zeros from a dimension
     ///
     /// # Arguments
@@ -78,12 +87,13 @@ impl LWE {
     /// // creates an LWE ciphertext with a dimension of 630
     /// let empty_ciphertexts = LWE::zero(630).unwrap();
     /// ```
-    pub fn zero(dimension: usize) -> Result<crate::LWE, CryptoAPIError> {
-        Ok(LWE {
-            ciphertext: crypto::lwe::LweCiphertext::allocate(0, LweSize(dimension + 1)),
+    pub fn zero(dimension: usize) -> Result<GenericLWE<T>, CryptoAPIError> {
+        Ok(GenericLWE {
+            ciphertext: crypto::lwe::LweCiphertext::allocate(T::ZERO, LweSize(dimension + 1)),
             variance: 0.,
             dimension,
             encoder: crate::Encoder::zero(),
+            key_fingerprint: KeyFingerprint::UNKNOWN,
         })
     }
 
@@ -96,6 +106,7 @@ impl LWE {
     ///
     /// # Output
     /// an LWE structure
+    /// * NoNoiseInCiphertext - if the noise of the key would be rounded away in the torus of T
     ///
     /// ```rust
     /// use concrete::*;
@@ -116,8 +127,8 @@ impl LWE {
         sk: &crate::LWESecretKey,
         message: f64,
         encoder: &crate::Encoder,
-    ) -> Result<LWE, CryptoAPIError> {
-        let plaintext = encoder.encode_core(message)?;
+    ) -> Result<GenericLWE<T>, CryptoAPIError> {
+        let plaintext: T = crate::generic_torus::from_torus(encoder.encode_core(message)?);
         let mut result_encoder: crate::Encoder = encoder.clone();
         let nb_bit_overlap: usize =
             result_encoder.update_precision_from_variance(f64::powi(sk.std_dev, 2i32))?;
@@ -131,13 +142,14 @@ impl LWE {
             );
         }
 
-        let mut res = LWE {
-            ciphertext: crypto::lwe::LweCiphertext::allocate(0, LweSize(sk.dimension + 1)),
+        let mut res = GenericLWE {
+            ciphertext: crypto::lwe::LweCiphertext::allocate(T::ZERO, LweSize(sk.dimension + 1)),
             variance: 0.,
             dimension: sk.dimension,
             encoder: result_encoder,
+            key_fingerprint: sk.fingerprint,
         };
-        res.encrypt_raw(sk, plaintext).unwrap();
+        res.encrypt_raw(sk, plaintext)?;
 
         Ok(res)
     }
@@ -168,26 +180,26 @@ impl LWE {
     pub fn encrypt_raw(
         &mut self,
         sk: &crate::LWESecretKey,
-        plaintext: Torus,
+        plaintext: T,
     ) -> Result<(), CryptoAPIError> {
-        // compute the variance
-        let var = sk.get_variance();
-
         // check if we have enough std dev to have noise in the ciphertext
-        if sk.std_dev < f64::powi(2., -(<Torus as Numeric>::BITS as i32) + 2) {
-            return Err(NoNoiseInCiphertext!(var));
-        }
+        crate::generic_torus::check_std_dev::<T>(sk.std_dev)?;
 
         // fill the variance array
-        self.variance = var;
+        self.variance = sk.get_variance();
+        self.key_fingerprint = sk.fingerprint;
 
         // encrypt
-        sk.val.encrypt_lwe(
-            &mut self.ciphertext,
-            &Plaintext(plaintext),
-            StandardDev::from_standard_dev(sk.std_dev),
-            &mut EncryptionRandomGenerator::new(None),
-        );
+        sk.with_generic_coefficients(|coefficients| {
+            with_lwe_secret_key!(sk.kind, coefficients, |key| {
+                key.encrypt_lwe(
+                    &mut self.ciphertext,
+                    &Plaintext(plaintext),
+                    StandardDev::from_standard_dev(sk.std_dev),
+                    &mut EncryptionRandomGenerator::new(None),
+                )
+            })
+        });
 
         Ok(())
     }
@@ -199,6 +211,7 @@ impl LWE {
     /// # Output
     /// * `result` - a f64
     /// * DimensionError - if the ciphertext and the key have incompatible dimensions
+    /// * KeyMismatch - if the ciphertext was not encrypted under the key
     /// ```rust
     /// use concrete::*;
     ///
@@ -223,14 +236,23 @@ impl LWE {
             return Err(DimensionError!(self.dimension, sk.dimension));
         }
 
+        // check the keys
+        self.key_fingerprint.check(sk.fingerprint)?;
+
         // create a temporary variable to store the result of the phase computation
-        let mut output = Plaintext(0);
+        let mut output = Plaintext(T::ZERO);
 
         // compute the phase
-        sk.val.decrypt_lwe(&mut output, &self.ciphertext);
+        sk.with_generic_coefficients(|coefficients| {
+            with_lwe_secret_key!(sk.kind, coefficients, |key| {
+                key.decrypt_lwe(&mut output, &self.ciphertext)
+            })
+        });
 
         // decode
-        let result: f64 = self.encoder.decode_single(output.0)?;
+        let result: f64 = self
+            .encoder
+            .decode_single(crate::generic_torus::to_torus(output.0))?;
 
         Ok(result)
     }
@@ -242,6 +264,7 @@ impl LWE {
     /// # Output
     /// * `result` - a f64
     /// * DimensionError - if the ciphertext and the key have incompatible dimensions
+    /// * KeyMismatch - if the ciphertext was not encrypted under the key
     /// ```rust
     /// use concrete::*;
     ///
@@ -266,22 +289,31 @@ impl LWE {
             return Err(DimensionError!(self.dimension, sk.dimension));
         }
 
+        // check the keys
+        self.key_fingerprint.check(sk.fingerprint)?;
+
         // create a temporary variable to store the result of the phase computation
-        let mut output = Plaintext(0);
+        let mut output = Plaintext(T::ZERO);
 
         // compute the phase
-        sk.val.decrypt_lwe(&mut output, &self.ciphertext);
+        sk.with_generic_coefficients(|coefficients| {
+            with_lwe_secret_key!(sk.kind, coefficients, |key| {
+                key.decrypt_lwe(&mut output, &self.ciphertext)
+            })
+        });
 
         // round context
         let mut enc_round = self.encoder.clone();
         enc_round.round = true;
 
         // decode
-        let result: f64 = enc_round.decode_single(output.0)?;
+        let result: f64 = enc_round.decode_single(crate::generic_torus::to_torus(output.0))?;
 
         Ok(result)
     }
+}
 
+impl<T: GenericTorus> GenericLWE<T> {
     /// Add a small message to a LWE ciphertext and does not change the encoding but changes the bodies of the ciphertext
     ///
     /// # Argument
@@ -310,7 +342,10 @@ impl LWE {
     /// // addition between ciphertext and message_2
     /// let ct_add = ciphertext.add_constant_static_encoder(message_2).unwrap();
     /// ```
-    pub fn add_constant_static_encoder(&self, message: f64) -> Result<crate::LWE, CryptoAPIError> {
+    pub fn add_constant_static_encoder(
+        &self,
+        message: f64,
+    ) -> Result<GenericLWE<T>, CryptoAPIError> {
         let mut res = self.clone();
         res.add_constant_static_encoder_inplace(message)?;
         Ok(res)
@@ -355,7 +390,9 @@ impl LWE {
             .ciphertext
             .as_tensor()
             .get_element(self.dimension)
-            .wrapping_add(ec_tmp.encode_outside_interval_operators(message)?);
+            .wrapping_add(crate::generic_torus::from_torus(
+                ec_tmp.encode_outside_interval_operators(message)?,
+            ));
         *self
             .ciphertext
             .as_mut_tensor()
@@ -395,7 +432,10 @@ impl LWE {
     ///     .add_constant_dynamic_encoder(message_2)
     ///     .unwrap();
     /// ```
-    pub fn add_constant_dynamic_encoder(&self, message: f64) -> Result<crate::LWE, CryptoAPIError> {
+    pub fn add_constant_dynamic_encoder(
+        &self,
+        message: f64,
+    ) -> Result<GenericLWE<T>, CryptoAPIError> {
         let mut res = self.clone();
         res.add_constant_dynamic_encoder_inplace(message)?;
         Ok(res)
@@ -488,9 +528,9 @@ impl LWE {
     /// ```
     pub fn add_with_new_min(
         &self,
-        ct: &crate::LWE,
+        ct: &GenericLWE<T>,
         new_min: f64,
-    ) -> Result<crate::LWE, CryptoAPIError> {
+    ) -> Result<GenericLWE<T>, CryptoAPIError> {
         let mut res = self.clone();
         res.add_with_new_min_inplace(ct, new_min)?;
         Ok(res)
@@ -537,7 +577,7 @@ impl LWE {
     /// ```
     pub fn add_with_new_min_inplace(
         &mut self,
-        ct: &crate::LWE,
+        ct: &GenericLWE<T>,
         new_min: f64,
     ) -> Result<(), CryptoAPIError> {
         // check dimensions
@@ -545,6 +585,9 @@ impl LWE {
             return Err(DimensionError!(self.dimension, ct.dimension));
         }
 
+        // check the keys
+        let key_fingerprint = self.key_fingerprint.combine(ct.key_fingerprint)?;
+
         // add the two ciphertexts together
         self.ciphertext.update_with_add(&ct.ciphertext);
 
@@ -567,10 +610,10 @@ impl LWE {
         let updated_body_value = if tmp_sum <= new_min {
             let tmp_shift = new_min + (new_min - tmp_sum);
             let plaintext = tmp_ec.encode_outside_interval_operators(tmp_shift)?;
-            lwe_body.wrapping_sub(plaintext)
+            lwe_body.wrapping_sub(crate::generic_torus::from_torus(plaintext))
         } else {
             let plaintext = tmp_ec.encode_outside_interval_operators(tmp_sum)?;
-            lwe_body.wrapping_add(plaintext)
+            lwe_body.wrapping_add(crate::generic_torus::from_torus(plaintext))
         };
         self.ciphertext.get_mut_body().0 = updated_body_value;
 
@@ -581,6 +624,7 @@ impl LWE {
         self.variance = npe::add_ciphertexts(self.variance, ct.variance);
         self.encoder.update_precision_from_variance(self.variance)?;
 
+        self.key_fingerprint = key_fingerprint;
         Ok(())
     }
 
@@ -622,7 +666,7 @@ impl LWE {
     /// // addition between ciphertext_1 and ciphertext_2
     /// let new_ciphertext = ciphertext_1.add_centered(&ciphertext_2).unwrap();
     /// ```
-    pub fn add_centered(&self, ct: &crate::LWE) -> Result<crate::LWE, CryptoAPIError> {
+    pub fn add_centered(&self, ct: &GenericLWE<T>) -> Result<GenericLWE<T>, CryptoAPIError> {
         let mut res = self.clone();
         res.add_centered_inplace(ct)?;
         Ok(res)
@@ -670,12 +714,15 @@ impl LWE {
     /// // addition between ciphertext_1 and ciphertext_2
     /// ciphertext_1.add_centered_inplace(&ciphertext_2).unwrap();
     /// ```
-    pub fn add_centered_inplace(&mut self, ct: &crate::LWE) -> Result<(), CryptoAPIError> {
+    pub fn add_centered_inplace(&mut self, ct: &GenericLWE<T>) -> Result<(), CryptoAPIError> {
         // check same dimensions
         if self.dimension != ct.dimension {
             return Err(DimensionError!(self.dimension, ct.dimension));
         }
 
+        // check the keys
+        let key_fingerprint = self.key_fingerprint.combine(ct.key_fingerprint)?;
+
         // check same deltas
 
         if !deltas_eq!(self.encoder.delta, ct.encoder.delta) {
@@ -688,7 +735,8 @@ impl LWE {
         // correction related to the addition
         let mut tmp_enc = self.encoder.clone();
         tmp_enc.o = 0.;
-        let correction: Torus = tmp_enc.encode_core(self.encoder.delta / 2.)?;
+        let correction: T =
+            crate::generic_torus::from_torus(tmp_enc.encode_core(self.encoder.delta / 2.)?);
         let update =
             self.ciphertext.as_tensor().as_slice()[self.dimension].wrapping_sub(correction);
         self.ciphertext.as_mut_tensor().as_mut_slice()[self.dimension] = update;
@@ -702,6 +750,7 @@ impl LWE {
 
         // update the encoder precision based on the variance
         self.encoder.update_precision_from_variance(self.variance)?;
+        self.key_fingerprint = key_fingerprint;
         Ok(())
     }
 
@@ -739,7 +788,7 @@ impl LWE {
     ///
     /// let ct_add = ciphertext_1.add_with_padding(&ciphertext_2);
     /// ```
-    pub fn add_with_padding(&self, ct: &crate::LWE) -> Result<crate::LWE, CryptoAPIError> {
+    pub fn add_with_padding(&self, ct: &GenericLWE<T>) -> Result<GenericLWE<T>, CryptoAPIError> {
         let mut res = self.clone();
         res.add_with_padding_inplace(ct)?;
         Ok(res)
@@ -778,7 +827,7 @@ impl LWE {
     ///
     /// ciphertext_1.add_with_padding_inplace(&ciphertext_2);
     /// ```
-    pub fn add_with_padding_inplace(&mut self, ct: &crate::LWE) -> Result<(), CryptoAPIError> {
+    pub fn add_with_padding_inplace(&mut self, ct: &GenericLWE<T>) -> Result<(), CryptoAPIError> {
         // check same paddings
         if self.encoder.nb_bit_padding != ct.encoder.nb_bit_padding {
             return Err(PaddingError!(
@@ -800,6 +849,9 @@ impl LWE {
             return Err(DimensionError!(self.dimension, ct.dimension));
         }
 
+        // check the keys
+        let key_fingerprint = self.key_fingerprint.combine(ct.key_fingerprint)?;
+
         // add ciphertexts together
         self.ciphertext.update_with_add(&ct.ciphertext);
 
@@ -818,6 +870,7 @@ impl LWE {
         // update the encoder precision based on the variance
         self.encoder.update_precision_from_variance(self.variance)?;
 
+        self.key_fingerprint = key_fingerprint;
         Ok(())
     }
 
@@ -855,7 +908,10 @@ impl LWE {
     ///
     /// let ct_add = ciphertext_1.add_with_padding_exact(&ciphertext_2);
     /// ```
-    pub fn add_with_padding_exact(&self, ct: &crate::LWE) -> Result<crate::LWE, CryptoAPIError> {
+    pub fn add_with_padding_exact(
+        &self,
+        ct: &GenericLWE<T>,
+    ) -> Result<GenericLWE<T>, CryptoAPIError> {
         let mut res = self.clone();
         res.add_with_padding_exact_inplace(ct)?;
         Ok(res)
@@ -896,7 +952,7 @@ impl LWE {
     /// ```
     pub fn add_with_padding_exact_inplace(
         &mut self,
-        ct: &crate::LWE,
+        ct: &GenericLWE<T>,
     ) -> Result<(), CryptoAPIError> {
         // check same paddings
         if self.encoder.nb_bit_padding != ct.encoder.nb_bit_padding {
@@ -919,6 +975,9 @@ impl LWE {
             return Err(DimensionError!(self.dimension, ct.dimension));
         }
 
+        // check the keys
+        let key_fingerprint = self.key_fingerprint.combine(ct.key_fingerprint)?;
+
         // add ciphertexts together
         self.ciphertext.update_with_add(&ct.ciphertext);
 
@@ -937,6 +996,7 @@ impl LWE {
         // update the encoder precision based on the variance
         self.encoder.update_precision_from_variance(self.variance)?;
 
+        self.key_fingerprint = key_fingerprint;
         Ok(())
     }
 
@@ -974,7 +1034,7 @@ impl LWE {
     ///
     /// let ct_sub = ciphertext_1.add_with_padding(&ciphertext_2);
     /// ```
-    pub fn sub_with_padding(&self, ct: &crate::LWE) -> Result<crate::LWE, CryptoAPIError> {
+    pub fn sub_with_padding(&self, ct: &GenericLWE<T>) -> Result<GenericLWE<T>, CryptoAPIError> {
         let mut res = self.clone();
         res.sub_with_padding_inplace(ct)?;
         Ok(res)
@@ -1013,7 +1073,7 @@ impl LWE {
     ///
     /// ciphertext_1.sub_with_padding_inplace(&ciphertext_2);
     /// ```
-    pub fn sub_with_padding_inplace(&mut self, ct: &crate::LWE) -> Result<(), CryptoAPIError> {
+    pub fn sub_with_padding_inplace(&mut self, ct: &GenericLWE<T>) -> Result<(), CryptoAPIError> {
         // check same paddings
         if self.encoder.nb_bit_padding != ct.encoder.nb_bit_padding {
             return Err(PaddingError!(
@@ -1035,11 +1095,16 @@ impl LWE {
             return Err(DimensionError!(self.dimension, ct.dimension));
         }
 
+        // check the keys
+        let key_fingerprint = self.key_fingerprint.combine(ct.key_fingerprint)?;
+
         // subtract ciphertexts together
         self.ciphertext.update_with_sub(&ct.ciphertext);
 
         // correction related to the subtraction
-        let correction: Torus = 1 << (<Torus as Numeric>::BITS - self.encoder.nb_bit_padding);
+        let correction: T = crate::generic_torus::from_torus(
+            1 << (<Torus as Numeric>::BITS - self.encoder.nb_bit_padding),
+        );
         let update =
             self.ciphertext.as_tensor().as_slice()[self.dimension].wrapping_add(correction);
         self.ciphertext.as_mut_tensor().as_mut_slice()[self.dimension] = update;
@@ -1055,6 +1120,7 @@ impl LWE {
         self.variance = npe::add_ciphertexts(self.variance, ct.variance);
         self.encoder.update_precision_from_variance(self.variance)?;
 
+        self.key_fingerprint = key_fingerprint;
         Ok(())
     }
 
@@ -1092,7 +1158,10 @@ impl LWE {
     ///
     /// let ct_sub = ciphertext_1.sub_with_padding_exact(&ciphertext_2);
     /// ```
-    pub fn sub_with_padding_exact(&self, ct: &crate::LWE) -> Result<crate::LWE, CryptoAPIError> {
+    pub fn sub_with_padding_exact(
+        &self,
+        ct: &GenericLWE<T>,
+    ) -> Result<GenericLWE<T>, CryptoAPIError> {
         let mut res = self.clone();
         res.sub_with_padding_exact_inplace(ct)?;
         Ok(res)
@@ -1133,7 +1202,7 @@ impl LWE {
     /// ```
     pub fn sub_with_padding_exact_inplace(
         &mut self,
-        ct: &crate::LWE,
+        ct: &GenericLWE<T>,
     ) -> Result<(), CryptoAPIError> {
         // check same paddings
         if self.encoder.nb_bit_padding != ct.encoder.nb_bit_padding {
@@ -1156,11 +1225,16 @@ impl LWE {
             return Err(DimensionError!(self.dimension, ct.dimension));
         }
 
+        // check the keys
+        let key_fingerprint = self.key_fingerprint.combine(ct.key_fingerprint)?;
+
         // subtract ciphertexts together
         self.ciphertext.update_with_sub(&ct.ciphertext);
 
         // correction related to the subtraction
-        let correction: Torus = 1 << (<Torus as Numeric>::BITS - self.encoder.nb_bit_padding);
+        let correction: T = crate::generic_torus::from_torus(
+            1 << (<Torus as Numeric>::BITS - self.encoder.nb_bit_padding),
+        );
         let update =
             self.ciphertext.as_tensor().as_slice()[self.dimension].wrapping_add(correction);
         self.ciphertext.as_mut_tensor().as_mut_slice()[self.dimension] = update;
@@ -1176,6 +1250,7 @@ impl LWE {
         self.variance = npe::add_ciphertexts(self.variance, ct.variance);
         self.encoder.update_precision_from_variance(self.variance)?;
 
+        self.key_fingerprint = key_fingerprint;
         Ok(())
     }
 
@@ -1211,7 +1286,10 @@ impl LWE {
     /// let mut ciphertext = LWE::encode_encrypt(&secret_key, message_1, &encoder).unwrap();
     /// let new_ciphertext = ciphertext.mul_constant_static_encoder(message_2).unwrap();
     /// ```
-    pub fn mul_constant_static_encoder(&self, message: i32) -> Result<crate::LWE, CryptoAPIError> {
+    pub fn mul_constant_static_encoder(
+        &self,
+        message: i32,
+    ) -> Result<GenericLWE<T>, CryptoAPIError> {
         let mut res = self.clone();
         res.mul_constant_static_encoder_inplace(message)?;
         Ok(res)
@@ -1254,11 +1332,11 @@ impl LWE {
     ) -> Result<(), CryptoAPIError> {
         // compute correction
         let cor0: Torus = self.encoder.encode_outside_interval_operators(0.)?;
-        let cor = cor0.wrapping_mul((message - 1) as Torus);
+        let cor: T = crate::generic_torus::from_torus(cor0.wrapping_mul((message - 1) as Torus));
 
         // multiplication
         self.ciphertext
-            .update_with_scalar_mul(Cleartext(message as Torus));
+            .update_with_scalar_mul(Cleartext(T::cast_from(message as Torus)));
 
         // apply correction
         let update = self.ciphertext.as_tensor().as_slice()[self.dimension].wrapping_sub(cor);
@@ -1322,7 +1400,7 @@ impl LWE {
         constant: f64,
         max_constant: f64,
         nb_bit_padding: usize,
-    ) -> Result<crate::LWE, CryptoAPIError> {
+    ) -> Result<GenericLWE<T>, CryptoAPIError> {
         let mut res = self.clone();
         res.mul_constant_with_padding_inplace(constant, max_constant, nb_bit_padding)?;
         Ok(res)
@@ -1375,21 +1453,10 @@ impl LWE {
         max_constant: f64,
         nb_bit_padding: usize,
     ) -> Result<(), CryptoAPIError> {
-        // check that the constant if below the maximum
-        if constant > max_constant || constant < -max_constant {
-            return Err(ConstantMaximumError!(constant, max_constant));
-        }
-        // check that zero is in the interval
-        else if self.encoder.o > 0. || self.encoder.o + self.encoder.delta < 0. {
-            return Err(ZeroInIntervalError!(self.encoder.o, self.encoder.delta));
-        }
-        // check bits of paddings
-        else if self.encoder.nb_bit_padding < nb_bit_padding {
-            return Err(NotEnoughPaddingError!(
-                self.encoder.nb_bit_padding,
-                nb_bit_padding
-            ));
-        }
+        // new encoder (check the constant, the interval and the padding)
+        let new_encoder = self
+            .encoder
+            .scale_abs(constant, max_constant, nb_bit_padding)?;
 
         // test if negative
         let negative: bool = constant < 0.;
@@ -1402,56 +1469,15 @@ impl LWE {
             (c_abs / max_constant * f64::powi(2., nb_bit_padding as i32)).round() as Torus;
 
         // encode 0 and subtract it
-        let tmp_sub = self.encoder.encode_core(0.)?;
+        let tmp_sub: T = crate::generic_torus::from_torus(self.encoder.encode_core(0.)?);
         let update = self.ciphertext.as_tensor().as_slice()[self.dimension].wrapping_sub(tmp_sub);
         self.ciphertext.as_mut_tensor().as_mut_slice()[self.dimension] = update;
 
         // scalar multiplication
-        self.ciphertext.update_with_scalar_mul(Cleartext(scal));
-
-        // new encoder
-        let new_o = self.encoder.o * max_constant;
-        let new_max =
-            (self.encoder.o + self.encoder.delta - self.encoder.get_granularity()) * max_constant;
-        let new_delta = new_max - new_o;
-
-        // compute the discretization of c_abs
-        let discret_c_abs = (scal as f64) * f64::powi(2., -(nb_bit_padding as i32)) * max_constant;
-
-        // compute  the rounding error on c_abs
-        let rounding_error = (discret_c_abs - c_abs).abs();
-
-        // get the ciphertext granularity
-        let granularity = self.encoder.get_granularity();
-
-        // compute the max of the ciphertext (based on the metadata of the encoder)
-        let max = f64::max(
-            (self.encoder.o + self.encoder.delta - self.encoder.get_granularity()).abs(),
-            self.encoder.o.abs(),
-        );
-
-        // compute the new granularity
-        let new_granularity = 2.
-            * (granularity * rounding_error / 2.
-                + granularity / 2. * discret_c_abs
-                + rounding_error * max)
-                .abs();
-
-        // compute the new precision
-        let new_precision = usize::min(
-            f64::log2(new_delta / new_granularity).floor() as usize,
-            self.encoder.nb_bit_precision,
-        );
+        self.ciphertext
+            .update_with_scalar_mul(Cleartext(T::cast_from(scal)));
 
-        // create the new encoder
-        let tmp_encoder = crate::Encoder::new(
-            new_o,
-            new_max,
-            usize::min(nb_bit_padding, self.encoder.nb_bit_precision),
-            self.encoder.nb_bit_padding - nb_bit_padding,
-        )?;
-        self.encoder.copy(&tmp_encoder);
-        self.encoder.nb_bit_precision = usize::min(self.encoder.nb_bit_precision, new_precision);
+        self.encoder.copy(&new_encoder);
         // call to the NPE to estimate the new variance
         self.variance = npe::LWE::single_scalar_mul(self.variance, scal);
 
@@ -1461,7 +1487,7 @@ impl LWE {
         }
 
         // encode 0 with the new encoder
-        let tmp_add = self.encoder.encode_core(0.)?;
+        let tmp_add: T = crate::generic_torus::from_torus(self.encoder.encode_core(0.)?);
         let update = self.ciphertext.as_tensor().as_slice()[self.dimension].wrapping_add(tmp_add);
         self.ciphertext.as_mut_tensor().as_mut_slice()[self.dimension] = update;
 
@@ -1487,7 +1513,7 @@ impl LWE {
                 .ciphertext
                 .as_tensor()
                 .get_element(self.dimension)
-                .wrapping_add(cor);
+                .wrapping_add(crate::generic_torus::from_torus(cor));
             *self
                 .ciphertext
                 .as_mut_tensor()
@@ -1528,7 +1554,7 @@ impl LWE {
     ///
     /// let new_ciphertext = ciphertext.opposite().unwrap();
     /// ```
-    pub fn opposite(&self) -> Result<crate::LWE, CryptoAPIError> {
+    pub fn opposite(&self) -> Result<GenericLWE<T>, CryptoAPIError> {
         let mut res = self.clone();
         res.opposite_inplace()?;
         Ok(res)
@@ -1588,7 +1614,8 @@ impl LWE {
                     - self.encoder.nb_bit_precision),
             );
         }
-        let update = self.ciphertext.as_tensor().as_slice()[self.dimension].wrapping_add(cor);
+        let update = self.ciphertext.as_tensor().as_slice()[self.dimension]
+            .wrapping_add(crate::generic_torus::from_torus(cor));
         self.ciphertext.as_mut_tensor().as_mut_slice()[self.dimension] = update;
 
         // change the encoder
@@ -1596,7 +1623,107 @@ impl LWE {
 
         Ok(())
     }
+}
+
+impl LWE {
+    /// Multiply the LWE ciphertext with an arbitrary real constant.
+    /// The constant is decomposed into an integer with `precision` significant bits
+    /// times a power of two: the ciphertext is multiplied by the integer
+    /// and the power of two is absorbed by a rescale of the delta of the output encoder,
+    /// so about `precision` bits of padding are consumed whatever the magnitude of the constant.
+    /// The granularity of the output encoder is a bound on the error of the result,
+    /// which takes into account the rounding of the constant and the input granularity.
+    ///
+    /// # Argument
+    /// * `constant` - a real constant
+    /// * `precision` - the number of significant bits used to represent the constant
+    ///
+    /// # Output
+    /// * a new LWE
+    /// * PrecisionError - if `precision` is zero
+    /// * ConstantMaximumError - if the constant is too big for the encoder
+    /// * NotEnoughPaddingError - if there is not enough padding
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// // encoder
+    /// let encoder = Encoder::new(-10., 10., 6, 8).unwrap();
+    ///
+    /// // generate a secret key
+    /// let secret_key = LWESecretKey::new(&LWE128_1024);
+    ///
+    /// // encode and encrypt
+    /// let ciphertext = LWE::encode_encrypt(&secret_key, 4.2, &encoder).unwrap();
+    ///
+    /// // multiply by 0.37 and by 1/3
+    /// let ciphertext_1 = ciphertext.mul_real(0.37, 6).unwrap();
+    /// let ciphertext_2 = ciphertext.mul_real(1. / 3., 6).unwrap();
+    /// ```
+    pub fn mul_real(&self, constant: f64, precision: usize) -> Result<crate::LWE, CryptoAPIError> {
+        let mut res = self.clone();
+        res.mul_real_inplace(constant, precision)?;
+        Ok(res)
+    }
+
+    /// Multiply the LWE ciphertext with an arbitrary real constant.
+    /// The constant is decomposed into an integer with `precision` significant bits
+    /// times a power of two: the ciphertext is multiplied by the integer
+    /// and the power of two is absorbed by a rescale of the delta of the output encoder,
+    /// so about `precision` bits of padding are consumed whatever the magnitude of the constant.
+    /// The granularity of the output encoder is a bound on the error of the result,
+    /// which takes into account the rounding of the constant and the input granularity.
+    ///
+    /// # Argument
+    /// * `constant` - a real constant
+    /// * `precision` - the number of significant bits used to represent the constant
+    ///
+    /// # Output
+    /// * PrecisionError - if `precision` is zero
+    /// * ConstantMaximumError - if the constant is too big for the encoder
+    /// * NotEnoughPaddingError - if there is not enough padding
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// // encoder
+    /// let encoder = Encoder::new(-10., 10., 6, 8).unwrap();
+    ///
+    /// // generate a secret key
+    /// let secret_key = LWESecretKey::new(&LWE128_1024);
+    ///
+    /// // encode and encrypt
+    /// let mut ciphertext = LWE::encode_encrypt(&secret_key, 4.2, &encoder).unwrap();
+    ///
+    /// // multiply by 0.37
+    /// ciphertext.mul_real_inplace(0.37, 6).unwrap();
+    /// ```
+    pub fn mul_real_inplace(
+        &mut self,
+        constant: f64,
+        precision: usize,
+    ) -> Result<(), CryptoAPIError> {
+        if precision == 0 {
+            return Err(PrecisionError!());
+        }
+
+        // number of fractional bits such that the constant has `precision` significant bits
+        let nb_fractional_bits: i32 = if constant == 0. || !constant.is_finite() {
+            0
+        } else {
+            precision as i32 - 1 - constant.abs().log2().floor() as i32
+        };
+
+        let mut ct = crate::VectorLWE::zero(self.dimension, 1)?;
+        ct.copy_lwe_in_nth_inplace(0, self)?;
+        *self = ct.multisum_fixed_point(&[constant], nb_fractional_bits)?;
+        Ok(())
+    }
+}
 
+impl<T: GenericTorus> GenericLWE<T> {
     /// Compute a key switching operation on every ciphertext from the LWE struct self
     ///
     /// # Argument
@@ -1604,6 +1731,7 @@ impl LWE {
     ///
     /// # Output
     /// * a LWE struct
+    /// * KeyMismatch - if the ciphertext was not encrypted under the input key of the key switching key
     ///
     ///
     /// # Example
@@ -1640,23 +1768,23 @@ impl LWE {
     /// // key switch
     /// let ciphertext_after = ciphertext_before.keyswitch(&ksk).unwrap();
     /// ```
-    pub fn keyswitch(&self, ksk: &crate::LWEKSK) -> Result<crate::LWE, CryptoAPIError> {
+    pub fn keyswitch(
+        &self,
+        ksk: &crate::GenericLWEKSK<T>,
+    ) -> Result<GenericLWE<T>, CryptoAPIError> {
+        // check the keys
+        self.key_fingerprint.check(ksk.fingerprint_before)?;
+
         // allocation for the result
-        let mut res: crate::LWE = crate::LWE::zero(ksk.dimension_after)?;
+        let mut res: GenericLWE<T> = GenericLWE::zero(ksk.dimension_after)?;
+        res.key_fingerprint = ksk.fingerprint_after;
 
         // key switch
-        ksk.ciphertexts
-            .keyswitch_ciphertext(&mut res.ciphertext, &self.ciphertext);
+        T::keyswitch_ciphertext(&ksk.ciphertexts, &mut res.ciphertext, &self.ciphertext);
 
         // deal with encoders, noise and new precision
         // calls the NPE to find out the amount of noise after KS
-        res.variance = <Torus as NPELWE>::key_switch(
-            self.dimension,
-            ksk.level,
-            ksk.base_log,
-            ksk.variance,
-            self.variance,
-        );
+        res.variance = ksk.get_output_variance(self.variance);
 
         // copy the encoders
         res.encoder.copy(&self.encoder);
@@ -1686,6 +1814,7 @@ impl LWE {
     /// * a LWE struct
     /// * IndexError - if the requested ciphertext does not exist
     /// * DimensionError - if the bootstrapping key and the input ciphertext have incompatible dimensions
+    /// * KeyMismatch - if the input ciphertext was not encrypted under the input key of the bootstrapping key
     ///
     /// # Example
     /// ```rust
@@ -1724,7 +1853,10 @@ impl LWE {
     ///     .bootstrap(&bootstrapping_key)
     ///     .unwrap();
     /// ```
-    pub fn bootstrap(&self, bsk: &crate::LWEBSK) -> Result<crate::LWE, CryptoAPIError> {
+    pub fn bootstrap(
+        &self,
+        bsk: &crate::GenericLWEBSK<T>,
+    ) -> Result<GenericLWE<T>, CryptoAPIError> {
         self.bootstrap_with_function(bsk, |x| x, &self.encoder)
     }
 
@@ -1739,6 +1871,7 @@ impl LWE {
     /// * a LWE struct
     /// * IndexError - if the requested ciphertext does not exist
     /// * DimensionError - if the bootstrapping key and the input ciphertext have incompatible dimensions
+    /// * KeyMismatch - if the input ciphertext was not encrypted under the input key of the bootstrapping key
     ///
     /// # Example
     /// ```rust
@@ -1780,10 +1913,10 @@ impl LWE {
     /// ```
     pub fn bootstrap_with_function<F: Fn(f64) -> f64>(
         &self,
-        bsk: &crate::LWEBSK,
+        bsk: &crate::GenericLWEBSK<T>,
         f: F,
         encoder_output: &crate::Encoder,
-    ) -> Result<crate::LWE, CryptoAPIError> {
+    ) -> Result<GenericLWE<T>, CryptoAPIError> {
         // check bsk compatibility
         if self.dimension != bsk.get_lwe_dimension() {
             return Err(DimensionError!(self.dimension, bsk.get_lwe_dimension()));
@@ -1792,48 +1925,49 @@ impl LWE {
         // generate the look up table (throw error if a bit of padding is missing)
         let lut = bsk.generate_functional_look_up_table(&self.encoder, encoder_output, f)?;
 
-        // build the trivial accumulator
-        let mut accumulator = GlweCiphertext::allocate(
-            0_u64,
-            PolynomialSize(bsk.polynomial_size),
-            GlweSize(bsk.dimension + 1),
-        );
-        accumulator
-            .as_mut_tensor()
-            .as_mut_slice()
-            .get_mut(
-                (bsk.dimension * bsk.polynomial_size)..((bsk.dimension + 1) * bsk.polynomial_size),
-            )
-            .unwrap()
-            .copy_from_slice(&lut);
-
-        // allocate the result
-        let mut result =
-            LweCiphertext::allocate(0, LweSize(bsk.dimension * bsk.polynomial_size + 1));
+        self.bootstrap_with_look_up_table(bsk, &lut, encoder_output)
+    }
 
-        if self.encoder.nb_bit_padding > 1 {
-            // remove the padding but one bit
-            let mut self_clone = self.clone();
-            self_clone.remove_padding_inplace(self.encoder.nb_bit_padding - 1)?;
+    /// Compute a bootstrap with a look up table built by one of the LWEBSK look up table generators,
+    /// and compute the output variance and the output precision
+    ///
+    /// # Argument
+    /// * `bsk` - the bootstrapping key
+    /// * `lut` - the look up table
+    /// * `encoder_output` - the encoder used to build the look up table
+    ///
+    /// # Output
+    /// * a LWE struct
+    fn bootstrap_with_look_up_table(
+        &self,
+        bsk: &crate::GenericLWEBSK<T>,
+        lut: &[T],
+        encoder_output: &crate::Encoder,
+    ) -> Result<GenericLWE<T>, CryptoAPIError> {
+        // compute the bootstrap
+        let result = self.bootstrap_with_look_up_table_raw(bsk, lut)?;
 
-            // compute the bootstrap
-            bsk.ciphertexts
-                .bootstrap(&mut result, &self_clone.ciphertext, & accumulator);
-        } else {
-            // compute the bootstrap
-            bsk.ciphertexts
-                .bootstrap(&mut result, &self.ciphertext, & accumulator);
-        }
+        self.get_bootstrap_output(bsk, result, encoder_output)
+    }
 
+    /// Wrap the raw output of a bootstrap of self into an LWE struct, with the output variance and
+    /// the output precision
+    ///
+    /// # Argument
+    /// * `bsk` - the bootstrapping key
+    /// * `result` - the raw output of the bootstrap
+    /// * `encoder_output` - the encoder used to build the look up table
+    ///
+    /// # Output
+    /// * a LWE struct
+    pub(crate) fn get_bootstrap_output(
+        &self,
+        bsk: &crate::GenericLWEBSK<T>,
+        result: LweCiphertext<Vec<T>>,
+        encoder_output: &crate::Encoder,
+    ) -> Result<GenericLWE<T>, CryptoAPIError> {
         // compute the new variance (without the drift)
-        let new_var: f64 = <Torus as npe::cross::Cross>::bootstrap(
-            self.dimension,
-            bsk.dimension,
-            bsk.level,
-            bsk.base_log,
-            bsk.polynomial_size,
-            bsk.variance,
-        );
+        let new_var: f64 = bsk.get_output_variance();
 
         // create the output encoder
         let mut new_encoder_output: crate::Encoder = encoder_output.clone();
@@ -1850,40 +1984,489 @@ impl LWE {
             );
         }
 
-        // calls the NPE to find out the amount of noise after rounding the input ciphertext (drift)
-        let nb_rounding_noise_bit: usize =
-            (npe::lwe::log2_rounding_noise(self.dimension)).ceil() as usize + 1;
-
-        // deals with the drift error
-        if nb_rounding_noise_bit + 1 + new_encoder_output.nb_bit_precision
-            > bsk.get_polynomial_size_log() + 1
-        {
-            let nb_bit_loss = 1 + new_encoder_output.nb_bit_precision + nb_rounding_noise_bit
-                - bsk.get_polynomial_size_log()
-                - 1;
-
-            new_encoder_output.nb_bit_precision = i32::max(
-                new_encoder_output.nb_bit_precision as i32 - nb_bit_loss as i32,
-                0i32,
-            ) as usize;
-            // drift
+        // deals with the drift error, the input keeping one bit of padding
+        let nb_bit_loss = bsk.update_precision_from_drift(&mut new_encoder_output, 1);
+        if nb_bit_loss > 0 {
             println!(
-                "{}: {} bit(s) of precision lost over {} bit(s) of message originally ({} bits are affected by the noise). Consider increasing the polynomial size of the RLWE secret key.",
+                "{}: {} bit(s) of precision lost over {} bit(s) of message originally. Consider increasing the polynomial size of the RLWE secret key.",
                 "Loss of precision during bootstrap due to the rounding".red().bold(),
-                nb_bit_loss, self.encoder.nb_bit_precision,nb_rounding_noise_bit
+                nb_bit_loss, self.encoder.nb_bit_precision
             );
         }
 
         // construct the output
-        let lwe = crate::LWE {
+        let lwe = GenericLWE {
             variance: new_var,
             ciphertext: result,
             dimension: bsk.polynomial_size * bsk.dimension,
             encoder: new_encoder_output,
+            key_fingerprint: bsk.output_fingerprint,
         };
 
         Ok(lwe)
     }
+}
+
+impl LWE {
+    /// Change the encoder of an LWE ciphertext with a bootstrap of the identity function,
+    /// for instance to shrink back an interval that has grown through a chain of additions,
+    /// or to restore some padding and precision for further computation.
+    /// The messages outside of the interval of the new encoder are clamped to its min or its max.
+    ///
+    /// # Argument
+    /// * `bsk` - a bootstrapping key
+    /// * `new_encoder` - the encoder of the output
+    ///
+    /// # Output
+    /// * a LWE struct under the output key of `bsk`
+    /// * DimensionError - if the bootstrapping key and the input ciphertext have incompatible dimensions
+    /// * KeyMismatch - if the input ciphertext was not encrypted under the input key of the bootstrapping key
+    /// * NotEnoughPaddingError - if the input ciphertext has no bit of padding
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// // encoders
+    /// let encoder = Encoder::new(-10., 10., 4, 2).unwrap();
+    /// let new_encoder = Encoder::new(0., 10., 4, 4).unwrap();
+    ///
+    /// // generate secret keys
+    /// let rlwe_secret_key = RLWESecretKey::new(&RLWE128_1024_1);
+    /// let secret_key_before = LWESecretKey::new(&LWE128_630);
+    ///
+    /// // bootstrapping key
+    /// let bootstrapping_key = LWEBSK::new(&secret_key_before, &rlwe_secret_key, 5, 3);
+    ///
+    /// // encode, encrypt and sum
+    /// let ciphertext_1 = LWE::encode_encrypt(&secret_key_before, 3.2, &encoder).unwrap();
+    /// let ciphertext_2 = LWE::encode_encrypt(&secret_key_before, 4.1, &encoder).unwrap();
+    /// let sum = ciphertext_1.add_with_padding(&ciphertext_2).unwrap();
+    ///
+    /// // the sum is now encoded in [-20, 20[ with 1 bit of padding, reencode it in [0, 10[
+    /// let ciphertext_out = sum.reencode(&bootstrapping_key, &new_encoder).unwrap();
+    /// ```
+    pub fn reencode(
+        &self,
+        bsk: &crate::LWEBSK,
+        new_encoder: &crate::Encoder,
+    ) -> Result<crate::LWE, CryptoAPIError> {
+        // check bsk compatibility
+        if self.dimension != bsk.get_lwe_dimension() {
+            return Err(DimensionError!(self.dimension, bsk.get_lwe_dimension()));
+        }
+
+        // generate the look up table (throw error if a bit of padding is missing)
+        let lut = bsk.generate_clamped_identity_look_up_table(&self.encoder, new_encoder)?;
+
+        self.bootstrap_with_look_up_table(bsk, &lut, new_encoder)
+    }
+
+    /// Compute a bootstrap and apply an arbitrary function to the LWE ciphertext, then key switch
+    /// the result back to a small LWE key, with a single noise analysis for the two operations
+    ///
+    /// # Argument
+    /// * `bsk` - the bootstrapping key
+    /// * `ksk` - the key switching key, from the output key of `bsk`
+    /// * `f` - the function to apply
+    /// * `encoder_output` - the encoder of the output
+    ///
+    /// # Output
+    /// * a LWE struct under the output key of `ksk`
+    /// * DimensionError - if the input ciphertext, the bootstrapping key and the key switching key have incompatible dimensions
+    /// * KeyMismatch - if the input ciphertext, the bootstrapping key and the key switching key do not chain their keys
+    /// * NotEnoughPaddingError - if the input ciphertext has no bit of padding
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// // encoders
+    /// let encoder_input = Encoder::new(-10., 10., 4, 1).unwrap();
+    /// let encoder_output = Encoder::new(0., 10., 4, 1).unwrap();
+    ///
+    /// // generate secret keys
+    /// let rlwe_secret_key = RLWESecretKey::new(&RLWE128_1024_1);
+    /// let secret_key = LWESecretKey::new(&LWE128_630);
+    /// let big_secret_key = rlwe_secret_key.to_lwe_secret_key();
+    ///
+    /// // bootstrapping key and key switching key back to the input key
+    /// let bsk = LWEBSK::new(&secret_key, &rlwe_secret_key, 5, 3);
+    /// let ksk = LWEKSK::new(&big_secret_key, &secret_key, 2, 7);
+    ///
+    /// // encode and encrypt
+    /// let ciphertext = LWE::encode_encrypt(&secret_key, -4.2, &encoder_input).unwrap();
+    ///
+    /// // the output is encrypted under the input key
+    /// let ciphertext_out = ciphertext
+    ///     .pbs_keyswitch(&bsk, &ksk, |x| f64::abs(x), &encoder_output)
+    ///     .unwrap();
+    /// ```
+    pub fn pbs_keyswitch<F: Fn(f64) -> f64>(
+        &self,
+        bsk: &crate::LWEBSK,
+        ksk: &crate::LWEKSK,
+        f: F,
+        encoder_output: &crate::Encoder,
+    ) -> Result<crate::LWE, CryptoAPIError> {
+        // check bsk and ksk compatibility
+        if self.dimension != bsk.get_lwe_dimension() {
+            return Err(DimensionError!(self.dimension, bsk.get_lwe_dimension()));
+        } else if ksk.dimension_before != bsk.dimension * bsk.polynomial_size {
+            return Err(DimensionError!(
+                ksk.dimension_before,
+                bsk.dimension * bsk.polynomial_size
+            ));
+        }
+        bsk.output_fingerprint.check(ksk.fingerprint_before)?;
+
+        // generate the look up table (throw error if a bit of padding is missing)
+        let lut = bsk.generate_functional_look_up_table(&self.encoder, encoder_output, f)?;
+
+        // compute the bootstrap then the key switch
+        let big_ciphertext = self.bootstrap_with_look_up_table_raw(bsk, &lut)?;
+        let mut res = crate::LWE::zero(ksk.dimension_after)?;
+        ksk.ciphertexts
+            .keyswitch_ciphertext(&mut res.ciphertext, &big_ciphertext);
+
+        // the noise of the bootstrap goes through the key switch
+        res.variance = ksk.get_output_variance(bsk.get_output_variance());
+        res.encoder = Self::get_pbs_output_encoder(
+            bsk,
+            encoder_output,
+            encoder_output.nb_bit_precision,
+            res.variance,
+            "bootstrap and key switch",
+        )?;
+        res.key_fingerprint = ksk.fingerprint_after;
+
+        Ok(res)
+    }
+
+    /// Key switch the LWE ciphertext to the input key of a bootstrapping key, then compute a
+    /// bootstrap and apply an arbitrary function, with a single noise analysis for the two operations
+    /// With this order, the ciphertexts stay under the big key between two operations and the
+    /// bootstrap cleans the noise of the key switch
+    ///
+    /// # Argument
+    /// * `ksk` - the key switching key, to the input key of `bsk`
+    /// * `bsk` - the bootstrapping key
+    /// * `f` - the function to apply
+    /// * `encoder_output` - the encoder of the output
+    ///
+    /// # Output
+    /// * a LWE struct under the output key of `bsk`
+    /// * DimensionError - if the input ciphertext, the key switching key and the bootstrapping key have incompatible dimensions
+    /// * KeyMismatch - if the input ciphertext, the key switching key and the bootstrapping key do not chain their keys
+    /// * NotEnoughPaddingError - if the input ciphertext has no bit of padding
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// // encoders
+    /// let encoder_input = Encoder::new(-10., 10., 4, 1).unwrap();
+    /// let encoder_output = Encoder::new(0., 10., 4, 1).unwrap();
+    ///
+    /// // generate secret keys
+    /// let rlwe_secret_key = RLWESecretKey::new(&RLWE128_1024_1);
+    /// let secret_key = LWESecretKey::new(&LWE128_630);
+    /// let big_secret_key = rlwe_secret_key.to_lwe_secret_key();
+    ///
+    /// // key switching key to the input key of the bootstrapping key
+    /// let ksk = LWEKSK::new(&big_secret_key, &secret_key, 2, 7);
+    /// let bsk = LWEBSK::new(&secret_key, &rlwe_secret_key, 5, 3);
+    ///
+    /// // encode and encrypt under the big key
+    /// let ciphertext = LWE::encode_encrypt(&big_secret_key, -4.2, &encoder_input).unwrap();
+    ///
+    /// // the output is encrypted under the big key again
+    /// let ciphertext_out = ciphertext
+    ///     .keyswitch_pbs(&ksk, &bsk, |x| f64::abs(x), &encoder_output)
+    ///     .unwrap();
+    /// ```
+    pub fn keyswitch_pbs<F: Fn(f64) -> f64>(
+        &self,
+        ksk: &crate::LWEKSK,
+        bsk: &crate::LWEBSK,
+        f: F,
+        encoder_output: &crate::Encoder,
+    ) -> Result<crate::LWE, CryptoAPIError> {
+        // check ksk and bsk compatibility
+        if self.dimension != ksk.dimension_before {
+            return Err(DimensionError!(self.dimension, ksk.dimension_before));
+        } else if ksk.dimension_after != bsk.get_lwe_dimension() {
+            return Err(DimensionError!(
+                ksk.dimension_after,
+                bsk.get_lwe_dimension()
+            ));
+        }
+        self.key_fingerprint.check(ksk.fingerprint_before)?;
+
+        // generate the look up table (throw error if a bit of padding is missing)
+        let lut = bsk.generate_functional_look_up_table(&self.encoder, encoder_output, f)?;
+
+        // compute the key switch, the input of the bootstrap keeps the input encoder
+        let mut small_ciphertext = crate::LWE::zero(ksk.dimension_after)?;
+        ksk.ciphertexts
+            .keyswitch_ciphertext(&mut small_ciphertext.ciphertext, &self.ciphertext);
+        small_ciphertext.variance = ksk.get_output_variance(self.variance);
+        small_ciphertext.encoder.copy(&self.encoder);
+        small_ciphertext.key_fingerprint = ksk.fingerprint_after;
+
+        // the bits of the input message hidden by the noise of the key switch are lost
+        let mut encoder_input = self.encoder.clone();
+        encoder_input.update_precision_from_variance(small_ciphertext.variance)?;
+
+        // compute the bootstrap
+        let result = small_ciphertext.bootstrap_with_look_up_table_raw(bsk, &lut)?;
+        let variance = bsk.get_output_variance();
+
+        Ok(crate::LWE {
+            ciphertext: result,
+            variance,
+            dimension: bsk.polynomial_size * bsk.dimension,
+            encoder: Self::get_pbs_output_encoder(
+                bsk,
+                encoder_output,
+                usize::min(
+                    encoder_output.nb_bit_precision,
+                    encoder_input.nb_bit_precision,
+                ),
+                variance,
+                "key switch and bootstrap",
+            )?,
+            key_fingerprint: bsk.output_fingerprint,
+        })
+    }
+
+    /// Compute the encoder of the output of a bootstrap combined with a key switch,
+    /// taking into account the output noise and the drift of the bootstrap at once
+    ///
+    /// # Argument
+    /// * `bsk` - the bootstrapping key
+    /// * `encoder_output` - the encoder used to build the look up table
+    /// * `nb_bit_precision` - the precision left before the output noise and the drift
+    /// * `variance` - the variance of the output
+    /// * `operation` - the name of the operation, for the notification of a loss of precision
+    ///
+    /// # Output
+    /// * the output encoder
+    fn get_pbs_output_encoder(
+        bsk: &crate::LWEBSK,
+        encoder_output: &crate::Encoder,
+        nb_bit_precision: usize,
+        variance: f64,
+        operation: &str,
+    ) -> Result<crate::Encoder, CryptoAPIError> {
+        let mut new_encoder_output: crate::Encoder = encoder_output.clone();
+        new_encoder_output.nb_bit_precision = nb_bit_precision;
+
+        // the output noise overlaps the message
+        new_encoder_output.update_precision_from_variance(variance)?;
+
+        // the drift, the input keeping one bit of padding
+        bsk.update_precision_from_drift(&mut new_encoder_output, 1);
+
+        // notification of a problem
+        let nb_bit_loss = encoder_output.nb_bit_precision - new_encoder_output.nb_bit_precision;
+        if nb_bit_loss > 0 {
+            println!(
+                "{}: {} bit(s) of precision lost over {} bit(s) of message originally. Consider increasing the number of levels of the keys and the polynomial size of the RLWE secret key.",
+                format!("Loss of precision during {}", operation).red().bold(),
+                nb_bit_loss, encoder_output.nb_bit_precision
+            );
+        }
+
+        Ok(new_encoder_output)
+    }
+
+    /// Compute a circuit bootstrap: convert an LWE ciphertext of a bit into a GGSW ciphertext of the same bit
+    /// Each level of the output GGSW needs one bootstrap and (dimension + 1) private functional key switches
+    ///
+    /// # Argument
+    /// * `bsk` - the bootstrapping key
+    /// * `pfksk` - the private functional key switching keys, from the output LWE key of `bsk` to the output RLWE key
+    /// * `base_log` - the log2 of the decomposition base of the output GGSW
+    /// * `level` - the number of levels of the decomposition of the output GGSW
+    ///
+    /// # Output
+    /// * a GGSW struct
+    /// * DimensionError - if the bootstrapping key, the key switching keys and the input ciphertext have incompatible dimensions
+    /// * KeyMismatch - if the bootstrapping key, the key switching keys and the input ciphertext have incompatible keys
+    /// * NotEnoughPaddingError - if the input ciphertext has no bit of padding
+    /// * PrecisionError - if the input ciphertext has no bit of precision
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// // generate secret keys
+    /// let rlwe_secret_key = RLWESecretKey::new(&RLWE128_1024_1);
+    /// let secret_key_before = LWESecretKey::new(&LWE128_630);
+    /// let secret_key_after = rlwe_secret_key.to_lwe_secret_key();
+    ///
+    /// // bootstrapping key and private functional key switching keys
+    /// let bsk = LWEBSK::new(&secret_key_before, &rlwe_secret_key, 5, 3);
+    /// let pfksk = LWEPFKSK::new(&secret_key_after, &rlwe_secret_key, 10, 3);
+    ///
+    /// // encode and encrypt a bit
+    /// let encoder = Encoder::new(0., 1., 1, 1).unwrap();
+    /// let ciphertext = LWE::encode_encrypt(&secret_key_before, 1., &encoder).unwrap();
+    ///
+    /// // circuit bootstrap
+    /// let ggsw = ciphertext.circuit_bootstrap(&bsk, &pfksk, 6, 4).unwrap();
+    /// ```
+    pub fn circuit_bootstrap(
+        &self,
+        bsk: &crate::LWEBSK,
+        pfksk: &crate::LWEPFKSK,
+        base_log: usize,
+        level: usize,
+    ) -> Result<crate::GGSW, CryptoAPIError> {
+        // check bsk and pfksk compatibility
+        if self.dimension != bsk.get_lwe_dimension() {
+            return Err(DimensionError!(self.dimension, bsk.get_lwe_dimension()));
+        } else if pfksk.dimension_before != bsk.dimension * bsk.polynomial_size {
+            return Err(DimensionError!(
+                pfksk.dimension_before,
+                bsk.dimension * bsk.polynomial_size
+            ));
+        }
+        bsk.output_fingerprint.check(pfksk.fingerprint_before)?;
+
+        let mut res = crate::GGSW::zero(
+            pfksk.polynomial_size,
+            pfksk.dimension_after,
+            base_log,
+            level,
+        )?;
+        res.kind = pfksk.kind_after;
+        res.fingerprint = pfksk.fingerprint_after;
+        let ct_size = (res.dimension + 1) * res.polynomial_size;
+
+        // variance of the output of the bootstraps
+        let var_bootstrap: f64 = bsk.get_output_variance();
+
+        for (j, mut rows) in res
+            .ciphertexts
+            .as_mut_tensor()
+            .subtensor_iter_mut((res.dimension + 1) * ct_size)
+            .enumerate()
+        {
+            // the encoder whose encoding of 1 is q / B^(j+1)
+            let encoder_level = crate::Encoder {
+                o: 0.,
+                delta: f64::powi(2., (base_log * (j + 1)) as i32),
+                nb_bit_precision: base_log * (j + 1),
+                nb_bit_padding: 0,
+                round: false,
+            };
+
+            // bootstrap to get an LWE of b * q / B^(j+1)
+            let lut =
+                bsk.generate_functional_look_up_table(&self.encoder, &encoder_level, |x| {
+                    if x >= 0.5 {
+                        1.
+                    } else {
+                        0.
+                    }
+                })?;
+            let lwe = self.bootstrap_with_look_up_table_raw(bsk, &lut)?;
+
+            // private functional key switches to get the rows of the level
+            for (i, mut row) in rows.subtensor_iter_mut(ct_size).enumerate() {
+                pfksk.private_functional_keyswitch_raw(
+                    i,
+                    row.as_mut_slice(),
+                    lwe.as_tensor().as_slice(),
+                );
+            }
+        }
+
+        // the noise of the mask rows is bigger than the one of the body row
+        res.variance = pfksk.get_output_variance(0, var_bootstrap);
+
+        Ok(res)
+    }
+}
+
+impl<T: GenericTorus> GenericLWE<T> {
+    /// Compute a bootstrap with a look up table built by one of the LWEBSK look up table generators
+    /// Every bit of padding but one is removed before the bootstrap
+    ///
+    /// # Argument
+    /// * `bsk` - the bootstrapping key
+    /// * `lut` - the look up table
+    ///
+    /// # Output
+    /// * the raw output LWE ciphertext, under the RLWE secret key of `bsk`
+    pub(crate) fn bootstrap_with_look_up_table_raw(
+        &self,
+        bsk: &crate::GenericLWEBSK<T>,
+        lut: &[T],
+    ) -> Result<LweCiphertext<Vec<T>>, CryptoAPIError> {
+        let (input, accumulator) = self.get_bootstrap_input(bsk, lut)?;
+
+        // allocate the result
+        let mut result =
+            LweCiphertext::allocate(T::ZERO, LweSize(bsk.dimension * bsk.polynomial_size + 1));
+
+        // compute the bootstrap
+        bsk.bootstrap_raw(&mut result, &input, &accumulator);
+
+        Ok(result)
+    }
+
+    /// Prepare the input of a bootstrap with a look up table built by one of the LWEBSK look up
+    /// table generators: every bit of padding but one is removed and the trivial accumulator is built
+    ///
+    /// # Argument
+    /// * `bsk` - the bootstrapping key
+    /// * `lut` - the look up table
+    ///
+    /// # Output
+    /// * the raw input LWE ciphertext and the accumulator
+    pub(crate) fn get_bootstrap_input(
+        &self,
+        bsk: &crate::GenericLWEBSK<T>,
+        lut: &[T],
+    ) -> Result<(Vec<T>, GlweCiphertext<Vec<T>>), CryptoAPIError> {
+        // check bsk compatibility
+        if self.dimension != bsk.get_lwe_dimension() {
+            return Err(DimensionError!(self.dimension, bsk.get_lwe_dimension()));
+        }
+
+        // check the keys
+        self.key_fingerprint.check(bsk.input_fingerprint)?;
+
+        // build the trivial accumulator
+        let mut accumulator = GlweCiphertext::allocate(
+            T::ZERO,
+            PolynomialSize(bsk.polynomial_size),
+            GlweSize(bsk.dimension + 1),
+        );
+        accumulator
+            .as_mut_tensor()
+            .as_mut_slice()
+            .get_mut(
+                (bsk.dimension * bsk.polynomial_size)..((bsk.dimension + 1) * bsk.polynomial_size),
+            )
+            .unwrap()
+            .copy_from_slice(lut);
+
+        if self.encoder.nb_bit_padding > 1 {
+            // remove the padding but one bit
+            let mut self_clone = self.clone();
+            self_clone.remove_padding_inplace(self.encoder.nb_bit_padding - 1)?;
+            Ok((
+                self_clone.ciphertext.as_tensor().as_slice().to_vec(),
+                accumulator,
+            ))
+        } else {
+            Ok((self.ciphertext.as_tensor().as_slice().to_vec(), accumulator))
+        }
+    }
 
     /// Multiply two LWE ciphertexts thanks to two bootstrapping procedures
     /// need to have 2 bits of padding at least
@@ -1945,9 +2528,9 @@ impl LWE {
     /// ```
     pub fn mul_from_bootstrap(
         &self,
-        ct: &crate::LWE,
-        bsk: &crate::LWEBSK,
-    ) -> Result<crate::LWE, CryptoAPIError> {
+        ct: &GenericLWE<T>,
+        bsk: &crate::GenericLWEBSK<T>,
+    ) -> Result<GenericLWE<T>, CryptoAPIError> {
         // clone twice from self
         let mut ct1 = self.clone();
         let mut ct2 = self.clone();
@@ -1984,7 +2567,295 @@ impl LWE {
 
         Ok(square1)
     }
+}
+
+impl LWE {
+    /// Compute the sign of an LWE ciphertext with a bootstrap
+    /// The output encrypts 1 if the message is non negative and -1 otherwise,
+    /// with an Encoder over [-1, 1] with 1 bit of precision and the padding of the input
+    ///
+    /// # Argument
+    /// * `bsk` - the bootstrapping key
+    ///
+    /// # Output
+    /// * an LWE struct
+    /// * DimensionError - if the bootstrapping key and the input ciphertext have incompatible dimensions
+    /// * KeyMismatch - if the input ciphertext was not encrypted under the input key of the bootstrapping key
+    /// * NotEnoughPaddingError - if the input ciphertext has no bit of padding
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// // encoder
+    /// let encoder = Encoder::new(-10., 10., 4, 1).unwrap();
+    ///
+    /// // generate secret keys
+    /// let rlwe_secret_key = RLWESecretKey::new(&RLWE128_1024_1);
+    /// let secret_key_before = LWESecretKey::new(&LWE128_630);
+    /// let secret_key_after = rlwe_secret_key.to_lwe_secret_key();
+    ///
+    /// // bootstrapping key
+    /// let bootstrapping_key = LWEBSK::new(&secret_key_before, &rlwe_secret_key, 5, 3);
+    ///
+    /// // encode and encrypt
+    /// let ciphertext = LWE::encode_encrypt(&secret_key_before, -3.2, &encoder).unwrap();
+    ///
+    /// // sign
+    /// let sign = ciphertext.sign(&bootstrapping_key).unwrap();
+    /// ```
+    pub fn sign(&self, bsk: &crate::LWEBSK) -> Result<crate::LWE, CryptoAPIError> {
+        let encoder_output = crate::Encoder::new(-1., 1., 1, self.encoder.nb_bit_padding)?;
+        self.bootstrap_with_function(bsk, |x| if x >= 0. { 1. } else { -1. }, &encoder_output)
+    }
+
+    /// Compare two LWE ciphertexts with a bootstrap
+    /// The output encrypts 1 if the first message is greater than the second one and 0 otherwise,
+    /// with an Encoder over [0, 1] with 1 bit of precision and the padding of the inputs
+    /// Messages closer than half the granularity of the Encoders are considered equal
+    /// The Encoders are aligned beforehand by consuming bits of padding
+    ///
+    /// # Argument
+    /// * `ct` - an LWE struct whose delta is the one of self times a power of two
+    /// * `bsk` - the bootstrapping key
+    ///
+    /// # Output
+    /// * an LWE struct
+    /// * DimensionError - if the ciphertexts or the bootstrapping key have incompatible dimensions
+    /// * DeltaError - if the ratio of the deltas is not a power of two
+    /// * NotEnoughPaddingError - if less than 2 bits of padding remain once the encoders are aligned
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// // encoders with the same delta
+    /// let encoder_1 = Encoder::new(-10., 10., 4, 2).unwrap();
+    /// let encoder_2 = Encoder::new(0., 20., 4, 2).unwrap();
+    ///
+    /// // generate secret keys
+    /// let rlwe_secret_key = RLWESecretKey::new(&RLWE128_1024_1);
+    /// let secret_key_before = LWESecretKey::new(&LWE128_630);
+    /// let secret_key_after = rlwe_secret_key.to_lwe_secret_key();
+    ///
+    /// // bootstrapping key
+    /// let bootstrapping_key = LWEBSK::new(&secret_key_before, &rlwe_secret_key, 5, 3);
+    ///
+    /// // encode and encrypt
+    /// let ciphertext_1 = LWE::encode_encrypt(&secret_key_before, 7.3, &encoder_1).unwrap();
+    /// let ciphertext_2 = LWE::encode_encrypt(&secret_key_before, 2.5, &encoder_2).unwrap();
+    ///
+    /// // comparison
+    /// let res = ciphertext_1
+    ///     .greater_than(&ciphertext_2, &bootstrapping_key)
+    ///     .unwrap();
+    /// ```
+    pub fn greater_than(
+        &self,
+        ct: &crate::LWE,
+        bsk: &crate::LWEBSK,
+    ) -> Result<crate::LWE, CryptoAPIError> {
+        // compute the difference
+        let difference = self.sub_for_comparison(ct)?;
+        let threshold: f64 = difference.encoder.get_granularity() / 2.;
+
+        // bootstrap
+        let encoder_output = crate::Encoder::new(0., 1., 1, self.encoder.nb_bit_padding)?;
+        difference.bootstrap_with_function(
+            bsk,
+            |x| if x > threshold { 1. } else { 0. },
+            &encoder_output,
+        )
+    }
+
+    /// Compute the maximum of two LWE ciphertexts with a bootstrap
+    /// It computes ct + max(self - ct, 0) with two bootstraps and the output Encoder is the one of ct with
+    /// the greatest of the two mins as new min and the largest of the two deltas
+    ///
+    /// # Argument
+    /// * `ct` - an LWE struct whose delta is the one of self times a power of two
+    /// * `bsk` - the bootstrapping key
+    ///
+    /// # Output
+    /// * an LWE struct
+    /// * DimensionError - if the ciphertexts or the bootstrapping key have incompatible dimensions
+    /// * DeltaError - if the ratio of the deltas is not a power of two
+    /// * NotEnoughPaddingError - if less than 2 bits of padding remain once the encoders are aligned
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// // encoders with the same delta
+    /// let encoder_1 = Encoder::new(-10., 10., 4, 2).unwrap();
+    /// let encoder_2 = Encoder::new(0., 20., 4, 2).unwrap();
+    ///
+    /// // generate secret keys
+    /// let rlwe_secret_key = RLWESecretKey::new(&RLWE128_1024_1);
+    /// let secret_key_before = LWESecretKey::new(&LWE128_630);
+    /// let secret_key_after = rlwe_secret_key.to_lwe_secret_key();
+    ///
+    /// // bootstrapping key
+    /// let bootstrapping_key = LWEBSK::new(&secret_key_before, &rlwe_secret_key, 5, 3);
+    ///
+    /// // encode and encrypt
+    /// let ciphertext_1 = LWE::encode_encrypt(&secret_key_before, 7.3, &encoder_1).unwrap();
+    /// let ciphertext_2 = LWE::encode_encrypt(&secret_key_before, 2.5, &encoder_2).unwrap();
+    ///
+    /// // maximum
+    /// let res = ciphertext_1
+    ///     .max_with(&ciphertext_2, &bootstrapping_key)
+    ///     .unwrap();
+    /// ```
+    pub fn max_with(
+        &self,
+        ct: &crate::LWE,
+        bsk: &crate::LWEBSK,
+    ) -> Result<crate::LWE, CryptoAPIError> {
+        let new_min: f64 = f64::max(self.encoder.o, ct.encoder.o);
+        self.add_bootstrapped_difference(ct, bsk, |x| f64::max(x, 0.), new_min)
+    }
+
+    /// Compute the minimum of two LWE ciphertexts with a bootstrap
+    /// It computes ct + min(self - ct, 0) with two bootstraps and the output Encoder is the one of ct with
+    /// the smallest of the two mins as new min and the largest of the two deltas
+    ///
+    /// # Argument
+    /// * `ct` - an LWE struct whose delta is the one of self times a power of two
+    /// * `bsk` - the bootstrapping key
+    ///
+    /// # Output
+    /// * an LWE struct
+    /// * DimensionError - if the ciphertexts or the bootstrapping key have incompatible dimensions
+    /// * DeltaError - if the ratio of the deltas is not a power of two
+    /// * NotEnoughPaddingError - if less than 2 bits of padding remain once the encoders are aligned
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// // encoders with the same delta
+    /// let encoder_1 = Encoder::new(-10., 10., 4, 2).unwrap();
+    /// let encoder_2 = Encoder::new(0., 20., 4, 2).unwrap();
+    ///
+    /// // generate secret keys
+    /// let rlwe_secret_key = RLWESecretKey::new(&RLWE128_1024_1);
+    /// let secret_key_before = LWESecretKey::new(&LWE128_630);
+    /// let secret_key_after = rlwe_secret_key.to_lwe_secret_key();
+    ///
+    /// // bootstrapping key
+    /// let bootstrapping_key = LWEBSK::new(&secret_key_before, &rlwe_secret_key, 5, 3);
+    ///
+    /// // encode and encrypt
+    /// let ciphertext_1 = LWE::encode_encrypt(&secret_key_before, 7.3, &encoder_1).unwrap();
+    /// let ciphertext_2 = LWE::encode_encrypt(&secret_key_before, 2.5, &encoder_2).unwrap();
+    ///
+    /// // minimum
+    /// let res = ciphertext_1
+    ///     .min_with(&ciphertext_2, &bootstrapping_key)
+    ///     .unwrap();
+    /// ```
+    pub fn min_with(
+        &self,
+        ct: &crate::LWE,
+        bsk: &crate::LWEBSK,
+    ) -> Result<crate::LWE, CryptoAPIError> {
+        let new_min: f64 = f64::min(self.encoder.o, ct.encoder.o);
+        self.add_bootstrapped_difference(ct, bsk, |x| f64::min(x, 0.), new_min)
+    }
+
+    /// Align the Encoders of self and ct for the comparisons without any bootstrap
+    /// The operand with the smallest delta is reinterpreted with the other delta by consuming bits
+    /// of padding, so the ratio of the deltas has to be a power of two, then the operand with the
+    /// most padding is multiplied by a power of two so that both operands have the same padding
+    fn align_for_comparison(
+        &self,
+        ct: &crate::LWE,
+    ) -> Result<(crate::LWE, crate::LWE), CryptoAPIError> {
+        let mut lhs = self.clone();
+        let mut rhs = ct.clone();
+
+        // same deltas: [o, o + delta[ is seen as [o, o + delta * 2^k[ with k bits of padding less
+        {
+            let (small, large) = if lhs.encoder.delta <= rhs.encoder.delta {
+                (&mut lhs, &rhs)
+            } else {
+                (&mut rhs, &lhs)
+            };
+            let log_ratio: i32 = (large.encoder.delta / small.encoder.delta).log2().round() as i32;
+            if !deltas_eq!(
+                small.encoder.delta * f64::powi(2., log_ratio),
+                large.encoder.delta
+            ) {
+                return Err(DeltaError!(self.encoder.delta, ct.encoder.delta));
+            }
+            let k = log_ratio as usize;
+            if small.encoder.nb_bit_padding < k {
+                return Err(NotEnoughPaddingError!(small.encoder.nb_bit_padding, k));
+            }
+            small.encoder.delta = large.encoder.delta;
+            small.encoder.nb_bit_padding -= k;
+            small.encoder.nb_bit_precision += k;
+        }
+
+        // same paddings
+        if lhs.encoder.nb_bit_padding > rhs.encoder.nb_bit_padding {
+            lhs.remove_padding_inplace(lhs.encoder.nb_bit_padding - rhs.encoder.nb_bit_padding)?;
+        } else if rhs.encoder.nb_bit_padding > lhs.encoder.nb_bit_padding {
+            rhs.remove_padding_inplace(rhs.encoder.nb_bit_padding - lhs.encoder.nb_bit_padding)?;
+        }
+
+        Ok((lhs, rhs))
+    }
+
+    /// Compute the exact difference self - ct used by the comparisons
+    /// It eats one bit of padding, so two bits of padding are needed to bootstrap the result
+    fn sub_for_comparison(&self, ct: &crate::LWE) -> Result<crate::LWE, CryptoAPIError> {
+        let (lhs, rhs) = self.align_for_comparison(ct)?;
+
+        // check that a bit of padding remains after the subtraction for both operands
+        for padding in [lhs.encoder.nb_bit_padding, rhs.encoder.nb_bit_padding].iter() {
+            if *padding < 2 {
+                return Err(NotEnoughPaddingError!(*padding, 2));
+            }
+        }
+        lhs.sub_with_padding_exact(&rhs)
+    }
+
+    /// Compute ct + f(self - ct) where f is evaluated with a bootstrap
+    /// ct is also bootstrapped (with the identity) so that the output is under the output key of `bsk`
+    /// The output of f uses the Encoder of ct shifted so that the sum has `new_min` as min
+    fn add_bootstrapped_difference<F: Fn(f64) -> f64>(
+        &self,
+        ct: &crate::LWE,
+        bsk: &crate::LWEBSK,
+        f: F,
+        new_min: f64,
+    ) -> Result<crate::LWE, CryptoAPIError> {
+        // compute the difference
+        let difference = self.sub_for_comparison(ct)?;
+
+        // the Encoder of ct with the largest of the two deltas
+        let mut encoder_base = ct.encoder.clone();
+        if self.encoder.delta > ct.encoder.delta {
+            encoder_base.delta = self.encoder.delta;
+            encoder_base.nb_bit_precision = self.encoder.nb_bit_precision;
+        }
+
+        // bootstrap with an Encoder aligned with the one of ct
+        let mut encoder_output = encoder_base.clone();
+        encoder_output.o = new_min - ct.encoder.o;
+        let correction = difference.bootstrap_with_function(bsk, f, &encoder_output)?;
+
+        // bootstrap ct with the identity so that both ciphertexts are under the same key
+        let ct_bootstrapped = ct.bootstrap_with_function(bsk, |x| x, &encoder_base)?;
+
+        // add them
+        ct_bootstrapped.add_with_new_min(&correction, new_min)
+    }
+}
 
+impl<T: GenericTorus> GenericLWE<T> {
     /// Return the size of one LWE ciphertext with the parameters of self
     ///
     /// # Output
@@ -1997,7 +2868,7 @@ impl LWE {
         write_to_file(path, self)
     }
 
-    pub fn load(path: &str) -> Result<LWE, Box<dyn Error>> {
+    pub fn load(path: &str) -> Result<GenericLWE<T>, Box<dyn Error>> {
         read_from_file(path)
     }
 
@@ -2037,8 +2908,8 @@ impl LWE {
         self.encoder.nb_bit_padding -= nb;
 
         // call to the NPE to estimate the new variance
-        let coeff: Torus = 1 << nb;
-        self.variance = npe::LWE::single_scalar_mul(self.variance, coeff);
+        let coeff: T = T::ONE << nb;
+        self.variance = <T as npe::LWE>::single_scalar_mul(self.variance, coeff);
 
         // update the encoder precision based on the variance
         self.encoder.update_precision_from_variance(self.variance)?;
@@ -2048,7 +2919,7 @@ impl LWE {
 }
 
 /// Print needed pieces of information about an LWE
-impl fmt::Display for LWE {
+impl<T: GenericTorus> fmt::Display for GenericLWE<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let n = 2;
 
@@ -1,6 +1,8 @@
 #![allow(clippy::modulo_one)]
 
 use crate::traits::GenericAdd;
+use concrete_core::math::tensor::{AsRefSlice, AsRefTensor};
+use itertools::izip;
 
 #[test]
 fn test_encode_encrypt_x_decrypt() {
@@ -480,6 +482,82 @@ fn test_encode_encrypt_x_mul_constant_with_padding_inplace_x_decrypt() {
     }
 }
 
+#[test]
+fn test_encode_encrypt_x_mul_real_x_decrypt() {
+    // random settings
+    let (min, max) = generate_random_interval!();
+    let precision: usize = random_index!(4) + 3;
+    let padding = 12;
+
+    // encoders
+    let encoder = crate::Encoder::new(min, max, precision, padding).unwrap();
+
+    // generate a secret key
+    let secret_key = crate::LWESecretKey::new(&crate::LWE128_1024);
+
+    // constants that are not exactly representable and whose magnitude varies
+    let max_message = f64::max(min.abs(), max.abs());
+    let constants: Vec<f64> = vec![
+        0.37,
+        1. / 3.,
+        -std::f64::consts::E,
+        random_message!(-100., 100.),
+        encoder.delta / max_message,
+    ];
+
+    for c in constants.iter() {
+        let message: f64 = random_message!(min, max);
+
+        // encode and encrypt
+        let ciphertext = crate::LWE::encode_encrypt(&secret_key, message, &encoder).unwrap();
+
+        // multiplication with a real constant
+        let res = ciphertext.mul_real(*c, 8).unwrap();
+        assert!(res.encoder.nb_bit_padding >= padding - 9);
+
+        // decryption
+        let decryption: f64 = res.decrypt_decode(&secret_key).unwrap();
+        assert_eq_granularity!(message * c, decryption, res.encoder);
+    }
+
+    // the constant is too big for the encoder
+    let ciphertext = crate::LWE::encode_encrypt(&secret_key, min, &encoder).unwrap();
+    let c = encoder.delta * f64::powi(2., padding as i32 + 1) / max_message;
+    assert!(ciphertext.mul_real(c, 8).is_err());
+    assert!(ciphertext.mul_real(0.5, 0).is_err());
+}
+
+#[test]
+fn test_encode_encrypt_x_integer_arithmetic_x_decrypt() {
+    // generate a secret key
+    let secret_key = crate::LWESecretKey::new(&crate::LWE128_1024);
+
+    for signed in [false, true].iter() {
+        let encoder = crate::Encoder::new_integer(4, *signed, 0).unwrap();
+        let min: i32 = if *signed { -8 } else { 0 };
+
+        for _ in 0..20 {
+            let m1: i32 = random_index!(16) as i32 + min;
+            let m2: i32 = random_index!(16) as i32 + min;
+            let c: i32 = random_index!(7) as i32 - 3;
+
+            // encode and encrypt
+            let ct1 = crate::LWE::encode_encrypt(&secret_key, m1 as f64, &encoder).unwrap();
+            let ct2 = crate::LWE::encode_encrypt(&secret_key, m2 as f64, &encoder).unwrap();
+
+            // addition and multiplication by a constant wrap modulo 16
+            let sum = ct1.add_with_new_min(&ct2, min as f64).unwrap();
+            let product = ct1.mul_constant_static_encoder(c).unwrap();
+
+            // decryption gives exact integers
+            let d_sum = sum.decrypt_decode_round(&secret_key).unwrap();
+            let d_product = product.decrypt_decode_round(&secret_key).unwrap();
+            assert_eq!(((m1 + m2 - min).rem_euclid(16) + min) as f64, d_sum);
+            assert_eq!(((m1 * c - min).rem_euclid(16) + min) as f64, d_product);
+        }
+    }
+}
+
 #[test]
 #[ignore]
 fn test_encode_encrypt_x_keyswitch_x_decrypt() {
@@ -558,6 +636,382 @@ fn test_encode_encrypt_x_bootstrap_x_decrypt() {
     }
 }
 
+#[test]
+fn test_encode_encrypt_x_bootstrap_x_keyswitch_x_decrypt_u32() {
+    // settings
+    let (min, max) = (0., 7.);
+    let padding: usize = 1;
+    let precision: usize = 3;
+    let base_log: usize = 7;
+    let level: usize = 3;
+
+    // encoder
+    let encoder = crate::Encoder::new(min, max, precision, padding).unwrap();
+
+    // secret keys
+    let rlwe_secret_key = crate::RLWESecretKey::new(&crate::RLWE128_1024_1);
+    let secret_key_input = crate::LWESecretKey::new(&crate::LWE128_630);
+    let secret_key_output = rlwe_secret_key.to_lwe_secret_key();
+
+    // keys on 32 bits
+    let bsk = crate::LWEBSK32::new(&secret_key_input, &rlwe_secret_key, base_log, level);
+    let ksk = crate::LWEKSK32::new(&secret_key_output, &secret_key_input, 2, 7);
+
+    for _ in 0..50 {
+        // a random message
+        let message: f64 = random_message!(min, max);
+
+        // encode and encrypt
+        let ciphertext =
+            crate::LWE32::encode_encrypt(&secret_key_input, message, &encoder).unwrap();
+        let decryption = ciphertext.decrypt_decode(&secret_key_input).unwrap();
+        assert_eq_granularity!(message, decryption, ciphertext.encoder);
+
+        // bootstrap
+        let ciphertext_output = ciphertext.bootstrap(&bsk).unwrap();
+        let decryption = ciphertext_output
+            .decrypt_decode(&secret_key_output)
+            .unwrap();
+        assert_eq_granularity!(message, decryption, ciphertext_output.encoder);
+
+        // key switch
+        let ciphertext_back = ciphertext_output.keyswitch(&ksk).unwrap();
+        let decryption = ciphertext_back.decrypt_decode(&secret_key_input).unwrap();
+        assert_eq_granularity!(message, decryption, ciphertext_back.encoder);
+    }
+
+    // the 32-bit ciphertexts are half the size of the 64-bit ones
+    let ciphertext = crate::LWE32::encode_encrypt(&secret_key_input, 1., &encoder).unwrap();
+    let ciphertext_64 = crate::LWE::encode_encrypt(&secret_key_input, 1., &encoder).unwrap();
+    assert_eq!(
+        2 * std::mem::size_of_val(ciphertext.ciphertext.as_tensor().as_slice()),
+        std::mem::size_of_val(ciphertext_64.ciphertext.as_tensor().as_slice())
+    );
+}
+
+#[test]
+fn test_encode_encrypt_x_operators_x_decrypt_u32() {
+    // encoder
+    let encoder = crate::Encoder::new(-10., 10., 5, 4).unwrap();
+
+    // generate a secret key
+    let secret_key = crate::LWESecretKey::new(&crate::LWE128_630);
+
+    for _ in 0..20 {
+        // two random messages whose sum stays in the interval
+        let message_1: f64 = random_message!(-5., 5.);
+        let message_2: f64 = random_message!(-5., 5.);
+
+        // encode and encrypt
+        let ciphertext_1 =
+            crate::LWE32::encode_encrypt(&secret_key, message_1, &encoder).unwrap();
+        let ciphertext_2 =
+            crate::LWE32::encode_encrypt(&secret_key, message_2, &encoder).unwrap();
+
+        // additions and subtractions
+        let ct = ciphertext_1.add_with_padding(&ciphertext_2).unwrap();
+        let decryption = ct.decrypt_decode(&secret_key).unwrap();
+        assert_eq_granularity!(message_1 + message_2, decryption, ct.encoder);
+
+        let ct = ciphertext_1.add_centered(&ciphertext_2).unwrap();
+        let decryption = ct.decrypt_decode(&secret_key).unwrap();
+        assert_eq_granularity!(message_1 + message_2, decryption, ct.encoder);
+
+        let ct = ciphertext_1.sub_with_padding(&ciphertext_2).unwrap();
+        let decryption = ct.decrypt_decode(&secret_key).unwrap();
+        assert_eq_granularity!(message_1 - message_2, decryption, ct.encoder);
+
+        let ct = ciphertext_1.add_constant_static_encoder(1.5).unwrap();
+        let decryption = ct.decrypt_decode(&secret_key).unwrap();
+        assert_eq_granularity!(message_1 + 1.5, decryption, ct.encoder);
+
+        // multiplications by constants
+        let ct = ciphertext_1.mul_constant_static_encoder(-2).unwrap();
+        let decryption = ct.decrypt_decode(&secret_key).unwrap();
+        assert_eq_granularity!(-2. * message_1, decryption, ct.encoder);
+
+        let ct = ciphertext_1.mul_constant_with_padding(-0.75, 1., 3).unwrap();
+        let decryption = ct.decrypt_decode(&secret_key).unwrap();
+        assert_eq_granularity!(-0.75 * message_1, decryption, ct.encoder);
+
+        // opposite
+        let ct = ciphertext_1.opposite().unwrap();
+        let decryption = ct.decrypt_decode(&secret_key).unwrap();
+        assert_eq_granularity!(-message_1, decryption, ct.encoder);
+    }
+}
+
+#[test]
+fn test_encode_encrypt_x_mul_from_bootstrap_x_decrypt_u32() {
+    // settings
+    let (min_1, max_1): (f64, f64) = (-15., 15.);
+    let (min_2, max_2): (f64, f64) = (5., 35.);
+    let (precision, padding) = (4, 2);
+    let base_log: usize = 6;
+    let level: usize = 3;
+
+    // encoders
+    let encoder_1 = crate::Encoder::new(min_1, max_1, precision, padding).unwrap();
+    let encoder_2 = crate::Encoder::new(min_2, max_2, precision, padding).unwrap();
+
+    // secret keys
+    let rlwe_secret_key = crate::RLWESecretKey::new(&crate::RLWE128_1024_1);
+    let secret_key_input = crate::LWESecretKey::new(&crate::LWE128_630);
+    let secret_key_output = rlwe_secret_key.to_lwe_secret_key();
+
+    // bootstrapping key on 32 bits
+    let bsk = crate::LWEBSK32::new(&secret_key_input, &rlwe_secret_key, base_log, level);
+
+    for _ in 0..10 {
+        // two random messages
+        let message_1: f64 = random_message!(min_1, max_1);
+        let message_2: f64 = random_message!(min_2, max_2);
+
+        // encode and encrypt
+        let ciphertext_1 =
+            crate::LWE32::encode_encrypt(&secret_key_input, message_1, &encoder_1).unwrap();
+        let ciphertext_2 =
+            crate::LWE32::encode_encrypt(&secret_key_input, message_2, &encoder_2).unwrap();
+
+        // multiplication
+        let ciphertext_res = ciphertext_1.mul_from_bootstrap(&ciphertext_2, &bsk).unwrap();
+
+        // decrypt
+        let decryption = ciphertext_res.decrypt_decode(&secret_key_output).unwrap();
+        assert_eq_granularity!(message_1 * message_2, decryption, ciphertext_res.encoder);
+    }
+}
+
+#[test]
+fn test_encrypt_u32_no_noise() {
+    let encoder = crate::Encoder::new(0., 1., 4, 1).unwrap();
+    let secret_key = crate::LWESecretKey::new(&crate::LWEParams::new(630, -40));
+    assert!(crate::LWE32::encode_encrypt(&secret_key, 0.5, &encoder).is_err());
+    assert!(crate::LWE::encode_encrypt(&secret_key, 0.5, &encoder).is_ok());
+}
+
+#[test]
+fn test_try_new_u32_no_noise() {
+    let secret_key_input = crate::LWESecretKey::new(&crate::LWE128_630);
+    let secret_key_output = crate::LWESecretKey::new(&crate::LWEParams::new(630, -40));
+    let rlwe_secret_key = crate::RLWESecretKey::new(&crate::RLWEParams::new(256, 1, -40).unwrap());
+    assert!(crate::LWEKSK32::try_new(&secret_key_input, &secret_key_output, 2, 6).is_err());
+    assert!(crate::LWEKSK::try_new(&secret_key_input, &secret_key_output, 2, 6).is_ok());
+    assert!(crate::LWEBSK32::try_new(&secret_key_input, &rlwe_secret_key, 6, 2).is_err());
+}
+
+#[test]
+#[should_panic(expected = "does not fit in the torus")]
+fn test_new_u32_no_noise() {
+    let secret_key_input = crate::LWESecretKey::new(&crate::LWE128_630);
+    let rlwe_secret_key = crate::RLWESecretKey::new(&crate::RLWEParams::new(256, 1, -40).unwrap());
+    crate::LWEBSK32::new(&secret_key_input, &rlwe_secret_key, 6, 2);
+}
+
+#[test]
+fn test_new_with_progress_x_bootstrap_x_keyswitch_x_decrypt() {
+    // settings
+    let (min, max) = (0., 7.);
+    let padding: usize = 1;
+    let precision: usize = 3;
+
+    // encoder
+    let encoder = crate::Encoder::new(min, max, precision, padding).unwrap();
+
+    // secret keys
+    let rlwe_secret_key = crate::RLWESecretKey::new(&crate::RLWE128_1024_1);
+    let secret_key_input = crate::LWESecretKey::new(&crate::LWE128_630);
+    let secret_key_output = rlwe_secret_key.to_lwe_secret_key();
+
+    // keys, recording the progress
+    let mut bsk_progress: Vec<f64> = Vec::new();
+    let bsk = crate::LWEBSK::new_with_progress(&secret_key_input, &rlwe_secret_key, 7, 3, |p| {
+        bsk_progress.push(p);
+        true
+    })
+    .unwrap();
+    let mut ksk_progress: Vec<f64> = Vec::new();
+    let ksk = crate::LWEKSK::new_with_progress(&secret_key_output, &secret_key_input, 2, 7, |p| {
+        ksk_progress.push(p);
+        true
+    })
+    .unwrap();
+
+    // the progress increases up to 1
+    for progress in [bsk_progress, ksk_progress].iter() {
+        assert!(progress.len() <= crate::utils::NB_PROGRESS_STEPS);
+        assert!(progress.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(*progress.last().unwrap(), 1.);
+    }
+
+    for _ in 0..20 {
+        // a random message
+        let message: f64 = random_message!(min, max);
+
+        // encode, encrypt, bootstrap and key switch
+        let ciphertext = crate::LWE::encode_encrypt(&secret_key_input, message, &encoder)
+            .unwrap()
+            .bootstrap(&bsk)
+            .unwrap()
+            .keyswitch(&ksk)
+            .unwrap();
+
+        // decrypt
+        let decryption = ciphertext.decrypt_decode(&secret_key_input).unwrap();
+        assert_eq_granularity!(message, decryption, ciphertext.encoder);
+    }
+
+    // a cancelled generation stops at the first report
+    let mut nb_reports: usize = 0;
+    let cancelled =
+        crate::LWEKSK::new_with_progress(&secret_key_output, &secret_key_input, 2, 7, |_| {
+            nb_reports += 1;
+            false
+        });
+    assert!(cancelled.is_err());
+    assert_eq!(nb_reports, 1);
+
+    // the last report, once the key is complete, does not cancel the generation
+    let complete =
+        crate::LWEKSK::new_with_progress(&secret_key_output, &secret_key_input, 2, 7, |p| p < 1.);
+    assert!(complete.is_ok());
+}
+
+#[test]
+fn test_encode_encrypt_x_decrypt_secret_key_kinds() {
+    // random settings
+    let (min, max) = generate_random_interval!();
+    let (precision, padding) = generate_precision_padding!(8, 8);
+
+    // encoder
+    let encoder = crate::Encoder::new(min, max, precision, padding).unwrap();
+
+    let kinds = [
+        crate::SecretKeyKind::Ternary,
+        crate::SecretKeyKind::Gaussian,
+    ];
+    for kind in kinds.iter() {
+        // generate a secret key
+        let secret_key = crate::LWESecretKey::new_with_kind(&crate::LWE128_1024, *kind);
+        assert_eq!(secret_key.kind, *kind);
+
+        for _ in 0..100 {
+            // a message
+            let message: f64 = random_message!(min, max);
+
+            // encode and encrypt
+            let ciphertext = crate::LWE::encode_encrypt(&secret_key, message, &encoder).unwrap();
+
+            // decryption
+            let decryption: f64 = ciphertext.decrypt_decode(&secret_key).unwrap();
+
+            // test
+            assert_eq_granularity!(message, decryption, ciphertext.encoder);
+        }
+    }
+}
+
+#[test]
+fn test_encode_encrypt_x_keyswitch_x_decrypt_secret_key_kinds() {
+    // random settings
+    let (min, max) = generate_random_interval!();
+    let (precision, padding) = generate_precision_padding!(4, 1);
+    let base_log = 9;
+    let level = 7;
+
+    // encoder
+    let encoder = crate::Encoder::new(min, max, precision, padding).unwrap();
+
+    // generate a ternary and a gaussian secret keys
+    let secret_key_before =
+        crate::LWESecretKey::new_with_kind(&crate::LWE128_1024, crate::SecretKeyKind::Ternary);
+    let secret_key_after =
+        crate::LWESecretKey::new_with_kind(&crate::LWE128_800, crate::SecretKeyKind::Gaussian);
+
+    // generate the key switching keys in both directions
+    let ksk = crate::LWEKSK::new(&secret_key_before, &secret_key_after, base_log, level);
+    let ksk_back = crate::LWEKSK::new(&secret_key_after, &secret_key_before, base_log, level);
+
+    for _ in 0..20 {
+        // a random message
+        let message: f64 = random_message!(min, max);
+        let ciphertext_before =
+            crate::LWE::encode_encrypt(&secret_key_before, message, &encoder).unwrap();
+
+        // key switches
+        let ciphertext_after = ciphertext_before.keyswitch(&ksk).unwrap();
+        let ciphertext_back = ciphertext_after.keyswitch(&ksk_back).unwrap();
+
+        // decryption
+        let decryption: f64 = ciphertext_after
+            .decrypt_decode_round(&secret_key_after)
+            .unwrap();
+        let decryption_back: f64 = ciphertext_back
+            .decrypt_decode_round(&secret_key_before)
+            .unwrap();
+
+        // test
+        assert_eq_granularity!(message, decryption, ciphertext_after.encoder);
+        assert_eq_granularity!(message, decryption_back, ciphertext_back.encoder);
+    }
+}
+
+#[test]
+fn test_encode_encrypt_x_bootstrap_x_decrypt_secret_key_kinds() {
+    // settings
+    let (min, max) = (0., 7.);
+    let padding: usize = 1;
+    let precision: usize = 3;
+    let base_log: usize = 7;
+    let level: usize = 3;
+
+    // encoders
+    let encoder_input = crate::Encoder::new(min, max, precision, padding).unwrap();
+
+    // a ternary output secret key
+    let rlwe_secret_key =
+        crate::RLWESecretKey::new_with_kind(&crate::RLWE128_1024_1, crate::SecretKeyKind::Ternary);
+    let secret_key_output = rlwe_secret_key.to_lwe_secret_key();
+    assert_eq!(secret_key_output.kind, crate::SecretKeyKind::Ternary);
+
+    // small ternary and gaussian input secret keys, expanded into binary keys in the bootstrapping keys
+    let kinds = [
+        crate::SecretKeyKind::Ternary,
+        crate::SecretKeyKind::Gaussian,
+    ];
+    for kind in kinds.iter() {
+        let secret_key_input =
+            crate::LWESecretKey::new_raw_with_kind(128, f64::powi(2., -25), *kind);
+
+        // bootstrapping key
+        let bootstrapping_key =
+            crate::LWEBSK::new(&secret_key_input, &rlwe_secret_key, base_log, level);
+        assert_eq!(bootstrapping_key.get_lwe_dimension(), 128);
+        assert_eq!(bootstrapping_key.output_kind, crate::SecretKeyKind::Ternary);
+        if *kind == crate::SecretKeyKind::Ternary {
+            assert_eq!(bootstrapping_key.nb_bit_expansion, 1);
+        }
+
+        for _ in 0..50 {
+            // a random message
+            let message: f64 = random_message!(min, max);
+
+            // encode and encrypt
+            let ciphertext_input =
+                crate::LWE::encode_encrypt(&secret_key_input, message, &encoder_input).unwrap();
+
+            // bootstrap
+            let ciphertext_output = ciphertext_input.bootstrap(&bootstrapping_key).unwrap();
+
+            // decrypt
+            let decryption = ciphertext_output
+                .decrypt_decode(&secret_key_output)
+                .unwrap();
+            assert_eq_granularity!(message, decryption, ciphertext_output.encoder);
+        }
+    }
+}
+
 #[test]
 fn test_encode_encrypt_x_mul_from_bootstrap_x_decrypt() {
     // random settings for the first encoder and some messages
@@ -603,6 +1057,444 @@ fn test_encode_encrypt_x_mul_from_bootstrap_x_decrypt() {
     }
 }
 
+#[test]
+fn test_encode_encrypt_x_add_with_padding_x_reencode_x_decrypt() {
+    // encoders
+    let (min, max) = generate_random_centered_interval!();
+    let encoder = crate::Encoder::new(min, max, 4, 2).unwrap();
+
+    // generate a secret key
+    let rlwe_secret_key = crate::RLWESecretKey::new(&crate::RLWE128_1024_1);
+    let secret_key_input = crate::LWESecretKey::new(&crate::LWE128_630);
+    let secret_key_output = rlwe_secret_key.to_lwe_secret_key();
+
+    // bootstrapping key
+    let bsk = crate::LWEBSK::new(&secret_key_input, &rlwe_secret_key, 5, 3);
+
+    for _ in 0..10 {
+        let message_1: f64 = random_message!(min, max);
+        let message_2: f64 = random_message!(min, max);
+
+        // encode, encrypt and sum: the interval grows and a bit of padding is consumed
+        let ct_1 = crate::LWE::encode_encrypt(&secret_key_input, message_1, &encoder).unwrap();
+        let ct_2 = crate::LWE::encode_encrypt(&secret_key_input, message_2, &encoder).unwrap();
+        let sum = ct_1.add_with_padding(&ct_2).unwrap();
+
+        // shrink the interval back and restore the padding
+        let ct_res = sum.reencode(&bsk, &encoder).unwrap();
+        assert_eq!(ct_res.encoder.nb_bit_padding, 2);
+        assert_eq!(ct_res.encoder.o, encoder.o);
+
+        // decrypt
+        let decryption = ct_res.decrypt_decode(&secret_key_output).unwrap();
+
+        // test with the clamped sum
+        let expected = f64::min(f64::max(message_1 + message_2, min), encoder.get_max());
+        assert!(
+            (expected - decryption).abs() < sum.encoder.get_granularity(),
+            "{} != {}",
+            expected,
+            decryption
+        );
+    }
+}
+
+#[test]
+fn test_encode_encrypt_x_sign_x_decrypt() {
+    // random settings for the encoder, with 0 in the interval
+    let (min, max) = generate_random_centered_interval!();
+    let encoder = crate::Encoder::new(min, max, 4, 1).unwrap();
+
+    // generate a secret key
+    let rlwe_secret_key = crate::RLWESecretKey::new(&crate::RLWE128_1024_1);
+    let secret_key_input = crate::LWESecretKey::new(&crate::LWE128_630);
+    let secret_key_output = rlwe_secret_key.to_lwe_secret_key();
+
+    // bootstrapping key
+    let bsk = crate::LWEBSK::new(&secret_key_input, &rlwe_secret_key, 5, 3);
+
+    for _ in 0..10 {
+        // random message away from 0
+        let mut message: f64 = random_message!(min, max);
+        if message.abs() < encoder.get_granularity() {
+            message = max;
+        }
+
+        // encode and encrypt
+        let ciphertext = crate::LWE::encode_encrypt(&secret_key_input, message, &encoder).unwrap();
+
+        // sign
+        let ciphertext_res = ciphertext.sign(&bsk).unwrap();
+
+        // decrypt
+        let decryption = ciphertext_res
+            .decrypt_decode_round(&secret_key_output)
+            .unwrap();
+
+        // test
+        assert_eq!(message.signum(), decryption);
+    }
+}
+
+#[test]
+fn test_encode_encrypt_x_greater_than_x_decrypt() {
+    // random settings for the encoders with the same delta
+    let (min1, max1) = generate_random_interval!();
+    let encoder_1 = crate::Encoder::new(min1, max1, 4, 2).unwrap();
+    let min2 = min1 + random_message!(-(max1 - min1) / 2., (max1 - min1) / 2.);
+    let max2 = min2 + max1 - min1;
+    let encoder_2 = crate::Encoder::new(min2, max2, 4, 2).unwrap();
+
+    // generate a secret key
+    let rlwe_secret_key = crate::RLWESecretKey::new(&crate::RLWE128_1024_1);
+    let secret_key_input = crate::LWESecretKey::new(&crate::LWE128_630);
+    let secret_key_output = rlwe_secret_key.to_lwe_secret_key();
+
+    // bootstrapping key
+    let bsk = crate::LWEBSK::new(&secret_key_input, &rlwe_secret_key, 5, 3);
+
+    for _ in 0..10 {
+        // random messages, not too close to each other
+        let message_1: f64 = random_message!(min1, max1);
+        let message_2: f64 = random_message!(min2, max2);
+        if (message_1 - message_2).abs() < encoder_1.get_granularity() {
+            continue;
+        }
+
+        // encode and encrypt
+        let ciphertext_1 =
+            crate::LWE::encode_encrypt(&secret_key_input, message_1, &encoder_1).unwrap();
+        let ciphertext_2 =
+            crate::LWE::encode_encrypt(&secret_key_input, message_2, &encoder_2).unwrap();
+
+        // comparison
+        let ciphertext_res = ciphertext_1.greater_than(&ciphertext_2, &bsk).unwrap();
+
+        // decrypt
+        let decryption = ciphertext_res
+            .decrypt_decode_round(&secret_key_output)
+            .unwrap();
+
+        // test
+        let expected: f64 = if message_1 > message_2 { 1. } else { 0. };
+        assert_eq!(expected, decryption);
+    }
+}
+
+#[test]
+fn test_encode_encrypt_x_max_with_min_with_x_decrypt() {
+    // random settings for the encoders with the same delta
+    let (min1, max1) = generate_random_interval!();
+    let encoder_1 = crate::Encoder::new(min1, max1, 4, 2).unwrap();
+    let min2 = min1 + random_message!(-(max1 - min1) / 2., (max1 - min1) / 2.);
+    let max2 = min2 + max1 - min1;
+    let encoder_2 = crate::Encoder::new(min2, max2, 4, 2).unwrap();
+
+    // generate a secret key
+    let rlwe_secret_key = crate::RLWESecretKey::new(&crate::RLWE128_1024_1);
+    let secret_key_input = crate::LWESecretKey::new(&crate::LWE128_630);
+    let secret_key_output = rlwe_secret_key.to_lwe_secret_key();
+
+    // bootstrapping key
+    let bsk = crate::LWEBSK::new(&secret_key_input, &rlwe_secret_key, 5, 3);
+
+    // margin so that the bootstrap noise does not wrap around the output interval
+    let margin: f64 = 2. * encoder_1.get_granularity();
+
+    for _ in 0..10 {
+        // random messages
+        let message_1: f64 = random_message!(min1 + margin, max1 - margin);
+        let message_2: f64 = random_message!(min2 + margin, max2 - margin);
+
+        // encode and encrypt
+        let ciphertext_1 =
+            crate::LWE::encode_encrypt(&secret_key_input, message_1, &encoder_1).unwrap();
+        let ciphertext_2 =
+            crate::LWE::encode_encrypt(&secret_key_input, message_2, &encoder_2).unwrap();
+
+        // maximum and minimum
+        let ciphertext_max = ciphertext_1.max_with(&ciphertext_2, &bsk).unwrap();
+        let ciphertext_min = ciphertext_1.min_with(&ciphertext_2, &bsk).unwrap();
+
+        // decrypt
+        let decryption_max = ciphertext_max.decrypt_decode(&secret_key_output).unwrap();
+        let decryption_min = ciphertext_min.decrypt_decode(&secret_key_output).unwrap();
+
+        // test
+        assert_eq_granularity!(
+            f64::max(message_1, message_2),
+            decryption_max,
+            ciphertext_max.encoder
+        );
+        assert_eq_granularity!(
+            f64::min(message_1, message_2),
+            decryption_min,
+            ciphertext_min.encoder
+        );
+    }
+}
+
+#[test]
+fn test_encode_encrypt_x_comparisons_different_deltas_x_decrypt() {
+    // encoders whose deltas differ by a factor 2 and with different paddings
+    let encoder_1 = crate::Encoder::new(-10., 10., 4, 3).unwrap();
+    let encoder_2 = crate::Encoder {
+        o: 0.,
+        delta: 2. * encoder_1.delta,
+        nb_bit_precision: 5,
+        nb_bit_padding: 2,
+        round: false,
+    };
+
+    // generate a secret key
+    let rlwe_secret_key = crate::RLWESecretKey::new(&crate::RLWE128_1024_1);
+    let secret_key_input = crate::LWESecretKey::new(&crate::LWE128_630);
+    let secret_key_output = rlwe_secret_key.to_lwe_secret_key();
+
+    // bootstrapping key
+    let bsk = crate::LWEBSK::new(&secret_key_input, &rlwe_secret_key, 5, 3);
+
+    // margin so that the noise of the two bootstraps does not wrap around the output interval,
+    // whose delta is the largest of the two deltas
+    let margin: f64 = encoder_2.delta / 8.;
+
+    for _ in 0..5 {
+        // random messages
+        let message_1: f64 = random_message!(-10. + margin, 10. - margin);
+        let message_2: f64 = random_message!(margin, encoder_2.delta - margin);
+
+        // encode and encrypt
+        let ciphertext_1 =
+            crate::LWE::encode_encrypt(&secret_key_input, message_1, &encoder_1).unwrap();
+        let ciphertext_2 =
+            crate::LWE::encode_encrypt(&secret_key_input, message_2, &encoder_2).unwrap();
+
+        // comparison, maximum and minimum in both orders
+        let ciphertext_gt = ciphertext_1.greater_than(&ciphertext_2, &bsk).unwrap();
+        let ciphertext_max = ciphertext_1.max_with(&ciphertext_2, &bsk).unwrap();
+        let ciphertext_min = ciphertext_2.min_with(&ciphertext_1, &bsk).unwrap();
+
+        // decrypt
+        let decryption_gt = ciphertext_gt
+            .decrypt_decode_round(&secret_key_output)
+            .unwrap();
+        let decryption_max = ciphertext_max.decrypt_decode(&secret_key_output).unwrap();
+        let decryption_min = ciphertext_min.decrypt_decode(&secret_key_output).unwrap();
+
+        // test
+        if (message_1 - message_2).abs() >= encoder_1.get_granularity() {
+            let expected: f64 = if message_1 > message_2 { 1. } else { 0. };
+            assert_eq!(expected, decryption_gt);
+        }
+        assert_eq_granularity!(
+            f64::max(message_1, message_2),
+            decryption_max,
+            ciphertext_max.encoder
+        );
+        assert_eq_granularity!(
+            f64::min(message_1, message_2),
+            decryption_min,
+            ciphertext_min.encoder
+        );
+    }
+}
+
+#[test]
+fn test_comparisons_incompatible_deltas() {
+    // encoders whose deltas do not differ by a power of two
+    let encoder_1 = crate::Encoder::new(0., 10., 4, 4).unwrap();
+    let encoder_2 = crate::Encoder::new(0., 30., 4, 4).unwrap();
+
+    // generate a secret key
+    let rlwe_secret_key = crate::RLWESecretKey::new(&crate::RLWE128_256_1);
+    let secret_key_input = crate::LWESecretKey::new(&crate::LWE128_630);
+
+    // bootstrapping key
+    let bsk = crate::LWEBSK::new(&secret_key_input, &rlwe_secret_key, 5, 3);
+
+    // encode and encrypt
+    let ciphertext_1 = crate::LWE::encode_encrypt(&secret_key_input, 3., &encoder_1).unwrap();
+    let ciphertext_2 = crate::LWE::encode_encrypt(&secret_key_input, 5., &encoder_2).unwrap();
+
+    // test
+    assert!(matches!(
+        ciphertext_1.greater_than(&ciphertext_2, &bsk),
+        Err(crate::CryptoAPIError::DeltaError { .. })
+    ));
+}
+
+#[test]
+fn test_encode_encrypt_x_pbs_keyswitch_x_keyswitch_pbs_x_decrypt() {
+    // settings
+    let (min, max) = (0., 7.);
+    let padding: usize = 1;
+    let precision: usize = 3;
+
+    // encoder
+    let encoder = crate::Encoder::new(min, max, precision, padding).unwrap();
+
+    // secret keys
+    let rlwe_secret_key = crate::RLWESecretKey::new(&crate::RLWE128_1024_1);
+    let secret_key_small = crate::LWESecretKey::new(&crate::LWE128_630);
+    let secret_key_big = rlwe_secret_key.to_lwe_secret_key();
+
+    // keys
+    let bsk = crate::LWEBSK::new(&secret_key_small, &rlwe_secret_key, 7, 3);
+    let ksk = crate::LWEKSK::new(&secret_key_big, &secret_key_small, 2, 7);
+
+    for _ in 0..10 {
+        // a random message
+        let message: f64 = random_message!(min, max);
+
+        // bootstrap then key switch, back to the small key
+        let ciphertext = crate::LWE::encode_encrypt(&secret_key_small, message, &encoder)
+            .unwrap()
+            .pbs_keyswitch(&bsk, &ksk, |x| x, &encoder)
+            .unwrap();
+        assert_eq!(ciphertext.dimension, 630);
+        assert_eq!(
+            ciphertext.variance,
+            ksk.get_output_variance(bsk.get_output_variance())
+        );
+        let decryption = ciphertext.decrypt_decode(&secret_key_small).unwrap();
+        assert_eq_granularity!(message, decryption, ciphertext.encoder);
+
+        // key switch then bootstrap, back to the big key
+        let ciphertext = crate::LWE::encode_encrypt(&secret_key_big, message, &encoder)
+            .unwrap()
+            .keyswitch_pbs(&ksk, &bsk, |x| x, &encoder)
+            .unwrap();
+        assert_eq!(ciphertext.dimension, 1024);
+        assert_eq!(ciphertext.variance, bsk.get_output_variance());
+        let decryption = ciphertext.decrypt_decode(&secret_key_big).unwrap();
+        assert_eq_granularity!(message, decryption, ciphertext.encoder);
+    }
+
+    // the keys must be chained
+    let ciphertext = crate::LWE::encode_encrypt(&secret_key_small, 1., &encoder).unwrap();
+    assert!(ciphertext
+        .keyswitch_pbs(&ksk, &bsk, |x| x, &encoder)
+        .is_err());
+}
+
+#[test]
+fn test_key_mismatch() {
+    // small parameters, the noise does not matter here
+    let lwe_params = crate::LWEParams::new(64, -20);
+    let rlwe_params = crate::RLWEParams::new(256, 1, -40).unwrap();
+    let encoder = crate::Encoder::new(0., 10., 2, 1).unwrap();
+
+    // two keys of the same dimension
+    let secret_key = crate::LWESecretKey::new(&lwe_params);
+    let other_secret_key = crate::LWESecretKey::new(&lwe_params);
+    let rlwe_secret_key = crate::RLWESecretKey::new(&rlwe_params);
+    assert_ne!(secret_key.fingerprint, other_secret_key.fingerprint);
+
+    let ciphertext = crate::LWE::encode_encrypt(&secret_key, 3., &encoder).unwrap();
+    let other_ciphertext = crate::LWE::encode_encrypt(&other_secret_key, 5., &encoder).unwrap();
+
+    // decryption with the wrong key
+    assert!(ciphertext.decrypt_decode(&secret_key).is_ok());
+    assert!(matches!(
+        ciphertext.decrypt_decode(&other_secret_key),
+        Err(crate::CryptoAPIError::KeyMismatch { .. })
+    ));
+
+    // operation between ciphertexts under different keys
+    assert!(matches!(
+        ciphertext.add_with_padding(&other_ciphertext),
+        Err(crate::CryptoAPIError::KeyMismatch { .. })
+    ));
+
+    // bootstrap and key switch with keys from another key
+    let bsk = crate::LWEBSK::new(&other_secret_key, &rlwe_secret_key, 8, 3);
+    let ksk = crate::LWEKSK::new(&other_secret_key, &secret_key, 3, 6);
+    assert!(matches!(
+        ciphertext.bootstrap(&bsk),
+        Err(crate::CryptoAPIError::KeyMismatch { .. })
+    ));
+    assert!(matches!(
+        ciphertext.keyswitch(&ksk),
+        Err(crate::CryptoAPIError::KeyMismatch { .. })
+    ));
+
+    // the outputs carry the fingerprints of the output keys
+    let switched = other_ciphertext.keyswitch(&ksk).unwrap();
+    assert_eq!(switched.key_fingerprint, secret_key.fingerprint);
+    assert!(switched.decrypt_decode(&secret_key).is_ok());
+    let bootstrapped = other_ciphertext.bootstrap(&bsk).unwrap();
+    assert!(bootstrapped
+        .decrypt_decode(&rlwe_secret_key.to_lwe_secret_key())
+        .is_ok());
+
+    // a trivial list takes the key of the first ciphertext copied in it
+    let mut list = crate::VectorLWE::zero(64, 2).unwrap();
+    assert!(list.key_fingerprint.is_unknown());
+    list.copy_lwe_in_nth_inplace(0, &ciphertext).unwrap();
+    assert_eq!(list.key_fingerprint, secret_key.fingerprint);
+    assert!(matches!(
+        list.copy_lwe_in_nth_inplace(1, &other_ciphertext),
+        Err(crate::CryptoAPIError::KeyMismatch { .. })
+    ));
+
+    // a failed operation does not take the key of the other ciphertext
+    let mut trivial = crate::LWE::zero(64).unwrap();
+    trivial.encoder = crate::Encoder::new(0., 20., 2, 1).unwrap();
+    assert!(matches!(
+        trivial.add_with_new_min_inplace(&ciphertext, 0.),
+        Err(crate::CryptoAPIError::DeltaError { .. })
+    ));
+    assert!(trivial.key_fingerprint.is_unknown());
+
+    // circuit bootstrap with private functional key switching keys from another key
+    let other_rlwe_secret_key = crate::RLWESecretKey::new(&rlwe_params);
+    let pfksk = crate::LWEPFKSK::new(
+        &other_rlwe_secret_key.to_lwe_secret_key(),
+        &other_rlwe_secret_key,
+        10,
+        3,
+    );
+    let bit = crate::LWE::encode_encrypt(
+        &other_secret_key,
+        1.,
+        &crate::Encoder::new(0., 1., 1, 1).unwrap(),
+    )
+    .unwrap();
+    assert!(matches!(
+        bit.circuit_bootstrap(&bsk, &pfksk, 6, 4),
+        Err(crate::CryptoAPIError::KeyMismatch { .. })
+    ));
+    let pfksk = crate::LWEPFKSK::new(
+        &rlwe_secret_key.to_lwe_secret_key(),
+        &rlwe_secret_key,
+        10,
+        3,
+    );
+    let ggsw = bit.circuit_bootstrap(&bsk, &pfksk, 6, 4).unwrap();
+    assert_eq!(ggsw.fingerprint, rlwe_secret_key.fingerprint);
+}
+
+#[test]
+fn test_greater_than_not_enough_padding() {
+    // encoders with only one bit of padding
+    let encoder = crate::Encoder::new(0., 10., 4, 1).unwrap();
+
+    // generate a secret key
+    let rlwe_secret_key = crate::RLWESecretKey::new(&crate::RLWE128_256_1);
+    let secret_key_input = crate::LWESecretKey::new(&crate::LWE128_630);
+
+    // bootstrapping key
+    let bsk = crate::LWEBSK::new(&secret_key_input, &rlwe_secret_key, 5, 3);
+
+    // encode and encrypt
+    let ciphertext_1 = crate::LWE::encode_encrypt(&secret_key_input, 3., &encoder).unwrap();
+    let ciphertext_2 = crate::LWE::encode_encrypt(&secret_key_input, 5., &encoder).unwrap();
+
+    // test
+    assert!(ciphertext_1.greater_than(&ciphertext_2, &bsk).is_err());
+    assert!(ciphertext_1.max_with(&ciphertext_2, &bsk).is_err());
+}
+
 #[test]
 fn test_encode_encrypt_x_add_with_new_min_inplace_x_decrypt() {
     // random number of messages
@@ -769,3 +1661,43 @@ fn test_valid_lookup_table() {
         assert_eq_granularity!(cst, decryption_zero, ciphertext_output_zero.encoder);
     }
 }
+
+#[test]
+fn test_encode_encrypt_x_circuit_bootstrap_x_cmux_x_decrypt() {
+    // secret keys
+    let rlwe_params = crate::RLWEParams::new(512, 1, -50).unwrap();
+    let rlwe_secret_key = crate::RLWESecretKey::new(&rlwe_params);
+    let secret_key_input = crate::LWESecretKey::new(&crate::LWE128_630);
+    let secret_key_output = rlwe_secret_key.to_lwe_secret_key();
+
+    // bootstrapping key and private functional key switching keys
+    let bsk = crate::LWEBSK::new(&secret_key_input, &rlwe_secret_key, 10, 4);
+    let pfksk = crate::LWEPFKSK::new(&secret_key_output, &rlwe_secret_key, 10, 4);
+
+    // two RLWE ciphertexts to select from
+    let encoder = crate::Encoder::new(0., 15., 4, 1).unwrap();
+    let messages0: Vec<f64> = vec![1., 5., 9.];
+    let messages1: Vec<f64> = vec![14., 2., 7.];
+    let ct0 =
+        crate::VectorRLWE::encode_encrypt_packed(&rlwe_secret_key, &messages0, &encoder).unwrap();
+    let ct1 =
+        crate::VectorRLWE::encode_encrypt_packed(&rlwe_secret_key, &messages1, &encoder).unwrap();
+
+    let encoder_bit = crate::Encoder::new(0., 1., 1, 1).unwrap();
+    for bit in 0..2 {
+        // encode and encrypt the bit
+        let ciphertext =
+            crate::LWE::encode_encrypt(&secret_key_input, bit as f64, &encoder_bit).unwrap();
+
+        // circuit bootstrap and cmux
+        let ggsw = ciphertext.circuit_bootstrap(&bsk, &pfksk, 6, 3).unwrap();
+        let res = ggsw.cmux(&ct0, &ct1).unwrap();
+        let decryptions = res.decrypt_decode_round(&rlwe_secret_key).unwrap();
+
+        // test
+        let expected = if bit == 0 { &messages0 } else { &messages1 };
+        for (m, d, enc) in izip!(expected.iter(), decryptions.iter(), res.encoders.iter()) {
+            assert_eq_granularity!(m, d, enc);
+        }
+    }
+}
@@ -1,43 +1,227 @@
+use std::borrow::Cow;
 use std::fmt;
 use backtrace::Backtrace;
 use colored::Colorize;
 use concrete_commons::dispersion::StandardDev;
-use concrete_commons::numeric::Numeric;
 use concrete_commons::parameters::{DecompositionBaseLog, DecompositionLevelCount, GlweSize, LweDimension, PolynomialSize};
 use concrete_core::{
-    math::tensor::{AsMutTensor, AsRefTensor},
+    math::tensor::{AsMutSlice, AsMutTensor, AsRefTensor},
     math::{fft::Complex64, tensor::Tensor},
 };
 use concrete_core::crypto::bootstrap::{FourierBootstrapKey,StandardBootstrapKey};
-use concrete_core::crypto::secret::generators::EncryptionRandomGenerator;
+use concrete_core::crypto::glwe::GlweCiphertext;
+use concrete_core::crypto::encoding::Plaintext;
+use concrete_core::crypto::lwe::LweCiphertext;
+use concrete_core::crypto::ggsw::GgswCiphertext;
 use concrete_core::math::fft::AlignedVec;
+use concrete_core::math::torus::UnsignedTorus;
 use crate::error::CryptoAPIError;
-use crate::Torus;
+use crate::utils::Zeroizing;
+use crate::{GenericTorus, KeyFingerprint, SecretKeyKind, Torus};
 
 use serde::{Serialize, Deserialize};
+#[cfg(feature = "multithread")]
+use concrete_core::math::tensor::AsRefSlice;
+#[cfg(feature = "multithread")]
+use rayon::prelude::*;
 
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
-pub struct LWEBSK {
-    pub ciphertexts: FourierBootstrapKey<AlignedVec<Complex64>,u64>,
+pub struct GenericLWEBSK<T: UnsignedTorus> {
+    pub ciphertexts: FourierBootstrapKey<AlignedVec<Complex64>, T>,
     pub variance: f64,
     pub dimension: usize,
     pub polynomial_size: usize,
     pub base_log: usize,
     pub level: usize,
+    /// 0 for a binary input key, otherwise the number of bits of the largest absolute value of
+    /// the input key coefficients, the blind rotation running on their binary decomposition
+    #[serde(default)]
+    pub nb_bit_expansion: usize,
+    #[serde(default)]
+    pub output_kind: SecretKeyKind,
+    /// the fingerprints of the input and output secret keys
+    #[serde(default)]
+    pub input_fingerprint: KeyFingerprint,
+    #[serde(default)]
+    pub output_fingerprint: KeyFingerprint,
 }
 
-impl LWEBSK {
+/// A bootstrapping key whose output coefficients are Torus elements
+pub type LWEBSK = GenericLWEBSK<Torus>;
+
+/// A bootstrapping key whose output coefficients are u32, see GenericTorus
+pub type LWEBSK32 = GenericLWEBSK<u32>;
+
+impl<T: GenericTorus> GenericLWEBSK<T> {
     /// Return the dimension of an LWE we can bootstrap with this key
     pub fn get_lwe_dimension(&self) -> usize {
+        self.get_blind_rotation_dimension() / usize::max(1, 2 * self.nb_bit_expansion)
+    }
+
+    /// Return the dimension of the binary LWE key the blind rotation runs on,
+    /// which is bigger than the input LWE dimension when the input key is not binary
+    pub fn get_blind_rotation_dimension(&self) -> usize {
         self.ciphertexts.as_tensor().len()
             / (usize::pow(self.dimension + 1, 2) * self.level * self.polynomial_size)
     }
 
+    /// Return the variance of the output of a bootstrap (without the drift)
+    pub(crate) fn get_output_variance(&self) -> f64 {
+        self.output_kind.get_bootstrap_variance_in::<T>(
+            self.get_blind_rotation_dimension(),
+            self.dimension,
+            self.level,
+            self.base_log,
+            self.polynomial_size,
+            self.variance,
+        )
+    }
+
+    /// Compute the blind rotation of an accumulator and the sample extraction
+    /// The mask of the input ciphertext is expanded first if the input key is not binary
+    ///
+    /// # Argument
+    /// * `result` - the output LWE ciphertext
+    /// * `input` - the input LWE ciphertext, mask first and body last
+    /// * `accumulator` - the accumulator containing the look up table
+    pub(crate) fn bootstrap_raw(
+        &self,
+        result: &mut LweCiphertext<Vec<T>>,
+        input: &[T],
+        accumulator: &GlweCiphertext<Vec<T>>,
+    ) {
+        T::fourier_bootstrap(&self.ciphertexts, result, &self.expand_input(input), accumulator);
+    }
+
+    /// Compute several blind rotations and sample extractions in parallel
+    /// The key is not Sync because of its FFT buffers, so every rayon job borrows the Fourier
+    /// coefficients of the key with its own buffers instead of copying the whole key
+    ///
+    /// # Argument
+    /// * `inputs` - pairs of an input LWE ciphertext, mask first and body last, and of its accumulator
+    ///
+    /// # Output
+    /// * the output LWE ciphertexts, in the order of the inputs
+    #[cfg(feature = "multithread")]
+    pub(crate) fn par_bootstrap_raw(
+        &self,
+        inputs: &[(Vec<T>, GlweCiphertext<Vec<T>>)],
+    ) -> Vec<LweCiphertext<Vec<T>>> {
+        let expanded: Vec<Cow<[T]>> = inputs
+            .iter()
+            .map(|(input, _)| self.expand_input(input))
+            .collect();
+        let coefficients: &[Complex64] = self.ciphertexts.as_tensor().as_slice();
+        let glwe_size = self.ciphertexts.glwe_size();
+        let polynomial_size = self.ciphertexts.polynomial_size();
+        let level = self.ciphertexts.level_count();
+        let base_log = self.ciphertexts.base_log();
+        let output_size =
+            concrete_commons::parameters::LweSize(self.dimension * self.polynomial_size + 1);
+
+        expanded
+            .par_iter()
+            .zip(inputs.par_iter())
+            .map_init(
+                || {
+                    FourierBootstrapKey::from_container(
+                        coefficients,
+                        glwe_size,
+                        polynomial_size,
+                        level,
+                        base_log,
+                    )
+                },
+                |bsk, (input, (_, accumulator))| {
+                    let mut result = LweCiphertext::allocate(T::ZERO, output_size);
+                    T::fourier_bootstrap_borrowed(bsk, &mut result, input, accumulator);
+                    result
+                },
+            )
+            .collect()
+    }
+
+    /// Expand the mask of an input ciphertext if the input key is not binary
+    fn expand_input<'a>(&self, input: &'a [T]) -> Cow<'a, [T]> {
+        if self.nb_bit_expansion == 0 {
+            return Cow::Borrowed(input);
+        }
+
+        // <a, s> = sum_k <2^k * a, bits_k(s+)> + <-2^k * a, bits_k(s-)>
+        let (mask, body) = input.split_at(input.len() - 1);
+        let mut expanded: Vec<T> = Vec::with_capacity(2 * self.nb_bit_expansion * mask.len() + 1);
+        for a in mask.iter() {
+            for k in 0..self.nb_bit_expansion {
+                expanded.push(*a << k);
+                expanded.push((*a << k).wrapping_neg());
+            }
+        }
+        expanded.extend_from_slice(body);
+        Cow::Owned(expanded)
+    }
+
+    /// Return the number of bits of the largest absolute value of the coefficients of a secret key,
+    /// or 0 for a binary key which does not need to be expanded
+    fn get_nb_bit_expansion(sk: &crate::LWESecretKey) -> usize {
+        if sk.kind == SecretKeyKind::Binary {
+            return 0;
+        }
+        let max = sk
+            .val
+            .as_slice()
+            .iter()
+            .map(|s| (*s as i64).unsigned_abs())
+            .max()
+            .unwrap_or(0);
+        usize::max(1, (64 - max.leading_zeros()) as usize)
+    }
+
+    /// Expand a secret key into the coefficients of a binary secret key made of the bits of the
+    /// positive parts and of the negative parts of its coefficients, in the order expected by
+    /// bootstrap_raw
+    fn expand_secret_key(sk: &crate::LWESecretKey, nb_bit_expansion: usize) -> Zeroizing<T> {
+        let mut expanded = Zeroizing(Vec::with_capacity(2 * nb_bit_expansion * sk.dimension));
+        for s in sk.val.as_slice().iter() {
+            let s = *s as i64;
+            let (positive, negative) = (i64::max(s, 0) as u64, i64::max(-s, 0) as u64);
+            for k in 0..nb_bit_expansion {
+                expanded.push(T::cast_from((positive >> k) & 1));
+                expanded.push(T::cast_from((negative >> k) & 1));
+            }
+        }
+        expanded
+    }
+
     /// Return the log2 of the polynomial size of the RLWE involved in the bootstrap
     pub fn get_polynomial_size_log(&self) -> usize {
         f64::log2(self.polynomial_size as f64) as usize
     }
 
+    /// Remove from the precision of the output encoder of a bootstrap the bits lost to the drift,
+    /// i.e. to the rounding of the input ciphertext before the blind rotation
+    ///
+    /// # Argument
+    /// * `encoder_output` - the output encoder, whose precision is updated
+    /// * `nb_bit_padding` - the number of bits of padding of the input ciphertext
+    ///
+    /// # Output
+    /// * the number of bits of precision lost
+    pub(crate) fn update_precision_from_drift(
+        &self,
+        encoder_output: &mut crate::Encoder,
+        nb_bit_padding: usize,
+    ) -> usize {
+        let nb_bit_drift = get_nb_bit_drift(
+            self.get_blind_rotation_dimension(),
+            self.get_polynomial_size_log(),
+            nb_bit_padding,
+            encoder_output.nb_bit_precision,
+        );
+        encoder_output.nb_bit_precision =
+            encoder_output.nb_bit_precision.saturating_sub(nb_bit_drift);
+        nb_bit_drift
+    }
+
     /// Build a lookup table af a function from two encoders
     ///
     /// # Argument
@@ -52,7 +236,7 @@ impl LWEBSK {
         encoder_input: &crate::Encoder,
         encoder_output: &crate::Encoder,
         f: F,
-    ) -> Result<Vec<Torus>, CryptoAPIError> {
+    ) -> Result<Vec<T>, CryptoAPIError> {
         // check that precision != 0
         if encoder_input.nb_bit_precision == 0 {
             return Err(PrecisionError!());
@@ -63,31 +247,24 @@ impl LWEBSK {
             return Err(NotEnoughPaddingError!(encoder_input.nb_bit_padding, 1));
         }
 
-        // clone the input encoder and set nb_bit_padding to 1
-        let mut encoder_input_clone = encoder_input.clone();
-        encoder_input_clone.nb_bit_padding = 1;
-
         // allocation of the result
-        let mut result: Vec<Torus> = vec![0; self.polynomial_size];
+        let mut result: Vec<T> = vec![T::ZERO; self.polynomial_size];
 
         // find the right index to start storing -val_i instead of val_i
         let minus_start_index: usize =
             self.polynomial_size - (self.polynomial_size >> (1 + encoder_input.nb_bit_precision));
 
-        for (i, res) in result.iter_mut().enumerate() {
-            // create a valid encoding from i
-            let shift: usize = <Torus as Numeric>::BITS - self.get_polynomial_size_log() - 1;
-            let encoded: Torus = (i as Torus) << shift;
-
-            // decode the encoding
-            let decoded: f64 = encoder_input_clone.decode_core(encoded)?;
+        // decode the inputs of the table
+        let inputs = encoder_input.get_look_up_table_inputs(self.polynomial_size)?;
 
+        for (i, (res, decoded)) in result.iter_mut().zip(inputs.iter()).enumerate() {
             // apply the function
-            let f_decoded: f64 = f(decoded);
+            let f_decoded: f64 = f(*decoded);
 
-            // encode the result
-            let output_encoded: Torus =
-                encoder_output.encode_outside_interval_operators(f_decoded)?;
+            // encode the result, and round it to the torus of T
+            let output_encoded: T = crate::generic_torus::from_torus(
+                encoder_output.encode_outside_interval_operators(f_decoded)?,
+            );
 
             *res = if i < minus_start_index {
                 output_encoded
@@ -110,11 +287,34 @@ impl LWEBSK {
         &self,
         encoder_input: &crate::Encoder,
         encoder_output: &crate::Encoder,
-    ) -> Result<Vec<Torus>, CryptoAPIError> {
+    ) -> Result<Vec<T>, CryptoAPIError> {
         self.generate_functional_look_up_table(encoder_input, encoder_output, |x| x)
     }
 
-    /// Create a valid bootstrapping key
+    /// Build a lookup table for the identity function from two encoders,
+    /// where the values outside of the output interval are clamped to its min or its max
+    ///
+    /// # Argument
+    /// * `encoder_input` - the encoder of the input (of the bootstrap)
+    /// * `encoder_output` - the encoder of the output (of the bootstrap)
+    ///
+    /// # Output
+    /// * a slice of Torus containing the lookup table
+    pub fn generate_clamped_identity_look_up_table(
+        &self,
+        encoder_input: &crate::Encoder,
+        encoder_output: &crate::Encoder,
+    ) -> Result<Vec<T>, CryptoAPIError> {
+        let (min, max) = (encoder_output.get_min(), encoder_output.get_max());
+        self.generate_functional_look_up_table(encoder_input, encoder_output, |x| {
+            f64::min(f64::max(x, min), max)
+        })
+    }
+
+    /// Create a valid bootstrapping key, using all the available threads
+    /// when the multithread feature is enabled
+    /// It panics if the standard deviation of `sk_output` is too small for the noise to fit in the
+    /// torus of T, as the key would not contain any noise: try_new reports it as an error instead
     ///
     /// # Argument
     /// * `sk_before` - an LWE secret key (input for the bootstrap)
@@ -129,41 +329,193 @@ impl LWEBSK {
         sk_output: &crate::RLWESecretKey,
         base_log: usize,
         level: usize,
-    ) -> LWEBSK {
+    ) -> GenericLWEBSK<T> {
+        // without a callback, the generation is never cancelled
+        Self::try_new(sk_input, sk_output, base_log, level)
+            .expect("The noise of the output secret key does not fit in the torus of the key")
+    }
+
+    /// Create a valid bootstrapping key, checking that the noise of `sk_output` fits in the torus
+    /// of T
+    ///
+    /// # Argument
+    /// * `sk_before` - an LWE secret key (input for the bootstrap)
+    /// * `sk_after` - an LWE secret key (output for the bootstrap)
+    /// * `base_log` - the log2 of the decomposition base
+    /// * `level` - the number of levels of the decomposition
+    ///
+    /// # Output
+    /// * an LWEBSK
+    /// * NoNoiseInCiphertext - if the standard deviation of `sk_output` is too small for the noise
+    ///   to fit in the torus
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// let sk_input = LWESecretKey::new(&LWE128_630);
+    ///
+    /// // the noise of RLWE128_2048_1 does not fit in 32 bits
+    /// let sk_output = RLWESecretKey::new(&RLWE128_2048_1);
+    /// assert!(LWEBSK32::try_new(&sk_input, &sk_output, 6, 4).is_err());
+    /// ```
+    pub fn try_new(
+        sk_input: &crate::LWESecretKey,
+        sk_output: &crate::RLWESecretKey,
+        base_log: usize,
+        level: usize,
+    ) -> Result<GenericLWEBSK<T>, CryptoAPIError> {
+        Self::new_with_progress(sk_input, sk_output, base_log, level, |_| true)
+    }
+
+    /// Create a valid bootstrapping key, reporting the progress of the generation
+    /// to a callback that can cancel it
+    ///
+    /// # Argument
+    /// * `sk_before` - an LWE secret key (input for the bootstrap)
+    /// * `sk_after` - an LWE secret key (output for the bootstrap)
+    /// * `base_log` - the log2 of the decomposition base
+    /// * `level` - the number of levels of the decomposition
+    /// * `callback` - a function called with the fraction of the key already generated (ending
+    ///   with 1.), returning false to cancel the generation
+    ///
+    /// # Output
+    /// * an LWEBSK
+    /// * CancelledError - if the callback returned false
+    /// * NoNoiseInCiphertext - if the standard deviation of `sk_output` is too small for the noise
+    ///   to fit in the torus
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// let sk_input = LWESecretKey::new(&LWE128_630);
+    /// let sk_output = RLWESecretKey::new(&RLWE128_1024_1);
+    ///
+    /// // print the progress of the generation
+    /// let bsk = LWEBSK::new_with_progress(&sk_input, &sk_output, 6, 4, |progress| {
+    ///     println!("{:.0}%", 100. * progress);
+    ///     true
+    /// })
+    /// .unwrap();
+    ///
+    /// // cancel the generation halfway
+    /// let cancelled = LWEBSK::new_with_progress(&sk_input, &sk_output, 6, 4, |progress| {
+    ///     progress < 0.5
+    /// });
+    /// assert!(cancelled.is_err());
+    /// ```
+    pub fn new_with_progress<F: FnMut(f64) -> bool + Send>(
+        sk_input: &crate::LWESecretKey,
+        sk_output: &crate::RLWESecretKey,
+        base_log: usize,
+        level: usize,
+        mut callback: F,
+    ) -> Result<GenericLWEBSK<T>, CryptoAPIError> {
+        crate::generic_torus::check_std_dev::<T>(sk_output.std_dev)?;
+
+        // the generation itself is not generic, so that it is compiled once with the library
+        T::generate_bsk(sk_input, sk_output, base_log, level, &mut callback)
+    }
+
+    /// Generate a bootstrapping key, reporting the progress to `callback`
+    ///
+    /// # Output
+    /// * the key
+    /// * CancelledError - if the callback returned false, the key not being finished
+    pub(crate) fn generate(
+        sk_input: &crate::LWESecretKey,
+        sk_output: &crate::RLWESecretKey,
+        base_log: usize,
+        level: usize,
+        callback: &mut (dyn FnMut(f64) -> bool + Send),
+    ) -> Result<GenericLWEBSK<T>, CryptoAPIError> {
+        // copies of the keys in the torus of T, wiped when dropped, a non binary input key being
+        // replaced by its binary expansion
+        let nb_bit_expansion = Self::get_nb_bit_expansion(sk_input);
+        let sk_input_val = if nb_bit_expansion == 0 {
+            Zeroizing(sk_input.to_generic_coefficients::<T>())
+        } else {
+            Self::expand_secret_key(sk_input, nb_bit_expansion)
+        };
+        let sk_output_val = Zeroizing(sk_output.to_generic_coefficients::<T>());
+
         // allocation for the bootstrapping key
         let mut coef_bsk  = StandardBootstrapKey::allocate(
-            0_u64,
-            GlweSize(sk_output.val.key_size().0 + 1),
-            sk_output.val.polynomial_size(),
+            T::ZERO,
+            GlweSize(sk_output.dimension + 1),
+            PolynomialSize(sk_output.polynomial_size),
             DecompositionLevelCount(level),
             DecompositionBaseLog(base_log),
-            sk_input.val.key_size(),
-        );
-        coef_bsk.fill_with_new_key(
-            &sk_input.val,
-            &sk_output.val,
-            StandardDev::from_standard_dev(sk_output.std_dev),
-            &mut EncryptionRandomGenerator::new(None),
+            LweDimension(sk_input_val.len()),
         );
-        let mut fourier_bsk : FourierBootstrapKey<AlignedVec<Complex64>,u64>=
+
+        // each coefficient of the input key is encrypted as a GGSW under the output key, viewed
+        // with its own kind
+        let noise = StandardDev::from_standard_dev(sk_output.std_dev);
+        let glwe_size = GlweSize(sk_output.dimension + 1);
+        let polynomial_size = PolynomialSize(sk_output.polynomial_size);
+        if let Some(progress) = crate::utils::fill_with_progress(
+            coef_bsk.as_mut_tensor().as_mut_slice(),
+            sk_input_val.len(),
+            |i, block, generator| {
+                let mut ggsw = GgswCiphertext::from_container(
+                    block,
+                    glwe_size,
+                    polynomial_size,
+                    DecompositionBaseLog(base_log),
+                );
+                with_glwe_secret_key!(
+                    sk_output.kind,
+                    sk_output_val.as_slice(),
+                    sk_output.polynomial_size,
+                    |key| {
+                        #[cfg(feature = "multithread")]
+                        key.par_encrypt_constant_ggsw(
+                            &mut ggsw,
+                            &Plaintext(sk_input_val[i]),
+                            noise,
+                            generator,
+                        );
+                        #[cfg(not(feature = "multithread"))]
+                        key.encrypt_constant_ggsw(
+                            &mut ggsw,
+                            &Plaintext(sk_input_val[i]),
+                            noise,
+                            generator,
+                        );
+                    }
+                )
+            },
+            callback,
+        ) {
+            return Err(CancelledError!(progress));
+        }
+
+        let mut fourier_bsk : FourierBootstrapKey<AlignedVec<Complex64>, T>=
             FourierBootstrapKey::allocate(
             Complex64::new(0., 0.),
-            GlweSize(sk_output.val.key_size().0 + 1),
-            sk_output.val.polynomial_size(),
+            GlweSize(sk_output.dimension + 1),
+            PolynomialSize(sk_output.polynomial_size),
             DecompositionLevelCount(level),
             DecompositionBaseLog(base_log),
-            sk_input.val.key_size(),
+            coef_bsk.key_size(),
         );
         fourier_bsk.fill_with_forward_fourier(&coef_bsk);
 
-        LWEBSK {
+        let bsk = GenericLWEBSK {
             ciphertexts: fourier_bsk,
             variance: f64::powi(sk_output.std_dev, 2),
             dimension: sk_output.dimension,
             polynomial_size: sk_output.polynomial_size,
             base_log,
             level,
-        }
+            nb_bit_expansion,
+            output_kind: sk_output.kind,
+            input_fingerprint: sk_input.fingerprint,
+            output_fingerprint: sk_output.fingerprint,
+        };
+        Ok(bsk)
     }
 
     /// Create an empty bootstrapping key
@@ -181,29 +533,59 @@ impl LWEBSK {
         sk_output: &crate::RLWESecretKey,
         base_log: usize,
         level: usize,
-    ) -> LWEBSK {
+    ) -> GenericLWEBSK<T> {
+        let nb_bit_expansion = Self::get_nb_bit_expansion(sk_input);
+
         // allocation for the bootstrapping key
         let fourier_bsk = FourierBootstrapKey::allocate(
             Complex64::new(0., 0.),
-            GlweSize(sk_output.val.key_size().0 + 1),
-            sk_output.val.polynomial_size(),
+            GlweSize(sk_output.dimension + 1),
+            PolynomialSize(sk_output.polynomial_size),
             DecompositionLevelCount(level),
             DecompositionBaseLog(base_log),
-            sk_input.val.key_size(),
+            LweDimension(sk_input.dimension * usize::max(1, 2 * nb_bit_expansion)),
         );
 
-        LWEBSK {
+        GenericLWEBSK {
             ciphertexts: fourier_bsk,
             variance: f64::powi(sk_output.std_dev, 2),
             dimension: sk_output.dimension,
             polynomial_size: sk_output.polynomial_size,
             base_log,
             level,
+            nb_bit_expansion,
+            output_kind: sk_output.kind,
+            input_fingerprint: sk_input.fingerprint,
+            output_fingerprint: sk_output.fingerprint,
         }
     }
+}
 
+/// Return the number of bits of precision the output of a bootstrap loses to the drift, i.e. to
+/// the rounding of the input ciphertext before the blind rotation
+///
+/// # Argument
+/// * `blind_rotation_dimension` - the dimension of the binary key of the blind rotation
+/// * `polynomial_size_log` - the log2 of the polynomial size of the bootstrapping key
+/// * `nb_bit_padding` - the number of bits of padding of the input ciphertext
+/// * `nb_bit_precision` - the precision of the output
+pub(crate) fn get_nb_bit_drift(
+    blind_rotation_dimension: usize,
+    polynomial_size_log: usize,
+    nb_bit_padding: usize,
+    nb_bit_precision: usize,
+) -> usize {
+    // calls the NPE to find out the amount of noise after rounding the input ciphertext
+    let nb_rounding_noise_bit: usize =
+        concrete_npe::lwe::log2_rounding_noise(blind_rotation_dimension).ceil() as usize + 1;
+    (nb_rounding_noise_bit + nb_bit_padding + nb_bit_precision)
+        .saturating_sub(polynomial_size_log + 1)
+}
+
+impl<T: GenericTorus> GenericLWEBSK<T> {
     pub fn save(&self, path: &str) {
-        let mut tensor = Tensor::allocate(0, self.ciphertexts.as_tensor().len() * 2 + 6);
+        let mut tensor: Tensor<Vec<u64>> =
+            Tensor::allocate(0, self.ciphertexts.as_tensor().len() * 2 + 11);
 
         *tensor.get_element_mut(0) = self.variance.to_bits();
         *tensor.get_element_mut(1) = self.dimension as u64;
@@ -221,13 +603,24 @@ impl LWEBSK {
             *couple.get_element_mut(1) = c.im.to_bits();
         }
 
+        // the key kinds come last so that older files can still be loaded
+        *tensor.get_element_mut(self.ciphertexts.as_tensor().len() * 2 + 6) =
+            self.nb_bit_expansion as u64;
+        *tensor.get_element_mut(self.ciphertexts.as_tensor().len() * 2 + 7) =
+            self.output_kind.to_torus();
+        *tensor.get_element_mut(self.ciphertexts.as_tensor().len() * 2 + 8) =
+            self.input_fingerprint.0;
+        *tensor.get_element_mut(self.ciphertexts.as_tensor().len() * 2 + 9) =
+            self.output_fingerprint.0;
+        *tensor.get_element_mut(self.ciphertexts.as_tensor().len() * 2 + 10) = T::BITS as u64;
+
         tensor.save_to_file(path).unwrap();
     }
 
-    pub fn load(path: &str) -> crate::LWEBSK {
-        let tensor = Tensor::load_from_file(path).expect("Failed to load file");
+    pub fn load(path: &str) -> GenericLWEBSK<T> {
+        let tensor: Tensor<Vec<u64>> = Tensor::load_from_file(path).expect("Failed to load file");
 
-        let mut res = crate::LWEBSK {
+        let mut res = GenericLWEBSK {
             variance: f64::from_bits(*tensor.get_element(0)),
             dimension: *tensor.get_element(1) as usize,
             polynomial_size: *tensor.get_element(2) as usize,
@@ -241,10 +634,15 @@ impl LWEBSK {
                 DecompositionBaseLog(*tensor.get_element(3) as usize),
                 LweDimension(*tensor.get_element(5) as usize),
             ),
+            nb_bit_expansion: 0,
+            output_kind: SecretKeyKind::Binary,
+            input_fingerprint: KeyFingerprint::UNKNOWN,
+            output_fingerprint: KeyFingerprint::UNKNOWN,
         };
 
+        let len = res.ciphertexts.as_tensor().len() * 2 + 6;
         for (couple, c) in tensor
-            .get_sub(6..)
+            .get_sub(6..len)
             .subtensor_iter(2)
             .zip(res.ciphertexts.as_mut_tensor().iter_mut())
         {
@@ -253,12 +651,27 @@ impl LWEBSK {
                 f64::from_bits(*couple.get_element(1)),
             );
         }
+        if tensor.len() > len {
+            res.nb_bit_expansion = *tensor.get_element(len) as usize;
+            res.output_kind = SecretKeyKind::from_torus(*tensor.get_element(len + 1));
+        }
+        if tensor.len() > len + 2 {
+            res.input_fingerprint = KeyFingerprint(*tensor.get_element(len + 2));
+            res.output_fingerprint = KeyFingerprint(*tensor.get_element(len + 3));
+        }
+        // the files saved before the keys were generic hold 64-bit keys
+        let nb_bits = if tensor.len() > len + 4 {
+            *tensor.get_element(len + 4)
+        } else {
+            64
+        };
+        assert_eq!(nb_bits, T::BITS as u64, "Wrong width of the bootstrapping key");
         res
     }
 }
 
 /// Print needed pieces of information about an LWEBSK
-impl fmt::Display for LWEBSK {
+impl<T: GenericTorus> fmt::Display for GenericLWEBSK<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let n = 2;
 
@@ -289,6 +702,17 @@ impl fmt::Display for LWEBSK {
         writeln!(f, "         -> polynomial_size = {}", self.polynomial_size)?;
         writeln!(f, "         -> base_log = {}", self.base_log)?;
         writeln!(f, "         -> level = {}", self.level)?;
+        writeln!(
+            f,
+            "         -> nb_bit_expansion = {}",
+            self.nb_bit_expansion
+        )?;
+        writeln!(f, "         -> output_kind = {:?}", self.output_kind)?;
+        writeln!(
+            f,
+            "         -> fingerprints = {} -> {}",
+            self.input_fingerprint, self.output_fingerprint
+        )?;
         writeln!(f, "}}")
     }
 }
@@ -1,26 +1,47 @@
-use crate::Torus;
+use crate::error::CryptoAPIError;
+use crate::utils::Zeroizing;
+use backtrace::Backtrace;
+use colored::Colorize;
+use crate::{GenericTorus, KeyFingerprint, Torus};
 use concrete_core::{
     crypto,
     math::tensor::Tensor,
-    math::tensor::{AsMutTensor, AsRefTensor},
+    math::tensor::{AsMutSlice, AsMutTensor, AsRefTensor},
 };
 use serde::{Deserialize, Serialize};
 use std::fmt;
 use concrete_commons::dispersion::StandardDev;
-use concrete_commons::parameters::{DecompositionBaseLog, DecompositionLevelCount, LweDimension};
-use concrete_core::crypto::secret::generators::EncryptionRandomGenerator;
+use concrete_commons::numeric::Numeric;
+use concrete_commons::parameters::{
+    DecompositionBaseLog, DecompositionLevelCount, LweDimension, LweSize,
+};
+use concrete_core::crypto::encoding::PlaintextList;
+use concrete_core::crypto::lwe::LweList;
 
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct LWEKSK {
-    pub ciphertexts: crypto::lwe::LweKeyswitchKey<Vec<Torus>>,
+pub struct GenericLWEKSK<T> {
+    pub ciphertexts: crypto::lwe::LweKeyswitchKey<Vec<T>>,
     pub base_log: usize,
     pub level: usize,
     pub dimension_before: usize,
     pub dimension_after: usize,
     pub variance: f64,
+    #[serde(default)]
+    pub kind_before: crate::SecretKeyKind,
+    /// the fingerprints of the input and output secret keys
+    #[serde(default)]
+    pub fingerprint_before: KeyFingerprint,
+    #[serde(default)]
+    pub fingerprint_after: KeyFingerprint,
 }
 
-impl LWEKSK {
+/// An LWE key switching key whose coefficients are Torus elements
+pub type LWEKSK = GenericLWEKSK<Torus>;
+
+/// An LWE key switching key whose coefficients are u32, see GenericTorus
+pub type LWEKSK32 = GenericLWEKSK<u32>;
+
+impl<T: GenericTorus> GenericLWEKSK<T> {
     /// Generate an empty LWE key switching key
     ///
     /// # Argument
@@ -36,26 +57,30 @@ impl LWEKSK {
         sk_after: &crate::LWESecretKey,
         base_log: usize,
         level: usize,
-    ) -> LWEKSK {
+    ) -> GenericLWEKSK<T> {
         let ksk_ciphertexts = crypto::lwe::LweKeyswitchKey::allocate(
-            0,
+            T::ZERO,
             DecompositionLevelCount(level),
             DecompositionBaseLog(base_log),
             LweDimension(sk_before.dimension),
             LweDimension(sk_after.dimension),
         );
 
-        LWEKSK {
+        GenericLWEKSK {
             ciphertexts: ksk_ciphertexts,
             base_log,
             level,
             dimension_before: sk_before.dimension,
             dimension_after: sk_after.dimension,
             variance: f64::powi(sk_after.std_dev, 2),
+            kind_before: sk_before.kind,
+            fingerprint_before: sk_before.fingerprint,
+            fingerprint_after: sk_after.fingerprint,
         }
     }
 
-    /// Generate a valid LWE key switching key
+    /// Generate a valid LWE key switching key, using all the available threads
+    /// when the multithread feature is enabled
     /// # Argument
     /// * `sk_before` - an LWE secret key (input for the key switch)
     /// * `sk_after` - an LWE secret key (output for the key switch)
@@ -64,39 +89,183 @@ impl LWEKSK {
     ///
     /// # Output
     /// * an LWEKSK
+    ///
+    /// It panics if the standard deviation of `sk_after` is too small for the noise to fit in the
+    /// torus of T, as the key would not contain any noise: try_new reports it as an error instead
     pub fn new(
         sk_before: &crate::LWESecretKey,
         sk_after: &crate::LWESecretKey,
         base_log: usize,
         level: usize,
-    ) -> LWEKSK {
+    ) -> GenericLWEKSK<T> {
+        // without a callback, the generation is never cancelled
+        Self::try_new(sk_before, sk_after, base_log, level)
+            .expect("The noise of the output secret key does not fit in the torus of the key")
+    }
+
+    /// Generate a valid LWE key switching key, checking that the noise of `sk_after` fits in the
+    /// torus of T
+    /// # Argument
+    /// * `sk_before` - an LWE secret key (input for the key switch)
+    /// * `sk_after` - an LWE secret key (output for the key switch)
+    /// * `base_log` - the log2 of the decomposition base
+    /// * `level` - the number of levels of the decomposition
+    ///
+    /// # Output
+    /// * an LWEKSK
+    /// * NoNoiseInCiphertext - if the standard deviation of `sk_after` is too small for the noise
+    ///   to fit in the torus
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// let sk_before = LWESecretKey::new(&LWE128_1024);
+    ///
+    /// // the noise of LWE128_2048 does not fit in 32 bits
+    /// let sk_after = LWESecretKey::new(&LWE128_2048);
+    /// assert!(LWEKSK32::try_new(&sk_before, &sk_after, 2, 6).is_err());
+    /// ```
+    pub fn try_new(
+        sk_before: &crate::LWESecretKey,
+        sk_after: &crate::LWESecretKey,
+        base_log: usize,
+        level: usize,
+    ) -> Result<GenericLWEKSK<T>, CryptoAPIError> {
+        Self::new_with_progress(sk_before, sk_after, base_log, level, |_| true)
+    }
+
+    /// Generate a valid LWE key switching key, reporting the progress of the generation
+    /// to a callback that can cancel it
+    /// # Argument
+    /// * `sk_before` - an LWE secret key (input for the key switch)
+    /// * `sk_after` - an LWE secret key (output for the key switch)
+    /// * `base_log` - the log2 of the decomposition base
+    /// * `level` - the number of levels of the decomposition
+    /// * `callback` - a function called with the fraction of the key already generated (ending
+    ///   with 1.), returning false to cancel the generation
+    ///
+    /// # Output
+    /// * an LWEKSK
+    /// * CancelledError - if the callback returned false
+    /// * NoNoiseInCiphertext - if the standard deviation of `sk_after` is too small for the noise
+    ///   to fit in the torus
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// let sk_before = LWESecretKey::new(&LWE128_1024);
+    /// let sk_after = LWESecretKey::new(&LWE128_630);
+    ///
+    /// // count the progress reports
+    /// let mut nb_reports: usize = 0;
+    /// let ksk = LWEKSK::new_with_progress(&sk_before, &sk_after, 2, 6, |_| {
+    ///     nb_reports += 1;
+    ///     true
+    /// })
+    /// .unwrap();
+    /// assert!(nb_reports > 0);
+    ///
+    /// // cancel the generation right away
+    /// let cancelled = LWEKSK::new_with_progress(&sk_before, &sk_after, 2, 6, |_| false);
+    /// assert!(cancelled.is_err());
+    /// ```
+    pub fn new_with_progress<F: FnMut(f64) -> bool + Send>(
+        sk_before: &crate::LWESecretKey,
+        sk_after: &crate::LWESecretKey,
+        base_log: usize,
+        level: usize,
+        mut callback: F,
+    ) -> Result<GenericLWEKSK<T>, CryptoAPIError> {
+        crate::generic_torus::check_std_dev::<T>(sk_after.std_dev)?;
+
+        // the generation itself is not generic, so that it is compiled once with the library
+        T::generate_ksk(sk_before, sk_after, base_log, level, &mut callback)
+    }
+
+    /// Generate a key switching key, reporting the progress to `callback`
+    ///
+    /// # Output
+    /// * the key
+    /// * CancelledError - if the callback returned false, the key not being finished
+    pub(crate) fn generate(
+        sk_before: &crate::LWESecretKey,
+        sk_after: &crate::LWESecretKey,
+        base_log: usize,
+        level: usize,
+        callback: &mut (dyn FnMut(f64) -> bool + Send),
+    ) -> Result<GenericLWEKSK<T>, CryptoAPIError> {
         let mut ksk_ciphertexts = crypto::lwe::LweKeyswitchKey::allocate(
-            0_u64,
+            T::ZERO,
             DecompositionLevelCount(level),
             DecompositionBaseLog(base_log),
             LweDimension(sk_before.dimension),
             LweDimension(sk_after.dimension),
         );
 
-        ksk_ciphertexts.fill_with_keyswitch_key(
-            &sk_before.val,
-            &sk_after.val,
-            StandardDev::from_standard_dev(sk_after.std_dev),
-            &mut EncryptionRandomGenerator::new(None),
-        );
+        // copies of the keys in the torus of T, wiped when dropped
+        let sk_before_val = Zeroizing(sk_before.to_generic_coefficients::<T>());
+        let sk_after_val = Zeroizing(sk_after.to_generic_coefficients::<T>());
+
+        // each coefficient s of the input key is encrypted under the output key, viewed with its
+        // own kind, as the LWE list of the s * q / B^(j+1)
+        if let Some(progress) = crate::utils::fill_with_progress(
+            ksk_ciphertexts.as_mut_tensor().as_mut_slice(),
+            sk_before.dimension,
+            |i, block, generator| {
+                let messages = Zeroizing(
+                    (1..=level)
+                        .map(|j| sk_before_val[i] << (<T as Numeric>::BITS - base_log * j))
+                        .collect(),
+                );
+                with_lwe_secret_key!(sk_after.kind, sk_after_val.as_slice(), |key| {
+                    key.encrypt_lwe_list(
+                        &mut LweList::from_container(block, LweSize(sk_after.dimension + 1)),
+                        &PlaintextList::from_container(messages.as_slice()),
+                        StandardDev::from_standard_dev(sk_after.std_dev),
+                        generator,
+                    )
+                })
+            },
+            callback,
+        ) {
+            return Err(CancelledError!(progress));
+        }
 
-        LWEKSK {
+        let ksk = GenericLWEKSK {
             ciphertexts: ksk_ciphertexts,
             base_log,
             level,
             dimension_before: sk_before.dimension,
             dimension_after: sk_after.dimension,
             variance: f64::powi(sk_after.std_dev, 2),
-        }
+            kind_before: sk_before.kind,
+            fingerprint_before: sk_before.fingerprint,
+            fingerprint_after: sk_after.fingerprint,
+        };
+        Ok(ksk)
     }
 
+    /// Return the variance of the output of a key switch, given the kind of the input key
+    ///
+    /// # Argument
+    /// * `variance` - the variance of the input LWE ciphertext
+    pub(crate) fn get_output_variance(&self, variance: f64) -> f64 {
+        self.kind_before.get_keyswitch_variance_in::<T>(
+            self.dimension_before,
+            self.level,
+            self.base_log,
+            self.variance,
+            variance,
+        )
+    }
+}
+
+impl<T: GenericTorus> GenericLWEKSK<T> {
     pub fn save(&self, path: &str) {
-        let mut tensor = Tensor::allocate(0, self.ciphertexts.as_tensor().len() + 6);
+        let mut tensor: Tensor<Vec<u64>> =
+            Tensor::allocate(0, self.ciphertexts.as_tensor().len() + 10);
 
         *tensor.get_element_mut(0) = self.variance.to_bits();
         *tensor.get_element_mut(1) = self.dimension_before as u64;
@@ -110,39 +279,64 @@ impl LWEKSK {
             .iter_mut()
             .zip(self.ciphertexts.as_tensor().iter())
         {
-            *dst = *src;
+            *dst = (*src).cast_into();
         }
+        // the kind of the input key and the fingerprints come last so that older files can
+        // still be loaded
+        let len = self.ciphertexts.as_tensor().len();
+        *tensor.get_element_mut(len + 6) = self.kind_before.to_torus();
+        *tensor.get_element_mut(len + 7) = self.fingerprint_before.0;
+        *tensor.get_element_mut(len + 8) = self.fingerprint_after.0;
+        *tensor.get_element_mut(len + 9) = T::BITS as u64;
         tensor.save_to_file(path).unwrap();
     }
 
-    pub fn load(path: &str) -> crate::LWEKSK {
+    pub fn load(path: &str) -> GenericLWEKSK<T> {
         let tensor: Tensor<Vec<u64>> = Tensor::load_from_file(path).unwrap();
 
-        let mut res = crate::LWEKSK {
+        let mut res = GenericLWEKSK {
             variance: f64::from_bits(*tensor.get_element(0)),
             dimension_before: *tensor.get_element(1) as usize,
             dimension_after: *tensor.get_element(2) as usize,
             base_log: *tensor.get_element(3) as usize,
             level: *tensor.get_element(4) as usize,
             ciphertexts: crypto::lwe::LweKeyswitchKey::allocate(
-                0,
+                T::ZERO,
                 DecompositionLevelCount(*tensor.get_element(4) as usize),
                 DecompositionBaseLog(*tensor.get_element(3) as usize),
                 LweDimension(*tensor.get_element(1) as usize),
                 LweDimension(*tensor.get_element(2) as usize),
             ),
+            kind_before: crate::SecretKeyKind::Binary,
+            fingerprint_before: KeyFingerprint::UNKNOWN,
+            fingerprint_after: KeyFingerprint::UNKNOWN,
         };
 
+        let len = res.ciphertexts.as_tensor().len();
         res.ciphertexts
             .as_mut_tensor()
-            .fill_with_one(&tensor.get_sub(6..), |v| *v);
+            .fill_with_one(&tensor.get_sub(6..(len + 6)), |v| T::cast_from(*v));
+        if tensor.len() > len + 6 {
+            res.kind_before = crate::SecretKeyKind::from_torus(*tensor.get_element(len + 6));
+        }
+        if tensor.len() > len + 7 {
+            res.fingerprint_before = KeyFingerprint(*tensor.get_element(len + 7));
+            res.fingerprint_after = KeyFingerprint(*tensor.get_element(len + 8));
+        }
+        // the files saved before the keys were generic hold 64-bit keys
+        let nb_bits = if tensor.len() > len + 9 {
+            *tensor.get_element(len + 9)
+        } else {
+            64
+        };
+        assert_eq!(nb_bits, T::BITS as u64, "Wrong width of the key switching key");
 
         res
     }
 }
 
 /// Print needed pieces of information about an LWEKSK
-impl fmt::Display for LWEKSK {
+impl<T: GenericTorus> fmt::Display for GenericLWEKSK<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let n = 2;
         let mut to_be_print: String = "".to_string();
@@ -175,6 +369,12 @@ impl fmt::Display for LWEKSK {
             to_be_print + &format!("         -> dimension before = {}\n", self.dimension_before);
         to_be_print =
             to_be_print + &format!("         -> dimension after = {}\n", self.dimension_after);
+        to_be_print = to_be_print + &format!("         -> kind before = {:?}\n", self.kind_before);
+        to_be_print = to_be_print
+            + &format!(
+                "         -> fingerprints = {} -> {}\n",
+                self.fingerprint_before, self.fingerprint_after
+            );
 
         to_be_print = to_be_print + &format!("         -> base_log = {}\n", self.base_log);
         to_be_print = to_be_print + &format!("         -> level = {}\n", self.level);
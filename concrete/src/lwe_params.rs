@@ -1,6 +1,10 @@
 //! lwe_params module describing the LWEParams structure
 
 use super::{read_from_file, write_to_file};
+use crate::error::CryptoAPIError;
+use crate::SecretKeyKind;
+use backtrace::Backtrace;
+use colored::Colorize;
 use serde::{Deserialize, Serialize};
 use std::error::Error;
 use std::fmt;
@@ -175,6 +179,70 @@ impl LWEParams {
         }
     }
 
+    /// Instantiate a new LWEParams with the provided dimension and standard deviation,
+    /// checking that they provide enough security according to estimated_security_bits,
+    /// rounded to the closest integer
+    /// # Arguments
+    /// * `dimension` -the size of an LWE mask
+    /// * `log2_std_dev` -the log2 of the standard deviation used for the error normal distribution
+    /// * `min_security` -the minimal number of bits of security
+    /// # Output
+    /// * a new instantiation of an LWEParams
+    /// * SecurityError - if the estimated security is below `min_security`
+    /// # Example
+    /// ```rust
+    /// use concrete::LWEParams;
+    ///
+    /// assert!(LWEParams::new_checked(630, -14, 128).is_ok());
+    /// assert!(LWEParams::new_checked(630, -24, 128).is_err());
+    /// ```
+    pub fn new_checked(
+        dimension: usize,
+        log2_std_dev: i32,
+        min_security: usize,
+    ) -> Result<LWEParams, CryptoAPIError> {
+        let params = LWEParams::new(dimension, log2_std_dev);
+        let security = params.estimated_security_bits();
+        // the parameter sets of this module only reach their security level up to rounding errors
+        if security.round() < min_security as f64 {
+            return Err(SecurityError!(security, min_security));
+        }
+        Ok(params)
+    }
+
+    /// Return an estimation of the number of bits of security of the parameters,
+    /// for binary secret keys and a modulus of 2^64, with params::estimate_security_bits
+    /// # Output
+    /// * the estimated number of bits of security
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// assert_eq!(LWE128_630.estimated_security_bits().round(), 128.);
+    /// assert_eq!(LWE80_1024.estimated_security_bits().round(), 80.);
+    /// assert!(LWEParams::new(1024, -30).estimated_security_bits() < 128.);
+    /// ```
+    pub fn estimated_security_bits(&self) -> f64 {
+        self.estimated_security_bits_with_kind(SecretKeyKind::Binary)
+    }
+
+    /// Return an estimation of the number of bits of security of the parameters,
+    /// for secret keys of a given kind and a modulus of 2^64
+    /// # Argument
+    /// * `kind` - the distribution of the coefficients of the secret key
+    /// # Output
+    /// * the estimated number of bits of security
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// let binary = LWE128_630.estimated_security_bits();
+    /// assert!(LWE128_630.estimated_security_bits_with_kind(SecretKeyKind::Ternary) > binary);
+    /// ```
+    pub fn estimated_security_bits_with_kind(&self, kind: SecretKeyKind) -> f64 {
+        crate::params::estimate_security_bits(self.dimension, self.log2_std_dev as f64, kind)
+    }
+
     pub fn get_std_dev(&self) -> f64 {
         f64::powi(2., self.log2_std_dev)
     }
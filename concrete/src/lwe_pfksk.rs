@@ -0,0 +1,271 @@
+//! lwe_pfksk module describing the LWEPFKSK structure
+
+use super::{read_from_file, write_to_file};
+use crate::utils::Zeroizing;
+use crate::{KeyFingerprint, SecretKeyKind, Torus};
+use concrete_commons::dispersion::StandardDev;
+use concrete_commons::numeric::Numeric;
+use concrete_commons::parameters::{CiphertextCount, GlweDimension, PolynomialSize};
+use concrete_core::crypto::encoding::PlaintextList;
+use concrete_core::crypto::glwe::GlweList;
+use concrete_core::crypto::secret::generators::EncryptionRandomGenerator;
+use concrete_core::math::tensor::{AsRefSlice, AsRefTensor};
+use itertools::izip;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt;
+
+/// Structure containing the private functional key switching keys used by the circuit bootstrap
+/// They switch an LWE ciphertext of a message m to RLWE ciphertexts of F_i * m,
+/// where F_i = -S_i for each polynomial S_i of the output RLWE secret key and F_dimension_after = 1
+/// For each function F_i, each coefficient s_p of the input key (plus a last coefficient equal to -1
+/// for the body) and each level j of the decomposition, it stores an RLWE encryption
+/// under the output key of F_i * s_p * q / B^(j+1)
+///
+/// # Attributes
+/// * `ciphertexts` - the RLWE encryptions, ordered by function, then by input key coefficient and then by level
+/// * `base_log` - the log2 of the decomposition base
+/// * `level` - the number of levels of the decomposition
+/// * `dimension_before` - the dimension of the input LWE secret key
+/// * `dimension_after` - the dimension of the output RLWE secret key
+/// * `polynomial_size` - the number of coefficients in a polynomial
+/// * `variance` - the variance of the noise of the encryptions
+/// * `kind_before` - the kind of the input LWE secret key (binary for older keys)
+/// * `kind_after` - the kind of the output RLWE secret key (binary for older keys)
+/// * `fingerprint_before` - the fingerprint of the input LWE secret key (unknown for older keys)
+/// * `fingerprint_after` - the fingerprint of the output RLWE secret key (unknown for older keys)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LWEPFKSK {
+    pub ciphertexts: GlweList<Vec<Torus>>,
+    pub base_log: usize,
+    pub level: usize,
+    pub dimension_before: usize,
+    pub dimension_after: usize,
+    pub polynomial_size: usize,
+    pub variance: f64,
+    #[serde(default)]
+    pub kind_before: SecretKeyKind,
+    #[serde(default)]
+    pub kind_after: SecretKeyKind,
+    #[serde(default)]
+    pub fingerprint_before: KeyFingerprint,
+    #[serde(default)]
+    pub fingerprint_after: KeyFingerprint,
+}
+
+impl LWEPFKSK {
+    /// Generate the private functional key switching keys of a circuit bootstrap
+    /// `sk_before` is usually the LWE secret key obtained from the RLWE secret key of the bootstrapping key
+    ///
+    /// # Argument
+    /// * `sk_before` - an LWE secret key (input for the key switch)
+    /// * `sk_after` - an RLWE secret key (output for the key switch)
+    /// * `base_log` - the log2 of the decomposition base
+    /// * `level` - the number of levels of the decomposition
+    ///
+    /// # Output
+    /// * an LWEPFKSK
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// // generate the secret keys
+    /// let rlwe_sk = RLWESecretKey::new(&RLWE128_256_1);
+    /// let sk_before = rlwe_sk.to_lwe_secret_key();
+    ///
+    /// // generate the private functional key switching keys
+    /// let pfksk = LWEPFKSK::new(&sk_before, &rlwe_sk, 10, 3);
+    /// ```
+    pub fn new(
+        sk_before: &crate::LWESecretKey,
+        sk_after: &crate::RLWESecretKey,
+        base_log: usize,
+        level: usize,
+    ) -> LWEPFKSK {
+        let polynomial_size = sk_after.polynomial_size;
+        let nb_functions = sk_after.dimension + 1;
+        let nb_coefficients = sk_before.dimension + 1;
+        let mut res = LWEPFKSK {
+            ciphertexts: GlweList::allocate(
+                0,
+                PolynomialSize(polynomial_size),
+                GlweDimension(sk_after.dimension),
+                CiphertextCount(nb_functions * nb_coefficients * level),
+            ),
+            base_log,
+            level,
+            dimension_before: sk_before.dimension,
+            dimension_after: sk_after.dimension,
+            polynomial_size,
+            variance: sk_after.get_variance(),
+            kind_before: sk_before.kind,
+            kind_after: sk_after.kind,
+            fingerprint_before: sk_before.fingerprint,
+            fingerprint_after: sk_after.fingerprint,
+        };
+
+        // the functions F_i = -S_i and F_dimension_after = 1
+        let mut functions = Zeroizing(vec![0 as Torus; nb_functions * polynomial_size]);
+        for (f, s) in izip!(functions.iter_mut(), sk_after.val.as_slice().iter()) {
+            *f = s.wrapping_neg();
+        }
+        functions[sk_after.dimension * polynomial_size] = 1;
+
+        // the input key coefficients with -1 for the body, allocated once so that no copy is left
+        // behind by a reallocation
+        let mut key_before = Zeroizing(Vec::with_capacity(nb_coefficients));
+        key_before.extend_from_slice(sk_before.val.as_slice());
+        key_before.push(Torus::MAX);
+
+        // build the plaintexts F_i * s_p * q / B^(j+1)
+        let mut plaintexts =
+            Zeroizing(vec![
+                0 as Torus;
+                nb_functions * nb_coefficients * level * polynomial_size
+            ]);
+        for (pt_function, function) in izip!(
+            plaintexts.chunks_mut(nb_coefficients * level * polynomial_size),
+            functions.chunks(polynomial_size)
+        ) {
+            for (pt_levels, s) in izip!(
+                pt_function.chunks_mut(level * polynomial_size),
+                key_before.iter()
+            ) {
+                for (j, pt) in pt_levels.chunks_mut(polynomial_size).enumerate() {
+                    let factor: Torus =
+                        s.wrapping_mul(1 << (<Torus as Numeric>::BITS - base_log * (j + 1)));
+                    for (p, f) in izip!(pt.iter_mut(), function.iter()) {
+                        *p = f.wrapping_mul(factor);
+                    }
+                }
+            }
+        }
+
+        // encrypt them with the output key
+        with_glwe_secret_key!(
+            sk_after.kind,
+            sk_after.val.as_slice(),
+            sk_after.polynomial_size,
+            |key| {
+                key.encrypt_glwe_list(
+                    &mut res.ciphertexts,
+                    &PlaintextList::from_container(plaintexts.as_slice()),
+                    StandardDev::from_standard_dev(sk_after.std_dev),
+                    &mut EncryptionRandomGenerator::new(None),
+                )
+            }
+        );
+
+        res
+    }
+
+    /// Private functional key switch of one raw LWE ciphertext with the function F_i
+    /// `ct_out` is overwritten with an RLWE ciphertext of F_i * m
+    ///
+    /// # Argument
+    /// * `function` - the index i of the function F_i
+    /// * `ct_out` - the output RLWE ciphertext, of size (dimension_after + 1) * polynomial_size
+    /// * `ct_in` - the input LWE ciphertext, of size dimension_before + 1
+    pub(crate) fn private_functional_keyswitch_raw(
+        &self,
+        function: usize,
+        ct_out: &mut [Torus],
+        ct_in: &[Torus],
+    ) {
+        let ct_size = (self.dimension_after + 1) * self.polynomial_size;
+        let function_size = (self.dimension_before + 1) * self.level * ct_size;
+        let ksk = &self.ciphertexts.as_tensor().as_slice()
+            [(function * function_size)..((function + 1) * function_size)];
+
+        for elt in ct_out.iter_mut() {
+            *elt = 0;
+        }
+
+        // subtract the decomposition of every coefficient (mask and body) times the key
+        for (coeff, ksk_levels) in izip!(ct_in.iter(), ksk.chunks(self.level * ct_size)) {
+            for (digit, ksk_ct) in izip!(
+                crate::rlwe_ksk::signed_decompose(*coeff, self.base_log, self.level).iter(),
+                ksk_levels.chunks(ct_size)
+            ) {
+                if *digit == 0 {
+                    continue;
+                }
+                for (out, k) in izip!(ct_out.iter_mut(), ksk_ct.iter()) {
+                    *out = out.wrapping_sub(digit.wrapping_mul(*k));
+                }
+            }
+        }
+    }
+
+    /// Return the variance of the output of a private functional key switch with the function F_i
+    /// The input noise is multiplied by F_i, so it grows with the polynomial size and the second
+    /// moment of the output key for F_i = -S_i, and the rounding noise depends on the input key
+    ///
+    /// # Argument
+    /// * `function` - the index i of the function F_i
+    /// * `variance` - the variance of the input LWE ciphertext
+    pub(crate) fn get_output_variance(&self, function: usize, variance: f64) -> f64 {
+        let variance_function = if function == self.dimension_after {
+            variance
+        } else {
+            variance * (self.polynomial_size as f64) * self.kind_after.get_second_moment()
+        };
+        self.kind_before.get_keyswitch_variance(
+            self.dimension_before + 1,
+            self.level,
+            self.base_log,
+            self.variance,
+            variance_function,
+        )
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), Box<dyn Error>> {
+        write_to_file(path, self)
+    }
+
+    pub fn load(path: &str) -> Result<LWEPFKSK, Box<dyn Error>> {
+        read_from_file(path)
+    }
+}
+
+/// Print needed pieces of information about an LWEPFKSK
+impl fmt::Display for LWEPFKSK {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let n = 2;
+
+        write!(f, "LWEPFKSK {{\n         -> samples = [")?;
+
+        if self.ciphertexts.as_tensor().len() <= 2 * n {
+            for elt in self.ciphertexts.as_tensor().iter() {
+                write!(f, "{}, ", *elt)?;
+            }
+        } else {
+            for elt in self.ciphertexts.as_tensor().get_sub(0..n).iter() {
+                write!(f, "{}, ", *elt)?;
+            }
+            write!(f, "...")?;
+
+            for elt in self
+                .ciphertexts
+                .as_tensor()
+                .get_sub(self.ciphertexts.as_tensor().len() - n..)
+                .iter()
+            {
+                write!(f, "{}, ", *elt)?;
+            }
+        }
+        writeln!(f, "]")?;
+        writeln!(f, "         -> variance = {}", self.variance)?;
+        writeln!(
+            f,
+            "         -> dimension before = {}",
+            self.dimension_before
+        )?;
+        writeln!(f, "         -> dimension after = {}", self.dimension_after)?;
+        writeln!(f, "         -> polynomial_size = {}", self.polynomial_size)?;
+        writeln!(f, "         -> base_log = {}", self.base_log)?;
+        writeln!(f, "         -> level = {}", self.level)?;
+        writeln!(f, "}}")
+    }
+}
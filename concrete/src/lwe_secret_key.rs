@@ -1,23 +1,31 @@
 use super::{read_from_file, write_to_file};
 use crate::error::CryptoAPIError;
+use crate::utils::Zeroizing;
 use backtrace::Backtrace;
 use colored::Colorize;
-use concrete_core::{
-    crypto::secret::{GlweSecretKey, LweSecretKey},
-    math::tensor::IntoTensor,
-};
 use serde::{Deserialize, Serialize};
 use std::error::Error;
 use std::fmt;
-use concrete_commons::key_kinds::BinaryKeyKind;
-use concrete_commons::parameters::{LweDimension, PolynomialSize};
-use concrete_core::crypto::secret::generators::SecretRandomGenerator;
+use crate::{GenericTorus, KeyFingerprint, SecretKeyKind, Torus};
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+/// Structure describing an LWE secret key
+/// # Attributes
+/// * `val` - the coefficients of the key, stored as Torus elements whatever `kind` is
+/// * `dimension` - the length of the LWE mask
+/// * `std_dev` - the standard deviation for the encryption
+/// * `kind` - the distribution the coefficients were sampled from (binary for older keys)
+/// * `fingerprint` - the public identifier of the key (unknown for older keys)
+///
+/// The coefficients are wiped when the key is dropped, never printed, and the key cannot be cloned
+#[derive(Serialize, Deserialize, PartialEq)]
 pub struct LWESecretKey {
-    pub val: LweSecretKey<BinaryKeyKind, Vec<u64>>,
+    pub(crate) val: LweKeyCoefficients,
     pub dimension: usize,
     pub std_dev: f64,
+    #[serde(default)]
+    pub kind: SecretKeyKind,
+    #[serde(default)]
+    pub fingerprint: KeyFingerprint,
 }
 
 impl LWESecretKey {
@@ -27,13 +35,23 @@ impl LWESecretKey {
     /// # Output
     /// * a new LWESecretKey
     pub fn new(params: &crate::LWEParams) -> LWESecretKey {
-        let val =  LweSecretKey::generate_binary(LweDimension(params.dimension), &mut SecretRandomGenerator::new
-            (None));
-        LWESecretKey {
-            val,
-            dimension: params.dimension,
-            std_dev: params.get_std_dev(),
-        }
+        LWESecretKey::new_with_kind(params, SecretKeyKind::Binary)
+    }
+
+    /// Generate a new secret key of a given kind from an LWEParams
+    /// # Argument
+    /// * `params` - an LWEParams instance
+    /// * `kind` - the distribution of the coefficients of the key
+    /// # Output
+    /// * a new LWESecretKey
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// let secret_key = LWESecretKey::new_with_kind(&LWE128_630, SecretKeyKind::Ternary);
+    /// ```
+    pub fn new_with_kind(params: &crate::LWEParams, kind: SecretKeyKind) -> LWESecretKey {
+        LWESecretKey::new_raw_with_kind(params.dimension, params.get_std_dev(), kind)
     }
 
     /// Generate a new secret key from a raw dimension (i.e. without a LWEParams input)
@@ -43,12 +61,52 @@ impl LWESecretKey {
     /// # Output
     /// * a new LWESecretKey
     pub fn new_raw(dimension: usize, std_dev: f64) -> LWESecretKey {
-        let val = LweSecretKey::generate_binary(LweDimension(dimension), &mut SecretRandomGenerator::new
-            (None));
+        LWESecretKey::new_raw_with_kind(dimension, std_dev, SecretKeyKind::Binary)
+    }
+
+    /// Generate a new secret key of a given kind from a raw dimension (i.e. without a LWEParams input)
+    /// # Argument
+    /// * `dimension` s the length the LWE mask
+    /// * `std_dev` - the standard deviation for the encryption
+    /// * `kind` - the distribution of the coefficients of the key
+    /// # Output
+    /// * a new LWESecretKey
+    pub fn new_raw_with_kind(dimension: usize, std_dev: f64, kind: SecretKeyKind) -> LWESecretKey {
         LWESecretKey {
-            val,
+            val: LweKeyCoefficients::new(kind.generate_lwe_secret_key(dimension)),
             dimension,
             std_dev,
+            kind,
+            fingerprint: KeyFingerprint::new(dimension, std_dev, kind),
+        }
+    }
+
+    /// Build a secret key from existing coefficients, e.g. the ones of the key of a client key of
+    /// concrete-boolean encrypting the same ciphertexts
+    ///
+    /// The fingerprint is not derived from the coefficients but trusted: a wrong one would defeat
+    /// the key mismatch checks, so this is only meant for the bridge of concrete-boolean, and is
+    /// hidden from the documentation
+    /// # Argument
+    /// * `coefficients` - the coefficients of the key, sampled from the distribution of `kind`
+    /// * `std_dev` - the standard deviation for the encryption
+    /// * `kind` - the distribution the coefficients were sampled from
+    /// * `fingerprint` - the fingerprint of the original key
+    /// # Output
+    /// * a new LWESecretKey, whose dimension is the number of coefficients
+    #[doc(hidden)]
+    pub fn from_coefficients(
+        coefficients: Vec<Torus>,
+        std_dev: f64,
+        kind: SecretKeyKind,
+        fingerprint: KeyFingerprint,
+    ) -> LWESecretKey {
+        LWESecretKey {
+            dimension: coefficients.len(),
+            val: LweKeyCoefficients::new(coefficients),
+            std_dev,
+            kind,
+            fingerprint,
         }
     }
 
@@ -65,16 +123,37 @@ impl LWESecretKey {
             return Err(LweToRlweError!(self.dimension, polynomial_size));
         }
         Ok(crate::RLWESecretKey {
-            val: GlweSecretKey::binary_from_container(
-                self.val.clone().into_tensor().into_container(),
-                PolynomialSize(polynomial_size),
-            ),
+            val: crate::GlweKeyCoefficients::new(self.val.as_slice().to_vec(), polynomial_size),
             dimension: self.dimension / polynomial_size,
             polynomial_size,
             std_dev: self.std_dev,
+            kind: self.kind,
+            fingerprint: self.fingerprint,
         })
     }
 
+    /// Copy the coefficients of the key into elements of the torus of T, the copy having to be
+    /// wiped by the caller
+    pub(crate) fn to_generic_coefficients<T: GenericTorus>(&self) -> Vec<T> {
+        self.val
+            .as_slice()
+            .iter()
+            .map(|s| T::cast_from(*s))
+            .collect()
+    }
+
+    /// Run a function on a copy of the coefficients of the key in the torus of T, the copy being
+    /// wiped afterwards
+    /// The function can view them as a key of concrete-core with with_lwe_secret_key!
+    pub(crate) fn with_generic_coefficients<T, R, F>(&self, f: F) -> R
+    where
+        T: GenericTorus,
+        F: FnOnce(&[T]) -> R,
+    {
+        let coefficients = Zeroizing(self.to_generic_coefficients::<T>());
+        f(&coefficients)
+    }
+
     /// Return the variance of the error distribution associated with the secret key
     /// # Output
     /// * a variance
@@ -91,13 +170,54 @@ impl LWESecretKey {
     }
 }
 
+/// The coefficients of an LWE secret key of any kind
+/// They are serialized with the layout of the binary LweSecretKey of concrete-core which stored
+/// them before the other kinds were introduced, so that the older key files can still be read,
+/// and they are wiped when dropped
+#[derive(Serialize, Deserialize, PartialEq)]
+pub(crate) struct LweKeyCoefficients {
+    tensor: Vec<Torus>,
+    kind: (),
+}
+
+impl LweKeyCoefficients {
+    pub(crate) fn new(coefficients: Vec<Torus>) -> LweKeyCoefficients {
+        LweKeyCoefficients {
+            tensor: coefficients,
+            kind: (),
+        }
+    }
+
+    pub(crate) fn as_slice(&self) -> &[Torus] {
+        &self.tensor
+    }
+}
+
+impl Drop for LweKeyCoefficients {
+    fn drop(&mut self) {
+        crate::utils::zeroize(&mut self.tensor);
+    }
+}
+
+/// Print the LWESecretKey without its coefficients
+impl fmt::Debug for LWESecretKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("LWESecretKey")
+            .field("dimension", &self.dimension)
+            .field("std_dev", &self.std_dev)
+            .field("kind", &self.kind)
+            .field("fingerprint", &self.fingerprint)
+            .finish_non_exhaustive()
+    }
+}
+
 impl fmt::Display for LWESecretKey {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let mut to_be_print: String = "".to_string();
         to_be_print = to_be_print
             + &format!(
-                " LWESecretKey {{\n         -> dimension = {}\n         -> std_dev = {}\n",
-                self.dimension, self.std_dev
+                " LWESecretKey {{\n         -> dimension = {}\n         -> std_dev = {}\n         -> kind = {:?}\n         -> fingerprint = {}\n",
+                self.dimension, self.std_dev, self.kind, self.fingerprint
             );
         to_be_print += "       }";
 
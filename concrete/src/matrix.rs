@@ -0,0 +1,116 @@
+//! matrix module describing a clear matrix used to compute products with ciphertexts
+use super::{read_from_file, write_to_file};
+use crate::error::CryptoAPIError;
+use backtrace::Backtrace;
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt;
+
+/// Structure describing a clear matrix of real values stored row by row
+/// # Attributes
+/// * `values` - the coefficients of the matrix, row after row
+/// * `nb_rows` - the number of rows
+/// * `nb_cols` - the number of columns
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Matrix {
+    pub values: Vec<f64>,
+    pub nb_rows: usize,
+    pub nb_cols: usize,
+}
+
+impl Matrix {
+    /// Instantiate a new Matrix from its coefficients given row after row
+    ///
+    /// # Argument
+    /// * `values` - the coefficients of the matrix, row after row
+    /// * `nb_rows` - the number of rows
+    /// * `nb_cols` - the number of columns
+    ///
+    /// # Output
+    /// * a new Matrix
+    /// * WrongSizeError - if `values` does not contain `nb_rows * nb_cols` coefficients
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::Matrix;
+    ///
+    /// // a 2x3 matrix
+    /// let matrix = Matrix::new(vec![1., 2., 3., 4., 5., 6.], 2, 3).unwrap();
+    /// ```
+    pub fn new(values: Vec<f64>, nb_rows: usize, nb_cols: usize) -> Result<Matrix, CryptoAPIError> {
+        if values.is_empty() || values.len() != nb_rows * nb_cols {
+            return Err(WrongSizeError!(values.len()));
+        }
+        Ok(Matrix {
+            values,
+            nb_rows,
+            nb_cols,
+        })
+    }
+
+    /// Instantiate a new Matrix from a list of rows of integer or real coefficients
+    ///
+    /// # Argument
+    /// * `rows` - the rows of the matrix
+    ///
+    /// # Output
+    /// * a new Matrix
+    /// * WrongSizeError - if the rows are empty or do not have the same length
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::Matrix;
+    ///
+    /// // a 2x3 matrix
+    /// let matrix = Matrix::from_rows(&[vec![1, -2, 3], vec![0, 5, -1]]).unwrap();
+    /// ```
+    pub fn from_rows<C: Into<f64> + Copy>(rows: &[Vec<C>]) -> Result<Matrix, CryptoAPIError> {
+        let nb_cols = rows.first().map_or(0, |row| row.len());
+        let mut values: Vec<f64> = Vec::with_capacity(rows.len() * nb_cols);
+        for row in rows.iter() {
+            if row.len() != nb_cols {
+                return Err(WrongSizeError!(row.len()));
+            }
+            values.extend(row.iter().map(|c| (*c).into()));
+        }
+        Matrix::new(values, rows.len(), nb_cols)
+    }
+
+    /// Return the n-th row of the matrix
+    ///
+    /// # Argument
+    /// * `n` - the index of the row
+    ///
+    /// # Output
+    /// * a slice containing the coefficients of the row
+    /// * IndexError - if n >= self.nb_rows
+    pub fn get_row(&self, n: usize) -> Result<&[f64], CryptoAPIError> {
+        if n >= self.nb_rows {
+            return Err(IndexError!(self.nb_rows, n));
+        }
+        Ok(&self.values[(n * self.nb_cols)..((n + 1) * self.nb_cols)])
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), Box<dyn Error>> {
+        write_to_file(path, self)
+    }
+
+    pub fn load(path: &str) -> Result<Matrix, Box<dyn Error>> {
+        read_from_file(path)
+    }
+}
+
+impl fmt::Display for Matrix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Matrix {{ nb_rows: {}, nb_cols: {} }}",
+            self.nb_rows, self.nb_cols
+        )?;
+        for row in self.values.chunks(self.nb_cols.max(1)) {
+            writeln!(f, "{:?}", row)?;
+        }
+        Ok(())
+    }
+}
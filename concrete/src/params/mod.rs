@@ -0,0 +1,473 @@
+//! params module searching the parameters of a bootstrap followed by a key switch
+//! for a given precision, padding and security level
+
+#[cfg(test)]
+mod tests;
+
+use crate::error::CryptoAPIError;
+use crate::{read_from_file, write_to_file, SecretKeyKind, Torus};
+use backtrace::Backtrace;
+use colored::Colorize;
+use concrete_commons::numeric::Numeric;
+use concrete_npe as npe;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt;
+
+/// The maximal probability of a wrong bootstrap accepted by optimize (2^-40)
+pub const MAX_ERROR_PROBABILITY: f64 = 1. / 1_099_511_627_776.;
+
+/// The LWE dimensions explored by optimize
+const LWE_DIMENSIONS: std::ops::RangeInclusive<usize> = 256..=1536;
+const LWE_DIMENSION_STEP: usize = 16;
+
+/// The RLWE dimensions and the log2 of the polynomial sizes explored by optimize
+const RLWE_DIMENSIONS: std::ops::RangeInclusive<usize> = 1..=2;
+const POLYNOMIAL_SIZES_LOG: std::ops::RangeInclusive<usize> = 8..=14;
+
+/// The maximal numbers of levels of the decompositions explored by optimize
+const MAX_BSK_LEVEL: usize = 10;
+const MAX_KSK_LEVEL: usize = 16;
+
+/// Structure describing a complete set of parameters to encrypt, bootstrap and key switch
+/// # Attributes
+/// * `lwe_params` - the parameters of the LWE secret key (input of the bootstrap, output of the key switch)
+/// * `rlwe_params` - the parameters of the RLWE secret key (output of the bootstrap, input of the key switch)
+/// * `bsk_base_log` - the log2 of the decomposition base of the bootstrapping key
+/// * `bsk_level` - the number of levels of the decomposition of the bootstrapping key
+/// * `ksk_base_log` - the log2 of the decomposition base of the key switching key
+/// * `ksk_level` - the number of levels of the decomposition of the key switching key
+/// * `precision` - the number of bits of precision of the encoders
+/// * `padding` - the number of bits of padding of the encoders, including the one consumed by the bootstrap
+/// * `error_probability` - the probability that a bootstrap outputs a wrong message
+/// * `cost` - the estimated number of elementary operations of a bootstrap followed by a key switch
+/// * `security_bits` - the estimated security of the weakest of the two secret keys
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ParameterBundle {
+    pub lwe_params: crate::LWEParams,
+    pub rlwe_params: crate::RLWEParams,
+    pub bsk_base_log: usize,
+    pub bsk_level: usize,
+    pub ksk_base_log: usize,
+    pub ksk_level: usize,
+    pub precision: usize,
+    pub padding: usize,
+    pub error_probability: f64,
+    pub cost: f64,
+    pub security_bits: f64,
+}
+
+impl ParameterBundle {
+    /// Instantiate a new Encoder with the precision and the padding of the parameters
+    ///
+    /// # Argument
+    /// * `min` - the minimum value of the interval
+    /// * `max` - the maximum value of the interval
+    ///
+    /// # Output
+    /// * a new Encoder
+    /// * MinMaxError - if min >= max
+    pub fn new_encoder(&self, min: f64, max: f64) -> Result<crate::Encoder, CryptoAPIError> {
+        crate::Encoder::new(min, max, self.precision, self.padding)
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), Box<dyn Error>> {
+        write_to_file(path, self)
+    }
+
+    pub fn load(path: &str) -> Result<ParameterBundle, Box<dyn Error>> {
+        read_from_file(path)
+    }
+}
+
+/// The best decomposition found for a given number of levels
+struct Decomposition {
+    base_log: usize,
+    level: usize,
+    variance: f64,
+}
+
+/// Search the cheapest parameters such that a ciphertext with `precision_bits` bits of precision,
+/// and `padding_needed` bits of padding consumed before each bootstrap, can be bootstrapped and
+/// key switched back to its original key with an error probability below MAX_ERROR_PROBABILITY,
+/// using the concrete-npe noise formulas and the security estimation of LWEParams
+///
+/// # Argument
+/// * `precision_bits` - the number of bits of precision of the messages
+/// * `padding_needed` - the number of bits of padding consumed by the computation between two bootstraps
+/// * `security_level` - the minimal number of bits of security of the secret keys
+///
+/// # Output
+/// * a ParameterBundle
+/// * PrecisionError - if `precision_bits` is 0
+/// * ParametersNotFoundError - if no parameters of the explored space are good enough
+///
+/// # Example
+/// ```rust
+/// use concrete::*;
+///
+/// let params = params::optimize(3, 1, 128).unwrap();
+///
+/// // generate the keys
+/// let sk_lwe = LWESecretKey::new(&params.lwe_params);
+/// let sk_rlwe = RLWESecretKey::new(&params.rlwe_params);
+/// let bsk = LWEBSK::new(&sk_lwe, &sk_rlwe, params.bsk_base_log, params.bsk_level);
+/// let ksk = LWEKSK::new(
+///     &sk_rlwe.to_lwe_secret_key(),
+///     &sk_lwe,
+///     params.ksk_base_log,
+///     params.ksk_level,
+/// );
+///
+/// // encode, encrypt, bootstrap and key switch
+/// let encoder = params.new_encoder(0., 7.).unwrap();
+/// let ciphertext = LWE::encode_encrypt(&sk_lwe, 5., &encoder).unwrap();
+/// let ciphertext = ciphertext
+///     .bootstrap_with_function(&bsk, |x| x - 2., &encoder)
+///     .unwrap()
+///     .keyswitch(&ksk)
+///     .unwrap();
+/// ```
+pub fn optimize(
+    precision_bits: usize,
+    padding_needed: usize,
+    security_level: usize,
+) -> Result<ParameterBundle, CryptoAPIError> {
+    if precision_bits == 0 {
+        return Err(PrecisionError!());
+    }
+
+    let mut best: Option<ParameterBundle> = None;
+    for rlwe_dimension in RLWE_DIMENSIONS {
+        for polynomial_size_log in POLYNOMIAL_SIZES_LOG {
+            let polynomial_size = 1 << polynomial_size_log;
+            let rlwe_log2_std_dev =
+                match min_log2_std_dev(rlwe_dimension * polynomial_size, security_level) {
+                    Some(log2_std_dev) => log2_std_dev,
+                    None => continue,
+                };
+            let rlwe_params = crate::RLWEParams {
+                polynomial_size,
+                dimension: rlwe_dimension,
+                log2_std_dev: rlwe_log2_std_dev,
+            };
+
+            for lwe_dimension in LWE_DIMENSIONS.step_by(LWE_DIMENSION_STEP) {
+                // the drift must not make the bootstrap lose some precision
+                if crate::lwe_bsk::get_nb_bit_drift(
+                    lwe_dimension,
+                    polynomial_size_log,
+                    1,
+                    precision_bits,
+                ) > 0
+                {
+                    continue;
+                }
+                let lwe_log2_std_dev = match min_log2_std_dev(lwe_dimension, security_level) {
+                    Some(log2_std_dev) => log2_std_dev,
+                    None => continue,
+                };
+                let lwe_params = crate::LWEParams::new(lwe_dimension, lwe_log2_std_dev);
+
+                let candidate = optimize_decompositions(
+                    &lwe_params,
+                    &rlwe_params,
+                    precision_bits,
+                    padding_needed,
+                );
+                if let Some(candidate) = candidate {
+                    if best.as_ref().map_or(true, |b| candidate.cost < b.cost) {
+                        best = Some(candidate);
+                    }
+                }
+            }
+        }
+    }
+
+    best.ok_or_else(|| ParametersNotFoundError!(precision_bits, padding_needed, security_level))
+}
+
+/// Find the cheapest decompositions of the bootstrapping and key switching keys for fixed
+/// LWE and RLWE parameters, if any of them reaches the maximal error probability
+fn optimize_decompositions(
+    lwe_params: &crate::LWEParams,
+    rlwe_params: &crate::RLWEParams,
+    precision_bits: usize,
+    padding_needed: usize,
+) -> Option<ParameterBundle> {
+    let n = lwe_params.dimension;
+    let k = rlwe_params.dimension;
+    let big_n = rlwe_params.polynomial_size;
+
+    // the best base for each number of levels of the bootstrapping key
+    let bsk_decompositions: Vec<Decomposition> = (1..=MAX_BSK_LEVEL)
+        .filter_map(|level| {
+            (1..=(<Torus as Numeric>::BITS / level))
+                .filter(|base_log| {
+                    // keep the integer computations of the NPE away from overflows
+                    (n * (k + 1) * level * big_n) as f64 * f64::powi(2., 2 * *base_log as i32)
+                        < f64::powi(2., 62)
+                })
+                .map(|base_log| Decomposition {
+                    base_log,
+                    level,
+                    variance: <Torus as npe::Cross>::bootstrap(
+                        n,
+                        k,
+                        level,
+                        base_log,
+                        big_n,
+                        f64::powi(rlwe_params.get_std_dev(), 2),
+                    ),
+                })
+                .min_by(|a, b| a.variance.partial_cmp(&b.variance).unwrap())
+        })
+        .collect();
+
+    // the best base for each number of levels of the key switching key
+    let ksk_decompositions: Vec<Decomposition> = (1..=MAX_KSK_LEVEL)
+        .filter_map(|level| {
+            (1..=(<Torus as Numeric>::BITS / level))
+                .map(|base_log| Decomposition {
+                    base_log,
+                    level,
+                    variance: <Torus as npe::LWE>::key_switch(
+                        k * big_n,
+                        level,
+                        base_log,
+                        f64::powi(lwe_params.get_std_dev(), 2),
+                        0.,
+                    ),
+                })
+                .min_by(|a, b| a.variance.partial_cmp(&b.variance).unwrap())
+        })
+        .collect();
+
+    // the rounding of the input of the bootstrap modulo 2N
+    let variance_drift = (n as f64 / 2. + 1.) / (48. * f64::powi(big_n as f64, 2));
+
+    // the bootstrap shifts the padding but one out of the ciphertext
+    let variance_factor = f64::powi(4., padding_needed as i32);
+    let bound = f64::powi(2., -(precision_bits as i32) - 2);
+
+    let mut best: Option<ParameterBundle> = None;
+    for bsk in bsk_decompositions.iter() {
+        for ksk in ksk_decompositions.iter() {
+            let variance = variance_factor * (bsk.variance + ksk.variance) + variance_drift;
+            let error_probability = erfc(bound / f64::sqrt(2. * variance));
+            if error_probability > MAX_ERROR_PROBABILITY {
+                continue;
+            }
+
+            // FFT based external products of the blind rotation, then the key switch
+            let cost_bootstrap = n as f64
+                * (((k + 1) * (bsk.level + 1)) as f64 * (big_n as f64 * (big_n as f64).log2())
+                    + (f64::powi((k + 1) as f64, 2) * (bsk.level * big_n) as f64));
+            let cost_keyswitch = (k * big_n * ksk.level * (n + 1)) as f64;
+            let cost = cost_bootstrap + cost_keyswitch;
+
+            if best.as_ref().map_or(true, |b| cost < b.cost) {
+                best = Some(ParameterBundle {
+                    lwe_params: lwe_params.clone(),
+                    rlwe_params: rlwe_params.clone(),
+                    bsk_base_log: bsk.base_log,
+                    bsk_level: bsk.level,
+                    ksk_base_log: ksk.base_log,
+                    ksk_level: ksk.level,
+                    precision: precision_bits,
+                    padding: padding_needed + 1,
+                    error_probability,
+                    cost,
+                    security_bits: f64::min(
+                        lwe_params.estimated_security_bits(),
+                        rlwe_params.estimated_security_bits(),
+                    ),
+                });
+            }
+        }
+    }
+    best
+}
+
+/// Parameter sets reaching 128 bits of security, sorted by dimension
+const SECURITY_128_PARAMS: [crate::LWEParams; 12] = [
+    crate::LWE128_256,
+    crate::LWE128_512,
+    crate::LWE128_630,
+    crate::LWE128_650,
+    crate::LWE128_688,
+    crate::LWE128_710,
+    crate::LWE128_750,
+    crate::LWE128_800,
+    crate::LWE128_830,
+    crate::LWE128_1024,
+    crate::LWE128_2048,
+    crate::LWE128_4096,
+];
+
+/// Parameter sets reaching 80 bits of security, sorted by dimension
+const SECURITY_80_PARAMS: [crate::LWEParams; 11] = [
+    crate::LWE80_256,
+    crate::LWE80_512,
+    crate::LWE80_630,
+    crate::LWE80_650,
+    crate::LWE80_688,
+    crate::LWE80_710,
+    crate::LWE80_750,
+    crate::LWE80_800,
+    crate::LWE80_830,
+    crate::LWE80_1024,
+    crate::LWE80_2048,
+];
+
+/// Return the ratio dimension / -log2_std_dev reaching the security of a list of parameter sets,
+/// interpolated linearly in log2(dimension) between them and constant outside of them
+fn interpolate_security_ratio(params: &[crate::LWEParams], dimension: usize) -> f64 {
+    let ratio = |p: &crate::LWEParams| p.dimension as f64 / -(p.log2_std_dev as f64);
+    let log_dimension = (dimension as f64).log2();
+    let first = &params[0];
+    let last = &params[params.len() - 1];
+    if dimension <= first.dimension {
+        return ratio(first);
+    }
+    if dimension >= last.dimension {
+        return ratio(last);
+    }
+    for pair in params.windows(2) {
+        if dimension <= pair[1].dimension {
+            let log_0 = (pair[0].dimension as f64).log2();
+            let log_1 = (pair[1].dimension as f64).log2();
+            let t = (log_dimension - log_0) / (log_1 - log_0);
+            return ratio(&pair[0]) + t * (ratio(&pair[1]) - ratio(&pair[0]));
+        }
+    }
+    ratio(last)
+}
+
+/// Estimate the number of bits of security of an LWE (or flattened RLWE) instance
+/// The security of a binary key is interpolated linearly in the ratio dimension / -log2_std_dev
+/// between the 80 and the 128 bits parameter sets of LWEParams of the same dimension, which were
+/// given by the LWE estimator: it is only a rough guide away from those security levels.
+/// Following the rescaling of Bai and Galbraith, a key whose coefficients have a standard
+/// deviation sigma_s is counted as a binary key (sigma_s = 1/2) with a standard deviation of the
+/// error multiplied by sqrt(2 * sigma_s), so that the ternary and Gaussian keys are estimated
+/// more secure than the binary ones.
+///
+/// # Argument
+/// * `dimension` - the size of the LWE mask
+/// * `log2_std_dev` - the log2 of the standard deviation of the error
+/// * `kind` - the distribution of the coefficients of the secret key
+///
+/// # Output
+/// * the estimated number of bits of security
+///
+/// # Example
+/// ```rust
+/// use concrete::params::estimate_security_bits;
+/// use concrete::SecretKeyKind;
+///
+/// assert_eq!(estimate_security_bits(630, -14., SecretKeyKind::Binary).round(), 128.);
+/// assert!(estimate_security_bits(630, -14., SecretKeyKind::Gaussian) > 128.);
+/// ```
+pub fn estimate_security_bits(dimension: usize, log2_std_dev: f64, kind: SecretKeyKind) -> f64 {
+    if dimension == 0 || log2_std_dev <= -(<Torus as Numeric>::BITS as f64) {
+        // no mask or an error rounded away by the torus
+        return 0.;
+    }
+    let log2_std_dev = log2_std_dev + f64::log2(2. * key_std_dev(kind)) / 2.;
+    if log2_std_dev >= 0. {
+        // the error covers the whole torus
+        return f64::INFINITY;
+    }
+    let ratio = dimension as f64 / -log2_std_dev;
+    let ratio_128 = interpolate_security_ratio(&SECURITY_128_PARAMS, dimension);
+    let ratio_80 = interpolate_security_ratio(&SECURITY_80_PARAMS, dimension);
+    f64::max(0., 80. + 48. * (ratio - ratio_80) / (ratio_128 - ratio_80))
+}
+
+/// Return the standard deviation of the coefficients of a secret key of a given kind
+fn key_std_dev(kind: SecretKeyKind) -> f64 {
+    match kind {
+        // a binary coefficient has a mean of 1/2
+        SecretKeyKind::Binary => 1. / 2.,
+        SecretKeyKind::Ternary | SecretKeyKind::Gaussian => f64::sqrt(kind.get_second_moment()),
+    }
+}
+/// Return the smallest log2 of the standard deviation reaching a security level, if any
+fn min_log2_std_dev(dimension: usize, security_level: usize) -> Option<i32> {
+    (-(<Torus as Numeric>::BITS as i32 - 1)..0).find(|log2_std_dev| {
+        estimate_security_bits(dimension, *log2_std_dev as f64, SecretKeyKind::Binary)
+            >= security_level as f64
+    })
+}
+
+/// Complementary error function, with a relative error below 1.2e-7 (Numerical Recipes erfcc),
+/// giving the probability that a centered normal error of standard deviation sigma is bigger
+/// than t in absolute value as erfc(t / (sqrt(2) * sigma))
+///
+/// # Example
+/// ```rust
+/// use concrete::params::erfc;
+///
+/// assert!((erfc(0.) - 1.).abs() < 1e-7);
+/// assert!((erfc(1.) - 0.157_299_207).abs() < 1e-7);
+/// ```
+pub fn erfc(x: f64) -> f64 {
+    let z = x.abs();
+    let t = 1. / (1. + 0.5 * z);
+    let polynomial = -1.265_512_23
+        + t * (1.000_023_68
+            + t * (0.374_091_96
+                + t * (0.096_784_18
+                    + t * (-0.186_288_06
+                        + t * (0.278_868_07
+                            + t * (-1.135_203_98
+                                + t * (1.488_515_87 + t * (-0.822_152_23 + t * 0.170_872_77))))))));
+    let res = t * f64::exp(-z * z + polynomial);
+    if x >= 0. {
+        res
+    } else {
+        2. - res
+    }
+}
+
+impl fmt::Display for ParameterBundle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "ParameterBundle {{")?;
+        writeln!(
+            f,
+            "         -> lwe: dimension = {}, log2_std_dev = {}",
+            self.lwe_params.dimension, self.lwe_params.log2_std_dev
+        )?;
+        writeln!(
+            f,
+            "         -> rlwe: dimension = {}, polynomial_size = {}, log2_std_dev = {}",
+            self.rlwe_params.dimension,
+            self.rlwe_params.polynomial_size,
+            self.rlwe_params.log2_std_dev
+        )?;
+        writeln!(
+            f,
+            "         -> bsk: base_log = {}, level = {}",
+            self.bsk_base_log, self.bsk_level
+        )?;
+        writeln!(
+            f,
+            "         -> ksk: base_log = {}, level = {}",
+            self.ksk_base_log, self.ksk_level
+        )?;
+        writeln!(
+            f,
+            "         -> precision = {}, padding = {}",
+            self.precision, self.padding
+        )?;
+        writeln!(
+            f,
+            "         -> error_probability = {:e}",
+            self.error_probability
+        )?;
+        writeln!(f, "         -> cost = {:e}", self.cost)?;
+        writeln!(f, "         -> security_bits = {}", self.security_bits)?;
+        writeln!(f, "}}")
+    }
+}
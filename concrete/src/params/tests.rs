@@ -0,0 +1,89 @@
+#[test]
+fn test_optimize_x_encode_encrypt_x_bootstrap_x_keyswitch_x_decrypt() {
+    // settings
+    let precision: usize = 3;
+    let padding_needed: usize = 1;
+    let params = crate::params::optimize(precision, padding_needed, 128).unwrap();
+    println!("{}", params);
+
+    // the bundle must keep its promises
+    assert!(params.security_bits >= 128.);
+    assert!(params.error_probability <= crate::params::MAX_ERROR_PROBABILITY);
+    assert_eq!(params.precision, precision);
+    assert_eq!(params.padding, padding_needed + 1);
+
+    // generate the keys
+    let sk_lwe = crate::LWESecretKey::new(&params.lwe_params);
+    let sk_rlwe = crate::RLWESecretKey::new(&params.rlwe_params);
+    let bsk = crate::LWEBSK::new(&sk_lwe, &sk_rlwe, params.bsk_base_log, params.bsk_level);
+    let ksk = crate::LWEKSK::new(
+        &sk_rlwe.to_lwe_secret_key(),
+        &sk_lwe,
+        params.ksk_base_log,
+        params.ksk_level,
+    );
+
+    // encoder
+    let encoder = params.new_encoder(0., 7.).unwrap();
+
+    for _ in 0..5 {
+        // two messages whose sum stays in the interval
+        let m1: f64 = random_index!(4) as f64;
+        let m2: f64 = random_index!(4) as f64;
+
+        // encode and encrypt
+        let c1 = crate::LWE::encode_encrypt(&sk_lwe, m1, &encoder).unwrap();
+        let c2 = crate::LWE::encode_encrypt(&sk_lwe, m2, &encoder).unwrap();
+
+        // consume the padding, bootstrap and key switch back to the input key
+        let sum = c1.add_with_padding(&c2).unwrap();
+        let res = sum
+            .bootstrap_with_function(&bsk, |x| x, &encoder)
+            .unwrap()
+            .keyswitch(&ksk)
+            .unwrap();
+
+        // decryption
+        let decryption: f64 = res.decrypt_decode_round(&sk_lwe).unwrap();
+
+        // test
+        assert_eq!(m1 + m2, decryption);
+    }
+}
+
+#[test]
+fn test_optimize_not_found() {
+    // the precision can not be reached with the explored polynomial sizes
+    assert!(crate::params::optimize(20, 0, 128).is_err());
+
+    // no precision at all
+    assert!(crate::params::optimize(0, 0, 128).is_err());
+}
+
+#[test]
+fn test_estimate_security_bits() {
+    use crate::params::estimate_security_bits;
+    use crate::SecretKeyKind;
+
+    // the parameter sets of LWEParams reach their security level
+    for params in [crate::LWE128_630, crate::LWE128_1024].iter() {
+        assert_eq!(params.estimated_security_bits().round(), 128.);
+        assert!(crate::LWEParams::new_checked(params.dimension, params.log2_std_dev, 128).is_ok());
+    }
+    assert_eq!(crate::LWE80_1024.estimated_security_bits().round(), 80.);
+
+    // the estimation grows with the dimension, the noise and the spread of the key
+    let binary = estimate_security_bits(800, -20., SecretKeyKind::Binary);
+    let ternary = estimate_security_bits(800, -20., SecretKeyKind::Ternary);
+    let gaussian = estimate_security_bits(800, -20., SecretKeyKind::Gaussian);
+    assert!(estimate_security_bits(900, -20., SecretKeyKind::Binary) > binary);
+    assert!(estimate_security_bits(800, -19.5, SecretKeyKind::Binary) > binary);
+    assert!(binary < ternary && ternary < gaussian);
+
+    // no mask or no noise
+    assert_eq!(estimate_security_bits(0, -20., SecretKeyKind::Gaussian), 0.);
+    assert_eq!(
+        estimate_security_bits(800, -64., SecretKeyKind::Gaussian),
+        0.
+    );
+}
@@ -0,0 +1,156 @@
+//! rlwe_automorphism_key module describing the RLWEAutomorphismKey structure
+
+use super::{read_from_file, write_to_file};
+use crate::error::CryptoAPIError;
+use crate::utils::Zeroizing;
+use crate::Torus;
+use backtrace::Backtrace;
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt;
+
+/// Structure containing an RLWE automorphism key
+/// It is the key switching key from tau_k(S) to S, where tau_k is the automorphism X -> X^k
+/// and S is an RLWE secret key
+/// The coefficients of tau_k(S) are the ones of S up to their order and signs, so they share the
+/// second moment of the kind of S, which is recorded as the input kind of the key switching key
+/// The automorphism is applied to ciphertexts encrypted under S, so both fingerprints of the key
+/// switching key are the one of S
+///
+/// # Attributes
+/// * `ksk` - the RLWE key switching key from tau_k(S) to S
+/// * `automorphism` - the exponent k of the automorphism X -> X^k
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RLWEAutomorphismKey {
+    pub ksk: crate::RLWEKSK,
+    pub automorphism: usize,
+}
+
+impl RLWEAutomorphismKey {
+    /// Generate an automorphism key for X -> X^k
+    ///
+    /// # Argument
+    /// * `sk` - an RLWE secret key
+    /// * `k` - the exponent of the automorphism, odd and smaller than 2 * polynomial_size
+    /// * `base_log` - the log2 of the decomposition base
+    /// * `level` - the number of levels of the decomposition
+    ///
+    /// # Output
+    /// * an RLWEAutomorphismKey
+    /// * AutomorphismError - if k is even or not smaller than 2 * polynomial_size
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// // generate a secret key
+    /// let sk = RLWESecretKey::new(&RLWE128_1024_1);
+    ///
+    /// // generate the key for X -> X^3
+    /// let autkey = RLWEAutomorphismKey::new(&sk, 3, 6, 4).unwrap();
+    /// ```
+    pub fn new(
+        sk: &crate::RLWESecretKey,
+        k: usize,
+        base_log: usize,
+        level: usize,
+    ) -> Result<RLWEAutomorphismKey, CryptoAPIError> {
+        if k % 2 == 0 || k >= 2 * sk.polynomial_size {
+            return Err(AutomorphismError!(k, sk.polynomial_size));
+        }
+
+        // compute tau_k(S) polynomial by polynomial
+        let mut key_before = Zeroizing(vec![0 as Torus; sk.dimension * sk.polynomial_size]);
+        for (out, key_polynomial) in key_before
+            .chunks_mut(sk.polynomial_size)
+            .zip(sk.val.as_slice().chunks(sk.polynomial_size))
+        {
+            apply_automorphism_polynomial(out, key_polynomial, k);
+        }
+
+        Ok(RLWEAutomorphismKey {
+            ksk: crate::RLWEKSK::new_from_raw_key(
+                &key_before,
+                sk.dimension,
+                sk.kind,
+                sk.fingerprint,
+                sk,
+                base_log,
+                level,
+            ),
+            automorphism: k,
+        })
+    }
+
+    /// Generate the automorphism keys needed by VectorRLWE::trace
+    /// The i-th key is for X -> X^(N / 2^i + 1) where N is the polynomial size
+    ///
+    /// # Argument
+    /// * `sk` - an RLWE secret key
+    /// * `base_log` - the log2 of the decomposition base
+    /// * `level` - the number of levels of the decomposition
+    ///
+    /// # Output
+    /// * a vector of log2(polynomial_size) RLWEAutomorphismKey
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// // generate a secret key
+    /// let sk = RLWESecretKey::new(&RLWE128_256_1);
+    ///
+    /// // generate the keys for the trace
+    /// let autkeys = RLWEAutomorphismKey::new_trace_keys(&sk, 6, 4).unwrap();
+    /// ```
+    pub fn new_trace_keys(
+        sk: &crate::RLWESecretKey,
+        base_log: usize,
+        level: usize,
+    ) -> Result<Vec<RLWEAutomorphismKey>, CryptoAPIError> {
+        let log_n = sk.polynomial_size.trailing_zeros() as usize;
+        let mut res: Vec<RLWEAutomorphismKey> = Vec::with_capacity(log_n);
+        for i in 0..log_n {
+            res.push(RLWEAutomorphismKey::new(
+                sk,
+                (sk.polynomial_size >> i) + 1,
+                base_log,
+                level,
+            )?);
+        }
+        Ok(res)
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), Box<dyn Error>> {
+        write_to_file(path, self)
+    }
+
+    pub fn load(path: &str) -> Result<RLWEAutomorphismKey, Box<dyn Error>> {
+        read_from_file(path)
+    }
+}
+
+/// Compute res = tau_k(polynomial) in the ring Z_q[X] / (X^N + 1), where tau_k is X -> X^k
+/// The coefficient of X^j is moved to X^(j * k mod 2N), and negated if j * k mod 2N >= N
+pub(crate) fn apply_automorphism_polynomial(res: &mut [Torus], polynomial: &[Torus], k: usize) {
+    let n = polynomial.len();
+    for (j, coeff) in polynomial.iter().enumerate() {
+        let index = (j * k) % (2 * n);
+        if index < n {
+            res[index] = *coeff;
+        } else {
+            res[index - n] = coeff.wrapping_neg();
+        }
+    }
+}
+
+/// Print needed pieces of information about an RLWEAutomorphismKey
+impl fmt::Display for RLWEAutomorphismKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "RLWEAutomorphismKey {{")?;
+        writeln!(f, "         -> automorphism = X -> X^{}", self.automorphism)?;
+        write!(f, "{}", self.ksk)?;
+        writeln!(f, "}}")
+    }
+}
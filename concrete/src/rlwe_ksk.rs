@@ -0,0 +1,362 @@
+//! rlwe_ksk module describing the RLWEKSK structure
+
+use super::{read_from_file, write_to_file};
+use crate::error::CryptoAPIError;
+use crate::fourier::FourierPolynomials;
+use crate::utils::Zeroizing;
+use crate::{KeyFingerprint, SecretKeyKind, Torus};
+use backtrace::Backtrace;
+use colored::Colorize;
+use concrete_commons::dispersion::StandardDev;
+use concrete_commons::numeric::Numeric;
+use concrete_commons::parameters::{CiphertextCount, GlweDimension, PolynomialSize};
+use concrete_core::crypto::encoding::PlaintextList;
+use concrete_core::crypto::glwe::GlweList;
+use concrete_core::crypto::secret::generators::EncryptionRandomGenerator;
+use concrete_core::math::tensor::{AsRefSlice, AsRefTensor};
+use itertools::izip;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt;
+
+/// Structure containing an RLWE key switching key
+/// For each polynomial S_i of the input key and each level j of the decomposition, it stores an
+/// RLWE encryption under the output key of S_i * q / B^(j+1)
+///
+/// # Attributes
+/// * `ciphertexts` - the RLWE encryptions, ordered by input key polynomial and then by level
+/// * `base_log` - the log2 of the decomposition base
+/// * `level` - the number of levels of the decomposition
+/// * `dimension_before` - the dimension of the input RLWE secret key
+/// * `dimension_after` - the dimension of the output RLWE secret key
+/// * `polynomial_size` - the number of coefficients in a polynomial
+/// * `variance` - the variance of the noise of the encryptions
+/// * `kind_before` - the kind of the input secret key (binary for older keys)
+/// * `fingerprint_before` - the fingerprint of the input secret key (unknown for older keys)
+/// * `fingerprint_after` - the fingerprint of the output secret key (unknown for older keys)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RLWEKSK {
+    pub ciphertexts: GlweList<Vec<Torus>>,
+    pub base_log: usize,
+    pub level: usize,
+    pub dimension_before: usize,
+    pub dimension_after: usize,
+    pub polynomial_size: usize,
+    pub variance: f64,
+    #[serde(default)]
+    pub kind_before: SecretKeyKind,
+    #[serde(default)]
+    pub fingerprint_before: KeyFingerprint,
+    #[serde(default)]
+    pub fingerprint_after: KeyFingerprint,
+}
+
+impl RLWEKSK {
+    /// Generate an empty RLWE key switching key
+    ///
+    /// # Argument
+    /// * `sk_before` - an RLWE secret key (input for the key switch)
+    /// * `sk_after` - an RLWE secret key (output for the key switch)
+    /// * `base_log` - the log2 of the decomposition base
+    /// * `level` - the number of levels of the decomposition
+    ///
+    /// # Output
+    /// * an RLWEKSK
+    /// * PolynomialSizeError - if the two secret keys have different polynomial sizes
+    /// * DecompositionError - if base_log or level is zero, or if base_log * level > 64
+    pub fn zero(
+        sk_before: &crate::RLWESecretKey,
+        sk_after: &crate::RLWESecretKey,
+        base_log: usize,
+        level: usize,
+    ) -> Result<RLWEKSK, CryptoAPIError> {
+        check_parameters(sk_before, sk_after, base_log, level)?;
+
+        Ok(RLWEKSK {
+            ciphertexts: GlweList::allocate(
+                0,
+                PolynomialSize(sk_after.polynomial_size),
+                GlweDimension(sk_after.dimension),
+                CiphertextCount(sk_before.dimension * level),
+            ),
+            base_log,
+            level,
+            dimension_before: sk_before.dimension,
+            dimension_after: sk_after.dimension,
+            polynomial_size: sk_after.polynomial_size,
+            variance: sk_after.get_variance(),
+            kind_before: sk_before.kind,
+            fingerprint_before: sk_before.fingerprint,
+            fingerprint_after: sk_after.fingerprint,
+        })
+    }
+
+    /// Generate a valid RLWE key switching key
+    /// The two secret keys must share the same polynomial size but can have different dimensions
+    ///
+    /// # Argument
+    /// * `sk_before` - an RLWE secret key (input for the key switch)
+    /// * `sk_after` - an RLWE secret key (output for the key switch)
+    /// * `base_log` - the log2 of the decomposition base
+    /// * `level` - the number of levels of the decomposition
+    ///
+    /// # Output
+    /// * an RLWEKSK
+    /// * PolynomialSizeError - if the two secret keys have different polynomial sizes
+    /// * DecompositionError - if base_log or level is zero, or if base_log * level > 64
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// // generate two secret keys
+    /// let sk_before = RLWESecretKey::new(&RLWE128_1024_1);
+    /// let sk_after = RLWESecretKey::new(&RLWE128_1024_1);
+    ///
+    /// // generate the key switching key
+    /// let rksk = RLWEKSK::new(&sk_before, &sk_after, 6, 4).unwrap();
+    /// ```
+    pub fn new(
+        sk_before: &crate::RLWESecretKey,
+        sk_after: &crate::RLWESecretKey,
+        base_log: usize,
+        level: usize,
+    ) -> Result<RLWEKSK, CryptoAPIError> {
+        check_parameters(sk_before, sk_after, base_log, level)?;
+        Ok(RLWEKSK::new_from_raw_key(
+            sk_before.val.as_slice(),
+            sk_before.dimension,
+            sk_before.kind,
+            sk_before.fingerprint,
+            sk_after,
+            base_log,
+            level,
+        ))
+    }
+
+    /// Generate a valid RLWE key switching key from the raw coefficients of the input key
+    /// The input key can have any small coefficients, e.g. the image of an RLWE secret key by an automorphism
+    ///
+    /// # Argument
+    /// * `key_before` - the coefficients of the input key polynomials, of length dimension_before * polynomial_size
+    /// * `dimension_before` - the number of polynomials of the input key
+    /// * `kind_before` - the distribution of the coefficients of the input key
+    /// * `fingerprint_before` - the fingerprint of the key of the input ciphertexts
+    /// * `sk_after` - an RLWE secret key (output for the key switch)
+    /// * `base_log` - the log2 of the decomposition base
+    /// * `level` - the number of levels of the decomposition
+    pub(crate) fn new_from_raw_key(
+        key_before: &[Torus],
+        dimension_before: usize,
+        kind_before: SecretKeyKind,
+        fingerprint_before: KeyFingerprint,
+        sk_after: &crate::RLWESecretKey,
+        base_log: usize,
+        level: usize,
+    ) -> RLWEKSK {
+        let polynomial_size = sk_after.polynomial_size;
+        let mut res = RLWEKSK {
+            ciphertexts: GlweList::allocate(
+                0,
+                PolynomialSize(polynomial_size),
+                GlweDimension(sk_after.dimension),
+                CiphertextCount(dimension_before * level),
+            ),
+            base_log,
+            level,
+            dimension_before,
+            dimension_after: sk_after.dimension,
+            polynomial_size,
+            variance: sk_after.get_variance(),
+            kind_before,
+            fingerprint_before,
+            fingerprint_after: sk_after.fingerprint,
+        };
+
+        // build the plaintexts S_i * q / B^(j+1)
+        let mut plaintexts =
+            Zeroizing(vec![0 as Torus; dimension_before * level * polynomial_size]);
+        for (pt_levels, key_polynomial) in izip!(
+            plaintexts.chunks_mut(level * polynomial_size),
+            key_before.chunks(polynomial_size)
+        ) {
+            for (j, pt) in pt_levels.chunks_mut(polynomial_size).enumerate() {
+                let factor: Torus = 1 << (<Torus as Numeric>::BITS - base_log * (j + 1));
+                for (p, s) in izip!(pt.iter_mut(), key_polynomial.iter()) {
+                    *p = s.wrapping_mul(factor);
+                }
+            }
+        }
+
+        // encrypt them with the output key
+        with_glwe_secret_key!(
+            sk_after.kind,
+            sk_after.val.as_slice(),
+            sk_after.polynomial_size,
+            |key| {
+                key.encrypt_glwe_list(
+                    &mut res.ciphertexts,
+                    &PlaintextList::from_container(plaintexts.as_slice()),
+                    StandardDev::from_standard_dev(sk_after.std_dev),
+                    &mut EncryptionRandomGenerator::new(None),
+                )
+            }
+        );
+
+        res
+    }
+
+    /// Convert the RLWE encryptions to the Fourier domain, once for all the key switches
+    pub(crate) fn fourier_ciphertexts(&self) -> FourierPolynomials<'_> {
+        FourierPolynomials::new(
+            self.ciphertexts.as_tensor().as_slice(),
+            self.polynomial_size,
+        )
+    }
+
+    /// Key switch one raw RLWE ciphertext
+    /// `ct_out` is overwritten with the result
+    ///
+    /// # Argument
+    /// * `fourier_ksk` - the RLWE encryptions of self, converted by fourier_ciphertexts
+    /// * `ct_out` - the output RLWE ciphertext, of size (dimension_after + 1) * polynomial_size
+    /// * `ct_in` - the input RLWE ciphertext, of size (dimension_before + 1) * polynomial_size
+    pub(crate) fn keyswitch_raw(
+        &self,
+        fourier_ksk: &FourierPolynomials,
+        ct_out: &mut [Torus],
+        ct_in: &[Torus],
+    ) {
+        let n = self.polynomial_size;
+
+        // start from the trivial encryption of the input body
+        for elt in ct_out.iter_mut() {
+            *elt = 0;
+        }
+        ct_out[(self.dimension_after * n)..].copy_from_slice(&ct_in[(self.dimension_before * n)..]);
+
+        // decompose every coefficient of the mask polynomials, the digits are negated so that
+        // the products with the key switching key are subtracted
+        let mut digits: Vec<Torus> = vec![0; self.dimension_before * self.level * n];
+        for (digit_levels, mask_polynomial) in izip!(
+            digits.chunks_mut(self.level * n),
+            ct_in[..(self.dimension_before * n)].chunks(n)
+        ) {
+            for (c, coeff) in mask_polynomial.iter().enumerate() {
+                for (j, d) in signed_decompose(*coeff, self.base_log, self.level)
+                    .iter()
+                    .enumerate()
+                {
+                    digit_levels[j * n + c] = d.wrapping_neg();
+                }
+            }
+        }
+
+        // the digit polynomial of the input polynomial i and of the level j multiplies the RLWE
+        // encryption i * level + j of the key switching key
+        let terms: Vec<(usize, &[Torus])> = digits
+            .chunks(n)
+            .enumerate()
+            .map(|(t, digit_polynomial)| (t * (self.dimension_after + 1), digit_polynomial))
+            .collect();
+        fourier_ksk.update_with_add_products(ct_out, &terms);
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), Box<dyn Error>> {
+        write_to_file(path, self)
+    }
+
+    pub fn load(path: &str) -> Result<RLWEKSK, Box<dyn Error>> {
+        read_from_file(path)
+    }
+}
+
+/// Check the polynomial sizes of the keys and the decomposition parameters of an RLWEKSK
+fn check_parameters(
+    sk_before: &crate::RLWESecretKey,
+    sk_after: &crate::RLWESecretKey,
+    base_log: usize,
+    level: usize,
+) -> Result<(), CryptoAPIError> {
+    if sk_before.polynomial_size != sk_after.polynomial_size {
+        return Err(PolynomialSizeError!(
+            sk_before.polynomial_size,
+            sk_after.polynomial_size
+        ));
+    } else if base_log == 0 || level == 0 || base_log * level > <Torus as Numeric>::BITS {
+        return Err(DecompositionError!(base_log, level));
+    }
+    Ok(())
+}
+
+/// Compute the signed decomposition of a Torus element after rounding it to base_log * level bits
+/// The j-th output is the digit associated with q / B^(j+1)
+/// The computations are made on 128 bits so that a decomposition on the 64 bits does not overflow
+pub(crate) fn signed_decompose(value: Torus, base_log: usize, level: usize) -> Vec<Torus> {
+    let shift = <Torus as Numeric>::BITS - base_log * level;
+    let base: u128 = 1 << base_log;
+
+    // round to the closest representable value
+    let mut state: u128 = ((value as u128) + ((1 << shift) >> 1)) >> shift;
+
+    // extract the digits from the least significant one
+    let mut res: Vec<Torus> = vec![0; level];
+    for digit in res.iter_mut().rev() {
+        let tmp = state & (base - 1);
+        state >>= base_log;
+        if tmp >= base / 2 {
+            *digit = (tmp as Torus).wrapping_sub(base as Torus);
+            state += 1;
+        } else {
+            *digit = tmp as Torus;
+        }
+    }
+    res
+}
+
+/// Print needed pieces of information about an RLWEKSK
+impl fmt::Display for RLWEKSK {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let n = 2;
+
+        write!(f, "RLWEKSK {{\n         -> samples = [")?;
+
+        if self.ciphertexts.as_tensor().len() <= 2 * n {
+            for elt in self.ciphertexts.as_tensor().iter() {
+                write!(f, "{}, ", *elt)?;
+            }
+        } else {
+            for elt in self.ciphertexts.as_tensor().get_sub(0..n).iter() {
+                write!(f, "{}, ", *elt)?;
+            }
+            write!(f, "...")?;
+
+            for elt in self
+                .ciphertexts
+                .as_tensor()
+                .get_sub(self.ciphertexts.as_tensor().len() - n..)
+                .iter()
+            {
+                write!(f, "{}, ", *elt)?;
+            }
+        }
+        writeln!(f, "]")?;
+        writeln!(f, "         -> variance = {}", self.variance)?;
+        writeln!(f, "         -> kind before = {:?}", self.kind_before)?;
+        writeln!(
+            f,
+            "         -> fingerprints = {} -> {}",
+            self.fingerprint_before, self.fingerprint_after
+        )?;
+        writeln!(
+            f,
+            "         -> dimension before = {}",
+            self.dimension_before
+        )?;
+        writeln!(f, "         -> dimension after = {}", self.dimension_after)?;
+        writeln!(f, "         -> polynomial_size = {}", self.polynomial_size)?;
+        writeln!(f, "         -> base_log = {}", self.base_log)?;
+        writeln!(f, "         -> level = {}", self.level)?;
+        writeln!(f, "}}")
+    }
+}
@@ -1,6 +1,7 @@
 //! rlwe_params module describing the RLWEParams structure
 use super::{read_from_file, write_to_file};
 use crate::error::CryptoAPIError;
+use crate::SecretKeyKind;
 use backtrace::Backtrace;
 use colored::Colorize;
 use serde::{Deserialize, Serialize};
@@ -171,6 +172,37 @@ impl RLWEParams {
         f64::powi(2., self.log2_std_dev)
     }
 
+    /// Return an estimation of the number of bits of security of the parameters,
+    /// for binary secret keys and a modulus of 2^64
+    /// The RLWE instance is seen as an LWE instance of dimension dimension * polynomial_size,
+    /// whose security is estimated as in LWEParams::estimated_security_bits
+    /// # Output
+    /// * the estimated number of bits of security
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// assert_eq!(RLWE128_1024_1.estimated_security_bits().round(), 128.);
+    /// assert_eq!(RLWE80_512_2.estimated_security_bits().round(), 80.);
+    /// ```
+    pub fn estimated_security_bits(&self) -> f64 {
+        self.estimated_security_bits_with_kind(SecretKeyKind::Binary)
+    }
+
+    /// Return an estimation of the number of bits of security of the parameters,
+    /// for secret keys of a given kind and a modulus of 2^64
+    /// # Argument
+    /// * `kind` - the distribution of the coefficients of the secret key
+    /// # Output
+    /// * the estimated number of bits of security
+    pub fn estimated_security_bits_with_kind(&self, kind: SecretKeyKind) -> f64 {
+        crate::params::estimate_security_bits(
+            self.dimension * self.polynomial_size,
+            self.log2_std_dev as f64,
+            kind,
+        )
+    }
+
     pub fn save(&self, path: &str) -> Result<(), Box<dyn Error>> {
         write_to_file(path, self)
     }
@@ -1,21 +1,29 @@
 use super::{read_from_file, write_to_file};
-use concrete_core::{
-    crypto::secret::{GlweSecretKey, LweSecretKey},
-    math::tensor::IntoTensor,
-};
 use serde::{Deserialize, Serialize};
 use std::error::Error;
 use std::fmt;
-use concrete_commons::key_kinds::BinaryKeyKind;
-use concrete_commons::parameters::{GlweDimension, PolynomialSize};
-use concrete_core::crypto::secret::generators::SecretRandomGenerator;
+use crate::{GenericTorus, KeyFingerprint, SecretKeyKind, Torus};
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
+/// Structure describing an RLWE secret key
+/// # Attributes
+/// * `val` - the coefficients of the key, stored as Torus elements whatever `kind` is
+/// * `polynomial_size` - the number of coefficients of the polynomials
+/// * `dimension` - the number of polynomials of the RLWE mask
+/// * `std_dev` - the standard deviation for the encryption
+/// * `kind` - the distribution the coefficients were sampled from (binary for older keys)
+/// * `fingerprint` - the public identifier of the key, shared with its LWE view (unknown for older keys)
+///
+/// The coefficients are wiped when the key is dropped, never printed, and the key cannot be cloned
+#[derive(Serialize, Deserialize, PartialEq)]
 pub struct RLWESecretKey {
-    pub val: GlweSecretKey<BinaryKeyKind, Vec<u64>> ,
+    pub(crate) val: GlweKeyCoefficients,
     pub polynomial_size: usize,
     pub dimension: usize,
     pub std_dev: f64,
+    #[serde(default)]
+    pub kind: SecretKeyKind,
+    #[serde(default)]
+    pub fingerprint: KeyFingerprint,
 }
 
 impl RLWESecretKey {
@@ -25,17 +33,28 @@ impl RLWESecretKey {
     /// # Output
     /// * a new RLWESecretKey
     pub fn new(params: &crate::RLWEParams) -> RLWESecretKey {
-        let val = GlweSecretKey::generate_binary(
-            GlweDimension(params.dimension),
-            PolynomialSize(params.polynomial_size),
-            &mut SecretRandomGenerator::new(None),
-        );
-        RLWESecretKey {
-            val,
-            polynomial_size: params.polynomial_size,
-            dimension: params.dimension,
-            std_dev: params.get_std_dev(),
-        }
+        RLWESecretKey::new_with_kind(params, SecretKeyKind::Binary)
+    }
+
+    /// Generate a new secret key of a given kind from an RLWEParams
+    /// # Argument
+    /// * `params` - an RLWEParams instance
+    /// * `kind` - the distribution of the coefficients of the key
+    /// # Output
+    /// * a new RLWESecretKey
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// let secret_key = RLWESecretKey::new_with_kind(&RLWE128_1024_1, SecretKeyKind::Gaussian);
+    /// ```
+    pub fn new_with_kind(params: &crate::RLWEParams, kind: SecretKeyKind) -> RLWESecretKey {
+        RLWESecretKey::new_raw_with_kind(
+            params.polynomial_size,
+            params.dimension,
+            params.get_std_dev(),
+            kind,
+        )
     }
 
     /// Generate a new secret key from a raw dimension (i.e. without a RLWEParams input)
@@ -45,16 +64,33 @@ impl RLWESecretKey {
     /// # Output
     /// * a new RLWESecretKey
     pub fn new_raw(polynomial_size: usize, dimension: usize, std_dev: f64) -> RLWESecretKey {
-        let val = GlweSecretKey::generate_binary(
-            GlweDimension(dimension),
-            PolynomialSize(polynomial_size),
-            &mut SecretRandomGenerator::new(None),
-        );
+        RLWESecretKey::new_raw_with_kind(polynomial_size, dimension, std_dev, SecretKeyKind::Binary)
+    }
+
+    /// Generate a new secret key of a given kind from a raw dimension (i.e. without a RLWEParams input)
+    /// # Argument
+    /// * `polynomial_size` - the size of the polynomial
+    /// * `dimension` - the length the LWE mask
+    /// * `std_dev` - the standard deviation for the encryption
+    /// * `kind` - the distribution of the coefficients of the key
+    /// # Output
+    /// * a new RLWESecretKey
+    pub fn new_raw_with_kind(
+        polynomial_size: usize,
+        dimension: usize,
+        std_dev: f64,
+        kind: SecretKeyKind,
+    ) -> RLWESecretKey {
         RLWESecretKey {
-            val,
+            val: GlweKeyCoefficients::new(
+                kind.generate_rlwe_secret_key(polynomial_size, dimension),
+                polynomial_size,
+            ),
             polynomial_size,
             dimension,
             std_dev,
+            kind,
+            fingerprint: KeyFingerprint::new(dimension * polynomial_size, std_dev, kind),
         }
     }
 
@@ -63,12 +99,25 @@ impl RLWESecretKey {
     /// * an LWE secret key
     pub fn to_lwe_secret_key(&self) -> crate::LWESecretKey {
         crate::LWESecretKey {
-            val: LweSecretKey::binary_from_container(self.val.clone().into_tensor().into_container()),
+            val: crate::LweKeyCoefficients::new(self.val.as_slice().to_vec()),
             dimension: self.dimension * self.polynomial_size,
             std_dev: self.std_dev,
+            kind: self.kind,
+            fingerprint: self.fingerprint,
         }
     }
 
+    /// Copy the coefficients of the key into elements of the torus of T, the copy having to be
+    /// wiped by the caller
+    /// They can be viewed as a key of concrete-core with with_glwe_secret_key!
+    pub(crate) fn to_generic_coefficients<T: GenericTorus>(&self) -> Vec<T> {
+        self.val
+            .as_slice()
+            .iter()
+            .map(|s| T::cast_from(*s))
+            .collect()
+    }
+
     /// Return the variance of the error distribution associated with the secret key
     /// # Output
     /// * the variance
@@ -85,13 +134,57 @@ impl RLWESecretKey {
     }
 }
 
+/// The coefficients of an RLWE secret key of any kind
+/// They are serialized with the layout of the binary GlweSecretKey of concrete-core which stored
+/// them before the other kinds were introduced, so that the older key files can still be read,
+/// and they are wiped when dropped
+#[derive(Serialize, Deserialize, PartialEq)]
+pub(crate) struct GlweKeyCoefficients {
+    tensor: Vec<Torus>,
+    poly_size: usize,
+    kind: (),
+}
+
+impl GlweKeyCoefficients {
+    pub(crate) fn new(coefficients: Vec<Torus>, polynomial_size: usize) -> GlweKeyCoefficients {
+        GlweKeyCoefficients {
+            tensor: coefficients,
+            poly_size: polynomial_size,
+            kind: (),
+        }
+    }
+
+    pub(crate) fn as_slice(&self) -> &[Torus] {
+        &self.tensor
+    }
+}
+
+impl Drop for GlweKeyCoefficients {
+    fn drop(&mut self) {
+        crate::utils::zeroize(&mut self.tensor);
+    }
+}
+
+/// Print the RLWESecretKey without its coefficients
+impl fmt::Debug for RLWESecretKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("RLWESecretKey")
+            .field("polynomial_size", &self.polynomial_size)
+            .field("dimension", &self.dimension)
+            .field("std_dev", &self.std_dev)
+            .field("kind", &self.kind)
+            .field("fingerprint", &self.fingerprint)
+            .finish_non_exhaustive()
+    }
+}
+
 impl fmt::Display for RLWESecretKey {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let mut to_be_print: String = "".to_string();
         to_be_print = to_be_print
             + &format!(
-                " RLWESecretKey {{\n         -> dimension = {}\n         -> polynomial_size = {}\n         -> std_dev = {}\n",
-                self.dimension, self.polynomial_size, self.std_dev
+                " RLWESecretKey {{\n         -> dimension = {}\n         -> polynomial_size = {}\n         -> std_dev = {}\n         -> kind = {:?}\n         -> fingerprint = {}\n",
+                self.dimension, self.polynomial_size, self.std_dev, self.kind, self.fingerprint
             );
         to_be_print += "       }";
 
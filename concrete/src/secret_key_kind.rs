@@ -0,0 +1,317 @@
+//! secret_key_kind module describing the distributions the secret keys can be sampled from
+
+use crate::Torus;
+use concrete_commons::dispersion::DispersionParameter;
+use concrete_commons::numeric::Numeric;
+use concrete_commons::parameters::{GlweDimension, LweDimension, PolynomialSize};
+use concrete_core::crypto::secret::generators::SecretRandomGenerator;
+use concrete_core::crypto::secret::{GlweSecretKey, LweSecretKey};
+use concrete_core::math::tensor::IntoTensor;
+use concrete_core::math::torus::UnsignedTorus;
+use concrete_npe as npe;
+use serde::{Deserialize, Serialize};
+
+/// Distribution of the coefficients of an LWE or RLWE secret key
+/// # Variants
+/// * `Binary` - coefficients uniformly sampled in {0, 1}
+/// * `Ternary` - coefficients uniformly sampled in {-1, 0, 1}
+/// * `Gaussian` - coefficients sampled from a rounded centered normal distribution
+///
+/// Whatever the kind, the coefficients are stored as wrapping Torus elements (-1 is Torus::MAX)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum SecretKeyKind {
+    Binary,
+    Ternary,
+    Gaussian,
+}
+
+impl Default for SecretKeyKind {
+    fn default() -> Self {
+        SecretKeyKind::Binary
+    }
+}
+
+impl SecretKeyKind {
+    /// Return the standard deviation of the coefficients of a Gaussian secret key,
+    /// as an integer (and not as a torus element)
+    pub fn get_gaussian_std_dev() -> f64 {
+        <Torus as UnsignedTorus>::GAUSSIAN_KEY_LOG_STD.get_standard_dev()
+            * f64::powi(2., <Torus as Numeric>::BITS as i32)
+    }
+
+    /// Return the expectation of the square of a coefficient of a secret key of this kind
+    ///
+    /// # Output
+    /// * 1/2 for a binary key, 2/3 for a ternary key, sigma^2 + 1/12 for a Gaussian key
+    pub fn get_second_moment(&self) -> f64 {
+        match self {
+            SecretKeyKind::Binary => 1. / 2.,
+            SecretKeyKind::Ternary => 2. / 3.,
+            SecretKeyKind::Gaussian => f64::powi(Self::get_gaussian_std_dev(), 2) + 1. / 12.,
+        }
+    }
+
+    /// Return the variance of the output of a key switch whose input key is of this kind
+    /// It follows the NPE formula, whose rounding term counts dimension_before binary key
+    /// coefficients, each of them being weighted here by twice the second moment of the key
+    ///
+    /// # Argument
+    /// * `dimension_before` - the size of the input LWE mask
+    /// * `level` - the number of levels of the decomposition
+    /// * `base_log` - the log2 of the decomposition base
+    /// * `var_ks` - the variance of the key switching key
+    /// * `var_input` - the variance of the input LWE
+    ///
+    /// # Output
+    /// * the output variance
+    pub fn get_keyswitch_variance(
+        &self,
+        dimension_before: usize,
+        level: usize,
+        base_log: usize,
+        var_ks: f64,
+        var_input: f64,
+    ) -> f64 {
+        self.get_keyswitch_variance_in::<Torus>(
+            dimension_before,
+            level,
+            base_log,
+            var_ks,
+            var_input,
+        )
+    }
+
+    /// Same as get_keyswitch_variance, for a key switch computed in the torus of T, e.g. the u32
+    /// one of LWE32 or of concrete-boolean
+    pub fn get_keyswitch_variance_in<T: Numeric>(
+        &self,
+        dimension_before: usize,
+        level: usize,
+        base_log: usize,
+        var_ks: f64,
+        var_input: f64,
+    ) -> f64 {
+        let q_square = f64::powi(2., 2 * <T as Numeric>::BITS as i32);
+        let key_weight = 2. * self.get_second_moment() * dimension_before as f64;
+        let res_1 = key_weight
+            * (1. / 24. * f64::powi(2., -2 * (base_log * level) as i32) + 1. / (48. * q_square));
+        let res_2 = (dimension_before * level) as f64
+            * (f64::powi(2., 2 * base_log as i32) / 12. + 1. / 6.)
+            * var_ks;
+        var_input + res_1 + res_2
+    }
+
+    /// Return the variance of the output of a bootstrap whose output key is of this kind
+    /// It follows the NPE formula, whose rounding terms count rlwe_dimension * polynomial_size
+    /// binary key coefficients, each of them being weighted here by twice the second moment of
+    /// the key
+    ///
+    /// # Argument
+    /// * `lwe_dimension` - the size of the binary LWE key the blind rotation runs on
+    /// * `rlwe_dimension` - the size of the RLWE mask
+    /// * `level` - the number of levels of the decomposition
+    /// * `base_log` - the log2 of the decomposition base
+    /// * `polynomial_size` - the number of coefficients of the polynomials
+    /// * `var_bsk` - the variance of the bootstrapping key
+    ///
+    /// # Output
+    /// * the output variance
+    pub fn get_bootstrap_variance(
+        &self,
+        lwe_dimension: usize,
+        rlwe_dimension: usize,
+        level: usize,
+        base_log: usize,
+        polynomial_size: usize,
+        var_bsk: f64,
+    ) -> f64 {
+        self.get_bootstrap_variance_in::<Torus>(
+            lwe_dimension,
+            rlwe_dimension,
+            level,
+            base_log,
+            polynomial_size,
+            var_bsk,
+        )
+    }
+
+    /// Same as get_bootstrap_variance, for a bootstrap computed in the torus of T, e.g. the u32
+    /// one of LWE32 or of concrete-boolean
+    pub fn get_bootstrap_variance_in<T: Numeric>(
+        &self,
+        lwe_dimension: usize,
+        rlwe_dimension: usize,
+        level: usize,
+        base_log: usize,
+        polynomial_size: usize,
+        var_bsk: f64,
+    ) -> f64 {
+        let b_g = f64::powi(2., base_log as i32);
+        let q_square = f64::powi(2., 2 * <T as Numeric>::BITS as i32);
+        let key_weight = 2. * self.get_second_moment() * (rlwe_dimension * polynomial_size) as f64;
+        let res_1 = (lwe_dimension * (rlwe_dimension + 1) * level * polynomial_size) as f64
+            * (b_g * b_g + 2.)
+            / 12.
+            * var_bsk;
+        let res_2 = lwe_dimension as f64
+            * ((key_weight + 2.) / (24. * f64::powi(b_g, 2 * level as i32))
+                + lwe_dimension as f64 * (key_weight / 48. - 1. / 12.) / q_square);
+        res_1 + res_2
+    }
+
+    /// Return the variance of the output of an external product with a GGSW encrypted under an
+    /// RLWE key of this kind
+    /// It follows the NPE formula, whose rounding term counts dimension * polynomial_size binary
+    /// key coefficients, each of them being weighted here by twice the second moment of the key
+    ///
+    /// # Argument
+    /// * `dimension` - the size of the RLWE mask
+    /// * `level` - the number of levels of the decomposition
+    /// * `base_log` - the log2 of the decomposition base
+    /// * `polynomial_size` - the number of coefficients of the polynomials
+    /// * `var_ggsw` - the variance of the GGSW
+    /// * `var_rlwe` - the variance of the input RLWE
+    ///
+    /// # Output
+    /// * the output variance
+    pub(crate) fn get_external_product_variance(
+        &self,
+        dimension: usize,
+        level: usize,
+        base_log: usize,
+        polynomial_size: usize,
+        var_ggsw: f64,
+        var_rlwe: f64,
+    ) -> f64 {
+        let b_g = f64::powi(2., base_log as i32);
+        let q_square = f64::powi(2., 2 * <Torus as Numeric>::BITS as i32);
+        let key_weight = 2. * self.get_second_moment() * (dimension * polynomial_size) as f64;
+        let res_1 =
+            ((dimension + 1) * level * polynomial_size) as f64 * (b_g * b_g + 2.) / 12. * var_ggsw;
+        let res_2 = (key_weight + 2.) / (24. * f64::powi(b_g, 2 * level as i32))
+            + (key_weight / 48. - 1. / 12.) / q_square;
+        res_1 + res_2 + var_rlwe
+    }
+
+    /// Return the variance of the output of a CMux with a GGSW encrypted under an RLWE key of
+    /// this kind, i.e. ct_0 + GGSW * (ct_1 - ct_0)
+    ///
+    /// # Argument
+    /// * `var_rlwe_0` - the variance of the RLWE ciphertext selected by 0
+    /// * `var_rlwe_1` - the variance of the RLWE ciphertext selected by 1
+    /// * `var_ggsw` - the variance of the GGSW
+    /// * `dimension` - the size of the RLWE mask
+    /// * `polynomial_size` - the number of coefficients of the polynomials
+    /// * `base_log` - the log2 of the decomposition base
+    /// * `level` - the number of levels of the decomposition
+    ///
+    /// # Output
+    /// * the output variance
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn get_cmux_variance(
+        &self,
+        var_rlwe_0: f64,
+        var_rlwe_1: f64,
+        var_ggsw: f64,
+        dimension: usize,
+        polynomial_size: usize,
+        base_log: usize,
+        level: usize,
+    ) -> f64 {
+        let var_external_product = self.get_external_product_variance(
+            dimension,
+            level,
+            base_log,
+            polynomial_size,
+            var_ggsw,
+            npe::add_ciphertexts(var_rlwe_0, var_rlwe_1),
+        );
+        npe::add_ciphertexts(var_external_product, var_rlwe_0)
+    }
+
+    /// Return the variance of the noise added by switching an LWE ciphertext whose key is of this
+    /// kind to the modulus 2^log_q: the rounding errors of the body and of the mask coefficients,
+    /// the latter being multiplied by the key coefficients
+    ///
+    /// # Argument
+    /// * `dimension` - the size of the LWE mask
+    /// * `log_q` - the log2 of the new modulus
+    ///
+    /// # Output
+    /// * the variance of the rounding noise
+    pub(crate) fn get_modulus_switch_variance(&self, dimension: usize, log_q: usize) -> f64 {
+        (1. + self.get_second_moment() * dimension as f64) * f64::powi(2., -2 * log_q as i32) / 12.
+    }
+
+    /// Sample the coefficients of a new LWE secret key of this kind
+    pub(crate) fn generate_lwe_secret_key(&self, dimension: usize) -> Vec<Torus> {
+        let mut generator = SecretRandomGenerator::new(None);
+        match self {
+            SecretKeyKind::Binary => {
+                LweSecretKey::generate_binary(LweDimension(dimension), &mut generator)
+                    .into_tensor()
+                    .into_container()
+            }
+            SecretKeyKind::Ternary => {
+                LweSecretKey::generate_ternary(LweDimension(dimension), &mut generator)
+                    .into_tensor()
+                    .into_container()
+            }
+            SecretKeyKind::Gaussian => {
+                LweSecretKey::generate_gaussian(LweDimension(dimension), &mut generator)
+                    .into_tensor()
+                    .into_container()
+            }
+        }
+    }
+
+    /// Sample the coefficients of a new RLWE secret key of this kind
+    pub(crate) fn generate_rlwe_secret_key(
+        &self,
+        polynomial_size: usize,
+        dimension: usize,
+    ) -> Vec<Torus> {
+        let mut generator = SecretRandomGenerator::new(None);
+        match self {
+            SecretKeyKind::Binary => GlweSecretKey::generate_binary(
+                GlweDimension(dimension),
+                PolynomialSize(polynomial_size),
+                &mut generator,
+            )
+            .into_tensor()
+            .into_container(),
+            SecretKeyKind::Ternary => GlweSecretKey::generate_ternary(
+                GlweDimension(dimension),
+                PolynomialSize(polynomial_size),
+                &mut generator,
+            )
+            .into_tensor()
+            .into_container(),
+            SecretKeyKind::Gaussian => GlweSecretKey::generate_gaussian(
+                GlweDimension(dimension),
+                PolynomialSize(polynomial_size),
+                &mut generator,
+            )
+            .into_tensor()
+            .into_container(),
+        }
+    }
+
+    /// Encode the kind into a Torus element, for the key files that are not written with serde
+    pub(crate) fn to_torus(self) -> Torus {
+        match self {
+            SecretKeyKind::Binary => 0,
+            SecretKeyKind::Ternary => 1,
+            SecretKeyKind::Gaussian => 2,
+        }
+    }
+
+    /// Decode a kind encoded with to_torus, anything unknown being a binary kind
+    pub(crate) fn from_torus(code: Torus) -> SecretKeyKind {
+        match code {
+            1 => SecretKeyKind::Ternary,
+            2 => SecretKeyKind::Gaussian,
+            _ => SecretKeyKind::Binary,
+        }
+    }
+}
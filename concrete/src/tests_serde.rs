@@ -6,10 +6,13 @@ use concrete_commons::parameters::{
 };
 use concrete_core::crypto::bootstrap::FourierBootstrapKey;
 use concrete_core::crypto::lwe::LweKeyswitchKey;
+use concrete_core::crypto::secret::LweSecretKey;
 use concrete_core::math::fft::Complex64;
 
 use crate::{
-    Encoder, LWEParams, LWESecretKey, RLWEParams, RLWESecretKey, VectorLWE, LWEBSK, LWEKSK,
+    CompressedVectorLWE, Encoder, KeyFingerprint, LWEParams, LWESecretKey, Matrix, ParameterBundle,
+    RLWEAutomorphismKey, RLWEParams, RLWESecretKey, SecretKeyKind, VectorLWE, GGSW, LWEBSK,
+    LWEBSK32, LWEKSK, LWEKSK32, LWEPFKSK, RLWEKSK,
 };
 
 fn delete_file<P: AsRef<Path>>(path: P) -> std::io::Result<()> {
@@ -39,6 +42,31 @@ fn test_lwe_save() {
     assert!(lwe_1 == lwe_2, "lwe_1 != lwe_2");
 }
 
+#[test]
+fn test_compressed_vector_lwe_save() {
+    let filename: &str = "compressed_vector_lwe.bin";
+    let encoder = Encoder::new(0., 1., 4, 1).unwrap();
+    let sk = LWESecretKey::new(&LWEParams::new(64, -20));
+    let lwe = VectorLWE::encode_encrypt(&sk, &[0.25, 0.5, 0.75], &encoder).unwrap();
+    let compressed_1 = lwe.modulus_switch(20, sk.kind).unwrap();
+    compressed_1.save(filename).unwrap();
+    let compressed_2 = CompressedVectorLWE::load(filename).unwrap();
+    delete_file(filename).unwrap();
+    println!("{} \n {}", compressed_1, compressed_2);
+    assert!(compressed_1 == compressed_2, "compressed_1 != compressed_2");
+}
+
+#[test]
+fn test_matrix_save() {
+    let filename: &str = "matrix.json";
+    let matrix_1 = Matrix::from_rows(&[vec![1.5, -2., 0.25], vec![3., 0., -1.]]).unwrap();
+    matrix_1.save(filename).unwrap();
+    let matrix_2 = Matrix::load(filename).unwrap();
+    delete_file(filename).unwrap();
+    println!("{} \n {}", matrix_1, matrix_2);
+    assert!(matrix_1 == matrix_2, "matrix_1 != matrix_2");
+}
+
 #[test]
 fn test_lwebsk_save() {
     let filename: &str = "lwebsk.json";
@@ -60,6 +88,10 @@ fn test_lwebsk_save() {
         polynomial_size: 512,
         base_log: 5,
         level: 4,
+        nb_bit_expansion: 1,
+        output_kind: SecretKeyKind::Gaussian,
+        input_fingerprint: KeyFingerprint(0x1234),
+        output_fingerprint: KeyFingerprint(u64::MAX),
     };
     a.save(filename);
     let b = LWEBSK::load(filename);
@@ -84,6 +116,9 @@ fn test_lweksk_save() {
         dimension_after: 512,
         base_log: 2,
         level: 7,
+        kind_before: SecretKeyKind::Ternary,
+        fingerprint_before: KeyFingerprint(u64::MAX),
+        fingerprint_after: KeyFingerprint(0x5678),
     };
     ksk1.save(filename);
     let ksk2 = LWEKSK::load(filename);
@@ -92,6 +127,120 @@ fn test_lweksk_save() {
     assert!(ksk1 == ksk2, "ksk1 != ksk2");
 }
 
+#[test]
+fn test_lweksk32_save() {
+    let filename: &str = "lweksk32.json";
+    let sk_before = LWESecretKey::new(&LWEParams::new(64, -20));
+    let sk_after = LWESecretKey::new(&LWEParams::new(32, -20));
+    let ksk1 = LWEKSK32::new(&sk_before, &sk_after, 4, 3);
+    ksk1.save(filename);
+    let ksk2 = LWEKSK32::load(filename);
+    delete_file(filename).unwrap();
+    assert!(ksk1 == ksk2, "ksk1 != ksk2");
+}
+
+#[test]
+#[should_panic(expected = "Wrong width of the key switching key")]
+fn test_lweksk32_load_64_bits() {
+    let filename: &str = "lweksk32_load.json";
+    let sk_before = LWESecretKey::new(&LWEParams::new(64, -20));
+    let sk_after = LWESecretKey::new(&LWEParams::new(32, -20));
+    LWEKSK::new(&sk_before, &sk_after, 4, 3).save(filename);
+    let ksk = std::panic::catch_unwind(|| LWEKSK32::load(filename));
+    delete_file(filename).unwrap();
+    std::panic::resume_unwind(ksk.unwrap_err());
+}
+
+#[test]
+fn test_lwebsk32_save() {
+    let filename: &str = "lwebsk32.json";
+    let sk_input = LWESecretKey::new(&LWEParams::new(20, -20));
+    let sk_output = RLWESecretKey::new(&RLWEParams::new(256, 1, -20).unwrap());
+    let bsk1 = LWEBSK32::new(&sk_input, &sk_output, 6, 2);
+    bsk1.save(filename);
+    let bsk2 = LWEBSK32::load(filename);
+    delete_file(filename).unwrap();
+    assert!(bsk1 == bsk2, "bsk1 != bsk2");
+}
+
+#[test]
+fn test_rlweksk_save() {
+    let filename: &str = "rlweksk.json";
+
+    let p = RLWEParams {
+        dimension: 2,
+        log2_std_dev: -30,
+        polynomial_size: 256,
+    };
+    let sk_before = RLWESecretKey::new(&p);
+    let sk_after = RLWESecretKey::new(&p);
+
+    let ksk1 = RLWEKSK::new(&sk_before, &sk_after, 4, 3).unwrap();
+    ksk1.save(filename).unwrap();
+    let ksk2 = RLWEKSK::load(filename).unwrap();
+    delete_file(filename).unwrap();
+    println!("{} \n {}", ksk1, ksk2);
+    assert!(ksk1 == ksk2, "ksk1 != ksk2");
+}
+
+#[test]
+fn test_rlwe_automorphism_key_save() {
+    let filename: &str = "rlwe_automorphism_key.json";
+
+    let p = RLWEParams {
+        dimension: 1,
+        log2_std_dev: -30,
+        polynomial_size: 256,
+    };
+    let sk = RLWESecretKey::new(&p);
+
+    let autkey1 = RLWEAutomorphismKey::new(&sk, 3, 4, 3).unwrap();
+    autkey1.save(filename).unwrap();
+    let autkey2 = RLWEAutomorphismKey::load(filename).unwrap();
+    delete_file(filename).unwrap();
+    println!("{} \n {}", autkey1, autkey2);
+    assert!(autkey1 == autkey2, "autkey1 != autkey2");
+}
+
+#[test]
+fn test_lwepfksk_save() {
+    let filename: &str = "lwepfksk.json";
+
+    let p = RLWEParams {
+        dimension: 1,
+        log2_std_dev: -30,
+        polynomial_size: 256,
+    };
+    let sk_before = LWESecretKey::new(&LWEParams::new(10, -30));
+    let sk_after = RLWESecretKey::new(&p);
+
+    let pfksk1 = LWEPFKSK::new(&sk_before, &sk_after, 4, 3);
+    pfksk1.save(filename).unwrap();
+    let pfksk2 = LWEPFKSK::load(filename).unwrap();
+    delete_file(filename).unwrap();
+    println!("{} \n {}", pfksk1, pfksk2);
+    assert!(pfksk1 == pfksk2, "pfksk1 != pfksk2");
+}
+
+#[test]
+fn test_ggsw_save() {
+    let filename: &str = "ggsw.json";
+
+    let p = RLWEParams {
+        dimension: 1,
+        log2_std_dev: -30,
+        polynomial_size: 256,
+    };
+    let sk = RLWESecretKey::new(&p);
+
+    let ggsw1 = GGSW::encrypt(&sk, 1, 6, 4).unwrap();
+    ggsw1.save(filename).unwrap();
+    let ggsw2 = GGSW::load(filename).unwrap();
+    delete_file(filename).unwrap();
+    println!("{} \n {}", ggsw1, ggsw2);
+    assert!(ggsw1 == ggsw2, "ggsw1 != ggsw2");
+}
+
 #[test]
 fn test_lweparams_save() {
     let filename: &str = "lweparams.json";
@@ -125,6 +274,78 @@ fn test_lwesecretkey_save() {
     assert!(sk1 == sk2, "sk1 != sk2");
 }
 
+#[test]
+fn test_lwesecretkey_kind_save() {
+    let filename: &str = "lwesk_kind.json";
+
+    let p = LWEParams {
+        dimension: 10,
+        log2_std_dev: 2,
+    };
+
+    let sk1 = LWESecretKey::new_with_kind(&p, SecretKeyKind::Ternary);
+
+    sk1.save(filename).unwrap();
+    let sk2 = LWESecretKey::load(filename).unwrap();
+    delete_file(filename).unwrap();
+    assert!(sk1 == sk2, "sk1 != sk2");
+
+    // a key serialized without its kind is a binary key
+    let mut json = serde_json::to_value(LWESecretKey::new(&p)).unwrap();
+    json.as_object_mut().unwrap().remove("kind");
+    let sk3: LWESecretKey = serde_json::from_value(json).unwrap();
+    assert_eq!(sk3.kind, SecretKeyKind::Binary);
+
+    // the coefficients keep the layout of the binary keys of concrete-core they were stored in
+    let legacy_val = LweSecretKey::binary_from_container(vec![1u64, 0, 1]);
+    let mut json = serde_json::to_value(LWESecretKey::new_raw(3, 0.5)).unwrap();
+    json["val"] = serde_json::to_value(&legacy_val).unwrap();
+    let sk4: LWESecretKey = serde_json::from_value(json).unwrap();
+    assert_eq!(sk4.val.as_slice(), &[1, 0, 1]);
+    assert_eq!(
+        bincode::serialize(&sk4.val).unwrap(),
+        bincode::serialize(&legacy_val).unwrap()
+    );
+}
+
+#[test]
+fn test_legacy_fingerprint() {
+    let p = LWEParams {
+        dimension: 10,
+        log2_std_dev: -20,
+    };
+    let sk = LWESecretKey::new(&p);
+    let encoder = Encoder::new(0., 1., 4, 1).unwrap();
+    let ct = VectorLWE::encode_encrypt(&sk, &[0.5], &encoder).unwrap();
+
+    // a key or a ciphertext serialized without its fingerprint has the unknown one
+    let mut json = serde_json::to_value(&sk).unwrap();
+    json.as_object_mut().unwrap().remove("fingerprint");
+    let legacy_sk: LWESecretKey = serde_json::from_value(json).unwrap();
+    assert!(legacy_sk.fingerprint.is_unknown());
+
+    let mut json = serde_json::to_value(&ct).unwrap();
+    json.as_object_mut().unwrap().remove("key_fingerprint");
+    let legacy_ct: VectorLWE = serde_json::from_value(json).unwrap();
+    assert!(legacy_ct.key_fingerprint.is_unknown());
+
+    // and can still be decrypted with any key of the right dimension
+    legacy_ct.decrypt_decode(&sk).unwrap();
+    ct.decrypt_decode(&legacy_sk).unwrap();
+}
+
+#[test]
+fn test_secretkey_debug_redacted() {
+    let lwe_sk = LWESecretKey::new_raw(10, 0.5);
+    let rlwe_sk = RLWESecretKey::new_raw(16, 2, 0.5);
+
+    for debug in [format!("{:?}", lwe_sk), format!("{:?}", rlwe_sk)].iter() {
+        assert!(debug.contains("dimension"));
+        assert!(!debug.contains("val"));
+        assert!(!debug.contains("tensor"));
+    }
+}
+
 #[test]
 fn test_rlweparams_save() {
     let filename: &str = "rlweparams.json";
@@ -141,6 +362,38 @@ fn test_rlweparams_save() {
     assert!(p1 == p2, "p1 != p2");
 }
 
+#[test]
+fn test_parameter_bundle_save() {
+    let filename: &str = "parameter_bundle.json";
+
+    let p1 = ParameterBundle {
+        lwe_params: LWEParams {
+            dimension: 10,
+            log2_std_dev: -2,
+        },
+        rlwe_params: RLWEParams {
+            dimension: 1,
+            log2_std_dev: -3,
+            polynomial_size: 1024,
+        },
+        bsk_base_log: 6,
+        bsk_level: 4,
+        ksk_base_log: 2,
+        ksk_level: 7,
+        precision: 3,
+        padding: 2,
+        error_probability: 0.5,
+        cost: 1000.,
+        security_bits: 1.5,
+    };
+
+    p1.save(filename).unwrap();
+    let p2 = ParameterBundle::load(filename).unwrap();
+    delete_file(filename).unwrap();
+    println!("{} \n {}", p1, p2);
+    assert!(p1 == p2, "p1 != p2");
+}
+
 #[test]
 fn test_rlwesecretkey_save() {
     let filename: &str = "rlwesk.json";
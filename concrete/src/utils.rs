@@ -0,0 +1,135 @@
+//! utils module gathering the helpers shared by the key structures
+
+use concrete_commons::numeric::Numeric;
+use concrete_core::crypto::secret::generators::EncryptionRandomGenerator;
+use concrete_core::math::random::RandomGenerator;
+#[cfg(feature = "multithread")]
+use rayon::prelude::*;
+use std::ops::{Deref, DerefMut};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+/// Overwrite some secret values with zeros, with volatile writes so that the compiler
+/// does not optimize them away when the memory is about to be freed, e.g. a copy of the
+/// coefficients of a secret key
+///
+/// Exported for concrete-boolean only, and hidden from the documentation: it is not part of the
+/// public API
+pub fn zeroize<T: Numeric>(values: &mut [T]) {
+    for value in values.iter_mut() {
+        // Safety: value is a valid and aligned mutable reference
+        unsafe { std::ptr::write_volatile(value, T::ZERO) };
+    }
+    std::sync::atomic::compiler_fence(std::sync::atomic::Ordering::SeqCst);
+}
+
+/// A buffer of secret values, e.g. a copy of the coefficients of a secret key, wiped with
+/// zeroize when it goes out of scope, including on an early return or a panic
+pub(crate) struct Zeroizing<T: Numeric>(pub(crate) Vec<T>);
+
+impl<T: Numeric> Deref for Zeroizing<T> {
+    type Target = Vec<T>;
+
+    fn deref(&self) -> &Vec<T> {
+        &self.0
+    }
+}
+
+impl<T: Numeric> DerefMut for Zeroizing<T> {
+    fn deref_mut(&mut self) -> &mut Vec<T> {
+        &mut self.0
+    }
+}
+
+impl<T: Numeric> Drop for Zeroizing<T> {
+    fn drop(&mut self) {
+        zeroize(&mut self.0);
+    }
+}
+
+/// The number of times a key generation with progress reports its progress
+pub(crate) const NB_PROGRESS_STEPS: usize = 100;
+
+/// Fill a key made of `nb_blocks` blocks of the same size (one block per coefficient of the
+/// input secret key), reporting the progress each time a multiple of 1 / NB_PROGRESS_STEPS of
+/// the blocks is filled, and once the key is complete
+/// Each block is filled with its own generator, as concrete-core forks one generator per GGSW
+/// of a bootstrapping key: the fork methods of concrete-core being private, the mask generator
+/// of each block is seeded from a single generator, in the order of the blocks, and its noise
+/// generator is seeded from the system. With the multithread feature, rayon splits the blocks
+/// between its jobs, which count them with an atomic counter.
+///
+/// # Argument
+/// * `output` - the coefficients of the key
+/// * `nb_blocks` - the number of blocks of the key
+/// * `fill` - a function filling a block from its index with a generator
+/// * `callback` - a function called with the fraction of the key filled, increasing up to 1.,
+///   returning false to cancel the generation, which is only possible before the key is complete
+///
+/// # Output
+/// * Some(progress) - if the callback cancelled the generation after `progress` of the key was
+///   filled, the key being then only partially filled and meant to be dropped
+pub(crate) fn fill_with_progress<T, F>(
+    output: &mut [T],
+    nb_blocks: usize,
+    fill: F,
+    callback: &mut (dyn FnMut(f64) -> bool + Send),
+) -> Option<f64>
+where
+    T: Send,
+    F: Fn(usize, &mut [T], &mut EncryptionRandomGenerator) + Sync,
+{
+    if let Some(block_len) = output.len().checked_div(nb_blocks) {
+        let mut seed_generator = RandomGenerator::new(None);
+        let seeds: Vec<u128> = (0..nb_blocks)
+            .map(|_| seed_generator.random_uniform())
+            .collect();
+        let nb_filled = AtomicUsize::new(0);
+        let cancelled = AtomicBool::new(false);
+        // the callback and the last progress it was called with
+        let reports = Mutex::new((&mut *callback, 0.));
+        let fill_block = |(i, block): (usize, &mut [T])| {
+            if cancelled.load(Ordering::SeqCst) {
+                return;
+            }
+            fill(
+                i,
+                block,
+                &mut EncryptionRandomGenerator::new(Some(seeds[i])),
+            );
+            let filled = nb_filled.fetch_add(1, Ordering::SeqCst) + 1;
+            if filled < nb_blocks
+                && filled * NB_PROGRESS_STEPS / nb_blocks
+                    > (filled - 1) * NB_PROGRESS_STEPS / nb_blocks
+            {
+                let mut reports = reports.lock().unwrap();
+                let (callback, last_progress) = &mut *reports;
+                let progress = filled as f64 / nb_blocks as f64;
+                if !cancelled.load(Ordering::SeqCst) && progress > *last_progress {
+                    *last_progress = progress;
+                    if !callback(progress) {
+                        cancelled.store(true, Ordering::SeqCst);
+                    }
+                }
+            }
+        };
+        #[cfg(feature = "multithread")]
+        output
+            .par_chunks_mut(block_len)
+            .enumerate()
+            .for_each(fill_block);
+        #[cfg(not(feature = "multithread"))]
+        output
+            .chunks_mut(block_len)
+            .enumerate()
+            .for_each(fill_block);
+        let (_, last_progress) = reports.into_inner().unwrap();
+        // another job may have filled the last blocks after the cancellation
+        if cancelled.into_inner() && nb_filled.into_inner() < nb_blocks {
+            return Some(last_progress);
+        }
+    }
+    // the key is complete, so that this last report cannot cancel the generation anymore
+    callback(1.);
+    None
+}
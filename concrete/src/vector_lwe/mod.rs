@@ -1,10 +1,11 @@
 //! vector_lwe ciphertext module
 
+use std::convert::TryFrom;
 use std::error::Error;
 use std::fmt;
 
 use crate::error::CryptoAPIError;
-use crate::{read_from_file, write_to_file, Torus};
+use crate::{read_from_file, write_to_file, GenericTorus, KeyFingerprint, Torus};
 use backtrace::Backtrace;
 use colored::Colorize;
 use concrete_commons::dispersion::StandardDev;
@@ -21,17 +22,15 @@ use concrete_core::{
     math::tensor::{AsMutSlice, AsMutTensor, AsRefSlice, AsRefTensor, IntoTensor},
 };
 
-use concrete_core::crypto::bootstrap::Bootstrap;
-
 use concrete_npe as npe;
-use concrete_npe::{ LWE};
 use itertools::izip;
 use serde::{Deserialize, Serialize};
 
 #[cfg(test)]
 mod tests;
 
-/// Structure containing a list of LWE ciphertexts.
+/// Structure containing a list of LWE ciphertexts, whose coefficients are elements of the torus
+/// of T (see GenericTorus), VectorLWE being the usual one with Torus coefficients.
 /// They all have the same dimension (i.e. the length of the LWE mask).
 ///
 /// # Attributes
@@ -40,16 +39,26 @@ mod tests;
 /// * `dimension` - the length the LWE mask
 /// * `nb_ciphertexts` - the number of LWE ciphertexts present in the list
 /// * `encoders` - the encoders of each LWE ciphertext of the list
+/// * `key_fingerprint` - the fingerprint of the secret key of the LWE ciphertexts (unknown for
+///   trivial and older ciphertexts)
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-pub struct VectorLWE {
-    pub ciphertexts: LweList<Vec<Torus>>,
+pub struct GenericVectorLWE<T> {
+    pub ciphertexts: LweList<Vec<T>>,
     pub variances: Vec<f64>,
     pub dimension: usize,
     pub nb_ciphertexts: usize,
     pub encoders: Vec<crate::Encoder>,
+    #[serde(default)]
+    pub key_fingerprint: KeyFingerprint,
 }
 
-impl VectorLWE {
+/// A list of LWE ciphertexts whose coefficients are Torus elements
+pub type VectorLWE = GenericVectorLWE<Torus>;
+
+/// A list of LWE ciphertexts whose coefficients are u32, see GenericTorus
+pub type VectorLWE32 = GenericVectorLWE<u32>;
+
+impl<T: GenericTorus> GenericVectorLWE<T> {
     /// Instantiate a new VectorLWE filled with zeros from a dimension and a number of ciphertexts
     /// `nb_ciphertexts` has to be at least 1.
     ///
@@ -71,13 +80,13 @@ impl VectorLWE {
     pub fn zero(
         dimension: usize,
         nb_ciphertexts: usize,
-    ) -> Result<crate::VectorLWE, CryptoAPIError> {
+    ) -> Result<GenericVectorLWE<T>, CryptoAPIError> {
         if nb_ciphertexts == 0 {
             return Err(ZeroCiphertextsInStructureError!(nb_ciphertexts));
         }
-        Ok(VectorLWE {
+        Ok(GenericVectorLWE {
             ciphertexts: LweList::allocate(
-                0,
+                T::ZERO,
                 LweSize(dimension + 1),
                 CiphertextCount(nb_ciphertexts),
             ),
@@ -85,6 +94,7 @@ impl VectorLWE {
             dimension,
             nb_ciphertexts,
             encoders: vec![crate::Encoder::zero(); nb_ciphertexts],
+            key_fingerprint: KeyFingerprint::UNKNOWN,
         })
     }
 
@@ -100,6 +110,7 @@ impl VectorLWE {
     /// * DimensionError if self and ct does not share the same dimension
     /// * IndexError if self_index >= self.nb_ciphertexts
     /// * IndexError if ct_index >= ct.nb_ciphertexts
+    /// * KeyMismatch if self and ct are encrypted under different keys
     ///
     /// # Example
     /// ```rust
@@ -115,7 +126,7 @@ impl VectorLWE {
     pub fn copy_in_nth_nth_inplace(
         &mut self,
         self_index: usize,
-        ct: &VectorLWE,
+        ct: &GenericVectorLWE<T>,
         ct_index: usize,
     ) -> Result<(), CryptoAPIError> {
         // check dimensions
@@ -133,6 +144,9 @@ impl VectorLWE {
             return Err(IndexError!(ct.nb_ciphertexts, ct_index));
         }
 
+        // check the keys
+        let key_fingerprint = self.key_fingerprint.combine(ct.key_fingerprint)?;
+
         // get the size of one lwe ciphertext
         let ct_size = self.get_ciphertext_size();
 
@@ -156,6 +170,7 @@ impl VectorLWE {
         // copy the encoder
         self.encoders[self_index].copy(&ct.encoders[ct_index]);
 
+        self.key_fingerprint = key_fingerprint;
         Ok(())
     }
 
@@ -177,7 +192,7 @@ impl VectorLWE {
     /// // extract the first ciphertext of ct
     /// let ct_extracted = ct.extract_nth(0).unwrap();
     /// ```
-    pub fn extract_nth(&self, n: usize) -> Result<crate::VectorLWE, CryptoAPIError> {
+    pub fn extract_nth(&self, n: usize) -> Result<GenericVectorLWE<T>, CryptoAPIError> {
         if n >= self.nb_ciphertexts {
             return Err(IndexError!(self.nb_ciphertexts, n));
         }
@@ -188,16 +203,75 @@ impl VectorLWE {
             .get_sub((n * (self.get_ciphertext_size()))..((n + 1) * (self.get_ciphertext_size())))
             .as_slice()
             .to_owned();
-        let result = VectorLWE {
+        let result = GenericVectorLWE {
             ciphertexts: LweList::from_container(ct.to_vec(), LweSize(self.dimension + 1)),
             variances: vec![self.variances[n]; 1],
             dimension: self.dimension,
             nb_ciphertexts: 1,
             encoders: vec![self.encoders[n].clone(); 1],
+            key_fingerprint: self.key_fingerprint,
         };
         Ok(result)
     }
 
+    /// Return a copy of the n-th LWE ciphertext as an LWE structure
+    ///
+    /// # Arguments
+    /// * `n` - the index of the ciphertext
+    ///
+    /// # Output
+    /// * an LWE struct
+    /// * IndexError if n >= self.nb_ciphertexts
+    pub(crate) fn nth_to_lwe(&self, n: usize) -> Result<crate::GenericLWE<T>, CryptoAPIError> {
+        if n >= self.nb_ciphertexts {
+            return Err(IndexError!(self.nb_ciphertexts, n));
+        }
+
+        let ct_size = self.get_ciphertext_size();
+        Ok(crate::GenericLWE {
+            ciphertext: LweCiphertext::from_container(
+                self.ciphertexts.as_tensor().as_slice()[(n * ct_size)..((n + 1) * ct_size)]
+                    .to_vec(),
+            ),
+            variance: self.variances[n],
+            dimension: self.dimension,
+            encoder: self.encoders[n].clone(),
+            key_fingerprint: self.key_fingerprint,
+        })
+    }
+
+    /// Copy an LWE structure at the n-th position of self
+    ///
+    /// # Arguments
+    /// * `n` - the index of the ciphertext to overwrite
+    /// * `ct` - the LWE to copy
+    ///
+    /// # Output
+    /// * DimensionError if self and ct does not share the same dimension
+    /// * IndexError if n >= self.nb_ciphertexts
+    /// * KeyMismatch if self and ct are encrypted under different keys
+    pub(crate) fn copy_lwe_in_nth_inplace(
+        &mut self,
+        n: usize,
+        ct: &crate::GenericLWE<T>,
+    ) -> Result<(), CryptoAPIError> {
+        if ct.dimension != self.dimension {
+            return Err(DimensionError!(self.dimension, ct.dimension));
+        } else if n >= self.nb_ciphertexts {
+            return Err(IndexError!(self.nb_ciphertexts, n));
+        }
+        let key_fingerprint = self.key_fingerprint.combine(ct.key_fingerprint)?;
+
+        let ct_size = self.get_ciphertext_size();
+        self.ciphertexts.as_mut_tensor().as_mut_slice()[(n * ct_size)..((n + 1) * ct_size)]
+            .copy_from_slice(ct.ciphertext.as_tensor().as_slice());
+        self.variances[n] = ct.variance;
+        self.encoders[n].copy(&ct.encoder);
+
+        self.key_fingerprint = key_fingerprint;
+        Ok(())
+    }
+
     /// Encrypt plaintexts from a Plaintext with the provided LWEParams
     ///
     /// # Arguments
@@ -229,8 +303,8 @@ impl VectorLWE {
     pub fn encrypt(
         sk: &crate::LWESecretKey,
         plaintexts: &crate::Plaintext,
-    ) -> Result<crate::VectorLWE, CryptoAPIError> {
-        let mut res = VectorLWE::zero(sk.dimension, plaintexts.nb_plaintexts)?;
+    ) -> Result<GenericVectorLWE<T>, CryptoAPIError> {
+        let mut res = GenericVectorLWE::zero(sk.dimension, plaintexts.nb_plaintexts)?;
         res.encrypt_inplace(sk, plaintexts)?;
         Ok(res)
     }
@@ -265,10 +339,10 @@ impl VectorLWE {
         sk: &crate::LWESecretKey,
         messages: &[f64],
         encoder: &crate::Encoder,
-    ) -> Result<VectorLWE, CryptoAPIError> {
-        let mut plaintexts: Vec<Torus> = vec![0; messages.len()];
+    ) -> Result<GenericVectorLWE<T>, CryptoAPIError> {
+        let mut plaintexts: Vec<T> = vec![T::ZERO; messages.len()];
         for (pt, m) in plaintexts.iter_mut().zip(messages.iter()) {
-            *pt = encoder.encode_core(*m)?;
+            *pt = crate::generic_torus::from_torus(encoder.encode_core(*m)?);
         }
         let mut result_encoder: crate::Encoder = encoder.clone();
         let nb_bit_overlap: usize =
@@ -283,9 +357,9 @@ impl VectorLWE {
             );
         }
 
-        let mut res = VectorLWE {
+        let mut res = GenericVectorLWE {
             ciphertexts: LweList::allocate(
-                0,
+                T::ZERO,
                 LweSize(sk.dimension + 1),
                 CiphertextCount(messages.len()),
             ),
@@ -293,8 +367,9 @@ impl VectorLWE {
             dimension: sk.dimension,
             nb_ciphertexts: messages.len(),
             encoders: vec![result_encoder; messages.len()],
+            key_fingerprint: sk.fingerprint,
         };
-        res.encrypt_raw(sk, &plaintexts).unwrap();
+        res.encrypt_raw(sk, &plaintexts)?;
 
         Ok(res)
     }
@@ -333,8 +408,8 @@ impl VectorLWE {
         sk: &crate::LWESecretKey,
         messages: &[f64],
         encoders: &[crate::Encoder],
-    ) -> Result<VectorLWE, CryptoAPIError> {
-        let mut plaintexts: Vec<Torus> = vec![0; messages.len()];
+    ) -> Result<GenericVectorLWE<T>, CryptoAPIError> {
+        let mut plaintexts: Vec<T> = vec![T::ZERO; messages.len()];
         let mut result_encoders: Vec<crate::Encoder> = encoders.to_vec();
         for (((pt, m), enc), res_enc) in plaintexts
             .iter_mut()
@@ -342,7 +417,7 @@ impl VectorLWE {
             .zip(encoders.iter())
             .zip(result_encoders.iter_mut())
         {
-            *pt = enc.encode_core(*m)?;
+            *pt = crate::generic_torus::from_torus(enc.encode_core(*m)?);
 
             let nb_bit_overlap: usize =
                 res_enc.update_precision_from_variance(f64::powi(sk.std_dev, 2i32))?;
@@ -357,9 +432,9 @@ impl VectorLWE {
             }
         }
 
-        let mut res = VectorLWE {
+        let mut res = GenericVectorLWE {
             ciphertexts: LweList::allocate(
-                0,
+                T::ZERO,
                 LweSize(sk.dimension + 1),
                 CiphertextCount(messages.len()),
             ),
@@ -367,8 +442,9 @@ impl VectorLWE {
             dimension: sk.dimension,
             nb_ciphertexts: messages.len(),
             encoders: result_encoders,
+            key_fingerprint: sk.fingerprint,
         };
-        res.encrypt_raw(sk, &plaintexts).unwrap();
+        res.encrypt_raw(sk, &plaintexts)?;
 
         Ok(res)
     }
@@ -406,7 +482,12 @@ impl VectorLWE {
         plaintexts: &crate::Plaintext,
     ) -> Result<(), CryptoAPIError> {
         // encryption
-        self.encrypt_raw(sk, &plaintexts.plaintexts).unwrap();
+        let raw_plaintexts: Vec<T> = plaintexts
+            .plaintexts
+            .iter()
+            .map(|pt| crate::generic_torus::from_torus(*pt))
+            .collect();
+        self.encrypt_raw(sk, &raw_plaintexts)?;
 
         for (output_enc, input_enc) in izip!(self.encoders.iter_mut(), plaintexts.encoders.iter()) {
             // copy the Encoders from the Plaintexts to the VectorLWE
@@ -454,28 +535,31 @@ impl VectorLWE {
     pub fn encrypt_raw(
         &mut self,
         sk: &crate::LWESecretKey,
-        plaintexts: &[Torus],
+        plaintexts: &[T],
     ) -> Result<(), CryptoAPIError> {
+        // check if we have enough std dev to have noise in the ciphertext
+        crate::generic_torus::check_std_dev::<T>(sk.std_dev)?;
+
         // compute the variance
         let var = sk.get_variance();
 
-        // check if we have enough std dev to have noise in the ciphertext
-        if sk.std_dev < f64::powi(2., -(<Torus as Numeric>::BITS as i32) + 2) {
-            return Err(NoNoiseInCiphertext!(var));
-        }
-
         // fill the variance array
         for (self_var, _pt) in izip!(self.variances.iter_mut(), plaintexts.iter()) {
             *self_var = var;
         }
 
         // encrypt
-        sk.val.encrypt_lwe_list(
-            &mut self.ciphertexts,
-            &PlaintextList::from_container(plaintexts),
-            StandardDev::from_standard_dev(sk.std_dev),
-            &mut EncryptionRandomGenerator::new(None),
-        );
+        sk.with_generic_coefficients(|coefficients| {
+            with_lwe_secret_key!(sk.kind, coefficients, |key| {
+                key.encrypt_lwe_list(
+                    &mut self.ciphertexts,
+                    &PlaintextList::from_container(plaintexts),
+                    StandardDev::from_standard_dev(sk.std_dev),
+                    &mut EncryptionRandomGenerator::new(None),
+                )
+            })
+        });
+        self.key_fingerprint = sk.fingerprint;
 
         Ok(())
     }
@@ -487,6 +571,7 @@ impl VectorLWE {
     /// # Output
     /// * `result` - a list of messages as f64
     /// * DimensionError - if the ciphertext and the key have incompatible dimensions
+    /// * KeyMismatch - if the ciphertext was not encrypted under the key
     /// ```rust
     /// use concrete::*;
     ///
@@ -514,21 +599,28 @@ impl VectorLWE {
             return Err(DimensionError!(self.dimension, sk.dimension));
         }
 
+        // check the keys
+        self.key_fingerprint.check(sk.fingerprint)?;
+
         // allocate the result
         let mut result: Vec<f64> = vec![0.; self.nb_ciphertexts];
 
         // create a temporary variable to store the result of the phase computation
-        let mut tmp: Vec<Torus> = vec![0; self.nb_ciphertexts];
+        let mut tmp: Vec<T> = vec![T::ZERO; self.nb_ciphertexts];
 
         // compute the phase
-        sk.val.decrypt_lwe_list(
-            &mut PlaintextList::from_container(tmp.as_mut_slice()),
-            &self.ciphertexts,
-        );
+        sk.with_generic_coefficients(|coefficients| {
+            with_lwe_secret_key!(sk.kind, coefficients, |key| {
+                key.decrypt_lwe_list(
+                    &mut PlaintextList::from_container(tmp.as_mut_slice()),
+                    &self.ciphertexts,
+                )
+            })
+        });
 
         // decode
         for (r, pt, enc) in izip!(result.iter_mut(), tmp.iter(), self.encoders.iter()) {
-            *r = enc.decode_single(*pt)?;
+            *r = enc.decode_single(crate::generic_torus::to_torus(*pt))?;
         }
 
         Ok(result)
@@ -541,6 +633,7 @@ impl VectorLWE {
     /// # Output
     /// * `result` - a list of messages as f64
     /// * DimensionError - if the ciphertext and the key have incompatible dimensions
+    /// * KeyMismatch - if the ciphertext was not encrypted under the key
     /// ```rust
     /// use concrete::*;
     ///
@@ -562,20 +655,27 @@ impl VectorLWE {
     ///
     /// let res = ct.decrypt_raw(&sk).unwrap();
     /// ```
-    pub fn decrypt_raw(&self, sk: &crate::LWESecretKey) -> Result<Vec<u64>, CryptoAPIError> {
+    pub fn decrypt_raw(&self, sk: &crate::LWESecretKey) -> Result<Vec<T>, CryptoAPIError> {
         // check dimensions
         if sk.dimension != self.dimension {
             return Err(DimensionError!(self.dimension, sk.dimension));
         }
 
+        // check the keys
+        self.key_fingerprint.check(sk.fingerprint)?;
+
         // create a temporary variable to store the result of the phase computation
-        let mut tmp: Vec<u64> = vec![0; self.nb_ciphertexts];
+        let mut tmp: Vec<T> = vec![T::ZERO; self.nb_ciphertexts];
 
         // compute the phase
-        sk.val.decrypt_lwe_list(
-            &mut PlaintextList::from_container(tmp.as_mut_slice()),
-            &self.ciphertexts,
-        );
+        sk.with_generic_coefficients(|coefficients| {
+            with_lwe_secret_key!(sk.kind, coefficients, |key| {
+                key.decrypt_lwe_list(
+                    &mut PlaintextList::from_container(tmp.as_mut_slice()),
+                    &self.ciphertexts,
+                )
+            })
+        });
 
         Ok(tmp)
     }
@@ -587,6 +687,7 @@ impl VectorLWE {
     /// # Output
     /// * `result` - a list of messages as f64
     /// * DimensionError - if the ciphertext and the key have incompatible dimensions
+    /// * KeyMismatch - if the ciphertext was not encrypted under the key
     /// ```rust
     /// use concrete::*;
     ///
@@ -617,28 +718,122 @@ impl VectorLWE {
             return Err(DimensionError!(self.dimension, sk.dimension));
         }
 
+        // check the keys
+        self.key_fingerprint.check(sk.fingerprint)?;
+
         // allocate the result
         let mut result: Vec<f64> = vec![0.; self.nb_ciphertexts];
 
         // create a temporary variable to store the result of the phase computation
-        let mut tmp: Vec<Torus> = vec![0; self.nb_ciphertexts];
+        let mut tmp: Vec<T> = vec![T::ZERO; self.nb_ciphertexts];
 
         // compute the phase
-        sk.val.decrypt_lwe_list(
-            &mut PlaintextList::from_container(tmp.as_mut_slice()),
-            &self.ciphertexts,
-        );
+        sk.with_generic_coefficients(|coefficients| {
+            with_lwe_secret_key!(sk.kind, coefficients, |key| {
+                key.decrypt_lwe_list(
+                    &mut PlaintextList::from_container(tmp.as_mut_slice()),
+                    &self.ciphertexts,
+                )
+            })
+        });
 
         // decode
         for (r, pt, enc) in izip!(result.iter_mut(), tmp.iter(), self.encoders.iter()) {
             let mut tmp_enc = enc.clone();
             tmp_enc.round = true;
-            *r = tmp_enc.decode_single(*pt)?;
+            *r = tmp_enc.decode_single(crate::generic_torus::to_torus(*pt))?;
         }
 
         Ok(result)
     }
+}
 
+impl VectorLWE {
+    /// Switch the ciphertexts to the modulus 2^log_q, so that each coefficient only takes log_q bits
+    /// Each coefficient is rounded to its log_q most significant bits, which adds a noise whose
+    /// variance depends on the kind of the secret key, and the precision of the encoders is
+    /// updated accordingly
+    ///
+    /// # Argument
+    /// * `log_q` - the log2 of the new modulus, between 1 and 64
+    /// * `kind` - the distribution of the coefficients of the secret key of self, which the
+    ///   ciphertexts do not record
+    ///
+    /// # Output
+    /// * a CompressedVectorLWE struct, that can still be decrypted with the secret key of self
+    /// * ModulusError - if log_q is not between 1 and 64
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// // encode and encrypt
+    /// let encoder = Encoder::new(-5., 5., 4, 1).unwrap();
+    /// let sk = LWESecretKey::new(&LWE128_630);
+    /// let ciphertexts = VectorLWE::encode_encrypt(&sk, &[-3.2, 4.3, 0.12], &encoder).unwrap();
+    ///
+    /// // 20 bits per coefficient instead of 64
+    /// let compressed = ciphertexts.modulus_switch(20, sk.kind).unwrap();
+    ///
+    /// // the 3 ciphertexts of 631 coefficients of 8 bytes take less than a third of their size
+    /// let size = ciphertexts.nb_ciphertexts * (ciphertexts.dimension + 1) * 8;
+    /// assert!(3 * compressed.get_size_in_bytes() < size);
+    /// ```
+    pub fn modulus_switch(
+        &self,
+        log_q: usize,
+        kind: crate::SecretKeyKind,
+    ) -> Result<crate::CompressedVectorLWE, CryptoAPIError> {
+        if log_q == 0 || log_q > <Torus as Numeric>::BITS {
+            return Err(ModulusError!(log_q));
+        }
+        let shift = <Torus as Numeric>::BITS - log_q;
+
+        // round every coefficient to its log_q most significant bits
+        let mut coefficients: Vec<Torus> = self.ciphertexts.as_tensor().as_slice().to_vec();
+        let mut var_rounding: f64 = 0.;
+        if shift > 0 {
+            let mask: Torus = Torus::MAX >> shift;
+            for c in coefficients.iter_mut() {
+                *c = ((*c >> (shift - 1)).wrapping_add(1) >> 1) & mask;
+            }
+
+            // the rounding errors of the body and of the mask multiplied by the key add up
+            var_rounding = kind.get_modulus_switch_variance(self.dimension, log_q);
+        }
+
+        // update the variances and the precision of the encoders
+        let mut variances: Vec<f64> = self.variances.clone();
+        let mut encoders: Vec<crate::Encoder> = self.encoders.clone();
+        for (var, enc) in izip!(variances.iter_mut(), encoders.iter_mut()) {
+            *var = npe::add_ciphertexts(*var, var_rounding);
+            let nb_bit_precision = enc.nb_bit_precision;
+            let nb_bit_overlap: usize = enc.update_precision_from_variance(*var)?;
+
+            // notification of a problem
+            if nb_bit_overlap > 0 {
+                println!(
+                    "{}: {} bit(s) lost, with {} bit(s) of message originally. Consider increasing log_q.",
+                    "Loss of precision during modulus switch".red().bold(),
+                    nb_bit_overlap,
+                    nb_bit_precision
+                );
+            }
+        }
+
+        Ok(crate::CompressedVectorLWE {
+            coefficients: crate::compressed_vector_lwe::pack(&coefficients, log_q),
+            log_q,
+            variances,
+            dimension: self.dimension,
+            nb_ciphertexts: self.nb_ciphertexts,
+            encoders,
+            key_fingerprint: self.key_fingerprint,
+        })
+    }
+}
+
+impl<T: GenericTorus> GenericVectorLWE<T> {
     /// Add small messages to a VectorLWE ciphertext and does not change the encoding but changes the bodies of the ciphertexts
     ///
     /// # Argument
@@ -671,7 +866,7 @@ impl VectorLWE {
     pub fn add_constant_static_encoder(
         &self,
         messages: &[f64],
-    ) -> Result<crate::VectorLWE, CryptoAPIError> {
+    ) -> Result<GenericVectorLWE<T>, CryptoAPIError> {
         let mut res = self.clone();
         res.add_constant_static_encoder_inplace(messages)?;
         Ok(res)
@@ -723,9 +918,9 @@ impl VectorLWE {
             }
             let mut ec_tmp = lwe_encoder.clone();
             ec_tmp.o = 0.;
-            let update = ciphertext
-                .get_element(self.dimension)
-                .wrapping_add(ec_tmp.encode_outside_interval_operators(*m)?);
+            let update = ciphertext.get_element(self.dimension).wrapping_add(
+                crate::generic_torus::from_torus(ec_tmp.encode_outside_interval_operators(*m)?),
+            );
             *ciphertext.get_element_mut(self.dimension) = update;
         }
         Ok(())
@@ -766,7 +961,7 @@ impl VectorLWE {
     pub fn add_constant_dynamic_encoder(
         &self,
         messages: &[f64],
-    ) -> Result<crate::VectorLWE, CryptoAPIError> {
+    ) -> Result<GenericVectorLWE<T>, CryptoAPIError> {
         let mut res = self.clone();
         res.add_constant_dynamic_encoder_inplace(messages)?;
         Ok(res)
@@ -861,9 +1056,9 @@ impl VectorLWE {
     /// ```
     pub fn add_with_new_min(
         &self,
-        ct: &crate::VectorLWE,
+        ct: &GenericVectorLWE<T>,
         new_min: &[f64],
-    ) -> Result<crate::VectorLWE, CryptoAPIError> {
+    ) -> Result<GenericVectorLWE<T>, CryptoAPIError> {
         let mut res = self.clone();
         res.add_with_new_min_inplace(ct, new_min)?;
         Ok(res)
@@ -910,7 +1105,7 @@ impl VectorLWE {
     /// ```
     pub fn add_with_new_min_inplace(
         &mut self,
-        ct: &crate::VectorLWE,
+        ct: &GenericVectorLWE<T>,
         new_min: &[f64],
     ) -> Result<(), CryptoAPIError> {
         // check dimensions
@@ -918,6 +1113,9 @@ impl VectorLWE {
             return Err(DimensionError!(self.dimension, ct.dimension));
         }
 
+        // check the keys
+        let key_fingerprint = self.key_fingerprint.combine(ct.key_fingerprint)?;
+
         // add the two ciphertexts together
         self.ciphertexts
             .as_mut_tensor()
@@ -941,9 +1139,11 @@ impl VectorLWE {
             }
             let mut tmp_ec = enc1.clone();
             tmp_ec.o = *new;
-            let update = ciphertext
-                .get_element(self.dimension)
-                .wrapping_add(tmp_ec.encode_outside_interval_operators(enc1.o + enc2.o)?);
+            let update = ciphertext.get_element(self.dimension).wrapping_add(
+                crate::generic_torus::from_torus(
+                    tmp_ec.encode_outside_interval_operators(enc1.o + enc2.o)?,
+                ),
+            );
             *ciphertext.get_element_mut(self.dimension) = update;
         }
 
@@ -961,6 +1161,7 @@ impl VectorLWE {
             *var1 = npe::add_ciphertexts(*var1, *var2);
             enc.update_precision_from_variance(*var1)?;
         }
+        self.key_fingerprint = key_fingerprint;
         Ok(())
     }
 
@@ -1002,7 +1203,10 @@ impl VectorLWE {
     /// // addition between ciphertext_1 and ciphertext_2
     /// let new_ciphertext = ciphertext_1.add_centered(&ciphertext_2).unwrap();
     /// ```
-    pub fn add_centered(&self, ct: &crate::VectorLWE) -> Result<crate::VectorLWE, CryptoAPIError> {
+    pub fn add_centered(
+        &self,
+        ct: &GenericVectorLWE<T>,
+    ) -> Result<GenericVectorLWE<T>, CryptoAPIError> {
         let mut res = self.clone();
         res.add_centered_inplace(ct)?;
         Ok(res)
@@ -1050,12 +1254,15 @@ impl VectorLWE {
     /// // addition between ciphertext_1 and ciphertext_2
     /// ciphertext_1.add_centered_inplace(&ciphertext_2).unwrap();
     /// ```
-    pub fn add_centered_inplace(&mut self, ct: &crate::VectorLWE) -> Result<(), CryptoAPIError> {
+    pub fn add_centered_inplace(&mut self, ct: &GenericVectorLWE<T>) -> Result<(), CryptoAPIError> {
         // check same dimensions
         if self.dimension != ct.dimension {
             return Err(DimensionError!(self.dimension, ct.dimension));
         }
 
+        // check the keys
+        let key_fingerprint = self.key_fingerprint.combine(ct.key_fingerprint)?;
+
         // check same deltas
         for (self_enc, ct_enc) in self.encoders.iter_mut().zip(ct.encoders.iter()) {
             if !deltas_eq!(self_enc.delta, ct_enc.delta) {
@@ -1078,7 +1285,8 @@ impl VectorLWE {
         ) {
             let mut tmp_enc = enc1.clone();
             tmp_enc.o = 0.;
-            let correction: Torus = tmp_enc.encode_core(enc1.delta / 2.)?;
+            let correction: T =
+                crate::generic_torus::from_torus(tmp_enc.encode_core(enc1.delta / 2.)?);
             let update = ciphertext
                 .get_element(self.dimension)
                 .wrapping_sub(correction);
@@ -1102,6 +1310,7 @@ impl VectorLWE {
             self_enc.update_precision_from_variance(*self_var)?;
         }
 
+        self.key_fingerprint = key_fingerprint;
         Ok(())
     }
 
@@ -1140,8 +1349,8 @@ impl VectorLWE {
     /// ```
     pub fn add_with_padding(
         &self,
-        ct: &crate::VectorLWE,
-    ) -> Result<crate::VectorLWE, CryptoAPIError> {
+        ct: &GenericVectorLWE<T>,
+    ) -> Result<GenericVectorLWE<T>, CryptoAPIError> {
         let mut res = self.clone();
         res.add_with_padding_inplace(ct)?;
         Ok(res)
@@ -1181,8 +1390,11 @@ impl VectorLWE {
     /// ```
     pub fn add_with_padding_inplace(
         &mut self,
-        ct: &crate::VectorLWE,
+        ct: &GenericVectorLWE<T>,
     ) -> Result<(), CryptoAPIError> {
+        // check the keys
+        let key_fingerprint = self.key_fingerprint.combine(ct.key_fingerprint)?;
+
         for (self_enc, ct_enc) in izip!(self.encoders.iter(), ct.encoders.iter()) {
             // check same paddings
             if self_enc.nb_bit_padding != ct_enc.nb_bit_padding {
@@ -1230,6 +1442,7 @@ impl VectorLWE {
             // update the encoder precision based on the variance
             self_enc.update_precision_from_variance(*self_var)?;
         }
+        self.key_fingerprint = key_fingerprint;
         Ok(())
     }
 
@@ -1268,8 +1481,8 @@ impl VectorLWE {
     /// ```
     pub fn sub_with_padding(
         &self,
-        ct: &crate::VectorLWE,
-    ) -> Result<crate::VectorLWE, CryptoAPIError> {
+        ct: &GenericVectorLWE<T>,
+    ) -> Result<GenericVectorLWE<T>, CryptoAPIError> {
         let mut res = self.clone();
         res.sub_with_padding_inplace(ct)?;
         Ok(res)
@@ -1309,8 +1522,11 @@ impl VectorLWE {
     /// ```
     pub fn sub_with_padding_inplace(
         &mut self,
-        ct: &crate::VectorLWE,
+        ct: &GenericVectorLWE<T>,
     ) -> Result<(), CryptoAPIError> {
+        // check the keys
+        let key_fingerprint = self.key_fingerprint.combine(ct.key_fingerprint)?;
+
         for (self_enc, ct_enc) in izip!(self.encoders.iter(), ct.encoders.iter()) {
             // check same paddings
             if self_enc.nb_bit_padding != ct_enc.nb_bit_padding {
@@ -1347,7 +1563,9 @@ impl VectorLWE {
             self.ciphertexts.as_mut_tensor().subtensor_iter_mut(ct_size),
             self.encoders.iter(),
         ) {
-            let correction: Torus = 1 << (<Torus as Numeric>::BITS - enc1.nb_bit_padding);
+            let correction: T = crate::generic_torus::from_torus(
+                1 << (<Torus as Numeric>::BITS - enc1.nb_bit_padding),
+            );
             let update = ciphertext
                 .get_element(self.dimension)
                 .wrapping_add(correction);
@@ -1370,6 +1588,7 @@ impl VectorLWE {
             *var1 = npe::add_ciphertexts(*var1, *var2);
             enc.update_precision_from_variance(*var1)?;
         }
+        self.key_fingerprint = key_fingerprint;
         Ok(())
     }
 
@@ -1408,7 +1627,7 @@ impl VectorLWE {
     pub fn mul_constant_static_encoder(
         &self,
         messages: &[i32],
-    ) -> Result<crate::VectorLWE, CryptoAPIError> {
+    ) -> Result<GenericVectorLWE<T>, CryptoAPIError> {
         let mut res = self.clone();
         res.mul_constant_static_encoder_inplace(messages)?;
         Ok(res)
@@ -1460,10 +1679,10 @@ impl VectorLWE {
         ) {
             // compute correction
             let cor0: Torus = encoder.encode_outside_interval_operators(0.)?;
-            let cor = cor0.wrapping_mul((*m - 1) as Torus);
+            let cor: T = crate::generic_torus::from_torus(cor0.wrapping_mul((*m - 1) as Torus));
 
             // multiplication
-            ciphertext.update_with_wrapping_scalar_mul(&(*m as Torus));
+            ciphertext.update_with_wrapping_scalar_mul(&T::cast_from(*m as Torus));
 
             // apply correction
             let update = ciphertext.get_element(self.dimension).wrapping_sub(cor);
@@ -1526,7 +1745,7 @@ impl VectorLWE {
         constants: &[f64],
         max_constant: f64,
         nb_bit_padding: usize,
-    ) -> Result<crate::VectorLWE, CryptoAPIError> {
+    ) -> Result<GenericVectorLWE<T>, CryptoAPIError> {
         let mut res = self.clone();
         res.mul_constant_with_padding_inplace(constants, max_constant, nb_bit_padding)?;
         Ok(res)
@@ -1623,56 +1842,16 @@ impl VectorLWE {
                 (c_abs / max_constant * f64::powi(2., nb_bit_padding as i32)).round() as Torus;
 
             // encode 0 and subtract it
-            let tmp_sub = encoder.encode_core(0.)?;
+            let tmp_sub: T = crate::generic_torus::from_torus(encoder.encode_core(0.)?);
             let update = ciphertext.get_element(self.dimension).wrapping_sub(tmp_sub);
             *ciphertext.get_element_mut(self.dimension) = update;
 
             // scalar multiplication
-            ciphertext.update_with_wrapping_scalar_mul(&scal);
+            ciphertext.update_with_wrapping_scalar_mul(&T::cast_from(scal));
 
             // new encoder
-            let new_o = encoder.o * max_constant;
-            let new_max = (encoder.o + encoder.delta - encoder.get_granularity()) * max_constant;
-            let new_delta = new_max - new_o;
-
-            // compute the discretization of c_abs
-            let discret_c_abs =
-                (scal as f64) * f64::powi(2., -(nb_bit_padding as i32)) * max_constant;
-
-            // compute  the rounding error on c_abs
-            let rounding_error = (discret_c_abs - c_abs).abs();
-
-            // get the ciphertext granularity
-            let granularity = encoder.get_granularity();
-
-            // compute the max of the ciphertext (based on the metadata of the encoder)
-            let max = f64::max(
-                (encoder.o + encoder.delta - encoder.get_granularity()).abs(),
-                encoder.o.abs(),
-            );
-
-            // compute the new granularity
-            let new_granularity = 2.
-                * (granularity * rounding_error / 2.
-                    + granularity / 2. * discret_c_abs
-                    + rounding_error * max)
-                    .abs();
-
-            // compute the new precision
-            let new_precision = usize::min(
-                f64::log2(new_delta / new_granularity).floor() as usize,
-                encoder.nb_bit_precision,
-            );
-
-            // create the new encoder
-            let tmp_encoder = crate::Encoder::new(
-                new_o,
-                new_max,
-                usize::min(nb_bit_padding, encoder.nb_bit_precision),
-                encoder.nb_bit_padding - nb_bit_padding,
-            )?;
-            encoder.copy(&tmp_encoder);
-            encoder.nb_bit_precision = usize::min(encoder.nb_bit_precision, new_precision);
+            let new_encoder = encoder.scale_abs(*c, max_constant, nb_bit_padding)?;
+            encoder.copy(&new_encoder);
             // call to the NPE to estimate the new variance
             *var = npe::LWE::single_scalar_mul(*var, scal);
 
@@ -1682,7 +1861,7 @@ impl VectorLWE {
             }
 
             // encode 0 with the new encoder
-            let tmp_add = encoder.encode_core(0.)?;
+            let tmp_add: T = crate::generic_torus::from_torus(encoder.encode_core(0.)?);
             let update = ciphertext.get_element(self.dimension).wrapping_add(tmp_add);
             *ciphertext.get_element_mut(self.dimension) = update;
 
@@ -1704,7 +1883,9 @@ impl VectorLWE {
                             - encoder.nb_bit_precision),
                     );
                 }
-                let update = ciphertext.get_element(self.dimension).wrapping_add(cor);
+                let update = ciphertext
+                    .get_element(self.dimension)
+                    .wrapping_add(crate::generic_torus::from_torus(cor));
                 *ciphertext.get_element_mut(self.dimension) = update;
 
                 // change the encoder
@@ -1745,7 +1926,7 @@ impl VectorLWE {
     ///
     /// let new_ciphertext = ciphertext.opposite_nth(3).unwrap();
     /// ```
-    pub fn opposite_nth(&self, n: usize) -> Result<crate::VectorLWE, CryptoAPIError> {
+    pub fn opposite_nth(&self, n: usize) -> Result<GenericVectorLWE<T>, CryptoAPIError> {
         let mut res = self.clone();
         res.opposite_nth_inplace(n)?;
         Ok(res)
@@ -1821,7 +2002,9 @@ impl VectorLWE {
                     - self.encoders[n].nb_bit_precision),
             );
         }
-        let update = ct.get_element(self.dimension).wrapping_add(cor);
+        let update = ct
+            .get_element(self.dimension)
+            .wrapping_add(crate::generic_torus::from_torus(cor));
         *ct.get_element_mut(self.dimension) = update;
 
         // change the encoder
@@ -1829,7 +2012,9 @@ impl VectorLWE {
 
         Ok(())
     }
+}
 
+impl<T: GenericTorus> GenericVectorLWE<T> {
     /// Compute a key switching operation on every ciphertext from the VectorLWE struct self
     ///
     /// # Argument
@@ -1837,6 +2022,7 @@ impl VectorLWE {
     ///
     /// # Output
     /// * a VectorLWE struct
+    /// * KeyMismatch - if the ciphertexts were not encrypted under the input key of the key switching key
     ///
     ///
     /// # Example
@@ -1873,14 +2059,20 @@ impl VectorLWE {
     /// // key switch
     /// let ciphertext_after = ciphertext_before.keyswitch(&ksk).unwrap();
     /// ```
-    pub fn keyswitch(&self, ksk: &crate::LWEKSK) -> Result<crate::VectorLWE, CryptoAPIError> {
+    pub fn keyswitch(
+        &self,
+        ksk: &crate::GenericLWEKSK<T>,
+    ) -> Result<GenericVectorLWE<T>, CryptoAPIError> {
+        // check the keys
+        self.key_fingerprint.check(ksk.fingerprint_before)?;
+
         // allocation for the result
-        let mut res: crate::VectorLWE =
-            crate::VectorLWE::zero(ksk.dimension_after, self.nb_ciphertexts)?;
+        let mut res: GenericVectorLWE<T> =
+            GenericVectorLWE::zero(ksk.dimension_after, self.nb_ciphertexts)?;
+        res.key_fingerprint = ksk.fingerprint_after;
 
         // key switch
-        ksk.ciphertexts
-            .keyswitch_list(&mut res.ciphertexts, &self.ciphertexts);
+        T::keyswitch_list(&ksk.ciphertexts, &mut res.ciphertexts, &self.ciphertexts);
 
         // deal with encoders, noise and new precision
         for (output_enc, input_enc, vout, vin) in izip!(
@@ -1890,13 +2082,7 @@ impl VectorLWE {
             self.variances.iter()
         ) {
             // calls the NPE to find out the amount of noise after KS
-            *vout = <Torus as LWE>::key_switch(
-                self.dimension,
-                ksk.level,
-                ksk.base_log,
-                ksk.variance,
-                *vin,
-            );
+            *vout = ksk.get_output_variance(*vin);
 
             // copy the encoders
             output_enc.copy(input_enc);
@@ -1928,6 +2114,7 @@ impl VectorLWE {
     /// * a VectorLWE struct
     /// * IndexError - if the requested ciphertext does not exist
     /// * DimensionError - if the bootstrapping key and the input ciphertext have incompatible dimensions
+    /// * KeyMismatch - if the input ciphertext was not encrypted under the input key of the bootstrapping key
     ///
     /// # Example
     /// ```rust
@@ -1968,9 +2155,9 @@ impl VectorLWE {
     /// ```
     pub fn bootstrap_nth(
         &self,
-        bsk: &crate::LWEBSK,
+        bsk: &crate::GenericLWEBSK<T>,
         n: usize,
-    ) -> Result<crate::VectorLWE, CryptoAPIError> {
+    ) -> Result<GenericVectorLWE<T>, CryptoAPIError> {
         self.bootstrap_nth_with_function(bsk, |x| x, &self.encoders[n], n)
     }
 
@@ -1986,6 +2173,7 @@ impl VectorLWE {
     /// * a VectorLWE struct
     /// * IndexError - if the requested ciphertext does not exist
     /// * DimensionError - if the bootstrapping key and the input ciphertext have incompatible dimensions
+    /// * KeyMismatch - if the input ciphertext was not encrypted under the input key of the bootstrapping key
     ///
     /// # Example
     /// ```rust
@@ -2027,11 +2215,11 @@ impl VectorLWE {
     /// ```
     pub fn bootstrap_nth_with_function<F: Fn(f64) -> f64>(
         &self,
-        bsk: &crate::LWEBSK,
+        bsk: &crate::GenericLWEBSK<T>,
         f: F,
         encoder_output: &crate::Encoder,
         n: usize,
-    ) -> Result<crate::VectorLWE, CryptoAPIError> {
+    ) -> Result<GenericVectorLWE<T>, CryptoAPIError> {
         // check the index n
         if n >= self.nb_ciphertexts {
             return Err(IndexError!(self.nb_ciphertexts, n));
@@ -2040,13 +2228,14 @@ impl VectorLWE {
         if self.dimension != bsk.get_lwe_dimension() {
             return Err(DimensionError!(self.dimension, bsk.get_lwe_dimension()));
         }
+        self.key_fingerprint.check(bsk.input_fingerprint)?;
 
         // generate the look up table
         let lut = bsk.generate_functional_look_up_table(&self.encoders[n], encoder_output, f)?;
 
         // build the trivial accumulator
         let mut accumulator = GlweCiphertext::allocate(
-            0,
+            T::ZERO,
             PolynomialSize(bsk.polynomial_size),
             GlweSize(bsk.dimension + 1),
         );
@@ -2062,7 +2251,7 @@ impl VectorLWE {
 
         // allocate the result
         let mut result =
-            LweCiphertext::allocate(0, LweSize(bsk.dimension * bsk.polynomial_size + 1));
+            LweCiphertext::allocate(T::ZERO, LweSize(bsk.dimension * bsk.polynomial_size + 1));
 
         if self.encoders[n].nb_bit_padding > 1 {
             // copy the ciphertext to bootstrap
@@ -2072,18 +2261,14 @@ impl VectorLWE {
                 .get_sub(n * (self.get_ciphertext_size())..((n + 1) * (self.get_ciphertext_size())))
                 .iter()
                 .copied()
-                .collect::<Vec<Torus>>();
+                .collect::<Vec<T>>();
 
             // shift of some bits to the left
             Tensor::from_container(ct_clone.as_mut_slice())
                 .update_with_scalar_shl(&(self.encoders[n].nb_bit_padding - 1));
 
             // compute the bootstrap
-            bsk.ciphertexts.bootstrap(
-                &mut result,
-                &LweCiphertext::from_container(ct_clone),
-                & accumulator,
-            );
+            bsk.bootstrap_raw(&mut result, &ct_clone, &accumulator);
         } else {
             // compute the bootstrap
             let ct_view = self
@@ -2091,20 +2276,11 @@ impl VectorLWE {
                 .as_tensor()
                 .get_sub(n * (self.get_ciphertext_size())..((n + 1) * (self.get_ciphertext_size())))
                 .into_container();
-            let ct = LweCiphertext::from_container(ct_view);
-            bsk.ciphertexts
-                .bootstrap(&mut result, &ct, & accumulator);
+            bsk.bootstrap_raw(&mut result, ct_view, &accumulator);
         }
 
         // compute the new variance (without the drift)
-        let new_var = <Torus as npe::Cross>::bootstrap(
-            self.dimension,
-            bsk.dimension,
-            bsk.level,
-            bsk.base_log,
-            bsk.polynomial_size,
-            bsk.variance,
-        );
+        let new_var = bsk.get_output_variance();
 
         // create the output encoder
         let mut new_encoder_output: crate::Encoder = encoder_output.clone();
@@ -2121,36 +2297,19 @@ impl VectorLWE {
             );
         }
 
-        // calls the NPE to find out the amount of noise after rounding the input ciphertext (drift)
-        let nb_rounding_noise_bit: usize =
-            (npe::lwe::log2_rounding_noise(self.dimension)).ceil() as usize + 1;
-
         // deals with the drift error
-        if nb_rounding_noise_bit
-            + self.encoders[n].nb_bit_padding
-            + new_encoder_output.nb_bit_precision
-            > bsk.get_polynomial_size_log() + 1
-        {
-            let nb_bit_loss = self.encoders[n].nb_bit_padding
-                + new_encoder_output.nb_bit_precision
-                + nb_rounding_noise_bit
-                - bsk.get_polynomial_size_log()
-                - 1;
-
-            new_encoder_output.nb_bit_precision = i32::max(
-                new_encoder_output.nb_bit_precision as i32 - nb_bit_loss as i32,
-                0i32,
-            ) as usize;
-            // drift
+        let nb_bit_loss = bsk
+            .update_precision_from_drift(&mut new_encoder_output, self.encoders[n].nb_bit_padding);
+        if nb_bit_loss > 0 {
             println!(
-                "{}: {} bit(s) of precision lost over {} bit(s) of message originally ({} bits are affected by the noise). Consider increasing the polynomial size of the RLWE secret key.",
+                "{}: {} bit(s) of precision lost over {} bit(s) of message originally. Consider increasing the polynomial size of the RLWE secret key.",
                 "Loss of precision during bootstrap due to the rounding".red().bold(),
-                nb_bit_loss, self.encoders[n].nb_bit_precision,nb_rounding_noise_bit
+                nb_bit_loss, self.encoders[n].nb_bit_precision
             );
         }
 
         // construct the output
-        let lwe = crate::VectorLWE {
+        let lwe = GenericVectorLWE {
             variances: vec![new_var; 1],
             ciphertexts: LweList::from_container(
                 result.into_tensor().into_container(),
@@ -2159,6 +2318,7 @@ impl VectorLWE {
             dimension: bsk.polynomial_size * bsk.dimension,
             nb_ciphertexts: 1,
             encoders: vec![new_encoder_output; 1],
+            key_fingerprint: bsk.output_fingerprint,
         };
 
         Ok(lwe)
@@ -2224,11 +2384,11 @@ impl VectorLWE {
     /// ```
     pub fn mul_from_bootstrap_nth(
         &self,
-        ct: &crate::VectorLWE,
-        bsk: &crate::LWEBSK,
+        ct: &GenericVectorLWE<T>,
+        bsk: &crate::GenericLWEBSK<T>,
         n_self: usize,
         n_ct: usize,
-    ) -> Result<crate::VectorLWE, CryptoAPIError> {
+    ) -> Result<GenericVectorLWE<T>, CryptoAPIError> {
         // extract twice from self
         let mut ct1 = self.extract_nth(n_self)?;
         let mut ct2 = self.extract_nth(n_self)?;
@@ -2268,6 +2428,506 @@ impl VectorLWE {
         Ok(square1)
     }
 
+    /// Multiply two VectorLWE ciphertexts element by element with bootstraps
+    /// It uses the (a + b)^2 / 4 - (a - b)^2 / 4 trick, so each product costs two bootstraps
+    /// The blind rotations of all the squares run in parallel when the multithread feature is enabled
+    ///
+    /// # Argument
+    /// * `ct` - a VectorLWE struct with the same number of ciphertexts, the same deltas and the same paddings as self
+    /// * `bsk` - the bootstrapping key
+    /// * `encoders_output` - an optional list of output encoders, one per product, whose intervals contain the products
+    ///
+    /// # Output
+    /// * a VectorLWE struct, its encoders are derived from `new_square_divided_by_four` if `encoders_output` is None
+    /// * otherwise the given encoders, with a precision reduced so that their granularity is not smaller than the error of the squares
+    /// * WrongSizeError - if the ciphertexts or the output encoders do not have the same length
+    /// * DimensionError - if the ciphertexts or the bootstrapping key have incompatible dimensions
+    /// * DeltaError - if the ciphertexts have incompatible deltas
+    /// * PaddingError - if the ciphertexts have incompatible paddings
+    /// * NotEnoughPaddingError - if the ciphertexts have less than 2 bits of padding
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// // encoders with the same delta
+    /// let encoder_1 = Encoder::new(-10., 10., 4, 2).unwrap();
+    /// let encoder_2 = Encoder::new(0., 20., 4, 2).unwrap();
+    ///
+    /// // generate secret keys
+    /// let rlwe_secret_key = RLWESecretKey::new(&RLWE128_1024_1);
+    /// let secret_key_before = LWESecretKey::new(&LWE128_630);
+    /// let secret_key_after = rlwe_secret_key.to_lwe_secret_key();
+    ///
+    /// // bootstrapping key
+    /// let bootstrapping_key = LWEBSK::new(&secret_key_before, &rlwe_secret_key, 5, 3);
+    ///
+    /// // encode and encrypt
+    /// let ciphertext_1 =
+    ///     VectorLWE::encode_encrypt(&secret_key_before, &[-3.2, 7.1], &encoder_1).unwrap();
+    /// let ciphertext_2 =
+    ///     VectorLWE::encode_encrypt(&secret_key_before, &[12.5, 4.], &encoder_2).unwrap();
+    ///
+    /// // the products are in [-200, 200]
+    /// let encoder_output = Encoder::new(-200., 200., 4, 1).unwrap();
+    /// let encoders_output = vec![encoder_output; 2];
+    ///
+    /// // multiplication
+    /// let res = ciphertext_1
+    ///     .mul_from_bootstrap(&ciphertext_2, &bootstrapping_key, Some(&encoders_output))
+    ///     .unwrap();
+    /// ```
+    pub fn mul_from_bootstrap(
+        &self,
+        ct: &GenericVectorLWE<T>,
+        bsk: &crate::GenericLWEBSK<T>,
+        encoders_output: Option<&[crate::Encoder]>,
+    ) -> Result<GenericVectorLWE<T>, CryptoAPIError> {
+        // check the sizes
+        if self.nb_ciphertexts != ct.nb_ciphertexts {
+            return Err(WrongSizeError!(ct.nb_ciphertexts));
+        }
+        if let Some(encoders) = encoders_output {
+            if encoders.len() != self.nb_ciphertexts {
+                return Err(WrongSizeError!(encoders.len()));
+            }
+        }
+
+        // compute the additions and the subtractions
+        let sum = self.add_with_padding(ct)?;
+        let difference = self.sub_with_padding(ct)?;
+
+        // list the ciphertexts to square with the encoders of the squares
+        let mut inputs: Vec<(crate::GenericLWE<T>, crate::Encoder)> =
+            Vec::with_capacity(2 * self.nb_ciphertexts);
+        let mut defaults: Vec<(usize, f64)> = Vec::with_capacity(self.nb_ciphertexts);
+        for i in 0..self.nb_ciphertexts {
+            // the default encoders of the squares have the same delta
+            let mut encoder_square_1 = sum.encoders[i].new_square_divided_by_four(2)?;
+            let mut encoder_square_2 = difference.encoders[i].new_square_divided_by_four(2)?;
+            if encoder_square_1.delta < encoder_square_2.delta {
+                encoder_square_1.delta = encoder_square_2.delta;
+            } else {
+                encoder_square_2.delta = encoder_square_1.delta;
+            }
+
+            defaults.push((
+                encoder_square_1.nb_bit_precision,
+                2. * encoder_square_1.delta,
+            ));
+
+            // the squares are encoded modulo the whole torus of the output encoder so that their
+            // difference lands in the output interval, even if the squares do not fit in it
+            if let Some(encoders) = encoders_output {
+                let nb_bit_padding = encoders[i].nb_bit_padding;
+                encoder_square_1 = crate::Encoder {
+                    o: encoders[i].o,
+                    delta: encoders[i].delta * f64::powi(2., nb_bit_padding as i32),
+                    nb_bit_precision: usize::max(
+                        encoders[i].nb_bit_precision,
+                        encoder_square_1.nb_bit_precision,
+                    ),
+                    nb_bit_padding: 0,
+                    round: false,
+                };
+                encoder_square_2 = encoder_square_1.clone();
+                encoder_square_2.o = 0.;
+            }
+            inputs.push((sum.nth_to_lwe(i)?, encoder_square_1));
+            inputs.push((difference.nth_to_lwe(i)?, encoder_square_2));
+        }
+
+        // bootstrap, only the blind rotations run in parallel since the key is borrowed by the threads
+        #[cfg(feature = "multithread")]
+        let squares: Vec<crate::GenericLWE<T>> = {
+            let raw_inputs = inputs
+                .iter()
+                .map(|(input, encoder)| {
+                    let lut =
+                        bsk.generate_functional_look_up_table(&input.encoder, encoder, |x| {
+                            (x * x) / 4.
+                        })?;
+                    input.get_bootstrap_input(bsk, &lut)
+                })
+                .collect::<Result<Vec<_>, CryptoAPIError>>()?;
+            izip!(inputs.iter(), bsk.par_bootstrap_raw(&raw_inputs))
+                .map(|((input, encoder), result)| input.get_bootstrap_output(bsk, result, encoder))
+                .collect::<Result<Vec<crate::GenericLWE<T>>, CryptoAPIError>>()?
+        };
+        #[cfg(not(feature = "multithread"))]
+        let squares: Vec<crate::GenericLWE<T>> = inputs
+            .iter()
+            .map(|(input, encoder)| input.bootstrap_with_function(bsk, |x| (x * x) / 4., encoder))
+            .collect::<Result<Vec<crate::GenericLWE<T>>, CryptoAPIError>>()?;
+
+        // subtract
+        let mut res =
+            GenericVectorLWE::zero(bsk.polynomial_size * bsk.dimension, self.nb_ciphertexts)?;
+        for (i, (pair, (nb_bit_precision_default, delta_default))) in
+            izip!(squares.chunks(2), defaults.iter()).enumerate()
+        {
+            let product = match encoders_output {
+                None => pair[0].sub_with_padding(&pair[1])?,
+                Some(encoders) => {
+                    let mut product = pair[0].clone();
+                    product.ciphertext.update_with_sub(&pair[1].ciphertext);
+                    product.variance = npe::add_ciphertexts(pair[0].variance, pair[1].variance);
+
+                    // the absolute error is the same as with the default encoders,
+                    // so the granularity cannot be smaller than the default one
+                    let nb_bit_precision_squares = usize::min(
+                        pair[0].encoder.nb_bit_precision,
+                        pair[1].encoder.nb_bit_precision,
+                    );
+                    let nb_bit_lost =
+                        f64::max(f64::ceil(f64::log2(delta_default / encoders[i].delta)), 0.)
+                            as usize;
+                    product.encoder = encoders[i].clone();
+                    product.encoder.nb_bit_precision = usize::min(
+                        usize::min(encoders[i].nb_bit_precision, nb_bit_precision_squares),
+                        usize::min(*nb_bit_precision_default, nb_bit_precision_squares)
+                            .saturating_sub(nb_bit_lost),
+                    );
+                    product
+                        .encoder
+                        .update_precision_from_variance(product.variance)?;
+                    product
+                }
+            };
+            res.copy_lwe_in_nth_inplace(i, &product)?;
+        }
+
+        Ok(res)
+    }
+}
+
+impl VectorLWE {
+    /// Change the encoder of the n-th LWE ciphertext with a bootstrap of the identity function,
+    /// the messages outside of the interval of the new encoder being clamped to its min or its max
+    ///
+    /// # Argument
+    /// * `bsk` - the bootstrapping key
+    /// * `new_encoder` - the encoder of the output
+    /// * `n` - the index of the ciphertext to reencode
+    ///
+    /// # Output
+    /// * a VectorLWE struct containing only one ciphertext, under the output key of `bsk`
+    /// * IndexError - if the requested ciphertext does not exist
+    /// * DimensionError - if the bootstrapping key and the input ciphertext have incompatible dimensions
+    /// * KeyMismatch - if the input ciphertext was not encrypted under the input key of the bootstrapping key
+    /// * NotEnoughPaddingError - if the input ciphertext has no bit of padding
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// // encoders
+    /// let encoder = Encoder::new(-10., 10., 4, 1).unwrap();
+    /// let new_encoder = Encoder::new(0., 10., 4, 4).unwrap();
+    ///
+    /// // generate secret keys
+    /// let rlwe_secret_key = RLWESecretKey::new(&RLWE128_1024_1);
+    /// let secret_key_before = LWESecretKey::new(&LWE128_630);
+    ///
+    /// // bootstrapping key
+    /// let bootstrapping_key = LWEBSK::new(&secret_key_before, &rlwe_secret_key, 5, 3);
+    ///
+    /// // a list of messages that we encrypt
+    /// let messages: Vec<f64> = vec![-6.2, 3.4, 9.1];
+    /// let ciphertext_before =
+    ///     VectorLWE::encode_encrypt(&secret_key_before, &messages, &encoder).unwrap();
+    ///
+    /// let ciphertext_out = ciphertext_before
+    ///     .reencode_nth(&bootstrapping_key, &new_encoder, 1)
+    ///     .unwrap();
+    /// ```
+    pub fn reencode_nth(
+        &self,
+        bsk: &crate::LWEBSK,
+        new_encoder: &crate::Encoder,
+        n: usize,
+    ) -> Result<crate::VectorLWE, CryptoAPIError> {
+        let ct = self.nth_to_lwe(n)?.reencode(bsk, new_encoder)?;
+        let mut res = VectorLWE::zero(ct.dimension, 1)?;
+        res.copy_lwe_in_nth_inplace(0, &ct)?;
+        Ok(res)
+    }
+
+    /// Compute a bootstrap and apply an arbitrary function to the n-th LWE ciphertext, then key
+    /// switch the result back to a small LWE key, with a single noise analysis for the two operations
+    ///
+    /// # Argument
+    /// * `bsk` - the bootstrapping key
+    /// * `ksk` - the key switching key, from the output key of `bsk`
+    /// * `f` - the function to apply
+    /// * `encoder_output` - the encoder of the output
+    /// * `n` - the index of the ciphertext to bootstrap
+    ///
+    /// # Output
+    /// * a VectorLWE struct containing only one ciphertext, under the output key of `ksk`
+    /// * IndexError - if the requested ciphertext does not exist
+    /// * DimensionError - if the input ciphertext, the bootstrapping key and the key switching key have incompatible dimensions
+    /// * KeyMismatch - if the input ciphertext, the bootstrapping key and the key switching key do not chain their keys
+    /// * NotEnoughPaddingError - if the input ciphertext has no bit of padding
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// // encoders
+    /// let encoder_input = Encoder::new(-10., 10., 4, 1).unwrap();
+    /// let encoder_output = Encoder::new(0., 10., 4, 1).unwrap();
+    ///
+    /// // generate secret keys
+    /// let rlwe_secret_key = RLWESecretKey::new(&RLWE128_1024_1);
+    /// let secret_key = LWESecretKey::new(&LWE128_630);
+    /// let big_secret_key = rlwe_secret_key.to_lwe_secret_key();
+    ///
+    /// // bootstrapping key and key switching key back to the input key
+    /// let bsk = LWEBSK::new(&secret_key, &rlwe_secret_key, 5, 3);
+    /// let ksk = LWEKSK::new(&big_secret_key, &secret_key, 2, 7);
+    ///
+    /// // a list of messages that we encrypt
+    /// let messages: Vec<f64> = vec![-6.2, 3.4, 9.1];
+    /// let ciphertexts = VectorLWE::encode_encrypt(&secret_key, &messages, &encoder_input).unwrap();
+    ///
+    /// // the output is encrypted under the input key
+    /// let ciphertext_out = ciphertexts
+    ///     .pbs_keyswitch_nth(&bsk, &ksk, |x| f64::abs(x), &encoder_output, 0)
+    ///     .unwrap();
+    /// ```
+    pub fn pbs_keyswitch_nth<F: Fn(f64) -> f64>(
+        &self,
+        bsk: &crate::LWEBSK,
+        ksk: &crate::LWEKSK,
+        f: F,
+        encoder_output: &crate::Encoder,
+        n: usize,
+    ) -> Result<crate::VectorLWE, CryptoAPIError> {
+        let ct = self
+            .nth_to_lwe(n)?
+            .pbs_keyswitch(bsk, ksk, f, encoder_output)?;
+        let mut res = VectorLWE::zero(ct.dimension, 1)?;
+        res.copy_lwe_in_nth_inplace(0, &ct)?;
+        Ok(res)
+    }
+
+    /// Key switch the n-th LWE ciphertext to the input key of a bootstrapping key, then compute a
+    /// bootstrap and apply an arbitrary function, with a single noise analysis for the two operations
+    ///
+    /// # Argument
+    /// * `ksk` - the key switching key, to the input key of `bsk`
+    /// * `bsk` - the bootstrapping key
+    /// * `f` - the function to apply
+    /// * `encoder_output` - the encoder of the output
+    /// * `n` - the index of the ciphertext to bootstrap
+    ///
+    /// # Output
+    /// * a VectorLWE struct containing only one ciphertext, under the output key of `bsk`
+    /// * IndexError - if the requested ciphertext does not exist
+    /// * DimensionError - if the input ciphertext, the key switching key and the bootstrapping key have incompatible dimensions
+    /// * KeyMismatch - if the input ciphertext, the key switching key and the bootstrapping key do not chain their keys
+    /// * NotEnoughPaddingError - if the input ciphertext has no bit of padding
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// // encoders
+    /// let encoder_input = Encoder::new(-10., 10., 4, 1).unwrap();
+    /// let encoder_output = Encoder::new(0., 10., 4, 1).unwrap();
+    ///
+    /// // generate secret keys
+    /// let rlwe_secret_key = RLWESecretKey::new(&RLWE128_1024_1);
+    /// let secret_key = LWESecretKey::new(&LWE128_630);
+    /// let big_secret_key = rlwe_secret_key.to_lwe_secret_key();
+    ///
+    /// // key switching key to the input key of the bootstrapping key
+    /// let ksk = LWEKSK::new(&big_secret_key, &secret_key, 2, 7);
+    /// let bsk = LWEBSK::new(&secret_key, &rlwe_secret_key, 5, 3);
+    ///
+    /// // a list of messages that we encrypt under the big key
+    /// let messages: Vec<f64> = vec![-6.2, 3.4, 9.1];
+    /// let ciphertexts =
+    ///     VectorLWE::encode_encrypt(&big_secret_key, &messages, &encoder_input).unwrap();
+    ///
+    /// // the output is encrypted under the big key again
+    /// let ciphertext_out = ciphertexts
+    ///     .keyswitch_pbs_nth(&ksk, &bsk, |x| f64::abs(x), &encoder_output, 2)
+    ///     .unwrap();
+    /// ```
+    pub fn keyswitch_pbs_nth<F: Fn(f64) -> f64>(
+        &self,
+        ksk: &crate::LWEKSK,
+        bsk: &crate::LWEBSK,
+        f: F,
+        encoder_output: &crate::Encoder,
+        n: usize,
+    ) -> Result<crate::VectorLWE, CryptoAPIError> {
+        let ct = self
+            .nth_to_lwe(n)?
+            .keyswitch_pbs(ksk, bsk, f, encoder_output)?;
+        let mut res = VectorLWE::zero(ct.dimension, 1)?;
+        res.copy_lwe_in_nth_inplace(0, &ct)?;
+        Ok(res)
+    }
+
+    /// Evaluate a look up table indexed by several encrypted bits (vertical packing)
+    /// The n-th ciphertext of the VectorLWE encrypts the n-th bit of the index, the first one being the least significant bit
+    /// Each bit is turned into a GGSW with a circuit bootstrap.
+    /// The table is split into polynomials of polynomial_size entries, a CMux tree over the most significant bits selects
+    /// one of them and a blind rotation made of CMuxes over the least significant bits brings the right entry to the constant coefficient.
+    /// Unlike bootstrap_nth_with_function, the size of the table is not limited by the polynomial size.
+    ///
+    /// # Argument
+    /// * `table` - the 2^nb_ciphertexts values of the table, as messages of `encoder_output`
+    /// * `bsk` - the bootstrapping key
+    /// * `pfksk` - the private functional key switching keys, from the output LWE key of `bsk` to the output RLWE key
+    /// * `encoder_output` - the encoder of the output
+    /// * `base_log` - the log2 of the decomposition base of the GGSW ciphertexts
+    /// * `level` - the number of levels of the decomposition of the GGSW ciphertexts
+    ///
+    /// # Output
+    /// * a VectorLWE with one ciphertext under the LWE key obtained from the output RLWE key of `pfksk`, its encoder precision takes the noise of the CMuxes into account
+    /// * WrongSizeError - if the table does not have 2^nb_ciphertexts values
+    /// * DimensionError - if the bootstrapping key, the key switching keys and the input ciphertexts have incompatible dimensions
+    /// * NotEnoughPaddingError - if an input ciphertext has no bit of padding
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// // generate secret keys
+    /// let rlwe_secret_key = RLWESecretKey::new(&RLWE128_1024_1);
+    /// let secret_key_before = LWESecretKey::new(&LWE128_630);
+    /// let secret_key_after = rlwe_secret_key.to_lwe_secret_key();
+    ///
+    /// // bootstrapping key and private functional key switching keys
+    /// let bsk = LWEBSK::new(&secret_key_before, &rlwe_secret_key, 5, 3);
+    /// let pfksk = LWEPFKSK::new(&secret_key_after, &rlwe_secret_key, 10, 3);
+    ///
+    /// // encrypt the index 5 = 0b101 bit by bit
+    /// let encoder_bit = Encoder::new(0., 1., 1, 1).unwrap();
+    /// let bits = VectorLWE::encode_encrypt(&secret_key_before, &[1., 0., 1.], &encoder_bit).unwrap();
+    ///
+    /// // the table of the square function
+    /// let table: Vec<f64> = (0..8).map(|x| (x * x) as f64).collect();
+    /// let encoder_output = Encoder::new(0., 63., 6, 0).unwrap();
+    ///
+    /// // look up
+    /// let res = bits
+    ///     .bootstrap_with_table(&table, &bsk, &pfksk, &encoder_output, 6, 4)
+    ///     .unwrap();
+    /// ```
+    pub fn bootstrap_with_table(
+        &self,
+        table: &[f64],
+        bsk: &crate::LWEBSK,
+        pfksk: &crate::LWEPFKSK,
+        encoder_output: &crate::Encoder,
+        base_log: usize,
+        level: usize,
+    ) -> Result<crate::VectorLWE, CryptoAPIError> {
+        // check the size of the table
+        if table.len() != 1 << self.nb_ciphertexts {
+            return Err(WrongSizeError!(table.len()));
+        }
+
+        // circuit bootstrap every bit of the index
+        let mut ggsws: Vec<crate::GGSW> = Vec::with_capacity(self.nb_ciphertexts);
+        for n in 0..self.nb_ciphertexts {
+            let bit = self.nth_to_lwe(n)?;
+            ggsws.push(bit.circuit_bootstrap(bsk, pfksk, base_log, level)?);
+        }
+
+        // the first bits are used for the blind rotation and the others for the CMux tree
+        let polynomial_size = pfksk.polynomial_size;
+        let ct_size = (pfksk.dimension_after + 1) * polynomial_size;
+        let nb_rotation_bits = usize::min(
+            self.nb_ciphertexts,
+            polynomial_size.trailing_zeros() as usize,
+        );
+        let (rotation_ggsws, tree_ggsws) = ggsws.split_at(nb_rotation_bits);
+
+        // encode the table into trivial RLWE ciphertexts
+        let mut accumulators: Vec<Vec<Torus>> = Vec::new();
+        for values in table.chunks(1 << nb_rotation_bits) {
+            let mut accumulator: Vec<Torus> = vec![0; ct_size];
+            for (acc, value) in izip!(
+                accumulator[(ct_size - polynomial_size)..].iter_mut(),
+                values.iter()
+            ) {
+                *acc = encoder_output.encode_outside_interval_operators(*value)?;
+            }
+            accumulators.push(accumulator);
+        }
+
+        // CMux tree over the most significant bits
+        let mut variance: f64 = 0.;
+        for ggsw in tree_ggsws.iter() {
+            let fourier_rows = ggsw.fourier_rows();
+            accumulators = accumulators
+                .chunks(2)
+                .map(|pair| {
+                    let mut res: Vec<Torus> = vec![0; ct_size];
+                    ggsw.cmux_raw(&fourier_rows, &mut res, &pair[0], &pair[1]);
+                    res
+                })
+                .collect();
+            variance = ggsw.get_cmux_variance(variance, variance);
+        }
+
+        // blind rotation over the least significant bits
+        let mut accumulator = accumulators.remove(0);
+        let mut rotated: Vec<Torus> = vec![0; ct_size];
+        for (i, ggsw) in rotation_ggsws.iter().enumerate() {
+            // multiply by X^(-2^i)
+            let shift: usize = 1 << i;
+            for (out, polynomial) in izip!(
+                rotated.chunks_mut(polynomial_size),
+                accumulator.chunks(polynomial_size)
+            ) {
+                for (j, coeff) in polynomial.iter().enumerate() {
+                    if j >= shift {
+                        out[j - shift] = *coeff;
+                    } else {
+                        out[j + polynomial_size - shift] = coeff.wrapping_neg();
+                    }
+                }
+            }
+
+            let mut res: Vec<Torus> = vec![0; ct_size];
+            ggsw.cmux_raw(&ggsw.fourier_rows(), &mut res, &accumulator, &rotated);
+            accumulator = res;
+            variance = ggsw.get_cmux_variance(variance, variance);
+        }
+
+        // extract the constant coefficient
+        let mut rlwe = crate::VectorRLWE::zero(polynomial_size, pfksk.dimension_after, 1)?;
+        rlwe.key_fingerprint = pfksk.fingerprint_after;
+        rlwe.ciphertexts
+            .as_mut_tensor()
+            .as_mut_slice()
+            .copy_from_slice(&accumulator);
+        rlwe.variances[0] = variance;
+        rlwe.encoders[0].copy(encoder_output);
+
+        // update the precision
+        let nb_bit_overlap: usize = rlwe.encoders[0].update_precision_from_variance(variance)?;
+        if nb_bit_overlap > 0 {
+            println!(
+                "{}: {} bit(s) of precision lost over {} bit(s) of message originally. Consider increasing the number of level and/or decreasing the log base.",
+                "Loss of precision during table look up".red().bold(),
+                nb_bit_overlap,
+                encoder_output.nb_bit_precision
+            );
+        }
+
+        rlwe.extract_1_lwe(0, 0)
+    }
+}
+
+impl<T: GenericTorus> GenericVectorLWE<T> {
     /// Return the size of one LWE ciphertext with the parameters of self
     ///
     /// # Output
@@ -2280,10 +2940,12 @@ impl VectorLWE {
         write_to_file(path, self)
     }
 
-    pub fn load(path: &str) -> Result<VectorLWE, Box<dyn Error>> {
+    pub fn load(path: &str) -> Result<GenericVectorLWE<T>, Box<dyn Error>> {
         read_from_file(path)
     }
+}
 
+impl VectorLWE {
     pub fn pp(&self) {
         for (variance, encoder) in izip!(self.variances.iter(), self.encoders.iter()) {
             let mut padding_res: String = "".to_string();
@@ -2412,6 +3074,7 @@ impl VectorLWE {
             dimension: self.dimension,
             nb_ciphertexts: 1,
             encoders: vec![new_encoder],
+            key_fingerprint: self.key_fingerprint,
         })
     }
 
@@ -2491,12 +3154,334 @@ impl VectorLWE {
             dimension: self.dimension,
             nb_ciphertexts: 1,
             encoders: vec![new_encoder],
+            key_fingerprint: self.key_fingerprint,
         })
     }
+
+    /// Compute the dot product between the LWE ciphertexts of self and a list of clear constants
+    /// and output it as a new VectorLWE containing one ciphertext.
+    /// The constants are written as fixed point numbers with the smallest number of fractional
+    /// bits that keeps the rounding error below the output granularity, and the output encoder
+    /// is the smallest interval containing every possible result,
+    /// so only the bits of padding that are really needed are consumed.
+    /// With integer constants whose result fits in the input interval size, no padding is consumed.
+    ///
+    /// # Argument
+    /// * `constants` - a list of integer or real constants (one for each ciphertext)
+    ///
+    /// # Output
+    /// * a new VectorLWE containing only one ciphertext
+    /// * NbCTError - if the number of constants and the number of ciphertexts are different
+    /// * ZeroCiphertextsInStructureError - if self contains no ciphertext
+    /// * DeltaError - if the ciphertexts do not share the same delta
+    /// * PaddingError - if the ciphertexts do not share the same number of bits of padding
+    /// * ConstantMaximumError - if one constant is too big for the encoder of its ciphertext
+    /// * NotEnoughPaddingError - if there is not enough padding to store the result
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// // encoder
+    /// let encoder = Encoder::new(-10., 10., 6, 8).unwrap();
+    ///
+    /// // generate a secret key
+    /// let secret_key = LWESecretKey::new(&LWE128_1024);
+    ///
+    /// // encode and encrypt
+    /// let messages: Vec<f64> = vec![-4.2, 3.5, 8.1];
+    /// let ciphertext = VectorLWE::encode_encrypt(&secret_key, &messages, &encoder).unwrap();
+    ///
+    /// // dot product with real or integer constants
+    /// let dot_1 = ciphertext.dot_product_clear(&[1.5, -2., 0.25]).unwrap();
+    /// let dot_2 = ciphertext.dot_product_clear(&[3, -1, 2]).unwrap();
+    /// ```
+    pub fn dot_product_clear<C: Into<f64> + Copy>(
+        &self,
+        constants: &[C],
+    ) -> Result<crate::VectorLWE, CryptoAPIError> {
+        let constants: Vec<f64> = constants.iter().map(|c| (*c).into()).collect();
+        let mut res = VectorLWE::zero(self.dimension, 1)?;
+        res.copy_lwe_in_nth_inplace(0, &self.dot_product_clear_lwe(&constants)?)?;
+        Ok(res)
+    }
+
+    /// Compute the product between a clear matrix and the LWE ciphertexts of self seen as a vector
+    /// and output it as a new VectorLWE with one ciphertext for each row of the matrix.
+    /// Each row is computed as with dot_product_clear so each output ciphertext gets its own encoder.
+    ///
+    /// # Argument
+    /// * `matrix` - a Matrix with as many columns as there are ciphertexts in self
+    ///
+    /// # Output
+    /// * a new VectorLWE containing matrix.nb_rows ciphertexts
+    /// * NbCTError - if the number of columns and the number of ciphertexts are different
+    /// * ZeroCiphertextsInStructureError - if self contains no ciphertext
+    /// * DeltaError - if the ciphertexts do not share the same delta
+    /// * PaddingError - if the ciphertexts do not share the same number of bits of padding
+    /// * ConstantMaximumError - if one coefficient is too big for the encoder of its ciphertext
+    /// * NotEnoughPaddingError - if there is not enough padding to store one of the results
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// // encoder
+    /// let encoder = Encoder::new(-10., 10., 6, 8).unwrap();
+    ///
+    /// // generate a secret key
+    /// let secret_key = LWESecretKey::new(&LWE128_1024);
+    ///
+    /// // encode and encrypt
+    /// let messages: Vec<f64> = vec![-4.2, 3.5, 8.1];
+    /// let ciphertext = VectorLWE::encode_encrypt(&secret_key, &messages, &encoder).unwrap();
+    ///
+    /// // matrix vector product
+    /// let matrix = Matrix::from_rows(&[vec![1., 0.5, -2.], vec![-0.75, 3., 1.]]).unwrap();
+    /// let product = ciphertext.matmul_clear(&matrix).unwrap();
+    /// ```
+    pub fn matmul_clear(&self, matrix: &crate::Matrix) -> Result<crate::VectorLWE, CryptoAPIError> {
+        if matrix.nb_cols != self.nb_ciphertexts {
+            return Err(NbCTError!(matrix.nb_cols, self.nb_ciphertexts));
+        }
+
+        let mut res = VectorLWE::zero(self.dimension, matrix.nb_rows)?;
+        for i in 0..matrix.nb_rows {
+            res.copy_lwe_in_nth_inplace(i, &self.dot_product_clear_lwe(matrix.get_row(i)?)?)?;
+        }
+        Ok(res)
+    }
+
+    /// Compute the dot product between the LWE ciphertexts of self and a list of real constants
+    /// and output it as an LWE
+    fn dot_product_clear_lwe(&self, constants: &[f64]) -> Result<crate::LWE, CryptoAPIError> {
+        let precision = self.check_multisum_inputs(constants)?;
+
+        // look for the smallest number of fractional bits for the constants such that
+        // the rounding error stays below half of the output granularity
+        let integers = constants.iter().all(|c| c.fract() == 0.);
+        let max_fractional_bits = if integers {
+            0
+        } else {
+            self.encoders[0].nb_bit_padding as i32
+        };
+        let mut nb_fractional_bits: i32 = 0;
+        for s in 0..=max_fractional_bits {
+            let bounds = self.fixed_point_bounds(constants, s);
+            if bounds.nb_bit_padding_consumed > self.encoders[0].nb_bit_padding {
+                break;
+            }
+            nb_fractional_bits = s;
+            if bounds.rounding_error <= bounds.delta / f64::powi(2., precision as i32 + 1) {
+                break;
+            }
+        }
+
+        self.multisum_fixed_point(constants, nb_fractional_bits)
+    }
+
+    /// Check that a list of constants can be used in a multisum with the LWE ciphertexts of self
+    /// and output the minimum precision of the encoders
+    ///
+    /// # Output
+    /// * NbCTError - if the number of constants and the number of ciphertexts are different
+    /// * ZeroCiphertextsInStructureError - if self contains no ciphertext
+    /// * DeltaError - if the ciphertexts do not share the same delta
+    /// * PaddingError - if the ciphertexts do not share the same number of bits of padding
+    /// * ConstantMaximumError - if one constant is too big for the encoder of its ciphertext
+    fn check_multisum_inputs(&self, constants: &[f64]) -> Result<usize, CryptoAPIError> {
+        // check if we have the same number of constants and ciphertexts
+        if constants.len() != self.nb_ciphertexts {
+            return Err(NbCTError!(constants.len(), self.nb_ciphertexts));
+        }
+        // check that there is at least one ciphertext
+        if self.encoders.is_empty() {
+            return Err(ZeroCiphertextsInStructureError!(self.encoders.len()));
+        }
+
+        let delta = self.encoders[0].delta;
+        let padding = self.encoders[0].nb_bit_padding;
+        let mut precision = self.encoders[0].nb_bit_precision;
+
+        // a product can not be bigger than what the padded torus can store,
+        // and a fixed point constant has to fit in a signed torus element
+        let max_constant_torus = f64::powi(2., (<Torus as Numeric>::BITS - 1 - padding) as i32);
+        for (c, encoder) in izip!(constants.iter(), self.encoders.iter()) {
+            if !deltas_eq!(delta, encoder.delta) {
+                return Err(DeltaError!(delta, encoder.delta));
+            } else if padding != encoder.nb_bit_padding {
+                return Err(PaddingError!(padding, encoder.nb_bit_padding));
+            }
+            precision = usize::min(precision, encoder.nb_bit_precision);
+
+            let max_message = f64::max(encoder.get_min().abs(), encoder.get_max().abs());
+            let max_constant = f64::min(
+                delta * f64::powi(2., padding as i32) / max_message,
+                max_constant_torus,
+            );
+            if !c.is_finite() || c.abs() > max_constant {
+                return Err(ConstantMaximumError!(*c, max_constant));
+            }
+        }
+        Ok(precision)
+    }
+
+    /// Compute the output interval and the error bounds of a multisum between the LWE ciphertexts
+    /// of self and a list of constants rounded to fixed point numbers with `nb_fractional_bits`
+    fn fixed_point_bounds(&self, constants: &[f64], nb_fractional_bits: i32) -> FixedPointBounds {
+        let scale = f64::powi(2., nb_fractional_bits);
+        let fixed_constants: Vec<f64> = constants.iter().map(|c| (c * scale).round()).collect();
+
+        // output interval of the multisum with the rounded constants
+        let mut min = 0.;
+        let mut max = 0.;
+        let mut rounding_error = 0.;
+        let mut input_error = 0.;
+        for (c, q, encoder) in izip!(
+            constants.iter(),
+            fixed_constants.iter(),
+            self.encoders.iter()
+        ) {
+            let rounded_c = q / scale;
+            let bound_1 = rounded_c * encoder.get_min();
+            let bound_2 = rounded_c * encoder.get_max();
+            min += f64::min(bound_1, bound_2);
+            max += f64::max(bound_1, bound_2);
+            rounding_error +=
+                (c - rounded_c).abs() * f64::max(encoder.get_min().abs(), encoder.get_max().abs());
+            input_error += rounded_c.abs() * encoder.get_granularity();
+        }
+
+        // number of bits of padding needed to store the output interval
+        let unit = self.encoders[0].delta / scale;
+        let nb_bit_padding_consumed = if max - min < unit {
+            0
+        } else {
+            f64::log2((max - min) / unit).floor() as usize + 1
+        };
+
+        FixedPointBounds {
+            fixed_constants,
+            min,
+            delta: unit * f64::powi(2., nb_bit_padding_consumed as i32),
+            nb_bit_padding_consumed,
+            rounding_error,
+            input_error,
+        }
+    }
+
+    /// Compute a multisum between the LWE ciphertexts of self and a list of real constants
+    /// rounded to fixed point numbers with `nb_fractional_bits`, and output it as an LWE.
+    /// The integer parts of the fixed point constants multiply the ciphertexts
+    /// while the fractional bits are taken into account by rescaling the delta of the output encoder,
+    /// whose precision is bounded by the rounding error of the constants and the input granularities.
+    ///
+    /// # Output
+    /// * NbCTError - if the number of constants and the number of ciphertexts are different
+    /// * ZeroCiphertextsInStructureError - if self contains no ciphertext
+    /// * DeltaError - if the ciphertexts do not share the same delta
+    /// * PaddingError - if the ciphertexts do not share the same number of bits of padding
+    /// * ConstantMaximumError - if one constant is too big for the encoder of its ciphertext
+    /// * NotEnoughPaddingError - if there is not enough padding to store the result
+    pub(crate) fn multisum_fixed_point(
+        &self,
+        constants: &[f64],
+        nb_fractional_bits: i32,
+    ) -> Result<crate::LWE, CryptoAPIError> {
+        let precision = self.check_multisum_inputs(constants)?;
+        let delta = self.encoders[0].delta;
+        let padding = self.encoders[0].nb_bit_padding;
+
+        let bounds = self.fixed_point_bounds(constants, nb_fractional_bits);
+        if bounds.nb_bit_padding_consumed > padding {
+            return Err(NotEnoughPaddingError!(
+                padding,
+                bounds.nb_bit_padding_consumed
+            ));
+        }
+
+        // homomorphic multisum
+        let mut ct = Tensor::from_container(vec![0 as Torus; self.dimension + 1]);
+        let mut variances: Vec<f64> = Vec::with_capacity(self.nb_ciphertexts);
+        let mut correction = 0.;
+        let max_constant_torus = f64::powi(2., (<Torus as Numeric>::BITS - 1 - padding) as i32);
+        for (ct_in, var_in, c, q, encoder) in izip!(
+            self.ciphertexts
+                .as_tensor()
+                .subtensor_iter(self.dimension + 1),
+            self.variances.iter(),
+            constants.iter(),
+            bounds.fixed_constants.iter(),
+            self.encoders.iter()
+        ) {
+            // the fixed point constant has to fit in a signed torus element
+            let q_torus = i64::try_from(*q as i128)
+                .map_err(|_| ConstantMaximumError!(*c, max_constant_torus))?;
+            ct.update_with_wrapping_add_element_mul(&ct_in, q_torus as Torus);
+            variances.push(*var_in * q * q);
+            correction += q * encoder.o;
+        }
+
+        // move the offsets of the inputs to the min of the output interval
+        correction = (correction - f64::powi(2., nb_fractional_bits) * bounds.min)
+            / delta
+            / f64::powi(2., padding as i32);
+        correction -= correction.floor();
+        let correction =
+            (correction * f64::powi(2., <Torus as Numeric>::BITS as i32)).round() as u128 as Torus;
+        let update = ct.get_element(self.dimension).wrapping_add(correction);
+        *ct.get_element_mut(self.dimension) = update;
+
+        // output encoder
+        let new_var = npe::add_several_ciphertexts(&variances);
+        let error = bounds.rounding_error + bounds.input_error;
+        let new_precision = if error > 0. {
+            usize::min(
+                precision,
+                f64::log2(bounds.delta / error).floor().max(0.) as usize,
+            )
+        } else {
+            precision
+        };
+        let mut new_encoder = crate::Encoder {
+            o: bounds.min,
+            delta: bounds.delta,
+            nb_bit_precision: new_precision,
+            nb_bit_padding: padding - bounds.nb_bit_padding_consumed,
+            round: self.encoders[0].round,
+        };
+        new_encoder.update_precision_from_variance(new_var)?;
+
+        Ok(crate::LWE {
+            ciphertext: LweCiphertext::from_container(ct.into_container()),
+            variance: new_var,
+            dimension: self.dimension,
+            encoder: new_encoder,
+            key_fingerprint: self.key_fingerprint,
+        })
+    }
+}
+
+/// Output interval and error bounds of a multisum with fixed point constants
+///
+/// # Attributes
+/// * `fixed_constants` - the constants multiplied by 2^nb_fractional_bits and rounded
+/// * `min` - the min of the output interval
+/// * `delta` - the delta of the output encoder
+/// * `nb_bit_padding_consumed` - the number of bits of padding needed to store the output
+/// * `rounding_error` - a bound on the error due to the rounding of the constants
+/// * `input_error` - a bound on the error due to the granularities of the inputs
+struct FixedPointBounds {
+    fixed_constants: Vec<f64>,
+    min: f64,
+    delta: f64,
+    nb_bit_padding_consumed: usize,
+    rounding_error: f64,
+    input_error: f64,
 }
 
 /// Print needed pieces of information about an VectorLWE
-impl fmt::Display for VectorLWE {
+impl<T: GenericTorus> fmt::Display for GenericVectorLWE<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let n = 2;
         let mut to_be_print: String = "".to_string();
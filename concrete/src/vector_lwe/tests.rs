@@ -552,6 +552,151 @@ fn test_encode_encrypt_x_keyswitch_x_decrypt() {
     assert_eq!(cpt, nb_messages);
 }
 
+#[test]
+fn test_encode_encrypt_x_keyswitch_x_decrypt_u32() {
+    // random settings
+    let (min, max) = generate_random_interval!();
+    let (precision, padding) = generate_precision_padding!(4, 1);
+    let nb_messages: usize = random_index!(30) + 10;
+    let base_log: usize = 4;
+    let level: usize = 6;
+
+    // encoder
+    let encoder = crate::Encoder::new(min, max, precision, padding).unwrap();
+
+    // generate two secret keys
+    let secret_key_before = crate::LWESecretKey::new(&crate::LWE128_1024);
+    let secret_key_after = crate::LWESecretKey::new(&crate::LWE128_1024);
+
+    // generate the key switching key on 32 bits
+    let ksk = crate::LWEKSK32::new(&secret_key_before, &secret_key_after, base_log, level);
+
+    // a list of messages that we encrypt
+    let messages: Vec<f64> = random_messages!(min, max, nb_messages);
+    let ciphertext_before =
+        crate::VectorLWE32::encode_encrypt(&secret_key_before, &messages, &encoder).unwrap();
+    let decryptions: Vec<f64> = ciphertext_before
+        .decrypt_decode(&secret_key_before)
+        .unwrap();
+    for (m, d, e) in izip!(
+        messages.iter(),
+        decryptions.iter(),
+        ciphertext_before.encoders.iter()
+    ) {
+        assert_eq_granularity!(*m, d, e);
+    }
+
+    // key switch
+    let ciphertext_after = ciphertext_before.keyswitch(&ksk).unwrap();
+
+    // decryption
+    let decryptions: Vec<f64> = ciphertext_after
+        .decrypt_decode_round(&secret_key_after)
+        .unwrap();
+    for (m, d, e) in izip!(
+        messages.iter(),
+        decryptions.iter(),
+        ciphertext_after.encoders.iter()
+    ) {
+        assert_eq_granularity!(*m, d, e);
+    }
+}
+
+#[test]
+fn test_encode_encrypt_x_operators_x_decrypt_u32() {
+    let nb_messages: usize = random_index!(10) + 2;
+
+    // encoder
+    let encoder = crate::Encoder::new(-10., 10., 5, 4).unwrap();
+
+    // generate a secret key
+    let secret_key = crate::LWESecretKey::new(&crate::LWE128_630);
+
+    // two lists of messages whose sums stay in the interval
+    let messages_1: Vec<f64> = random_messages!(-5., 5., nb_messages);
+    let messages_2: Vec<f64> = random_messages!(-5., 5., nb_messages);
+
+    // encode and encrypt
+    let ciphertext_1 =
+        crate::VectorLWE32::encode_encrypt(&secret_key, &messages_1, &encoder).unwrap();
+    let ciphertext_2 =
+        crate::VectorLWE32::encode_encrypt(&secret_key, &messages_2, &encoder).unwrap();
+
+    // additions, subtractions and multiplications by constants
+    let sum = ciphertext_1.add_with_padding(&ciphertext_2).unwrap();
+    let centered = ciphertext_1.add_centered(&ciphertext_2).unwrap();
+    let difference = ciphertext_1.sub_with_padding(&ciphertext_2).unwrap();
+    let scaled = ciphertext_1
+        .mul_constant_static_encoder(&vec![-2; nb_messages])
+        .unwrap();
+    let opposite = ciphertext_1.opposite_nth(0).unwrap();
+
+    let decryptions_sum = sum.decrypt_decode(&secret_key).unwrap();
+    let decryptions_centered = centered.decrypt_decode(&secret_key).unwrap();
+    let decryptions_difference = difference.decrypt_decode(&secret_key).unwrap();
+    let decryptions_scaled = scaled.decrypt_decode(&secret_key).unwrap();
+    for (i, (m1, m2)) in izip!(messages_1.iter(), messages_2.iter()).enumerate() {
+        assert_eq_granularity!(m1 + m2, decryptions_sum[i], sum.encoders[i]);
+        assert_eq_granularity!(m1 + m2, decryptions_centered[i], centered.encoders[i]);
+        assert_eq_granularity!(m1 - m2, decryptions_difference[i], difference.encoders[i]);
+        assert_eq_granularity!(-2. * m1, decryptions_scaled[i], scaled.encoders[i]);
+    }
+    let decryption_opposite = opposite.decrypt_decode(&secret_key).unwrap();
+    assert_eq_granularity!(-messages_1[0], decryption_opposite[0], opposite.encoders[0]);
+}
+
+#[test]
+fn test_encode_encrypt_x_mul_from_bootstrap_x_decrypt_u32() {
+    let nb_messages: usize = random_index!(4) + 2;
+
+    // encoders
+    let encoder_1 = crate::Encoder::new(-15., 15., 4, 2).unwrap();
+    let encoder_2 = crate::Encoder::new(5., 35., 4, 2).unwrap();
+
+    // secret keys
+    let rlwe_secret_key = crate::RLWESecretKey::new(&crate::RLWE128_1024_1);
+    let secret_key_input = crate::LWESecretKey::new(&crate::LWE128_630);
+    let secret_key_output = rlwe_secret_key.to_lwe_secret_key();
+
+    // bootstrapping key on 32 bits
+    let bsk = crate::LWEBSK32::new(&secret_key_input, &rlwe_secret_key, 6, 3);
+
+    // two lists of messages
+    let messages_1: Vec<f64> = random_messages!(-15., 15., nb_messages);
+    let messages_2: Vec<f64> = random_messages!(5., 35., nb_messages);
+
+    // encode and encrypt
+    let ciphertext_1 =
+        crate::VectorLWE32::encode_encrypt(&secret_key_input, &messages_1, &encoder_1).unwrap();
+    let ciphertext_2 =
+        crate::VectorLWE32::encode_encrypt(&secret_key_input, &messages_2, &encoder_2).unwrap();
+
+    // multiplications
+    let products = ciphertext_1
+        .mul_from_bootstrap(&ciphertext_2, &bsk, None)
+        .unwrap();
+    let product_nth = ciphertext_1
+        .mul_from_bootstrap_nth(&ciphertext_2, &bsk, 1, 0)
+        .unwrap();
+
+    // decrypt
+    let decryptions = products.decrypt_decode(&secret_key_output).unwrap();
+    for (m1, m2, d, e) in izip!(
+        messages_1.iter(),
+        messages_2.iter(),
+        decryptions.iter(),
+        products.encoders.iter()
+    ) {
+        assert_eq_granularity!(m1 * m2, d, e);
+    }
+    let decryption_nth = product_nth.decrypt_decode(&secret_key_output).unwrap();
+    assert_eq_granularity!(
+        messages_1[1] * messages_2[0],
+        decryption_nth[0],
+        product_nth.encoders[0]
+    );
+}
+
 #[test]
 fn test_encode_encrypt_x_bootstrap_nth_x_decrypt() {
     // random settings
@@ -604,6 +749,39 @@ fn test_encode_encrypt_x_bootstrap_nth_x_decrypt() {
     }
 }
 
+#[test]
+fn test_encode_encrypt_x_reencode_nth_x_decrypt() {
+    // encoders: the new interval is the positive half of the input one
+    let (_, max) = generate_random_centered_interval!();
+    let encoder_input = crate::Encoder::new(-max, max, 4, 1).unwrap();
+    let new_encoder = crate::Encoder::new(0., max, 4, 3).unwrap();
+    let nb_messages: usize = random_index!(5) + 2;
+
+    // secret keys
+    let rlwe_secret_key = crate::RLWESecretKey::new(&crate::RLWE128_1024_1);
+    let secret_key_input = crate::LWESecretKey::new(&crate::LWE128_630);
+    let secret_key_output = rlwe_secret_key.to_lwe_secret_key();
+
+    // bootstrapping key
+    let bsk = crate::LWEBSK::new(&secret_key_input, &rlwe_secret_key, 5, 3);
+
+    // encode and encrypt
+    let messages: Vec<f64> = random_messages!(-max, max, nb_messages);
+    let ciphertexts =
+        crate::VectorLWE::encode_encrypt(&secret_key_input, &messages, &encoder_input).unwrap();
+
+    for (n, m) in messages.iter().enumerate() {
+        // reencode and decrypt
+        let ct_res = ciphertexts.reencode_nth(&bsk, &new_encoder, n).unwrap();
+        let decryption = ct_res.decrypt_decode(&secret_key_output).unwrap()[0];
+
+        // negative messages are clamped to 0
+        assert_eq!(ct_res.encoders[0].nb_bit_padding, 3);
+        let expected = f64::min(f64::max(*m, 0.), new_encoder.get_max());
+        assert_eq_granularity!(expected, decryption, encoder_input);
+    }
+}
+
 #[test]
 fn test_encode_encrypt_x_mul_from_bootstrap_nth_nth_x_decrypt() {
     let nb_messages: usize = 1;
@@ -657,6 +835,111 @@ fn test_encode_encrypt_x_mul_from_bootstrap_nth_nth_x_decrypt() {
     );
 }
 
+#[test]
+fn test_encode_encrypt_x_mul_from_bootstrap_x_decrypt() {
+    let nb_messages: usize = random_index!(4) + 1;
+
+    // random settings for the first encoder and some messages
+    let (min1, max1) = generate_random_interval!();
+    let encoder_1 = crate::Encoder::new(min1, max1, 5, 2).unwrap();
+    let messages_1: Vec<f64> = random_messages!(min1, max1, nb_messages);
+
+    // random settings for the second encoder and some messages
+    let (min2, _max2) = generate_random_interval!();
+    let max2 = min2 + max1 - min1;
+    let encoder_2 = crate::Encoder::new(min2, max2, 5, 2).unwrap();
+    let messages_2: Vec<f64> = random_messages!(min2, max2, nb_messages);
+
+    // output encoders with the exact interval of the products
+    let corners: Vec<f64> = vec![min1 * min2, min1 * max2, max1 * min2, max1 * max2];
+    let min_out = corners.iter().cloned().fold(f64::INFINITY, f64::min);
+    let max_out = corners.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    let encoders_output = vec![crate::Encoder::new(min_out, max_out, 5, 1).unwrap(); nb_messages];
+
+    // generate a secret key
+    let rlwe_secret_key = crate::RLWESecretKey::new(&crate::RLWE128_1024_1);
+    let secret_key_input = crate::LWESecretKey::new(&crate::LWE128_630);
+    let secret_key_output = rlwe_secret_key.to_lwe_secret_key();
+
+    // bootstrapping key
+    let bsk = crate::LWEBSK::new(&secret_key_input, &rlwe_secret_key, 5, 3);
+
+    // encode and encrypt
+    let ciphertext_1 =
+        crate::VectorLWE::encode_encrypt(&secret_key_input, &messages_1, &encoder_1).unwrap();
+    let ciphertext_2 =
+        crate::VectorLWE::encode_encrypt(&secret_key_input, &messages_2, &encoder_2).unwrap();
+
+    // multiplications with the default and the given output encoders
+    let ciphertext_res_default = ciphertext_1
+        .mul_from_bootstrap(&ciphertext_2, &bsk, None)
+        .unwrap();
+    let ciphertext_res = ciphertext_1
+        .mul_from_bootstrap(&ciphertext_2, &bsk, Some(&encoders_output))
+        .unwrap();
+
+    // decrypt
+    let decryptions_default = ciphertext_res_default
+        .decrypt_decode(&secret_key_output)
+        .unwrap();
+    let decryptions = ciphertext_res.decrypt_decode(&secret_key_output).unwrap();
+
+    // test
+    for (m1, m2, d_default, d, enc_default, enc) in izip!(
+        messages_1.iter(),
+        messages_2.iter(),
+        decryptions_default.iter(),
+        decryptions.iter(),
+        ciphertext_res_default.encoders.iter(),
+        ciphertext_res.encoders.iter()
+    ) {
+        assert_eq_granularity!(m1 * m2, d_default, enc_default);
+        assert_eq_granularity!(m1 * m2, d, enc);
+        assert!(enc.delta < enc_default.delta);
+    }
+}
+
+#[test]
+fn test_encode_encrypt_x_bootstrap_with_table_x_decrypt() {
+    // random index of 10 bits, the table is bigger than one polynomial
+    let nb_bits: usize = 10;
+    let index: usize = random_index!(1 << nb_bits);
+    let bits: Vec<f64> = (0..nb_bits).map(|i| ((index >> i) & 1) as f64).collect();
+
+    // random table
+    let encoder_output = crate::Encoder::new(0., 63., 6, 0).unwrap();
+    let table: Vec<f64> = (0..(1 << nb_bits))
+        .map(|_| random_index!(64) as f64)
+        .collect();
+
+    // secret keys
+    let rlwe_params = crate::RLWEParams::new(512, 1, -50).unwrap();
+    let rlwe_secret_key = crate::RLWESecretKey::new(&rlwe_params);
+    let secret_key_input = crate::LWESecretKey::new(&crate::LWE128_630);
+    let secret_key_output = rlwe_secret_key.to_lwe_secret_key();
+
+    // bootstrapping key and private functional key switching keys
+    let bsk = crate::LWEBSK::new(&secret_key_input, &rlwe_secret_key, 10, 4);
+    let pfksk = crate::LWEPFKSK::new(&secret_key_output, &rlwe_secret_key, 10, 4);
+
+    // encode and encrypt the bits of the index
+    let encoder_bit = crate::Encoder::new(0., 1., 1, 1).unwrap();
+    let ciphertexts =
+        crate::VectorLWE::encode_encrypt(&secret_key_input, &bits, &encoder_bit).unwrap();
+
+    // table look up
+    let res = ciphertexts
+        .bootstrap_with_table(&table, &bsk, &pfksk, &encoder_output, 6, 3)
+        .unwrap();
+
+    // decrypt
+    let decryptions = res.decrypt_decode_round(&secret_key_output).unwrap();
+
+    // test
+    assert_eq_granularity!(table[index], decryptions[0], res.encoders[0]);
+    assert_eq!(res.encoders[0].nb_bit_precision, 6);
+}
+
 #[test]
 fn test_encode_encrypt_x_add_with_new_min_inplace_x_decrypt() {
     // random number of messages
@@ -815,3 +1098,129 @@ fn test_encode_encrypt_several_encoders_x_sum_with_new_min_x_decrypt_decode_roun
     assert_eq_granularity!(clear_sum, d, ct_sum.encoders[0]);
     assert_eq!(precision, ct_sum.encoders[0].nb_bit_precision);
 }
+
+#[test]
+fn test_encode_encrypt_several_encoders_x_dot_product_clear_x_decrypt() {
+    // random number of messages
+    let nb_messages: usize = random_index!(10) + 1;
+    let precision: usize = 5;
+    let padding: usize = 12;
+
+    // generate nb_messages encoders with the same length
+    let (min1, max1) = generate_random_interval!();
+    let encoder_1 = crate::Encoder::new(min1, max1, precision, padding).unwrap();
+    let mut encoders: Vec<crate::Encoder> = vec![encoder_1; nb_messages];
+    for enc_in in encoders.iter_mut() {
+        let (new_min, _) = generate_random_interval!();
+        enc_in.o = new_min;
+    }
+
+    // generate messages and real constants
+    let mut messages: Vec<f64> = vec![0.; nb_messages];
+    for (m, enc_in) in izip!(messages.iter_mut(), encoders.iter()) {
+        *m = random_message!(enc_in.get_min(), enc_in.get_max());
+    }
+    let constants: Vec<f64> = random_messages!(-3., 3., nb_messages);
+
+    // generate a secret key
+    let secret_key = crate::LWESecretKey::new(&crate::LWE128_1024);
+
+    // encode and encrypt
+    let ciphertexts =
+        crate::VectorLWE::encode_encrypt_several_encoders(&secret_key, &messages, &encoders)
+            .unwrap();
+
+    // dot product
+    let ct_dot = ciphertexts.dot_product_clear(&constants).unwrap();
+
+    // decryption
+    let d = ct_dot.decrypt_decode(&secret_key).unwrap()[0];
+
+    // test
+    let mut clear_dot: f64 = 0.;
+    for (m, c) in izip!(messages.iter(), constants.iter()) {
+        clear_dot += m * c;
+    }
+    assert_eq_granularity!(clear_dot, d, ct_dot.encoders[0]);
+}
+
+#[test]
+fn test_encode_encrypt_x_dot_product_clear_integers_x_decrypt() {
+    // the output interval [-60, 75] needs 3 bits of padding
+    let encoder = crate::Encoder::new(0., 15., 4, 4).unwrap();
+    let messages: Vec<f64> = vec![3., 7., 2., 1.];
+    let constants: Vec<i32> = vec![2, -1, 3, -2];
+
+    // generate a secret key
+    let secret_key = crate::LWESecretKey::new(&crate::LWE128_1024);
+
+    // encode, encrypt and compute the dot product
+    let ciphertexts = crate::VectorLWE::encode_encrypt(&secret_key, &messages, &encoder).unwrap();
+    let ct_dot = ciphertexts.dot_product_clear(&constants).unwrap();
+
+    // decryption
+    let d = ct_dot.decrypt_decode_round(&secret_key).unwrap()[0];
+    assert_eq_granularity!(3., d, ct_dot.encoders[0]);
+    assert_eq!(ct_dot.encoders[0].nb_bit_padding, 1);
+    assert_eq!(ct_dot.encoders[0].nb_bit_precision, 4);
+
+    // not enough padding to store a result that is far too big
+    assert!(ciphertexts
+        .dot_product_clear(&[100, 100, 100, 100])
+        .is_err());
+
+    // a constant bigger than what the torus can store
+    assert!(ciphertexts.dot_product_clear(&[1e30, 1., 1., 1.]).is_err());
+
+    // wrong number of constants
+    assert!(ciphertexts.dot_product_clear(&[1, 2]).is_err());
+
+    // no ciphertext at all
+    let empty = crate::VectorLWE {
+        ciphertexts: concrete_core::crypto::lwe::LweList::from_container(
+            Vec::new(),
+            concrete_commons::parameters::LweSize(ciphertexts.dimension + 1),
+        ),
+        variances: Vec::new(),
+        dimension: ciphertexts.dimension,
+        nb_ciphertexts: 0,
+        encoders: Vec::new(),
+        key_fingerprint: ciphertexts.key_fingerprint,
+    };
+    assert!(matches!(
+        empty.dot_product_clear::<f64>(&[]),
+        Err(crate::CryptoAPIError::ZeroCiphertextsInStructureError { .. })
+    ));
+}
+
+#[test]
+fn test_encode_encrypt_x_matmul_clear_x_decrypt() {
+    // random settings
+    let nb_messages: usize = random_index!(5) + 2;
+    let nb_rows: usize = random_index!(5) + 1;
+    let (min, max) = generate_random_interval!();
+    let encoder = crate::Encoder::new(min, max, 5, 10).unwrap();
+
+    // messages and a random matrix
+    let messages: Vec<f64> = random_messages!(min, max, nb_messages);
+    let coefficients: Vec<f64> = random_messages!(-2., 2., nb_messages * nb_rows);
+    let matrix = crate::Matrix::new(coefficients, nb_rows, nb_messages).unwrap();
+
+    // generate a secret key
+    let secret_key = crate::LWESecretKey::new(&crate::LWE128_1024);
+
+    // encode, encrypt and compute the product
+    let ciphertexts = crate::VectorLWE::encode_encrypt(&secret_key, &messages, &encoder).unwrap();
+    let ct_product = ciphertexts.matmul_clear(&matrix).unwrap();
+    assert_eq!(ct_product.nb_ciphertexts, nb_rows);
+
+    // decryption
+    let decryptions = ct_product.decrypt_decode(&secret_key).unwrap();
+    for (i, (d, enc)) in izip!(decryptions.iter(), ct_product.encoders.iter()).enumerate() {
+        let mut clear: f64 = 0.;
+        for (m, c) in izip!(messages.iter(), matrix.get_row(i).unwrap().iter()) {
+            clear += m * c;
+        }
+        assert_eq_granularity!(clear, d, enc);
+    }
+}
@@ -13,7 +13,7 @@ use concrete_core::{
 };
 use concrete_npe as npe;
 use crate::error::CryptoAPIError;
-use crate::{read_from_file, write_to_file, Torus};
+use crate::{read_from_file, write_to_file, KeyFingerprint, Torus};
 use concrete_commons::dispersion::StandardDev;
 use concrete_commons::numeric::Numeric;
 use concrete_commons::parameters::{CiphertextCount, GlweDimension, PolynomialSize};
@@ -35,6 +35,8 @@ mod tests;
 /// * `polynomial_size` - the number of coefficients in a polynomial
 /// * `nb_ciphertexts` - the number of RLWE ciphertexts present in the list
 /// * `encoders` - the encoders of each RLWE ciphertext of the list
+/// * `key_fingerprint` - the fingerprint of the secret key of the RLWE ciphertexts (unknown for
+///   trivial and older ciphertexts, and after a key switch)
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct VectorRLWE {
     pub ciphertexts: GlweList<Vec<Torus>>,
@@ -43,6 +45,8 @@ pub struct VectorRLWE {
     pub polynomial_size: usize,
     pub nb_ciphertexts: usize,
     pub encoders: Vec<crate::Encoder>,
+    #[serde(default)]
+    pub key_fingerprint: KeyFingerprint,
 }
 
 impl VectorRLWE {
@@ -92,6 +96,7 @@ impl VectorRLWE {
             polynomial_size,
             nb_ciphertexts,
             encoders: vec![crate::Encoder::zero(); nb_ciphertexts * polynomial_size],
+            key_fingerprint: KeyFingerprint::UNKNOWN,
         })
     }
 
@@ -437,12 +442,15 @@ impl VectorRLWE {
         // set the variances
         self.variances = vec![sk.get_variance(); self.nb_ciphertexts * self.polynomial_size];
 
-        sk.val.encrypt_glwe_list(
-            &mut self.ciphertexts,
-            &PlaintextList::from_container(plaintexts),
-            StandardDev::from_standard_dev(sk.std_dev),
-            &mut EncryptionRandomGenerator::new(None),
-        );
+        with_glwe_secret_key!(sk.kind, sk.val.as_slice(), sk.polynomial_size, |key| {
+            key.encrypt_glwe_list(
+                &mut self.ciphertexts,
+                &PlaintextList::from_container(plaintexts),
+                StandardDev::from_standard_dev(sk.std_dev),
+                &mut EncryptionRandomGenerator::new(None),
+            )
+        });
+        self.key_fingerprint = sk.fingerprint;
 
         Ok(())
     }
@@ -456,6 +464,7 @@ impl VectorRLWE {
     /// * an array of f64
     /// * PolynomialSizeError - if the polynomial size of the secret key and the polynomial size of the RLWE ciphertext are different
     /// * DimensionError - if the dimension of the secret key and the dimension of the RLWE cipertext are different
+    /// * KeyMismatch - if the RLWE ciphertext was not encrypted under the secret key
     ///
     /// # Example
     /// ```rust
@@ -489,16 +498,21 @@ impl VectorRLWE {
             return Err(DimensionError!(sk.dimension, self.dimension));
         }
 
+        // check the keys
+        self.key_fingerprint.check(sk.fingerprint)?;
+
         let mut result: Vec<f64> = vec![0.; self.nb_valid()];
 
         // create a vec with the good size for the plaintexts
         let mut tmp_pt: Vec<Torus> = vec![0; self.polynomial_size * self.nb_ciphertexts];
 
         // compute the phase for all ciphertext
-        sk.val.decrypt_glwe_list(
-            &mut PlaintextList::from_container(tmp_pt.as_mut_slice()),
-            &self.ciphertexts,
-        );
+        with_glwe_secret_key!(sk.kind, sk.val.as_slice(), sk.polynomial_size, |key| {
+            key.decrypt_glwe_list(
+                &mut PlaintextList::from_container(tmp_pt.as_mut_slice()),
+                &self.ciphertexts,
+            )
+        });
 
         // decode as soon as the encoding is valid
         let mut i: usize = 0;
@@ -520,6 +534,7 @@ impl VectorRLWE {
     /// * an array of f64
     /// * PolynomialSizeError - if the polynomial size of the secret key and the polynomial size of the RLWE ciphertext are different
     /// * DimensionError - if the dimension of the secret key and the dimension of the RLWE cipertext are different
+    /// * KeyMismatch - if the RLWE ciphertext was not encrypted under the secret key
     ///
     /// # Example
     /// ```rust
@@ -556,16 +571,21 @@ impl VectorRLWE {
             return Err(DimensionError!(sk.dimension, self.dimension));
         }
 
+        // check the keys
+        self.key_fingerprint.check(sk.fingerprint)?;
+
         let mut result: Vec<f64> = vec![0.; self.nb_valid()];
 
         // create a vec with the good size for the plaintexts
         let mut tmp_pt: Vec<Torus> = vec![0; self.polynomial_size * self.nb_ciphertexts];
 
         // compute the phase for all ciphertext
-        sk.val.decrypt_glwe_list(
-            &mut PlaintextList::from_container(tmp_pt.as_mut_slice()),
-            &self.ciphertexts,
-        );
+        with_glwe_secret_key!(sk.kind, sk.val.as_slice(), sk.polynomial_size, |key| {
+            key.decrypt_glwe_list(
+                &mut PlaintextList::from_container(tmp_pt.as_mut_slice()),
+                &self.ciphertexts,
+            )
+        });
 
         // decode as soon as the encoding is valid
         let mut i: usize = 0;
@@ -593,6 +613,7 @@ impl VectorRLWE {
     /// * an array of encoders
     /// * PolynomialSizeError - if the polynomial size of the secret key and the polynomial size of the RLWE ciphertext are different
     /// * DimensionError - if the dimension of the secret key and the dimension of the RLWE cipertext are different
+    /// * KeyMismatch - if the RLWE ciphertext was not encrypted under the secret key
     /// # Example
     /// ```rust
     /// use concrete::*;
@@ -687,6 +708,7 @@ impl VectorRLWE {
 
         // allocation for the result
         let mut res = crate::VectorLWE::zero(lwe_dimension, 1)?;
+        res.key_fingerprint = self.key_fingerprint;
 
         // compute the index for the variance and the encoder
         let index = n_coeff + n_ciphertext * self.polynomial_size;
@@ -923,6 +945,9 @@ impl VectorRLWE {
             ));
         }
 
+        // check the keys
+        let key_fingerprint = self.key_fingerprint.combine(ct.key_fingerprint)?;
+
         // check same deltas
         for (self_enc, ct_enc) in self.encoders.iter_mut().zip(ct.encoders.iter()) {
             if self_enc.is_valid() && ct_enc.is_valid() && !deltas_eq!(self_enc.delta, ct_enc.delta)
@@ -983,6 +1008,7 @@ impl VectorRLWE {
                 }
             }
         }
+        self.key_fingerprint = key_fingerprint;
         Ok(())
     }
 
@@ -1034,6 +1060,9 @@ impl VectorRLWE {
             ));
         }
 
+        // check the keys
+        let key_fingerprint = self.key_fingerprint.combine(ct.key_fingerprint)?;
+
         // check the Encoder lists
         for (self_enc, ct_enc) in self.encoders.iter_mut().zip(ct.encoders.iter()) {
             if self_enc.is_valid() && ct_enc.is_valid() {
@@ -1085,6 +1114,7 @@ impl VectorRLWE {
             }
         }
 
+        self.key_fingerprint = key_fingerprint;
         Ok(())
     }
 
@@ -1157,6 +1187,9 @@ impl VectorRLWE {
             }
         }
 
+        // check the keys
+        let key_fingerprint = self.key_fingerprint.combine(ct.key_fingerprint)?;
+
         // subtract ciphertexts together
         self.ciphertexts
             .as_mut_tensor()
@@ -1214,6 +1247,7 @@ impl VectorRLWE {
             }
         }
 
+        self.key_fingerprint = key_fingerprint;
         Ok(())
     }
 
@@ -1484,6 +1518,495 @@ impl VectorRLWE {
         Ok(())
     }
 
+    /// Compute a key switching operation on every RLWE ciphertext of the VectorRLWE struct self
+    /// The packed coefficients and their encoders are preserved
+    ///
+    /// # Argument
+    /// * `ksk` - the RLWE key switching key
+    ///
+    /// # Output
+    /// * a VectorRLWE struct encrypted under the output key of `ksk`
+    /// * PolynomialSizeError - if the polynomial size of the key switching key and the one of the RLWE ciphertexts are different
+    /// * DimensionError - if the input dimension of the key switching key and the dimension of the RLWE ciphertexts are different
+    /// * KeyMismatch - if the RLWE ciphertexts are not encrypted under the input key of the key switching key
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// // generate two secret keys
+    /// let sk_before = RLWESecretKey::new(&RLWE128_1024_1);
+    /// let sk_after = RLWESecretKey::new(&RLWE128_1024_1);
+    ///
+    /// // generate the key switching key
+    /// let rksk = RLWEKSK::new(&sk_before, &sk_after, 6, 4).unwrap();
+    ///
+    /// // random settings for the encoder and some random messages
+    /// let encoder = Encoder::new(-43., -10., 5, 2).unwrap();
+    /// let messages: Vec<f64> = vec![-39.69, -19.37, -40.74, -41.26, -35.77];
+    ///
+    /// // encode and encrypt
+    /// let ct_before = VectorRLWE::encode_encrypt_packed(&sk_before, &messages, &encoder).unwrap();
+    ///
+    /// // key switch
+    /// let ct_after = ct_before.keyswitch(&rksk).unwrap();
+    /// ```
+    pub fn keyswitch(&self, ksk: &crate::RLWEKSK) -> Result<crate::VectorRLWE, CryptoAPIError> {
+        if ksk.polynomial_size != self.polynomial_size {
+            return Err(PolynomialSizeError!(
+                ksk.polynomial_size,
+                self.polynomial_size
+            ));
+        } else if ksk.dimension_before != self.dimension {
+            return Err(DimensionError!(ksk.dimension_before, self.dimension));
+        }
+        self.key_fingerprint.check(ksk.fingerprint_before)?;
+
+        // allocation for the result, encrypted under the output key
+        let mut res = VectorRLWE::zero(
+            self.polynomial_size,
+            ksk.dimension_after,
+            self.nb_ciphertexts,
+        )?;
+        res.key_fingerprint = ksk.fingerprint_after;
+
+        // key switch every RLWE ciphertext
+        let fourier_ksk = ksk.fourier_ciphertexts();
+        let ct_size_after = res.get_ciphertext_size();
+        for (mut ct_out, ct_in) in izip!(
+            res.ciphertexts
+                .as_mut_tensor()
+                .subtensor_iter_mut(ct_size_after),
+            self.ciphertexts
+                .as_tensor()
+                .subtensor_iter(self.get_ciphertext_size())
+        ) {
+            ksk.keyswitch_raw(&fourier_ksk, ct_out.as_mut_slice(), ct_in.as_slice());
+        }
+
+        // deal with encoders, noise and new precision
+        for (output_enc, input_enc, vout, vin) in izip!(
+            res.encoders.iter_mut(),
+            self.encoders.iter(),
+            res.variances.iter_mut(),
+            self.variances.iter()
+        ) {
+            // copy the encoders
+            output_enc.copy(input_enc);
+
+            if input_enc.is_valid() {
+                // calls the NPE to find out the amount of noise after KS,
+                // each coefficient sees as many terms as an LWE of dimension dimension * polynomial_size
+                *vout = ksk.kind_before.get_keyswitch_variance(
+                    self.dimension * self.polynomial_size,
+                    ksk.level,
+                    ksk.base_log,
+                    ksk.variance,
+                    *vin,
+                );
+
+                // update the precision
+                let nb_bit_overlap: usize = output_enc.update_precision_from_variance(*vout)?;
+
+                // notification of a problem
+                if nb_bit_overlap > 0 {
+                    println!(
+                        "{}: {} bit(s) lost, with {} bit(s) of message originally",
+                        "Loss of precision during key switch".red().bold(),
+                        nb_bit_overlap,
+                        input_enc.nb_bit_precision
+                    );
+                }
+            }
+        }
+
+        Ok(res)
+    }
+
+    /// Apply the automorphism X -> X^k on every RLWE ciphertext and key switch back to the original key
+    /// The message of the j-th coefficient is moved to the coefficient j * k mod 2N,
+    /// and is negated if j * k mod 2N >= N
+    ///
+    /// # Argument
+    /// * `k` - the exponent of the automorphism
+    /// * `autkey` - the automorphism key for X -> X^k
+    ///
+    /// # Output
+    /// * a VectorRLWE struct
+    /// * AutomorphismError - if k is even or not smaller than 2 * polynomial_size
+    /// * AutomorphismKeyError - if `autkey` is not a key for X -> X^k
+    /// * PolynomialSizeError - if the polynomial size of the key and the one of the RLWE ciphertexts are different
+    /// * DimensionError - if the dimension of the key and the dimension of the RLWE ciphertexts are different
+    /// * KeyMismatch - if the RLWE ciphertexts are not encrypted under the key of `autkey`
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// // generate a secret key and an automorphism key
+    /// let sk = RLWESecretKey::new(&RLWE128_1024_1);
+    /// let autkey = RLWEAutomorphismKey::new(&sk, 3, 6, 4).unwrap();
+    ///
+    /// // random settings for the encoder and some random messages
+    /// let encoder = Encoder::new(-43., -10., 5, 2).unwrap();
+    /// let messages: Vec<f64> = vec![-39.69, -19.37, -40.74, -41.26, -35.77];
+    ///
+    /// // encode and encrypt
+    /// let ct = VectorRLWE::encode_encrypt_packed(&sk, &messages, &encoder).unwrap();
+    ///
+    /// // apply X -> X^3
+    /// let ct_aut = ct.apply_automorphism(3, &autkey).unwrap();
+    /// ```
+    pub fn apply_automorphism(
+        &self,
+        k: usize,
+        autkey: &crate::RLWEAutomorphismKey,
+    ) -> Result<crate::VectorRLWE, CryptoAPIError> {
+        self.check_automorphism_key(k, autkey)?;
+
+        // allocation for the result, the automorphism key switches back to the key of self
+        let mut res = VectorRLWE::zero(self.polynomial_size, self.dimension, self.nb_ciphertexts)?;
+        res.key_fingerprint = self.key_fingerprint;
+
+        // apply the automorphism and key switch every RLWE ciphertext
+        let fourier_ksk = autkey.ksk.fourier_ciphertexts();
+        let ct_size = self.get_ciphertext_size();
+        for (mut ct_out, ct_in) in izip!(
+            res.ciphertexts.as_mut_tensor().subtensor_iter_mut(ct_size),
+            self.ciphertexts.as_tensor().subtensor_iter(ct_size)
+        ) {
+            self.automorphism_keyswitch_raw(
+                ct_out.as_mut_slice(),
+                ct_in.as_slice(),
+                autkey,
+                &fourier_ksk,
+            );
+        }
+
+        // move the encoders and the variances, and fix the negated messages
+        let n = self.polynomial_size;
+        for i in 0..self.nb_ciphertexts {
+            for j in 0..n {
+                let input_enc = &self.encoders[i * n + j];
+                let index = (j * k) % (2 * n);
+                let index_out = i * n + (index % n);
+
+                res.encoders[index_out].copy(input_enc);
+                if !input_enc.is_valid() {
+                    continue;
+                }
+
+                // calls the NPE to find out the amount of noise after KS
+                res.variances[index_out] = autkey.ksk.kind_before.get_keyswitch_variance(
+                    self.dimension * n,
+                    autkey.ksk.level,
+                    autkey.ksk.base_log,
+                    autkey.ksk.variance,
+                    self.variances[i * n + j],
+                );
+
+                // the message was negated so we add the same correction as in LWE::opposite_inplace
+                if index >= n {
+                    let enc = &mut res.encoders[index_out];
+                    let mut cor: Torus = 0;
+                    if enc.nb_bit_padding > 0 {
+                        cor = (1 << (<Torus as Numeric>::BITS - enc.nb_bit_padding))
+                            - (1 << (<Torus as Numeric>::BITS
+                                - enc.nb_bit_padding
+                                - enc.nb_bit_precision));
+                    } else {
+                        cor = cor.wrapping_sub(
+                            1 << (<Torus as Numeric>::BITS
+                                - enc.nb_bit_padding
+                                - enc.nb_bit_precision),
+                        );
+                    }
+                    let body_index = i * ct_size + self.dimension * n + (index - n);
+                    let body = res.ciphertexts.as_mut_tensor().as_mut_slice();
+                    body[body_index] = body[body_index].wrapping_add(cor);
+                    enc.opposite_inplace()?;
+                }
+
+                // update the precision
+                let nb_bit_overlap: usize = res.encoders[index_out]
+                    .update_precision_from_variance(res.variances[index_out])?;
+
+                // notification of a problem
+                if nb_bit_overlap > 0 {
+                    println!(
+                        "{}: {} bit(s) lost, with {} bit(s) of message originally",
+                        "Loss of precision during automorphism".red().bold(),
+                        nb_bit_overlap,
+                        input_enc.nb_bit_precision
+                    );
+                }
+            }
+        }
+
+        Ok(res)
+    }
+
+    /// Compute a partial trace of every RLWE ciphertext, by applying c <- c + tau_k(c) for
+    /// each automorphism key given, with k = N / 2^i + 1 for the i-th key
+    /// After s steps, only the coefficients whose index is a multiple of 2^s are kept and multiplied by 2^s,
+    /// the others are zeroed. With the log2(N) keys of RLWEAutomorphismKey::new_trace_keys,
+    /// only the constant coefficient is kept and multiplied by N.
+    /// Like VectorLWE::sum_with_padding, each step eats one bit of padding.
+    ///
+    /// # Argument
+    /// * `autkeys` - the automorphism keys, as output by RLWEAutomorphismKey::new_trace_keys
+    ///
+    /// # Output
+    /// * a VectorRLWE struct
+    /// * AutomorphismKeyError - if the i-th key is not a key for X -> X^(N / 2^i + 1)
+    /// * PolynomialSizeError - if the polynomial size of the keys and the one of the RLWE ciphertexts are different
+    /// * DimensionError - if the dimension of the keys and the dimension of the RLWE ciphertexts are different
+    /// * KeyMismatch - if the RLWE ciphertexts are not encrypted under the key of the keys
+    /// * NotEnoughPaddingError - if a kept coefficient does not have enough padding
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// // generate a secret key and the automorphism keys
+    /// let sk = RLWESecretKey::new(&RLWE128_256_1);
+    /// let autkeys = RLWEAutomorphismKey::new_trace_keys(&sk, 6, 4).unwrap();
+    ///
+    /// // settings for the encoder with enough padding and some messages
+    /// let encoder = Encoder::new(0., 1., 2, 9).unwrap();
+    /// let messages: Vec<f64> = vec![0.5, 0.25, 0.75];
+    ///
+    /// // encode and encrypt
+    /// let ct = VectorRLWE::encode_encrypt_packed(&sk, &messages, &encoder).unwrap();
+    ///
+    /// // the constant coefficient now encrypts 256 * 0.5
+    /// let ct_trace = ct.trace(&autkeys).unwrap();
+    /// ```
+    pub fn trace(
+        &self,
+        autkeys: &[crate::RLWEAutomorphismKey],
+    ) -> Result<crate::VectorRLWE, CryptoAPIError> {
+        let n = self.polynomial_size;
+        let nb_steps = autkeys.len();
+
+        // check the keys
+        for (i, autkey) in autkeys.iter().enumerate() {
+            self.check_automorphism_key((n >> i) + 1, autkey)?;
+        }
+
+        // check the paddings of the kept coefficients
+        for (j, enc) in self.encoders.iter().enumerate() {
+            if (j % n) % (1 << nb_steps) == 0 && enc.is_valid() && enc.nb_bit_padding < nb_steps {
+                return Err(NotEnoughPaddingError!(enc.nb_bit_padding, nb_steps));
+            }
+        }
+
+        // compute c <- c + tau_k(c) for every automorphism
+        let mut res = self.clone();
+        let ct_size = self.get_ciphertext_size();
+        let mut tmp: Vec<Torus> = vec![0; ct_size];
+        for autkey in autkeys.iter() {
+            let fourier_ksk = autkey.ksk.fourier_ciphertexts();
+            for mut ct in res.ciphertexts.as_mut_tensor().subtensor_iter_mut(ct_size) {
+                self.automorphism_keyswitch_raw(&mut tmp, ct.as_slice(), autkey, &fourier_ksk);
+                for (c, t) in izip!(ct.as_mut_slice().iter_mut(), tmp.iter()) {
+                    *c = c.wrapping_add(*t);
+                }
+            }
+        }
+
+        // update the encoders and the variances
+        for (j, (enc, var, input_enc)) in izip!(
+            res.encoders.iter_mut(),
+            res.variances.iter_mut(),
+            self.encoders.iter()
+        )
+        .enumerate()
+        {
+            if (j % n) % (1 << nb_steps) != 0 {
+                // this coefficient is now a zero
+                *enc = crate::Encoder::zero();
+                continue;
+            } else if !enc.is_valid() {
+                continue;
+            }
+
+            for autkey in autkeys.iter() {
+                // the noise of c and the one of tau_k(c) are added in the kept coefficient,
+                // along with the key switch noise
+                *var = autkey.ksk.kind_before.get_keyswitch_variance(
+                    self.dimension * n,
+                    autkey.ksk.level,
+                    autkey.ksk.base_log,
+                    autkey.ksk.variance,
+                    2. * *var,
+                );
+
+                // the message is doubled
+                enc.o *= 2.;
+                enc.delta *= 2.;
+                enc.nb_bit_padding -= 1;
+            }
+
+            // update the precision
+            let nb_bit_overlap: usize = enc.update_precision_from_variance(*var)?;
+
+            // notification of a problem
+            if nb_bit_overlap > 0 {
+                println!(
+                    "{}: {} bit(s) lost, with {} bit(s) of message originally",
+                    "Loss of precision during trace".red().bold(),
+                    nb_bit_overlap,
+                    input_enc.nb_bit_precision
+                );
+            }
+        }
+
+        Ok(res)
+    }
+
+    /// Sum the valid coefficients of every RLWE ciphertext into its constant coefficient, by
+    /// multiplying it by the clear polynomial sum_j X^(-j) over the indices j of the valid
+    /// coefficients, the other coefficients are then zeroed
+    /// Like VectorLWE::sum_with_padding, it eats ceil(log2(k)) bits of padding for k summed
+    /// coefficients, and all the summed coefficients must share the same delta and padding
+    ///
+    /// # Output
+    /// * a VectorRLWE struct
+    /// * DeltaError - if the valid coefficients of an RLWE ciphertext have different deltas
+    /// * PaddingError - if the valid coefficients of an RLWE ciphertext have different paddings
+    /// * NotEnoughPaddingError - if the valid coefficients do not have enough padding
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// // generate a secret key
+    /// let sk = RLWESecretKey::new(&RLWE128_256_1);
+    ///
+    /// // settings for the encoder with enough padding and some messages
+    /// let encoder = Encoder::new(0., 1., 4, 3).unwrap();
+    /// let messages: Vec<f64> = vec![0.5, 0.25, 0.75];
+    ///
+    /// // encode and encrypt
+    /// let ct = VectorRLWE::encode_encrypt_packed(&sk, &messages, &encoder).unwrap();
+    ///
+    /// // the constant coefficient now encrypts 0.5 + 0.25 + 0.75
+    /// let ct_sum = ct.sum_coefficients().unwrap();
+    /// ```
+    pub fn sum_coefficients(&self) -> Result<crate::VectorRLWE, CryptoAPIError> {
+        let n = self.polynomial_size;
+        let ct_size = self.get_ciphertext_size();
+
+        // allocation for the result
+        let mut res = VectorRLWE::zero(n, self.dimension, self.nb_ciphertexts)?;
+        res.key_fingerprint = self.key_fingerprint;
+
+        for (mut ct_out, ct_in, encoders_in, variances_in, encoders_out, variances_out) in izip!(
+            res.ciphertexts.as_mut_tensor().subtensor_iter_mut(ct_size),
+            self.ciphertexts.as_tensor().subtensor_iter(ct_size),
+            self.encoders.chunks(n),
+            self.variances.chunks(n),
+            res.encoders.chunks_mut(n),
+            res.variances.chunks_mut(n)
+        ) {
+            let valid: Vec<usize> = (0..n).filter(|j| encoders_in[*j].is_valid()).collect();
+            if valid.is_empty() {
+                continue;
+            }
+
+            // check the encoders and compute the one of the sum
+            let first = &encoders_in[valid[0]];
+            let nb_bit_padding_consumed: usize = f64::ceil(f64::log2(valid.len() as f64)) as usize;
+            let mut new_var: f64 = 0.;
+            let mut new_o: f64 = 0.;
+            for j in valid.iter() {
+                let enc = &encoders_in[*j];
+                if !deltas_eq!(first.delta, enc.delta) {
+                    return Err(DeltaError!(first.delta, enc.delta));
+                } else if first.nb_bit_padding != enc.nb_bit_padding {
+                    return Err(PaddingError!(first.nb_bit_padding, enc.nb_bit_padding));
+                }
+                new_var = npe::add_ciphertexts(new_var, variances_in[*j]);
+                new_o += enc.o;
+            }
+            if first.nb_bit_padding < nb_bit_padding_consumed {
+                return Err(NotEnoughPaddingError!(
+                    first.nb_bit_padding,
+                    nb_bit_padding_consumed
+                ));
+            }
+
+            // the constant coefficient of X^(-j) * M is the coefficient j of M,
+            // with X^(-j) = -X^(N - j) for j > 0
+            let mut selector: Vec<Torus> = vec![0; n];
+            for j in valid.iter() {
+                if *j == 0 {
+                    selector[0] = 1;
+                } else {
+                    selector[n - j] = (1 as Torus).wrapping_neg();
+                }
+            }
+            crate::fourier::FourierPolynomials::new(ct_in.as_slice(), n)
+                .update_with_add_products(ct_out.as_mut_slice(), &[(0, selector.as_slice())]);
+
+            encoders_out[0] = crate::Encoder {
+                o: new_o,
+                delta: first.delta * f64::powi(2., nb_bit_padding_consumed as i32),
+                nb_bit_precision: first.nb_bit_precision,
+                nb_bit_padding: first.nb_bit_padding - nb_bit_padding_consumed,
+                round: first.round,
+            };
+            encoders_out[0].update_precision_from_variance(new_var)?;
+            variances_out[0] = new_var;
+        }
+
+        Ok(res)
+    }
+
+    /// Check that an automorphism key can be used with this VectorRLWE for X -> X^k
+    fn check_automorphism_key(
+        &self,
+        k: usize,
+        autkey: &crate::RLWEAutomorphismKey,
+    ) -> Result<(), CryptoAPIError> {
+        if k % 2 == 0 || k >= 2 * self.polynomial_size {
+            return Err(AutomorphismError!(k, self.polynomial_size));
+        } else if autkey.automorphism != k {
+            return Err(AutomorphismKeyError!(k, autkey.automorphism));
+        } else if autkey.ksk.polynomial_size != self.polynomial_size {
+            return Err(PolynomialSizeError!(
+                autkey.ksk.polynomial_size,
+                self.polynomial_size
+            ));
+        } else if autkey.ksk.dimension_before != self.dimension {
+            return Err(DimensionError!(autkey.ksk.dimension_before, self.dimension));
+        }
+        self.key_fingerprint.check(autkey.ksk.fingerprint_before)
+    }
+
+    /// Apply an automorphism on one raw RLWE ciphertext and key switch it back to the original key
+    fn automorphism_keyswitch_raw(
+        &self,
+        ct_out: &mut [Torus],
+        ct_in: &[Torus],
+        autkey: &crate::RLWEAutomorphismKey,
+        fourier_ksk: &crate::fourier::FourierPolynomials,
+    ) {
+        let mut permuted: Vec<Torus> = vec![0; ct_in.len()];
+        for (out, polynomial) in izip!(
+            permuted.chunks_mut(self.polynomial_size),
+            ct_in.chunks(self.polynomial_size)
+        ) {
+            crate::rlwe_automorphism_key::apply_automorphism_polynomial(
+                out,
+                polynomial,
+                autkey.automorphism,
+            );
+        }
+        autkey.ksk.keyswitch_raw(fourier_ksk, ct_out, &permuted);
+    }
+
     /// Return the number of valid encoders (i.e. how many messages are carried in those RLWE ciphertexts)
     pub fn nb_valid(&self) -> usize {
         let mut res: usize = 0;
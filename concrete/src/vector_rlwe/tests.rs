@@ -578,3 +578,277 @@ fn test_encode_encrypt_on_cst_x_mul_constant_with_padding_inplace_x_decrypt_with
     }
     assert_eq!(cpt, nb_messages);
 }
+
+#[test]
+fn test_encode_encrypt_packed_x_keyswitch_x_decrypt() {
+    // generate two secret keys with possibly different dimensions
+    let polynomial_size: usize = 1024;
+    let log_std_dev: i32 = -(random_index!(10) as i32 + 30);
+    let params_before =
+        crate::RLWEParams::new(polynomial_size, random_index!(2) + 1, log_std_dev).unwrap();
+    let params_after =
+        crate::RLWEParams::new(polynomial_size, random_index!(2) + 1, log_std_dev).unwrap();
+    let sk_before = crate::RLWESecretKey::new(&params_before);
+    let sk_after = crate::RLWESecretKey::new(&params_after);
+
+    // generate the key switching key
+    let (base_log, level): (usize, usize) = (6, 4);
+    let rksk = crate::RLWEKSK::new(&sk_before, &sk_after, base_log, level).unwrap();
+
+    // random number of messages
+    let nb_messages: usize = random_index!(polynomial_size - 1) + 1;
+
+    // random settings for the encoder and some random messages
+    let (min, max) = generate_random_interval!();
+    let (precision, padding) = generate_precision_padding!(8, 8);
+    let encoder = crate::Encoder::new(min, max, precision, padding).unwrap();
+    let messages: Vec<f64> = random_messages!(min, max, nb_messages);
+
+    // encode and encrypt
+    let ct_before =
+        crate::VectorRLWE::encode_encrypt_packed(&sk_before, &messages, &encoder).unwrap();
+
+    // key switch and decrypt
+    let ct_after = ct_before.keyswitch(&rksk).unwrap();
+    let decryptions = ct_after.decrypt_decode_round(&sk_after).unwrap();
+
+    // test
+    assert_eq!(ct_after.dimension, sk_after.dimension);
+    let mut cpt: usize = 0;
+    for (m, d, enc) in izip!(
+        messages.iter(),
+        decryptions.iter(),
+        ct_after.encoders.iter()
+    ) {
+        assert_eq_granularity!(m, d, enc);
+        cpt += 1;
+    }
+    assert_eq!(cpt, nb_messages);
+}
+
+#[test]
+fn test_encode_encrypt_packed_x_keyswitch_x_decrypt_secret_key_kinds() {
+    // a gaussian input key and a ternary output key
+    let params = crate::RLWEParams::new(1024, 1, -40).unwrap();
+    let sk_before = crate::RLWESecretKey::new_with_kind(&params, crate::SecretKeyKind::Gaussian);
+    let sk_after = crate::RLWESecretKey::new_with_kind(&params, crate::SecretKeyKind::Ternary);
+
+    // the key switching key records the kind of its input key
+    let (base_log, level): (usize, usize) = (6, 4);
+    let rksk = crate::RLWEKSK::new(&sk_before, &sk_after, base_log, level).unwrap();
+    assert_eq!(rksk.kind_before, crate::SecretKeyKind::Gaussian);
+
+    // encode, encrypt, key switch and decrypt
+    let encoder = crate::Encoder::new(0., 1., 4, 1).unwrap();
+    let messages: Vec<f64> = vec![0.25, 0.5, 0.75];
+    let ct_before =
+        crate::VectorRLWE::encode_encrypt_packed(&sk_before, &messages, &encoder).unwrap();
+    let ct_after = ct_before.keyswitch(&rksk).unwrap();
+    let decryptions = ct_after.decrypt_decode_round(&sk_after).unwrap();
+    for (m, d, enc) in izip!(
+        messages.iter(),
+        decryptions.iter(),
+        ct_after.encoders.iter()
+    ) {
+        assert_eq_granularity!(m, d, enc);
+    }
+
+    // the rounding noise grows with the second moment of the input key
+    let binary_variance = <crate::Torus as concrete_npe::LWE>::key_switch(
+        1024,
+        level,
+        base_log,
+        rksk.variance,
+        ct_before.variances[0],
+    );
+    let kind_variance = crate::SecretKeyKind::Binary.get_keyswitch_variance(
+        1024,
+        level,
+        base_log,
+        rksk.variance,
+        ct_before.variances[0],
+    );
+    assert!(f64::abs(kind_variance - binary_variance) <= 1e-12 * binary_variance);
+    assert!(ct_after.variances[0] > binary_variance);
+}
+
+#[test]
+fn test_keyswitch_decomposition() {
+    let params = crate::RLWEParams::new(256, 1, -40).unwrap();
+    let sk_before = crate::RLWESecretKey::new(&params);
+    let sk_after = crate::RLWESecretKey::new(&params);
+
+    // the decomposition cannot be empty or take more than the 64 bits of the torus
+    for (base_log, level) in [(0, 4), (6, 0), (13, 5)].iter() {
+        assert!(crate::RLWEKSK::new(&sk_before, &sk_after, *base_log, *level).is_err());
+        assert!(crate::RLWEKSK::zero(&sk_before, &sk_after, *base_log, *level).is_err());
+    }
+
+    // a decomposition on the 64 bits is valid
+    let rksk = crate::RLWEKSK::new(&sk_before, &sk_after, 16, 4).unwrap();
+    let encoder = crate::Encoder::new(0., 1., 4, 1).unwrap();
+    let messages: Vec<f64> = vec![0.25, 0.5, 0.75];
+    let ct_before =
+        crate::VectorRLWE::encode_encrypt_packed(&sk_before, &messages, &encoder).unwrap();
+    let decryptions = ct_before
+        .keyswitch(&rksk)
+        .unwrap()
+        .decrypt_decode_round(&sk_after)
+        .unwrap();
+    for (m, d) in izip!(messages.iter(), decryptions.iter()) {
+        assert!(f64::abs(m - d) < 0.1);
+    }
+}
+
+#[test]
+fn test_encode_encrypt_packed_x_apply_automorphism_x_decrypt() {
+    // generate a secret key
+    let polynomial_size: usize = 1024;
+    let params = crate::RLWEParams::new(polynomial_size, 1, -30).unwrap();
+    let sk = crate::RLWESecretKey::new(&params);
+
+    // generate the key for X -> X^(2N - 1), which is its own inverse
+    let k: usize = 2 * polynomial_size - 1;
+    let autkey = crate::RLWEAutomorphismKey::new(&sk, k, 6, 4).unwrap();
+
+    // random number of messages
+    let nb_messages: usize = random_index!(polynomial_size - 1) + 1;
+
+    // random settings for the encoder and some random messages
+    let (min, max) = generate_random_interval!();
+    let (precision, padding) = generate_precision_padding!(6, 4);
+    let encoder = crate::Encoder::new(min, max, precision, padding).unwrap();
+    let messages: Vec<f64> = random_messages!(min, max, nb_messages);
+
+    // encode and encrypt
+    let ct = crate::VectorRLWE::encode_encrypt_packed(&sk, &messages, &encoder).unwrap();
+
+    // apply the automorphism twice and decrypt
+    let ct_aut = ct.apply_automorphism(k, &autkey).unwrap();
+    let ct_res = ct_aut.apply_automorphism(k, &autkey).unwrap();
+    let decryptions = ct_res.decrypt_decode_round(&sk).unwrap();
+
+    // test
+    let mut cpt: usize = 0;
+    for (m, d, enc) in izip!(messages.iter(), decryptions.iter(), ct_res.encoders.iter()) {
+        assert_eq_granularity!(m, d, enc);
+        cpt += 1;
+    }
+    assert_eq!(cpt, nb_messages);
+
+    // the constant coefficient is left untouched by the first automorphism
+    let decryptions_aut = ct_aut.decrypt_decode_round(&sk).unwrap();
+    assert_eq_granularity!(messages[0], decryptions_aut[0], ct_aut.encoders[0]);
+}
+
+#[test]
+fn test_encode_encrypt_packed_x_trace_x_decrypt() {
+    // generate a secret key and the automorphism keys
+    let polynomial_size: usize = 256;
+    let params = crate::RLWEParams::new(polynomial_size, 1, -30).unwrap();
+    let sk = crate::RLWESecretKey::new(&params);
+    let autkeys = crate::RLWEAutomorphismKey::new_trace_keys(&sk, 6, 4).unwrap();
+
+    // random number of messages
+    let nb_messages: usize = random_index!(polynomial_size - 1) + 1;
+
+    // random settings for the encoder with enough padding and some random messages
+    let (min, max) = generate_random_interval!();
+    let encoder = crate::Encoder::new(min, max, 2, 9).unwrap();
+    let messages: Vec<f64> = random_messages!(min, max, nb_messages);
+
+    // encode and encrypt
+    let ct = crate::VectorRLWE::encode_encrypt_packed(&sk, &messages, &encoder).unwrap();
+
+    // trace and decrypt
+    let ct_trace = ct.trace(&autkeys).unwrap();
+    let decryptions = ct_trace.decrypt_decode_round(&sk).unwrap();
+
+    // test: only the constant coefficient is left, multiplied by the polynomial size
+    assert_eq!(ct_trace.nb_valid(), 1);
+    assert_eq_granularity!(
+        messages[0] * polynomial_size as f64,
+        decryptions[0],
+        ct_trace.encoders[0]
+    );
+
+    // not enough padding
+    let encoder = crate::Encoder::new(min, max, 2, 4).unwrap();
+    let ct = crate::VectorRLWE::encode_encrypt_packed(&sk, &messages, &encoder).unwrap();
+    assert!(ct.trace(&autkeys).is_err());
+}
+
+#[test]
+fn test_encode_encrypt_packed_x_sum_coefficients_x_decrypt() {
+    // generate a secret key
+    let polynomial_size: usize = 1024;
+    let params = crate::RLWEParams::new(polynomial_size, 1, -30).unwrap();
+    let sk = crate::RLWESecretKey::new(&params);
+
+    // random number of messages
+    let nb_messages: usize = random_index!(polynomial_size - 1) + 1;
+
+    // random settings for the encoder with enough padding and some random messages
+    let (min, max) = generate_random_interval!();
+    let encoder = crate::Encoder::new(min, max, 4, 11).unwrap();
+    let messages: Vec<f64> = random_messages!(min, max, nb_messages);
+
+    // encode and encrypt
+    let ct = crate::VectorRLWE::encode_encrypt_packed(&sk, &messages, &encoder).unwrap();
+
+    // sum and decrypt
+    let ct_sum = ct.sum_coefficients().unwrap();
+    let decryptions = ct_sum.decrypt_decode_round(&sk).unwrap();
+
+    // test: only the constant coefficient is left, with the sum of the messages
+    assert_eq!(ct_sum.nb_valid(), 1);
+    let sum: f64 = messages.iter().sum();
+    assert_eq_granularity!(sum, decryptions[0], ct_sum.encoders[0]);
+
+    // not enough padding
+    let encoder = crate::Encoder::new(min, max, 4, 1).unwrap();
+    let ct = crate::VectorRLWE::encode_encrypt_packed(&sk, &messages, &encoder).unwrap();
+    assert_eq!(ct.sum_coefficients().is_err(), nb_messages > 2);
+}
+
+#[test]
+fn test_key_mismatch() {
+    // three keys of the same parameters
+    let params = crate::RLWEParams::new(256, 1, -40).unwrap();
+    let sk_before = crate::RLWESecretKey::new(&params);
+    let sk_after = crate::RLWESecretKey::new(&params);
+    let other_sk = crate::RLWESecretKey::new(&params);
+    let encoder = crate::Encoder::new(0., 10., 4, 1).unwrap();
+    let ct = crate::VectorRLWE::encode_encrypt_packed(&sk_before, &[3., 5.], &encoder).unwrap();
+
+    // the output of a key switch is encrypted under the output key
+    let rksk = crate::RLWEKSK::new(&sk_before, &sk_after, 6, 4).unwrap();
+    let switched = ct.keyswitch(&rksk).unwrap();
+    assert_eq!(switched.key_fingerprint, sk_after.fingerprint);
+    assert!(matches!(
+        switched.decrypt_decode(&sk_before),
+        Err(crate::CryptoAPIError::KeyMismatch { .. })
+    ));
+
+    // key switch and automorphism with the keys of another key
+    let other_rksk = crate::RLWEKSK::new(&other_sk, &sk_after, 6, 4).unwrap();
+    assert!(matches!(
+        ct.keyswitch(&other_rksk),
+        Err(crate::CryptoAPIError::KeyMismatch { .. })
+    ));
+    let autkey = crate::RLWEAutomorphismKey::new(&other_sk, 3, 6, 4).unwrap();
+    assert!(matches!(
+        ct.apply_automorphism(3, &autkey),
+        Err(crate::CryptoAPIError::KeyMismatch { .. })
+    ));
+
+    // external product with a GGSW of another key
+    let ggsw = crate::GGSW::encrypt(&other_sk, 1, 6, 4).unwrap();
+    assert!(matches!(
+        ggsw.external_product(&ct),
+        Err(crate::CryptoAPIError::KeyMismatch { .. })
+    ));
+    let ggsw = crate::GGSW::encrypt(&sk_before, 1, 6, 4).unwrap();
+    let product = ggsw.external_product(&ct).unwrap();
+    assert_eq!(product.key_fingerprint, sk_before.fingerprint);
+}